@@ -37,9 +37,13 @@ struct TerminalApp {
     asset_selector: AssetSelectorState,
     mode_control: ModeControlState,
     risk_panel: RiskPanelState,
+    decision_log: DecisionLogState,
     
     // WebSocket client
     ws_client: Option<MetricsClient>,
+    connecting: Option<Connecting>,
+    /// Why the last connect attempt failed
+    connect_error: Option<String>,
     
     // Runtime
     runtime: tokio::runtime::Runtime,
@@ -59,42 +63,75 @@ impl TerminalApp {
             .build()
             .unwrap();
         
+        Self::with_runtime(runtime)
+    }
+    
+    /// Default panels, not yet connected, on `runtime`
+    fn with_runtime(runtime: tokio::runtime::Runtime) -> Self {
         Self {
             account_manager: AccountManagerState::default(),
             universe_settings: UniverseSettingsState::default(),
             asset_selector: AssetSelectorState::default(),
             mode_control: ModeControlState::default(),
             risk_panel: RiskPanelState::default(),
+            decision_log: DecisionLogState::default(),
             ws_client: None,
+            connecting: None,
+            connect_error: None,
             runtime,
         }
     }
     
     fn connect_to_engine(&mut self, url: &str) {
-        let (tx, rx) = std::sync::mpsc::channel();
-        let url_owned = url.to_string();
+        self.connect_error = None;
+        self.connecting = Some(Connecting::start(&self.runtime, url));
+    }
+    
+    /// Pick up the result of a connect attempt once it has finished
+    fn poll_connection(&mut self) {
+        let Some(result) = self.connecting.as_ref().and_then(Connecting::poll) else {
+            return;
+        };
         
-        self.runtime.spawn(async move {
-            match MetricsClient::connect(&url_owned).await {
-                Ok(client) => {
-                    let _ = tx.send(Ok(client));
-                }
-                Err(e) => {
-                    let _ = tx.send(Err(e));
+        self.connecting = None;
+        match result {
+            Ok(client) => {
+                // A client from an earlier connect would keep its sockets reconnecting
+                if let Some(old) = self.ws_client.replace(client) {
+                    old.close();
                 }
+                tracing::info!("Connected to engine");
+            }
+            Err(e) => {
+                tracing::error!("Failed to connect: {}", e);
+                self.connect_error = Some(e.to_string());
             }
+        }
+    }
+}
+
+/// A connect attempt running on the runtime, polled from `update`
+struct Connecting(std::sync::mpsc::Receiver<Result<MetricsClient>>);
+
+impl Connecting {
+    fn start(runtime: &tokio::runtime::Runtime, url: &str) -> Self {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let url = url.to_string();
+        
+        runtime.spawn(async move {
+            let _ = tx.send(MetricsClient::connect(&url).await);
         });
         
-        // Try to receive immediately (non-blocking)
-        if let Ok(result) = rx.try_recv() {
-            match result {
-                Ok(client) => {
-                    self.ws_client = Some(client);
-                    tracing::info!("Connected to engine");
-                }
-                Err(e) => {
-                    tracing::error!("Failed to connect: {}", e);
-                }
+        Self(rx)
+    }
+    
+    /// The outcome, or `None` while the attempt is still running
+    fn poll(&self) -> Option<Result<MetricsClient>> {
+        match self.0.try_recv() {
+            Ok(result) => Some(result),
+            Err(std::sync::mpsc::TryRecvError::Empty) => None,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                Some(Err(Error::Internal("connect task ended without a result".to_string())))
             }
         }
     }
@@ -105,6 +142,8 @@ impl eframe::App for TerminalApp {
         // Request continuous repaint for 60fps
         ctx.request_repaint();
         
+        self.poll_connection();
+        
         // Top menu bar
         egui::TopBottomPanel::top("menu_bar").show(ctx, |ui| {
             egui::menu::bar(ui, |ui| {
@@ -124,17 +163,29 @@ impl eframe::App for TerminalApp {
                 
                 // Connection status
                 let connected = self.ws_client.is_some();
-                let color = if connected {
-                    egui::Color32::GREEN
-                } else {
-                    egui::Color32::RED
-                };
-                ui.colored_label(color, if connected { "● Connected" } else { "● Disconnected" });
+                match &self.ws_client {
+                    Some(client) if client.is_connected() => {
+                        ui.colored_label(egui::Color32::GREEN, "● Connected");
+                    }
+                    Some(_) => {
+                        ui.colored_label(egui::Color32::YELLOW, "● Reconnecting…");
+                    }
+                    None if self.connecting.is_some() => {
+                        ui.colored_label(egui::Color32::YELLOW, "● Connecting…");
+                    }
+                    None => {
+                        ui.colored_label(egui::Color32::RED, "● Disconnected");
+                    }
+                }
                 
-                if !connected {
+                if !connected && self.connecting.is_none() {
                     if ui.button("Connect").clicked() {
                         self.connect_to_engine("ws://localhost:8081/metrics");
                     }
+                    
+                    if let Some(error) = &self.connect_error {
+                        ui.colored_label(egui::Color32::RED, error);
+                    }
                 }
             });
         });
@@ -146,6 +197,10 @@ impl eframe::App for TerminalApp {
                 ui.add_space(10.0);
                 
                 // Tabs
+                match &self.ws_client {
+                    Some(ws_client) => self.account_manager.update_from_ws(ws_client),
+                    None => self.account_manager.engine_disconnected(),
+                }
                 egui::containers::CollapsingHeader::new("Account Management")
                     .default_open(true)
                     .show(ui, |ui| {
@@ -158,6 +213,10 @@ impl eframe::App for TerminalApp {
                         self.universe_settings.ui(ui);
                     });
                 
+                match &self.ws_client {
+                    Some(ws_client) => self.asset_selector.update_from_ws(ws_client),
+                    None => self.asset_selector.engine_disconnected(),
+                }
                 egui::containers::CollapsingHeader::new("Asset Selection")
                     .default_open(false)
                     .show(ui, |ui| {
@@ -182,6 +241,17 @@ impl eframe::App for TerminalApp {
                     self.risk_panel.update_from_ws(ws_client);
                 }
                 self.risk_panel.ui(ui);
+                
+                ui.add_space(10.0);
+                
+                if let Some(ws_client) = &self.ws_client {
+                    self.decision_log.update_from_ws(ws_client);
+                }
+                egui::containers::CollapsingHeader::new("Decisions")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        self.decision_log.ui(ui);
+                    });
             });
         });
         
@@ -206,4 +276,49 @@ fn configure_fonts(ctx: &egui::Context) {
     );
     
     ctx.set_fonts(fonts);
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+    
+    /// Poll like `update` does, one frame at a time, until the attempt finishes
+    fn wait(app: &mut TerminalApp) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while app.connecting.is_some() {
+            assert!(Instant::now() < deadline, "connect never completed");
+            std::thread::sleep(Duration::from_millis(16));
+            app.poll_connection();
+        }
+    }
+    
+    #[test]
+    fn test_completed_connect_is_picked_up() {
+        let runtime = tokio::runtime::Builder::new_multi_thread().enable_all().build().unwrap();
+        let listener = runtime.block_on(tokio::net::TcpListener::bind("127.0.0.1:0")).unwrap();
+        let url = format!("ws://{}/metrics", listener.local_addr().unwrap());
+        
+        // Engine stand-in: accepts every socket the client opens and holds it
+        runtime.spawn(async move {
+            let mut sockets = Vec::new();
+            while let Ok((tcp, _)) = listener.accept().await {
+                if let Ok(ws) = tokio_tungstenite::accept_async(tcp).await {
+                    sockets.push(ws);
+                }
+            }
+        });
+        
+        let mut app = TerminalApp::with_runtime(runtime);
+        app.connect_to_engine(&url);
+        wait(&mut app);
+        assert!(app.ws_client.as_ref().expect("client stored").is_connected());
+        assert!(app.connect_error.is_none());
+        
+        // Nothing listening: the failure is reported rather than lost
+        app.connect_to_engine("ws://127.0.0.1:1/metrics");
+        wait(&mut app);
+        assert!(app.connect_error.unwrap().contains("Connection failed"));
+        assert!(app.ws_client.is_some());
+    }
+}
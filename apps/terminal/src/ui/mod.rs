@@ -1,6 +1,6 @@
 // apps/terminal/src/ui/mod.rs
 use common::*;
-use common::security::{ApiCredentials, CredentialStore};
+use common::security::{ApiCredentials, CredentialStore, DataSourceKeys, StoredAccount};
 use egui::{Color32, RichText, Ui};
 use std::collections::{HashMap, BTreeMap};
 
@@ -9,12 +9,14 @@ pub mod universe_settings;
 pub mod asset_selector;
 pub mod mode_control;
 pub mod risk_panel;
+pub mod decision_log;
 
 pub use account_manager::AccountManagerState;
 pub use universe_settings::UniverseSettingsState;
 pub use asset_selector::AssetSelectorState;
 pub use mode_control::ModeControlState;
 pub use risk_panel::RiskPanelState;
+pub use decision_log::DecisionLogState;
 
 // apps/terminal/src/ui/account_manager.rs
 #[derive(Default)]
@@ -36,15 +38,42 @@ pub struct AccountInfo {
     pub venue: Venue,
     pub is_paper: bool,
     pub balances: HashMap<String, f64>,
+    /// Loaded from the credential index rather than entered this session
+    pub stored: bool,
+    pub balance: BalanceStatus,
+}
+
+/// Progress of a "Check Balance" request
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum BalanceStatus {
+    #[default]
+    Idle,
+    /// Clicked, not yet sent to the engine
+    Requested,
+    Loading,
+    Failed(String),
+}
+
+/// Where saved accounts are listed from
+pub trait AccountIndex {
+    fn list_accounts(&self) -> Result<Vec<StoredAccount>>;
+}
+
+impl AccountIndex for CredentialStore {
+    fn list_accounts(&self) -> Result<Vec<StoredAccount>> {
+        CredentialStore::list_accounts(self)
+    }
 }
 
 impl AccountManagerState {
     pub fn ui(&mut self, ui: &mut Ui) {
         ui.heading("A1. Account Manager");
         
-        // Initialize credential store
+        // Initialize credential store and the accounts saved in it
         if self.cred_store.is_none() {
-            self.cred_store = Some(CredentialStore::new_simple());
+            let store = CredentialStore::new_simple();
+            self.load_stored_accounts(&store);
+            self.cred_store = Some(store);
         }
         
         ui.add_space(10.0);
@@ -98,7 +127,7 @@ impl AccountManagerState {
                 ui.add(egui::TextEdit::singleline(&mut self.api_secret).password(true));
             });
             
-            if self.venue == Venue::BinanceFutures {
+            if self.venue.allows_passphrase() {
                 ui.horizontal(|ui| {
                     ui.label("Passphrase:");
                     ui.add(egui::TextEdit::singleline(&mut self.passphrase).password(true));
@@ -119,6 +148,7 @@ impl AccountManagerState {
         ui.add_space(20.0);
         
         // List configured accounts
+        let mut check_balance = None;
         ui.group(|ui| {
             ui.label(RichText::new("Configured Accounts").strong());
             
@@ -139,8 +169,15 @@ impl AccountManagerState {
                                 self.selected_account = Some(label.clone());
                             }
                             
-                            if ui.button("💰 Check Balance").clicked() {
-                                // TODO: Query balance from engine
+                            if info.stored {
+                                ui.label(RichText::new("🔑 stored").small().color(Color32::GRAY));
+                            }
+                            
+                            let loading = matches!(info.balance, BalanceStatus::Requested | BalanceStatus::Loading);
+                            if loading {
+                                ui.spinner();
+                            } else if ui.button("💰 Check Balance").clicked() {
+                                check_balance = Some(label.clone());
                             }
                             
                             if ui.button("🗑").clicked() {
@@ -148,6 +185,12 @@ impl AccountManagerState {
                             }
                         });
                         
+                        if let BalanceStatus::Failed(error) = &info.balance {
+                            ui.indent(label, |ui| {
+                                ui.label(RichText::new(format!("⚠ {}", error)).small().color(Color32::RED));
+                            });
+                        }
+                        
                         // Show balances if available
                         if !info.balances.is_empty() {
                             ui.indent(label, |ui| {
@@ -170,19 +213,61 @@ impl AccountManagerState {
                 });
             }
         });
+        
+        if let Some(label) = check_balance {
+            if let Some(info) = self.accounts.get_mut(&label) {
+                info.balance = BalanceStatus::Requested;
+            }
+        }
     }
     
-    fn save_account(&mut self) {
-        if self.new_label.is_empty() || self.api_key.is_empty() || self.api_secret.is_empty() {
-            tracing::warn!("Missing required fields");
-            return;
+    /// Send requested balance checks and store the engine's answers
+    pub fn update_from_ws(&mut self, client: &crate::ws_client::MetricsClient) {
+        for (label, info) in self.accounts.iter_mut() {
+            if info.balance == BalanceStatus::Requested {
+                info.balance = match client.query_balances(info.venue, label, !info.is_paper) {
+                    Ok(()) => BalanceStatus::Loading,
+                    Err(e) => BalanceStatus::Failed(e.to_string()),
+                };
+            }
         }
         
-        let creds = if self.passphrase.is_empty() {
-            ApiCredentials::new(self.api_key.clone(), self.api_secret.clone(), self.is_paper)
-        } else {
-            ApiCredentials::new(self.api_key.clone(), self.api_secret.clone(), self.is_paper)
-                .with_passphrase(self.passphrase.clone())
+        while let Some(reply) = client.take_balance_reply() {
+            self.apply_balance_reply(reply);
+        }
+    }
+    
+    /// Checks can't reach the engine; fail them instead of spinning forever
+    pub fn engine_disconnected(&mut self) {
+        for info in self.accounts.values_mut() {
+            if matches!(info.balance, BalanceStatus::Requested | BalanceStatus::Loading) {
+                info.balance = BalanceStatus::Failed("Not connected to the engine".to_string());
+            }
+        }
+    }
+    
+    fn apply_balance_reply(&mut self, reply: BalanceReply) {
+        // The account may have been removed or re-added on another venue meanwhile
+        let Some(info) = self.accounts.get_mut(&reply.label).filter(|info| info.venue == reply.venue) else {
+            return;
+        };
+        
+        match reply.error {
+            Some(error) => info.balance = BalanceStatus::Failed(error),
+            None => {
+                info.balances = reply.balances;
+                info.balance = BalanceStatus::Idle;
+            }
+        }
+    }
+    
+    fn save_account(&mut self) {
+        let creds = match self.credentials() {
+            Ok(creds) => creds,
+            Err(e) => {
+                tracing::warn!("{}", e);
+                return;
+            }
         };
         
         if let Some(store) = &self.cred_store {
@@ -194,6 +279,8 @@ impl AccountManagerState {
                             venue: self.venue,
                             is_paper: self.is_paper,
                             balances: HashMap::new(),
+                            stored: false,
+                            balance: BalanceStatus::Idle,
                         },
                     );
                     
@@ -207,6 +294,49 @@ impl AccountManagerState {
         }
     }
     
+    /// Credentials from the form, once every field the venue needs is filled
+    fn credentials(&self) -> Result<ApiCredentials> {
+        if self.new_label.is_empty() || self.api_key.is_empty() || self.api_secret.is_empty() {
+            return Err(Error::InvalidCredentials("Missing required fields".to_string()));
+        }
+        
+        let creds = ApiCredentials::new(self.api_key.clone(), self.api_secret.clone(), self.is_paper);
+        if !self.venue.allows_passphrase() {
+            // A passphrase typed before switching venue isn't carried over
+            return Ok(creds);
+        }
+        
+        if self.passphrase.is_empty() {
+            if self.venue.requires_passphrase() {
+                return Err(Error::InvalidCredentials(format!("{:?} requires a passphrase", self.venue)));
+            }
+            return Ok(creds);
+        }
+        Ok(creds.with_passphrase(self.passphrase.clone()))
+    }
+    
+    /// List the accounts saved in `store`; secrets stay in the keychain
+    /// until an account is used
+    fn load_stored_accounts(&mut self, store: &impl AccountIndex) {
+        let stored = match store.list_accounts() {
+            Ok(stored) => stored,
+            Err(e) => {
+                tracing::error!("Failed to list saved accounts: {}", e);
+                return;
+            }
+        };
+        
+        for account in stored {
+            self.accounts.entry(account.label).or_insert(AccountInfo {
+                venue: account.venue,
+                is_paper: !account.live,
+                balances: HashMap::new(),
+                stored: true,
+                balance: BalanceStatus::Idle,
+            });
+        }
+    }
+    
     fn clear_form(&mut self) {
         self.new_label.clear();
         self.api_key.clear();
@@ -223,6 +353,11 @@ pub struct UniverseSettingsState {
     pub the_graph: String,
     pub crypto_panic: String,
     pub flipside: String,
+    pub cred_store: Option<CredentialStore>,
+    /// Show the keys in clear text instead of masked
+    pub reveal: bool,
+    /// Outcome of the last save, shown under the button
+    pub save_status: Option<std::result::Result<(), String>>,
 }
 
 impl UniverseSettingsState {
@@ -230,39 +365,90 @@ impl UniverseSettingsState {
         ui.heading("A2. Universe Creation Settings");
         ui.add_space(10.0);
         
+        // First time the panel opens: fill the fields from the keychain
+        if self.cred_store.is_none() {
+            let store = CredentialStore::new_simple();
+            match DataSourceKeys::load(&store) {
+                Ok(keys) => self.fill_from(&keys),
+                Err(Error::NotFound(_)) => {}
+                Err(e) => tracing::error!("Failed to load data source keys: {}", e),
+            }
+            self.cred_store = Some(store);
+        }
+        
         ui.group(|ui| {
-            ui.label("Data Source API Keys");
-            
-            ui.horizontal(|ui| {
-                ui.label("GeckoTerminal:");
-                ui.text_edit_singleline(&mut self.gecko_terminal);
-            });
-            
-            ui.horizontal(|ui| {
-                ui.label("Birdeye:");
-                ui.text_edit_singleline(&mut self.birdeye);
-            });
-            
-            ui.horizontal(|ui| {
-                ui.label("The Graph:");
-                ui.text_edit_singleline(&mut self.the_graph);
-            });
-            
             ui.horizontal(|ui| {
-                ui.label("CryptoPanic:");
-                ui.text_edit_singleline(&mut self.crypto_panic);
+                ui.label("Data Source API Keys");
+                ui.checkbox(&mut self.reveal, "Show keys");
             });
             
-            ui.horizontal(|ui| {
-                ui.label("Flipside:");
-                ui.text_edit_singleline(&mut self.flipside);
-            });
+            let masked = !self.reveal;
+            for (name, key) in [
+                ("GeckoTerminal:", &mut self.gecko_terminal),
+                ("Birdeye:", &mut self.birdeye),
+                ("The Graph:", &mut self.the_graph),
+                ("CryptoPanic:", &mut self.crypto_panic),
+                ("Flipside:", &mut self.flipside),
+            ] {
+                ui.horizontal(|ui| {
+                    ui.label(name);
+                    ui.add(egui::TextEdit::singleline(key).password(masked));
+                });
+            }
             
             if ui.button("💾 Save Keys").clicked() {
+                self.save_keys();
+            }
+            
+            match &self.save_status {
+                Some(Ok(())) => {
+                    ui.label(RichText::new("✓ Keys saved").small().color(Color32::GREEN));
+                }
+                Some(Err(error)) => {
+                    ui.label(RichText::new(format!("⚠ {}", error)).small().color(Color32::RED));
+                }
+                None => {}
+            }
+        });
+    }
+    
+    fn save_keys(&mut self) {
+        let Some(store) = &self.cred_store else {
+            return;
+        };
+        
+        self.save_status = Some(match self.keys().save(store) {
+            Ok(()) => {
                 tracing::info!("Data source keys saved");
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Failed to save data source keys: {}", e);
+                Err(e.to_string())
             }
         });
     }
+    
+    /// The fields as stored keys; blank fields are unset
+    fn keys(&self) -> DataSourceKeys {
+        let key = |field: &String| Some(field.trim().to_string()).filter(|k| !k.is_empty());
+        DataSourceKeys {
+            gecko_terminal: key(&self.gecko_terminal),
+            birdeye: key(&self.birdeye),
+            the_graph: key(&self.the_graph),
+            crypto_panic: key(&self.crypto_panic),
+            flipside: key(&self.flipside),
+        }
+    }
+    
+    fn fill_from(&mut self, keys: &DataSourceKeys) {
+        let field = |key: &Option<String>| key.clone().unwrap_or_default();
+        self.gecko_terminal = field(&keys.gecko_terminal);
+        self.birdeye = field(&keys.birdeye);
+        self.the_graph = field(&keys.the_graph);
+        self.crypto_panic = field(&keys.crypto_panic);
+        self.flipside = field(&keys.flipside);
+    }
 }
 
 // apps/terminal/src/ui/asset_selector.rs
@@ -273,9 +459,90 @@ pub struct AssetSelectorState {
     pub suggestions: Vec<String>,
     pub selected_assets: Vec<String>,
     pub auto_universe: bool,
+    /// Engine's ranked universe, shown in auto mode
+    pub live_universe: Vec<UniverseAsset>,
+    pub search: SymbolSearchState,
+    /// Last keystroke or venue change not yet sent as a query
+    pub edited_at: Option<std::time::Instant>,
+    /// Query sent to the engine and awaiting its reply
+    pub pending: Option<SymbolQuery>,
+}
+
+/// Autocomplete status shown under the search box
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum SymbolSearchState {
+    #[default]
+    Idle,
+    Loading,
+    Ready,
+    Unavailable(String),
 }
 
+/// Rows of the live universe shown in auto mode
+const AUTO_UNIVERSE_ROWS: usize = 10;
+
+/// Quiet period after the last keystroke before a query is sent
+const SEARCH_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(300);
+
 impl AssetSelectorState {
+    /// Copy the latest universe snapshot in and drive symbol autocomplete,
+    /// without blocking the UI thread
+    pub fn update_from_ws(&mut self, client: &crate::ws_client::MetricsClient) {
+        if let Some(universe) = client.try_universe() {
+            self.live_universe = universe;
+        }
+        
+        if self.edited_at.is_some_and(|t| t.elapsed() >= SEARCH_DEBOUNCE) {
+            self.edited_at = None;
+            let query = SymbolQuery { venue: self.venue, prefix: self.query.trim().to_uppercase() };
+            match client.query_symbols(query.venue, &query.prefix) {
+                Ok(()) => {
+                    self.pending = Some(query);
+                    self.search = SymbolSearchState::Loading;
+                }
+                Err(e) => self.search = SymbolSearchState::Unavailable(e.to_string()),
+            }
+        }
+        
+        if let Some(reply) = client.take_symbol_reply() {
+            // Replies to superseded queries are dropped
+            let current = self.pending.as_ref()
+                .is_some_and(|q| q.venue == reply.venue && q.prefix == reply.prefix);
+            if current {
+                self.pending = None;
+                match reply.error {
+                    Some(error) => {
+                        self.suggestions.clear();
+                        self.search = SymbolSearchState::Unavailable(error);
+                    }
+                    None => {
+                        self.suggestions = reply.symbols;
+                        self.search = SymbolSearchState::Ready;
+                    }
+                }
+            }
+        }
+    }
+    
+    /// No engine connection: explain instead of showing empty results
+    pub fn engine_disconnected(&mut self) {
+        if self.edited_at.take().is_some() || self.pending.take().is_some() {
+            self.suggestions.clear();
+            self.search = SymbolSearchState::Unavailable("Not connected to the engine".to_string());
+        }
+    }
+    
+    fn query_edited(&mut self) {
+        if self.query.trim().len() >= 2 {
+            self.edited_at = Some(std::time::Instant::now());
+        } else {
+            self.edited_at = None;
+            self.pending = None;
+            self.suggestions.clear();
+            self.search = SymbolSearchState::Idle;
+        }
+    }
+    
     pub fn ui(&mut self, ui: &mut Ui) {
         ui.heading("A3. Asset Selection");
         ui.add_space(10.0);
@@ -286,6 +553,7 @@ impl AssetSelectorState {
             ui.group(|ui| {
                 ui.label("Manual Asset Selection");
                 
+                let venue_before = self.venue;
                 ui.horizontal(|ui| {
                     ui.label("Venue:");
                     egui::ComboBox::from_id_source("asset_venue")
@@ -299,13 +567,27 @@ impl AssetSelectorState {
                 
                 ui.horizontal(|ui| {
                     ui.label("Search:");
-                    if ui.text_edit_singleline(&mut self.query).changed() {
-                        if self.query.len() >= 2 {
-                            self.fetch_suggestions();
-                        }
+                    if ui.text_edit_singleline(&mut self.query).changed() || self.venue != venue_before {
+                        self.query_edited();
                     }
                 });
                 
+                match &self.search {
+                    SymbolSearchState::Loading => {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label(RichText::new("Loading…").italics().color(Color32::GRAY));
+                        });
+                    }
+                    SymbolSearchState::Unavailable(reason) => {
+                        ui.label(RichText::new(format!("Symbol search unavailable: {}", reason)).color(Color32::YELLOW));
+                    }
+                    SymbolSearchState::Ready if self.suggestions.is_empty() => {
+                        ui.label(RichText::new("No matching symbols").italics().color(Color32::GRAY));
+                    }
+                    _ => {}
+                }
+                
                 if !self.suggestions.is_empty() {
                     ui.label("Suggestions:");
                     egui::ScrollArea::vertical().max_height(150.0).show(ui, |ui| {
@@ -342,16 +624,35 @@ impl AssetSelectorState {
             ui.label(RichText::new("Engine will automatically select top assets based on universe scoring")
                 .italics()
                 .color(Color32::LIGHT_BLUE));
+            
+            ui.add_space(10.0);
+            
+            ui.group(|ui| {
+                ui.label(RichText::new(format!("Live Universe (top {})", AUTO_UNIVERSE_ROWS)).strong());
+                
+                if self.live_universe.is_empty() {
+                    ui.label(RichText::new("Waiting for the engine's first universe build").italics().color(Color32::GRAY));
+                    return;
+                }
+                
+                egui::Grid::new("live_universe").striped(true).show(ui, |ui| {
+                    ui.label(RichText::new("Rank").strong());
+                    ui.label(RichText::new("Symbol").strong());
+                    ui.label(RichText::new("Venue").strong());
+                    ui.label(RichText::new("Score").strong());
+                    ui.end_row();
+                    
+                    for asset in self.live_universe.iter().take(AUTO_UNIVERSE_ROWS) {
+                        ui.label(format!("#{}", asset.rank));
+                        ui.label(&asset.symbol);
+                        ui.label(format!("{:?}", asset.venue));
+                        ui.label(RichText::new(format!("{:.3}", asset.score)).monospace());
+                        ui.end_row();
+                    }
+                });
+            });
         }
     }
-    
-    fn fetch_suggestions(&mut self) {
-        // TODO: Call engine API for autocomplete
-        self.suggestions = vec![
-            format!("{}USDT", self.query.to_uppercase()),
-            format!("{}-PERP", self.query.to_uppercase()),
-        ];
-    }
 }
 
 // apps/terminal/src/ui/mode_control.rs
@@ -395,8 +696,14 @@ pub struct RiskPanelState {
 }
 
 impl RiskPanelState {
+    /// Copy the latest engine frames in without blocking the UI thread
     pub fn update_from_ws(&mut self, client: &crate::ws_client::MetricsClient) {
-        // TODO: Get latest data from WebSocket client
+        if let Some(risk) = client.try_risk() {
+            self.risk_snapshot = risk;
+        }
+        if let Some(perf) = client.try_performance() {
+            self.perf_metrics = perf;
+        }
     }
     
     pub fn ui(&mut self, ui: &mut Ui) {
@@ -410,6 +717,7 @@ impl RiskPanelState {
                 self.metric_row(ui, "Net Notional:", format!("${:.0}", self.risk_snapshot.net_notional));
                 self.metric_row(ui, "Positions:", format!("{}", self.risk_snapshot.num_positions));
                 self.metric_row(ui, "Margin Used:", format!("${:.0}", self.risk_snapshot.total_margin_used));
+                self.metric_row(ui, "Maintenance:", format!("${:.0}", self.risk_snapshot.total_maintenance_margin));
                 self.metric_row(ui, "Available:", format!("${:.0}", self.risk_snapshot.available_margin));
                 
                 ui.add_space(5.0);
@@ -503,4 +811,186 @@ impl RiskPanelState {
             });
         });
     }
+}
+
+// apps/terminal/src/ui/decision_log.rs
+#[derive(Default)]
+pub struct DecisionLogState {
+    pub decisions: Vec<crate::ws_client::DecisionEntry>,
+    pub executed_only: bool,
+}
+
+impl DecisionLogState {
+    /// Copy the latest decisions in without blocking the UI thread
+    pub fn update_from_ws(&mut self, client: &crate::ws_client::MetricsClient) {
+        if let Some(decisions) = client.try_decisions() {
+            self.decisions = decisions;
+        }
+    }
+    
+    pub fn ui(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label(format!("{} decisions", self.decisions.len()));
+            ui.checkbox(&mut self.executed_only, "Executed only");
+        });
+        
+        ui.add_space(5.0);
+        
+        egui::ScrollArea::vertical()
+            .id_source("decision_log")
+            .max_height(300.0)
+            .show(ui, |ui| {
+                egui::Grid::new("decision_grid")
+                    .striped(true)
+                    .num_columns(8)
+                    .show(ui, |ui| {
+                        for header in ["Time", "Symbol", "Side", "Style", "Size", "Edge (bps)", "Conf", "Result"] {
+                            ui.label(RichText::new(header).strong());
+                        }
+                        ui.end_row();
+                        
+                        // Newest first
+                        for entry in self.decisions.iter().rev().filter(|e| e.executed || !self.executed_only) {
+                            let time = chrono::DateTime::from_timestamp_nanos(entry.timestamp_ns)
+                                .with_timezone(&chrono::Local)
+                                .format("%H:%M:%S%.3f");
+                            ui.monospace(time.to_string());
+                            ui.label(&entry.symbol);
+                            ui.label(entry.decision.side.map_or("-".to_string(), |side| format!("{:?}", side)));
+                            ui.label(format!("{:?}", entry.decision.style));
+                            ui.monospace(format!("{:.4}", entry.decision.size_fraction));
+                            ui.monospace(format!("{:+.2}", entry.prediction.edge_bps));
+                            ui.monospace(format!("{:.3}", entry.prediction.confidence));
+                            
+                            if entry.executed {
+                                ui.colored_label(Color32::GREEN, "Executed");
+                            } else if entry.decision.should_trade {
+                                ui.colored_label(Color32::YELLOW, "Not sent");
+                            } else {
+                                ui.colored_label(Color32::GRAY, &entry.decision.reason);
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    struct StubIndex(Vec<StoredAccount>);
+    
+    impl AccountIndex for StubIndex {
+        fn list_accounts(&self) -> Result<Vec<StoredAccount>> {
+            Ok(self.0.clone())
+        }
+    }
+    
+    #[test]
+    fn test_accounts_load_from_store() {
+        let mut state = AccountManagerState::default();
+        state.accounts.insert("main".to_string(), AccountInfo {
+            venue: Venue::IBKR,
+            is_paper: true,
+            balances: HashMap::new(),
+            stored: false,
+            balance: BalanceStatus::Idle,
+        });
+        
+        state.load_stored_accounts(&StubIndex(vec![
+            StoredAccount { venue: Venue::Hyperliquid, label: "hl".to_string(), live: true },
+            StoredAccount { venue: Venue::BinanceFutures, label: "main".to_string(), live: false },
+        ]));
+        
+        assert_eq!(state.accounts.len(), 2);
+        let hl = &state.accounts["hl"];
+        assert_eq!(hl.venue, Venue::Hyperliquid);
+        assert!(!hl.is_paper);
+        assert!(hl.stored);
+        
+        // An account entered this session isn't replaced
+        assert_eq!(state.accounts["main"].venue, Venue::IBKR);
+        assert!(!state.accounts["main"].stored);
+    }
+    
+    #[test]
+    fn test_passphrase_optional_and_persisted() {
+        let mut state = AccountManagerState {
+            new_label: "passphrase-test".to_string(),
+            venue: Venue::BinanceFutures,
+            is_paper: true,
+            api_key: "key".to_string(),
+            api_secret: "secret".to_string(),
+            ..AccountManagerState::default()
+        };
+        assert!(state.credentials().unwrap().passphrase.is_none());
+        
+        state.passphrase = "hunter2".to_string();
+        let creds = state.credentials().unwrap();
+        assert_eq!(creds.passphrase.as_deref(), Some("hunter2"));
+        
+        let store = CredentialStore::new_simple();
+        store.save(Venue::BinanceFutures, "passphrase-test", &creds).unwrap();
+        let loaded = store.load(Venue::BinanceFutures, "passphrase-test", false).unwrap();
+        assert_eq!(loaded.passphrase.as_deref(), Some("hunter2"));
+        store.delete(Venue::BinanceFutures, "passphrase-test", false).unwrap();
+        
+        // Venues without one never store a stale passphrase
+        state.venue = Venue::Hyperliquid;
+        assert!(state.credentials().unwrap().passphrase.is_none());
+    }
+    
+    #[test]
+    fn test_blank_key_fields_are_unset() {
+        let mut settings = UniverseSettingsState {
+            birdeye: "  be-key ".to_string(),
+            flipside: "   ".to_string(),
+            ..UniverseSettingsState::default()
+        };
+        
+        let keys = settings.keys();
+        assert_eq!(keys.birdeye.as_deref(), Some("be-key"));
+        assert!(keys.flipside.is_none() && keys.gecko_terminal.is_none());
+        
+        let mut stored = DataSourceKeys::default();
+        stored.the_graph = Some("tg-key".to_string());
+        settings.fill_from(&stored);
+        assert_eq!(settings.the_graph, "tg-key");
+        assert!(settings.birdeye.is_empty());
+    }
+    
+    #[test]
+    fn test_balance_reply_lands_in_account() {
+        let mut state = AccountManagerState::default();
+        state.load_stored_accounts(&StubIndex(vec![
+            StoredAccount { venue: Venue::Hyperliquid, label: "hl".to_string(), live: true },
+            StoredAccount { venue: Venue::BinanceFutures, label: "bn".to_string(), live: true },
+        ]));
+        
+        // Without an engine the check fails inline
+        state.accounts.get_mut("hl").unwrap().balance = BalanceStatus::Requested;
+        state.update_from_ws(&crate::ws_client::MetricsClient::empty());
+        assert!(matches!(state.accounts["hl"].balance, BalanceStatus::Failed(_)));
+        
+        state.apply_balance_reply(BalanceReply {
+            venue: Venue::Hyperliquid,
+            label: "hl".to_string(),
+            balances: HashMap::from([("USDC".to_string(), 1_000.0)]),
+            error: None,
+        });
+        state.apply_balance_reply(BalanceReply {
+            venue: Venue::BinanceFutures,
+            label: "bn".to_string(),
+            balances: HashMap::new(),
+            error: Some("Invalid credentials".to_string()),
+        });
+        
+        let hl = &state.accounts["hl"];
+        assert_eq!(hl.balances.get("USDC"), Some(&1_000.0));
+        assert_eq!(hl.balance, BalanceStatus::Idle);
+        assert_eq!(state.accounts["bn"].balance, BalanceStatus::Failed("Invalid credentials".to_string()));
+        assert!(state.accounts["bn"].balances.is_empty());
+    }
 }
\ No newline at end of file
@@ -1,69 +1,322 @@
 // apps/terminal/src/ws_client.rs
 use common::*;
 use futures::{SinkExt, StreamExt};
+use serde::Deserialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use tokio_tungstenite::{connect_async, tungstenite::Message};
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::sync::{mpsc, watch, RwLock};
+use tokio_tungstenite::tungstenite::{client::IntoClientRequest, Message};
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+/// Decisions kept for the rolling log
+const MAX_DECISIONS: usize = 200;
+
+/// Wait before the first reconnect attempt; doubles per failure up to RECONNECT_MAX
+const RECONNECT_MIN: Duration = Duration::from_millis(500);
+const RECONNECT_MAX: Duration = Duration::from_secs(30);
+
+/// The engine's control token, which `/balances` requires
+const CONTROL_TOKEN_ENV: &str = "ENGINE_CONTROL_TOKEN";
+const CONTROL_TOKEN_HEADER: &str = "x-control-token";
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// One routing decision from the engine's `/decisions` feed
+#[derive(Debug, Clone, Deserialize)]
+pub struct DecisionEntry {
+    pub timestamp_ns: i64,
+    pub symbol: String,
+    pub prediction: Prediction,
+    pub decision: RouteDecision,
+    /// An order was sent for it
+    #[serde(default)]
+    pub executed: bool,
+}
+
+/// Which state a streaming socket's frames update
+#[derive(Debug, Clone, Copy)]
+enum Feed {
+    Frames,
+    Decisions,
+}
 
 /// Metrics client for terminal UI
+#[derive(Clone)]
 pub struct MetricsClient {
     performance: Arc<RwLock<PerformanceMetrics>>,
     risk: Arc<RwLock<RiskSnapshot>>,
     alerts: Arc<RwLock<Vec<Alert>>>,
+    universe: Arc<RwLock<Vec<UniverseAsset>>>,
+    decisions: Arc<RwLock<VecDeque<DecisionEntry>>>,
+    symbol_tx: mpsc::UnboundedSender<SymbolQuery>,
+    symbol_reply: Arc<RwLock<Option<SymbolReply>>>,
+    balance_tx: mpsc::UnboundedSender<BalanceQuery>,
+    balance_replies: Arc<RwLock<VecDeque<BalanceReply>>>,
+    /// The metrics socket is open; false while it's being re-established
+    connected: Arc<AtomicBool>,
+    /// Set by `close`; the socket tasks exit instead of reconnecting
+    stop: Arc<watch::Sender<bool>>,
 }
 
 impl MetricsClient {
+    /// Connect to the engine's metrics socket, plus its `/universe`,
+    /// `/symbols`, `/balances` and `/decisions` sockets on the same host when
+    /// the engine serves them. Each socket reconnects on its own if it drops.
+    /// `/balances` is only served to clients with `ENGINE_CONTROL_TOKEN` set.
     pub async fn connect(url: &str) -> Result<Self> {
-        let (ws_stream, _) = connect_async(url)
-            .await
-            .map_err(|e| Error::WebSocket(format!("Connection failed: {}", e)))?;
+        let (symbol_tx, symbol_rx) = mpsc::unbounded_channel();
+        let (balance_tx, balance_rx) = mpsc::unbounded_channel();
+        let client = Self { symbol_tx, balance_tx, ..Self::empty() };
+        client.subscribe(url, Feed::Frames, Some(client.connected.clone())).await?;
         
-        let (mut write, mut read) = ws_stream.split();
+        let universe_url = sibling_url(url, "/universe");
+        if let Err(e) = client.subscribe(&universe_url, Feed::Frames, None).await {
+            tracing::warn!("Universe stream unavailable at {}: {}", universe_url, e);
+        }
         
-        let performance = Arc::new(RwLock::new(PerformanceMetrics::default()));
-        let risk = Arc::new(RwLock::new(RiskSnapshot::default()));
-        let alerts = Arc::new(RwLock::new(Vec::new()));
+        let symbols_url = sibling_url(url, "/symbols");
+        if let Err(e) = client.subscribe_symbols(&symbols_url, symbol_rx).await {
+            tracing::warn!("Symbol search unavailable at {}: {}", symbols_url, e);
+        }
         
-        let perf_clone = performance.clone();
-        let risk_clone = risk.clone();
-        let alerts_clone = alerts.clone();
+        let balances_url = sibling_url(url, "/balances");
+        let token = std::env::var(CONTROL_TOKEN_ENV).ok().filter(|t| !t.is_empty());
+        if let Err(e) = client.subscribe_balances(&balances_url, token, balance_rx).await {
+            tracing::warn!("Balance queries unavailable at {}: {}", balances_url, e);
+        }
+        
+        let decisions_url = sibling_url(url, "/decisions");
+        if let Err(e) = client.subscribe(&decisions_url, Feed::Decisions, None).await {
+            tracing::warn!("Decision feed unavailable at {}: {}", decisions_url, e);
+        }
+        
+        Ok(client)
+    }
+    
+    /// Append one decision frame, dropping the oldest past MAX_DECISIONS
+    async fn apply_decision(&self, text: &str) {
+        let entry = match serde_json::from_str::<DecisionEntry>(text) {
+            Ok(entry) => entry,
+            Err(e) => {
+                tracing::warn!("Malformed decision frame: {}", e);
+                return;
+            }
+        };
+        
+        let mut decisions = self.decisions.write().await;
+        decisions.push_back(entry);
+        if decisions.len() > MAX_DECISIONS {
+            decisions.pop_front();
+        }
+    }
+    
+    /// Forward queued symbol queries and keep the latest reply
+    async fn subscribe_symbols(&self, url: &str, mut queries: mpsc::UnboundedReceiver<SymbolQuery>) -> Result<()> {
+        let mut socket = open(url, None).await?;
+        let replies = self.symbol_reply.clone();
+        let mut stop = self.stop.subscribe();
+        let url = url.to_string();
         
-        // Spawn receive loop
         tokio::spawn(async move {
-            while let Some(msg) = read.next().await {
-                match msg {
-                    Ok(Message::Text(text)) => {
-                        // Try to parse as different message types
-                        if let Ok(perf) = serde_json::from_str::<PerformanceMetrics>(&text) {
-                            *perf_clone.write().await = perf;
-                        } else if let Ok(r) = serde_json::from_str::<RiskSnapshot>(&text) {
-                            *risk_clone.write().await = r;
-                        } else if let Ok(alert) = serde_json::from_str::<Alert>(&text) {
-                            let mut alerts = alerts_clone.write().await;
-                            alerts.push(alert);
-                            if alerts.len() > 100 {
-                                alerts.remove(0);
+            loop {
+                tokio::select! {
+                    _ = stopped(&mut stop) => break,
+                    query = queries.recv() => {
+                        let Some(query) = query else { break };
+                        let Ok(json) = serde_json::to_string(&query) else { continue };
+                        match send_or_reopen(socket, &url, json, &mut stop).await {
+                            Some(reopened) => socket = reopened,
+                            None => break,
+                        }
+                    }
+                    msg = socket.next() => {
+                        let dropped = match msg {
+                            Some(Ok(Message::Text(text))) => {
+                                match serde_json::from_str::<SymbolReply>(&text) {
+                                    Ok(reply) => *replies.write().await = Some(reply),
+                                    Err(e) => tracing::warn!("Malformed symbol reply: {}", e),
+                                }
+                                false
                             }
+                            Some(Ok(Message::Close(_))) | None => true,
+                            Some(Err(e)) => {
+                                tracing::error!("Symbols WebSocket error: {}", e);
+                                true
+                            }
+                            _ => false,
+                        };
+                        if dropped {
+                            let Some(reopened) = reopen(&url, None, &mut stop).await else { break };
+                            socket = reopened;
                         }
                     }
-                    Ok(Message::Close(_)) => {
-                        tracing::warn!("WebSocket closed");
-                        break;
+                }
+            }
+        });
+        
+        Ok(())
+    }
+    
+    /// Forward queued balance queries, authenticated by `token`, and queue
+    /// every reply. Queries still unanswered when the socket drops get an
+    /// error reply rather than waiting on an answer that won't come.
+    async fn subscribe_balances(
+        &self,
+        url: &str,
+        token: Option<String>,
+        mut queries: mpsc::UnboundedReceiver<BalanceQuery>,
+    ) -> Result<()> {
+        let mut socket = open(url, token.as_deref()).await?;
+        let replies = self.balance_replies.clone();
+        let mut stop = self.stop.subscribe();
+        let url = url.to_string();
+        
+        tokio::spawn(async move {
+            let token = token.as_deref();
+            let mut pending: VecDeque<BalanceQuery> = VecDeque::new();
+            loop {
+                let dropped = tokio::select! {
+                    _ = stopped(&mut stop) => break,
+                    query = queries.recv() => {
+                        let Some(query) = query else { break };
+                        let Ok(json) = serde_json::to_string(&query) else { continue };
+                        pending.push_back(query);
+                        socket.send(Message::Text(json.into())).await.is_err()
                     }
-                    Err(e) => {
-                        tracing::error!("WebSocket error: {}", e);
-                        break;
+                    msg = socket.next() => match msg {
+                        Some(Ok(Message::Text(text))) => {
+                            match serde_json::from_str::<BalanceReply>(&text) {
+                                Ok(reply) => {
+                                    let asked = pending.iter().position(|q| q.venue == reply.venue && q.label == reply.label);
+                                    if let Some(i) = asked {
+                                        pending.remove(i);
+                                    }
+                                    replies.write().await.push_back(reply);
+                                }
+                                Err(e) => tracing::warn!("Malformed balance reply: {}", e),
+                            }
+                            false
+                        }
+                        Some(Ok(Message::Close(_))) | None => true,
+                        Some(Err(e)) => {
+                            tracing::error!("Balances WebSocket error: {}", e);
+                            true
+                        }
+                        _ => false,
+                    },
+                };
+                
+                if dropped {
+                    fail_pending(&replies, &mut pending).await;
+                    let Some(reopened) = reopen(&url, token, &mut stop).await else { break };
+                    socket = reopened;
+                }
+            }
+        });
+        
+        Ok(())
+    }
+    
+    /// Apply every frame from `url` to `feed`, reopening the socket whenever
+    /// it drops. `connected`, when given, tracks whether it's open.
+    async fn subscribe(&self, url: &str, feed: Feed, connected: Option<Arc<AtomicBool>>) -> Result<()> {
+        let mut socket = open(url, None).await?;
+        let receiver = self.clone();
+        let mut stop = self.stop.subscribe();
+        let url = url.to_string();
+        let set_connected = move |up: bool| {
+            if let Some(flag) = &connected {
+                flag.store(up, Ordering::Relaxed);
+            }
+        };
+        set_connected(true);
+        
+        tokio::spawn(async move {
+            loop {
+                loop {
+                    let msg = tokio::select! {
+                        _ = stopped(&mut stop) => return,
+                        msg = socket.next() => msg,
+                    };
+                    match msg {
+                        Some(Ok(Message::Text(text))) => match feed {
+                            Feed::Frames => receiver.apply_frame(&text).await,
+                            Feed::Decisions => receiver.apply_decision(&text).await,
+                        },
+                        Some(Ok(Message::Close(_))) | None => break,
+                        Some(Err(e)) => {
+                            tracing::error!("WebSocket error on {}: {}", url, e);
+                            break;
+                        }
+                        _ => {}
                     }
-                    _ => {}
                 }
+                
+                tracing::warn!("WebSocket {} closed, reconnecting", url);
+                set_connected(false);
+                let Some(reopened) = reopen(&url, None, &mut stop).await else { return };
+                socket = reopened;
+                set_connected(true);
             }
         });
         
-        Ok(Self {
-            performance,
-            risk,
-            alerts,
-        })
+        Ok(())
+    }
+    
+    pub(crate) fn empty() -> Self {
+        // No query sockets: queries fail until `connect` wires them up
+        let (symbol_tx, _) = mpsc::unbounded_channel();
+        let (balance_tx, _) = mpsc::unbounded_channel();
+        Self {
+            performance: Arc::new(RwLock::new(PerformanceMetrics::default())),
+            risk: Arc::new(RwLock::new(RiskSnapshot::default())),
+            alerts: Arc::new(RwLock::new(Vec::new())),
+            universe: Arc::new(RwLock::new(Vec::new())),
+            decisions: Arc::new(RwLock::new(VecDeque::new())),
+            symbol_tx,
+            symbol_reply: Arc::new(RwLock::new(None)),
+            balance_tx,
+            balance_replies: Arc::new(RwLock::new(VecDeque::new())),
+            connected: Arc::new(AtomicBool::new(false)),
+            stop: Arc::new(watch::channel(false).0),
+        }
+    }
+    
+    /// Stop every socket task, including ones waiting to reconnect
+    pub fn close(&self) {
+        self.stop.send_replace(true);
+    }
+    
+    /// The metrics socket is up; the UI shows "reconnecting" while it isn't
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+    
+    /// Store one text frame from the engine
+    async fn apply_frame(&self, text: &str) {
+        let msg = match serde_json::from_str::<WsMsg>(text) {
+            Ok(msg) => msg,
+            Err(e) => {
+                tracing::warn!("Malformed engine frame: {}", e);
+                return;
+            }
+        };
+        
+        match msg {
+            WsMsg::Metrics(perf) => *self.performance.write().await = perf,
+            WsMsg::Risk(risk) => *self.risk.write().await = risk,
+            WsMsg::Universe(universe) => *self.universe.write().await = universe,
+            WsMsg::Alert(alert) => {
+                let mut alerts = self.alerts.write().await;
+                alerts.push(alert);
+                if alerts.len() > 100 {
+                    alerts.remove(0);
+                }
+            }
+        }
     }
     
     pub async fn get_performance(&self) -> PerformanceMetrics {
@@ -77,4 +330,309 @@ impl MetricsClient {
     pub async fn get_alerts(&self) -> Vec<Alert> {
         self.alerts.read().await.clone()
     }
+    
+    /// Ranked universe from the engine's last rebuild/refresh
+    pub async fn get_universe(&self) -> Vec<UniverseAsset> {
+        self.universe.read().await.clone()
+    }
+    
+    /// Non-blocking read for the egui update loop; `None` while the receive
+    /// task holds the lock, in which case the caller keeps its last value
+    pub fn try_performance(&self) -> Option<PerformanceMetrics> {
+        self.performance.try_read().ok().map(|p| p.clone())
+    }
+    
+    /// Non-blocking read for the egui update loop, see `try_performance`
+    pub fn try_risk(&self) -> Option<RiskSnapshot> {
+        self.risk.try_read().ok().map(|r| r.clone())
+    }
+    
+    /// Ask the engine for `venue` symbols starting with `prefix`; the answer
+    /// arrives via `take_symbol_reply`
+    pub fn query_symbols(&self, venue: Venue, prefix: &str) -> Result<()> {
+        self.symbol_tx
+            .send(SymbolQuery { venue, prefix: prefix.to_string() })
+            .map_err(|_| Error::WebSocket("symbol search is not connected".to_string()))
+    }
+    
+    /// Latest symbol reply, if one arrived since the last call (non-blocking)
+    pub fn take_symbol_reply(&self) -> Option<SymbolReply> {
+        self.symbol_reply.try_write().ok().and_then(|mut reply| reply.take())
+    }
+    
+    /// Ask the engine for the live balances of a saved account; the answer
+    /// arrives via `take_balance_reply`
+    pub fn query_balances(&self, venue: Venue, label: &str, live: bool) -> Result<()> {
+        self.balance_tx
+            .send(BalanceQuery { venue, label: label.to_string(), live })
+            .map_err(|_| Error::WebSocket("balance queries are not connected".to_string()))
+    }
+    
+    /// Oldest balance reply not yet taken (non-blocking)
+    pub fn take_balance_reply(&self) -> Option<BalanceReply> {
+        self.balance_replies.try_write().ok().and_then(|mut replies| replies.pop_front())
+    }
+    
+    /// Non-blocking read for the egui update loop, see `try_performance`
+    pub fn try_universe(&self) -> Option<Vec<UniverseAsset>> {
+        self.universe.try_read().ok().map(|u| u.clone())
+    }
+    
+    /// Non-blocking read for the egui update loop, oldest first
+    pub fn try_decisions(&self) -> Option<Vec<DecisionEntry>> {
+        self.decisions.try_read().ok().map(|d| d.iter().cloned().collect())
+    }
+}
+
+/// Replace the path of a `ws://host:port/path` URL
+fn sibling_url(url: &str, path: &str) -> String {
+    let authority_start = url.find("://").map(|i| i + 3).unwrap_or(0);
+    let base = match url[authority_start..].find('/') {
+        Some(i) => &url[..authority_start + i],
+        None => url,
+    };
+    format!("{}{}", base, path)
+}
+
+/// Open `url`, presenting `token` as the control token when given
+async fn open(url: &str, token: Option<&str>) -> Result<Socket> {
+    let mut request = url
+        .into_client_request()
+        .map_err(|e| Error::WebSocket(format!("Invalid URL {}: {}", url, e)))?;
+    if let Some(token) = token {
+        let value = token
+            .parse()
+            .map_err(|_| Error::WebSocket(format!("{} is not a valid header value", CONTROL_TOKEN_ENV)))?;
+        request.headers_mut().insert(CONTROL_TOKEN_HEADER, value);
+    }
+    
+    let (socket, _) = connect_async(request)
+        .await
+        .map_err(|e| Error::WebSocket(format!("Connection failed: {}", e)))?;
+    Ok(socket)
+}
+
+/// Reopen `url` after it dropped, backing off between failed attempts;
+/// `None` once the client is closed
+async fn reopen(url: &str, token: Option<&str>, stop: &mut watch::Receiver<bool>) -> Option<Socket> {
+    let mut delay = RECONNECT_MIN;
+    loop {
+        let attempt = async {
+            tokio::time::sleep(delay).await;
+            open(url, token).await
+        };
+        let result = tokio::select! {
+            _ = stopped(stop) => return None,
+            result = attempt => result,
+        };
+        
+        match result {
+            Ok(socket) => {
+                tracing::info!("Reconnected to {}", url);
+                return Some(socket);
+            }
+            Err(e) => {
+                tracing::debug!("Reconnect to {} failed: {}", url, e);
+                delay = (delay * 2).min(RECONNECT_MAX);
+            }
+        }
+    }
+}
+
+/// Send `json`, reopening the socket and resending once if it has dropped;
+/// `None` once the client is closed
+async fn send_or_reopen(mut socket: Socket, url: &str, json: String, stop: &mut watch::Receiver<bool>) -> Option<Socket> {
+    if socket.send(Message::Text(json.clone().into())).await.is_ok() {
+        return Some(socket);
+    }
+    
+    let mut socket = reopen(url, None, stop).await?;
+    let _ = socket.send(Message::Text(json.into())).await;
+    Some(socket)
+}
+
+/// Resolves once `MetricsClient::close` has been called
+async fn stopped(stop: &mut watch::Receiver<bool>) {
+    let _ = stop.wait_for(|stop| *stop).await;
+}
+
+/// Answer every query the dropped socket took with an error
+async fn fail_pending(replies: &RwLock<VecDeque<BalanceReply>>, pending: &mut VecDeque<BalanceQuery>) {
+    let mut replies = replies.write().await;
+    for query in pending.drain(..) {
+        replies.push_back(BalanceReply {
+            venue: query.venue,
+            label: query.label,
+            balances: HashMap::new(),
+            error: Some("Connection to the engine dropped before it answered".to_string()),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[tokio::test]
+    async fn test_risk_frame_readable_without_blocking() {
+        let client = MetricsClient::empty();
+        let frame = serde_json::to_string(&WsMsg::Risk(RiskSnapshot {
+            gross_notional: 125_000.0,
+            num_positions: 3,
+            kill_switch_active: true,
+            ..RiskSnapshot::default()
+        })).unwrap();
+        
+        client.apply_frame(&frame).await;
+        
+        let risk = client.try_risk().expect("risk lock is free");
+        assert_eq!(risk.gross_notional, 125_000.0);
+        assert_eq!(risk.num_positions, 3);
+        assert!(risk.kill_switch_active);
+        
+        // A risk frame must not be mistaken for performance metrics
+        assert_eq!(client.try_performance().unwrap().orders_per_sec, 0.0);
+    }
+    
+    #[tokio::test]
+    async fn test_universe_frame_and_sibling_url() {
+        assert_eq!(sibling_url("ws://localhost:8081/metrics", "/universe"), "ws://localhost:8081/universe");
+        assert_eq!(sibling_url("ws://localhost:8081", "/universe"), "ws://localhost:8081/universe");
+        
+        let client = MetricsClient::empty();
+        let frame = serde_json::to_string(&WsMsg::Universe(vec![UniverseAsset {
+            symbol: "ETH".to_string(),
+            venue: Venue::Hyperliquid,
+            category: AssetCategory::CryptoFutures,
+            score: 0.7,
+            rank: 2,
+            metrics: AssetMetrics::default(),
+        }])).unwrap();
+        
+        client.apply_frame(&frame).await;
+        
+        let universe = client.get_universe().await;
+        assert_eq!(universe.len(), 1);
+        assert_eq!(universe[0].rank, 2);
+        assert_eq!(client.try_performance().unwrap().orders_per_sec, 0.0);
+        
+        // Without a symbols socket a query fails instead of waiting forever
+        assert!(client.query_symbols(Venue::Hyperliquid, "BT").is_err());
+        assert!(client.take_symbol_reply().is_none());
+        assert!(client.query_balances(Venue::Hyperliquid, "main", true).is_err());
+        assert!(client.take_balance_reply().is_none());
+    }
+    
+    #[tokio::test]
+    async fn test_reconnects_after_drop() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/metrics", listener.local_addr().unwrap());
+        let risk = |num_positions: usize| {
+            let frame = WsMsg::Risk(RiskSnapshot { num_positions, ..RiskSnapshot::default() });
+            Message::Text(serde_json::to_string(&frame).unwrap().into())
+        };
+        
+        let engine = tokio::spawn(async move {
+            // First session: one frame, then the engine goes away
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            ws.send(risk(1)).await.unwrap();
+            drop(ws);
+            
+            // The client comes back on its own
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            ws.send(risk(2)).await.unwrap();
+            ws
+        });
+        
+        let client = MetricsClient::empty();
+        client.subscribe(&url, Feed::Frames, Some(client.connected.clone())).await.unwrap();
+        assert!(client.is_connected());
+        
+        let _ws = tokio::time::timeout(Duration::from_secs(5), engine).await.unwrap().unwrap();
+        let resumed = tokio::time::timeout(Duration::from_secs(5), async {
+            while client.get_risk().await.num_positions != 2 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }).await;
+        assert!(resumed.is_ok(), "state stopped updating after the reconnect");
+        assert!(client.is_connected());
+    }
+    
+    #[tokio::test]
+    async fn test_dropped_balance_query_fails_and_close_stops_reconnects() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/balances", listener.local_addr().unwrap());
+        
+        let (balance_tx, balance_rx) = mpsc::unbounded_channel();
+        let client = MetricsClient { balance_tx, ..MetricsClient::empty() };
+        let engine = tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            (listener, ws)
+        });
+        client.subscribe_balances(&url, None, balance_rx).await.unwrap();
+        let (listener, mut ws) = engine.await.unwrap();
+        
+        // The engine takes the query, then goes away without answering
+        client.query_balances(Venue::Hyperliquid, "main", true).unwrap();
+        assert!(ws.next().await.unwrap().unwrap().is_text());
+        drop(ws);
+        
+        let reply = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                if let Some(reply) = client.take_balance_reply() {
+                    return reply;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }).await.expect("the lost query was never answered");
+        assert_eq!(reply.label, "main");
+        assert!(reply.error.unwrap().contains("dropped"));
+        
+        // Closed while backing off: no reconnect arrives
+        client.close();
+        let reconnect = tokio::time::timeout(RECONNECT_MIN * 3, listener.accept()).await;
+        assert!(reconnect.is_err(), "closed client reconnected");
+    }
+    
+    #[tokio::test]
+    async fn test_decision_frames_roll() {
+        let client = MetricsClient::empty();
+        let frame = |i: usize| serde_json::json!({
+            "timestamp_ns": i,
+            "symbol": "BTC",
+            "category": "CryptoFutures",
+            "prediction": {
+                "timestamp_ns": i,
+                "symbol": "BTC",
+                "edge_bps": 3.0,
+                "confidence": 0.2,
+                "horizon_ms": 5000,
+                "model_version": "test",
+            },
+            "decision": {
+                "style": "MakerPassive",
+                "size_fraction": 0.0,
+                "hold_duration_s": 0.0,
+                "urgency": 0.0,
+                "should_trade": false,
+                "side": null,
+                "reason": "Low confidence: 0.200 < 0.600",
+            },
+        }).to_string();
+        
+        client.apply_decision("not json").await;
+        for i in 0..MAX_DECISIONS + 5 {
+            client.apply_decision(&frame(i)).await;
+        }
+        
+        let decisions = client.try_decisions().unwrap();
+        assert_eq!(decisions.len(), MAX_DECISIONS);
+        assert_eq!(decisions[0].timestamp_ns, 5);
+        assert!(!decisions[0].decision.should_trade);
+        assert!(!decisions[0].executed);
+        assert!(decisions[0].decision.reason.starts_with("Low confidence"));
+    }
 }
\ No newline at end of file
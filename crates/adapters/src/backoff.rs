@@ -0,0 +1,66 @@
+// crates/adapters/src/backoff.rs - Reconnect delays for venue streams
+use rand::Rng;
+use std::time::Duration;
+
+/// Exponential reconnect backoff with jitter
+#[derive(Debug, Clone)]
+pub struct ReconnectBackoff {
+    base: Duration,
+    max: Duration,
+    attempt: u32,
+}
+
+impl Default for ReconnectBackoff {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(500), Duration::from_secs(30))
+    }
+}
+
+impl ReconnectBackoff {
+    pub fn new(base: Duration, max: Duration) -> Self {
+        Self { base, max, attempt: 0 }
+    }
+    
+    /// Delay before the next attempt: `base * 2^attempt` capped at `max`, the
+    /// upper half jittered so clients dropped together don't retry in lockstep
+    pub fn next_delay(&mut self) -> Duration {
+        let ceiling = self.base.saturating_mul(1 << self.attempt.min(16)).min(self.max);
+        self.attempt = self.attempt.saturating_add(1);
+        
+        let half = ceiling / 2;
+        half + half.mul_f64(rand::thread_rng().gen::<f64>())
+    }
+    
+    /// Failed attempts since the last `reset`
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+    
+    /// Start over from `base` once a connection has proven healthy
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_backoff_grows_caps_and_resets() {
+        let mut backoff = ReconnectBackoff::new(Duration::from_millis(100), Duration::from_secs(1));
+        
+        let bounds = [(50, 100), (100, 200), (200, 400), (400, 800), (500, 1_000), (500, 1_000)];
+        for (low, high) in bounds {
+            let delay = backoff.next_delay();
+            assert!(
+                delay >= Duration::from_millis(low) && delay <= Duration::from_millis(high),
+                "{:?} outside {}..{}ms", delay, low, high
+            );
+        }
+        assert_eq!(backoff.attempt(), 6);
+        
+        backoff.reset();
+        assert!(backoff.next_delay() <= Duration::from_millis(100));
+    }
+}
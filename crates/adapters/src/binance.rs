@@ -1,5 +1,1322 @@
-// TODO: Implement similar to hyperliquid.rs
-// Follow the same pattern with WebSocket and REST API
+// crates/adapters/src/binance.rs
+use crate::*;
+use common::*;
+use common::security::{sign_request, ApiCredentials};
+use futures::{SinkExt, StreamExt};
+use serde::Deserialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::{mpsc, RwLock};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+const REST_URL: &str = "https://fapi.binance.com";
+const WS_URL: &str = "wss://fstream.binance.com";
+const TESTNET_REST_URL: &str = "https://testnet.binancefuture.com";
+const TESTNET_WS_URL: &str = "wss://stream.binancefuture.com";
+
+const RECV_WINDOW_MS: u64 = 5000;
+const SNAPSHOT_DEPTH: usize = 1000;
+const BOOK_DEPTH: usize = 20;
+const MAX_RECENT_TRADES: usize = 100;
+/// Diff events held while waiting for a depth snapshot
+const MAX_PENDING_UPDATES: usize = 1000;
+
+/// How often the countdown cancel is refreshed while the stream is healthy
+const DEAD_MAN_REFRESH: std::time::Duration = std::time::Duration::from_secs(15);
+
+/// Binance error codes for orders that no longer exist
+const UNKNOWN_ORDER_CODES: [i64; 2] = [-2011, -2013];
+
+/// Commission rates are per symbol; the account's tier is read off this one
+const FEE_REFERENCE_SYMBOL: &str = "BTCUSDT";
+
+/// Assumed when `/fapi/v1/commissionRate` can't be read
+const FALLBACK_FEES: FeeTier = FeeTier {
+    maker_fee_bps: 2.0,
+    taker_fee_bps: 5.0,
+    volume_30d: 0.0,
+};
+
+type HookSlot = Arc<parking_lot::RwLock<Option<Arc<dyn DisconnectHook>>>>;
+
+/// Venue identity of an order we placed
+#[derive(Debug, Clone)]
+struct TrackedOrder {
+    symbol: String,
+    order_id: u64,
+}
+
+/// State shared between the adapter and its stream tasks
+#[derive(Clone)]
+struct StreamState {
+    books: Arc<RwLock<HashMap<String, DepthSync>>>,
+    trades: Arc<parking_lot::Mutex<HashMap<String, VecDeque<Trade>>>>,
+    snapshot_tx: mpsc::UnboundedSender<MarketSnapshot>,
+    client: reqwest::Client,
+    rate_limiter: RateLimiter,
+    rest_url: &'static str,
+}
+
+impl StreamState {
+    fn record_trade(&self, trade: Trade) {
+        let mut trades = self.trades.lock();
+        let recent = trades.entry(trade.symbol.clone()).or_default();
+        
+        recent.push_back(trade);
+        while recent.len() > MAX_RECENT_TRADES {
+            recent.pop_front();
+        }
+    }
+    
+    fn emit(&self, sync: &DepthSync) {
+        let symbol = sync.book.symbol.clone();
+        let recent_trades = self.trades
+            .lock()
+            .get(&symbol)
+            .map(|t| t.iter().cloned().collect())
+            .unwrap_or_default();
+        
+        let snapshot = MarketSnapshot {
+            timestamp_ns: sync.timestamp_ns,
+            symbol,
+            orderbook: sync.book.to_orderbook(sync.timestamp_ns, BOOK_DEPTH),
+            recent_trades,
+            funding_rate_bps: None,
+            open_interest: None,
+            volume_24h: 0.0,
+            quality: DataQuality::Live,
+        };
+        
+        let _ = self.snapshot_tx.send(snapshot);
+    }
+}
+
 pub struct BinanceAdapter {
-    // Implementation needed
+    credentials: ApiCredentials,
+    ws_url: &'static str,
+    stream: StreamState,
+    snapshot_rx: std::sync::Mutex<Option<mpsc::UnboundedReceiver<MarketSnapshot>>>,
+    connected: AtomicBool,
+    disconnect_hook: HookSlot,
+    orders: parking_lot::RwLock<HashMap<String, TrackedOrder>>,
+    leverage_tiers: parking_lot::RwLock<HashMap<String, Vec<margin::LeverageTier>>>,
+    dry_run: DryRun,
+}
+
+impl BinanceAdapter {
+    /// USDⓈ-M futures adapter; paper credentials use the futures testnet
+    pub fn new(credentials: ApiCredentials) -> Self {
+        let (snapshot_tx, snapshot_rx) = mpsc::unbounded_channel();
+        let (rest_url, ws_url) = if credentials.is_paper {
+            (TESTNET_REST_URL, TESTNET_WS_URL)
+        } else {
+            (REST_URL, WS_URL)
+        };
+        
+        Self {
+            credentials,
+            ws_url,
+            stream: StreamState {
+                books: Arc::new(RwLock::new(HashMap::new())),
+                trades: Arc::new(parking_lot::Mutex::new(HashMap::new())),
+                snapshot_tx,
+                client: reqwest::Client::builder()
+                    .timeout(std::time::Duration::from_secs(10))
+                    .build()
+                    .unwrap(),
+                rate_limiter: RateLimiter::new(50, 5.0), // 5 req/sec
+                rest_url,
+            },
+            snapshot_rx: std::sync::Mutex::new(Some(snapshot_rx)),
+            connected: AtomicBool::new(false),
+            disconnect_hook: Arc::new(parking_lot::RwLock::new(None)),
+            orders: parking_lot::RwLock::new(HashMap::new()),
+            leverage_tiers: parking_lot::RwLock::new(HashMap::new()),
+            dry_run: DryRun::default(),
+        }
+    }
+    
+    /// Log and locally ack orders instead of submitting them (see `DryRun`)
+    pub fn with_dry_run(self, enabled: bool) -> Self {
+        self.dry_run.set_enabled(enabled);
+        self
+    }
+    
+    pub fn dry_run(&self) -> &DryRun {
+        &self.dry_run
+    }
+    
+    fn stream_url(&self, symbols: &[String], channel: &str) -> String {
+        let streams: Vec<String> = symbols
+            .iter()
+            .map(|s| format!("{}@{}", s.to_lowercase(), channel))
+            .collect();
+        
+        format!("{}/stream?streams={}", self.ws_url, streams.join("/"))
+    }
+    
+    /// Run one combined-stream connection; `hook` is only set for the depth stream
+    async fn ws_loop(url: String, state: StreamState, hook_slot: Option<HookSlot>) {
+        loop {
+            match connect_async(url.as_str()).await {
+                Ok((ws_stream, _)) => {
+                    tracing::info!("Binance WS connected");
+                    let (mut write, mut read) = ws_stream.split();
+                    
+                    let hook = hook_slot.as_ref().and_then(|slot| slot.read().clone());
+                    let mut refresh = tokio::time::interval(DEAD_MAN_REFRESH);
+                    
+                    loop {
+                        tokio::select! {
+                            _ = refresh.tick() => {
+                                if let Some(hook) = &hook {
+                                    hook.on_alive(Venue::BinanceFutures).await;
+                                }
+                            }
+                            msg = read.next() => match msg {
+                                Some(Ok(Message::Text(text))) => {
+                                    if let Err(e) = Self::handle_ws_message(&text, &state).await {
+                                        tracing::warn!("Failed to handle Binance WS message: {}", e);
+                                    }
+                                }
+                                Some(Ok(Message::Ping(payload))) => {
+                                    if write.send(Message::Pong(payload)).await.is_err() {
+                                        break;
+                                    }
+                                }
+                                Some(Ok(Message::Close(_))) | None => {
+                                    tracing::warn!("Binance WS closed");
+                                    break;
+                                }
+                                Some(Err(e)) => {
+                                    tracing::error!("Binance WS error: {}", e);
+                                    break;
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                    
+                    if let Some(hook) = &hook {
+                        // Books are stale once the diff stream drops
+                        let symbols: Vec<String> = {
+                            let mut books = state.books.write().await;
+                            books.values_mut().for_each(DepthSync::reset);
+                            books.keys().cloned().collect()
+                        };
+                        hook.on_disconnect(Venue::BinanceFutures, &symbols).await;
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("Failed to connect to Binance WS: {}", e);
+                }
+            }
+            
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+        }
+    }
+    
+    async fn handle_ws_message(text: &str, state: &StreamState) -> Result<()> {
+        #[derive(Deserialize)]
+        struct Combined {
+            data: serde_json::Value,
+        }
+        
+        let msg: Combined = serde_json::from_str(text)?;
+        
+        match msg.data.get("e").and_then(|e| e.as_str()) {
+            Some("depthUpdate") => {
+                let update: DepthUpdate = serde_json::from_value(msg.data)?;
+                Self::handle_depth(update, state).await
+            }
+            Some("aggTrade") => {
+                let trade: AggTrade = serde_json::from_value(msg.data)?;
+                state.record_trade(trade.into_trade()?);
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+    
+    async fn handle_depth(update: DepthUpdate, state: &StreamState) -> Result<()> {
+        let symbol = update.symbol.clone();
+        
+        let needs_snapshot = {
+            let mut books = state.books.write().await;
+            let sync = books
+                .entry(symbol.clone())
+                .or_insert_with(|| DepthSync::new(symbol.clone()));
+            
+            match sync.on_update(update) {
+                SyncOutcome::Applied => {
+                    state.emit(sync);
+                    false
+                }
+                SyncOutcome::Stale => false,
+                SyncOutcome::Buffered => true,
+                SyncOutcome::Gap => {
+                    tracing::warn!("Binance depth gap on {}; resyncing from snapshot", symbol);
+                    true
+                }
+            }
+        };
+        
+        if !needs_snapshot {
+            return Ok(());
+        }
+        
+        let snapshot = Self::fetch_depth_snapshot(state, &symbol).await?;
+        let mut books = state.books.write().await;
+        
+        if let Some(sync) = books.get_mut(&symbol) {
+            if sync.load_snapshot(snapshot) {
+                state.emit(sync);
+            }
+        }
+        
+        Ok(())
+    }
+    
+    async fn fetch_depth_snapshot(state: &StreamState, symbol: &str) -> Result<DepthSnapshot> {
+        let query = encode_query(&[
+            ("symbol", symbol.to_string()),
+            ("limit", SNAPSHOT_DEPTH.to_string()),
+        ]);
+        
+        Self::request_with(state, reqwest::Method::GET, "/fapi/v1/depth", &query, None).await
+    }
+    
+    /// Resolve a client id (or raw venue order id) to the order's venue identity
+    fn resolve_order(&self, order_id: &str) -> Result<TrackedOrder> {
+        let orders = self.orders.read();
+        
+        if let Some(order) = orders.get(order_id) {
+            return Ok(order.clone());
+        }
+        
+        if let Ok(id) = order_id.parse::<u64>() {
+            if let Some(order) = orders.values().find(|o| o.order_id == id) {
+                return Ok(order.clone());
+            }
+        }
+        
+        Err(Error::NotFound(format!("Unknown Binance order: {}", order_id)))
+    }
+    
+    fn forget_order(&self, order_id: u64) {
+        self.orders.write().retain(|_, o| o.order_id != order_id);
+    }
+    
+    async fn public_get<R: for<'de> Deserialize<'de>>(
+        &self,
+        path: &str,
+        params: &[(&str, String)],
+    ) -> Result<R> {
+        let query = encode_query(params);
+        Self::request_with(&self.stream, reqwest::Method::GET, path, &query, None).await
+    }
+    
+    /// Signed (USER_DATA / TRADE) request
+    async fn signed<R: for<'de> Deserialize<'de>>(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        params: &[(&str, String)],
+    ) -> Result<R> {
+        let query = sign_query(
+            &self.credentials.api_secret,
+            params,
+            chrono::Utc::now().timestamp_millis(),
+        );
+        
+        Self::request_with(&self.stream, method, path, &query, Some(&self.credentials.api_key)).await
+    }
+    
+    async fn request_with<R: for<'de> Deserialize<'de>>(
+        state: &StreamState,
+        method: reqwest::Method,
+        path: &str,
+        query: &str,
+        api_key: Option<&str>,
+    ) -> Result<R> {
+        let _guard = state.rate_limiter.acquire().await;
+        
+        let url = if query.is_empty() {
+            format!("{}{}", state.rest_url, path)
+        } else {
+            format!("{}{}?{}", state.rest_url, path, query)
+        };
+        
+        let mut request = state.client.request(method, url);
+        if let Some(key) = api_key {
+            request = request.header("X-MBX-APIKEY", key);
+        }
+        
+        let response = request.send().await?;
+        
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(api_error(&error_text));
+        }
+        
+        Ok(response.json().await?)
+    }
+}
+
+#[async_trait]
+impl MarketDataStream for BinanceAdapter {
+    async fn subscribe_orderbook(&mut self, symbols: &[String]) -> Result<()> {
+        {
+            let mut books = self.stream.books.write().await;
+            for symbol in symbols {
+                books
+                    .entry(symbol.to_uppercase())
+                    .or_insert_with(|| DepthSync::new(symbol.to_uppercase()));
+            }
+        }
+        
+        let url = self.stream_url(symbols, "depth");
+        let state = self.stream.clone();
+        let hook_slot = self.disconnect_hook.clone();
+        
+        tokio::spawn(async move {
+            Self::ws_loop(url, state, Some(hook_slot)).await;
+        });
+        
+        Ok(())
+    }
+    
+    async fn subscribe_trades(&mut self, symbols: &[String]) -> Result<()> {
+        let url = self.stream_url(symbols, "aggTrade");
+        let state = self.stream.clone();
+        
+        tokio::spawn(async move {
+            Self::ws_loop(url, state, None).await;
+        });
+        
+        Ok(())
+    }
+    
+    fn snapshot_receiver(&self) -> Result<mpsc::UnboundedReceiver<MarketSnapshot>> {
+        take_receiver(&self.snapshot_rx, Venue::BinanceFutures)
+    }
+}
+
+#[async_trait]
+impl AccountData for BinanceAdapter {
+    async fn balances(&self) -> Result<HashMap<String, Balance>> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct BalanceItem {
+            asset: String,
+            balance: String,
+            available_balance: String,
+        }
+        
+        let items: Vec<BalanceItem> = self.signed(reqwest::Method::GET, "/fapi/v2/balance", &[]).await?;
+        
+        let mut balances = HashMap::new();
+        for item in items {
+            let total = item.balance.parse::<f64>().unwrap_or(0.0);
+            let free = item.available_balance.parse::<f64>().unwrap_or(0.0);
+            balances.insert(
+                item.asset.clone(),
+                Balance {
+                    asset: item.asset,
+                    free,
+                    locked: (total - free).max(0.0),
+                    total,
+                },
+            );
+        }
+        
+        Ok(balances)
+    }
+    
+    async fn positions(&self) -> Result<Vec<Position>> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct PositionRisk {
+            symbol: String,
+            position_amt: String,
+            entry_price: String,
+            mark_price: String,
+            #[serde(rename = "unRealizedProfit")]
+            unrealized_profit: String,
+            leverage: String,
+            liquidation_price: String,
+            notional: String,
+        }
+        
+        let items: Vec<PositionRisk> = self.signed(reqwest::Method::GET, "/fapi/v2/positionRisk", &[]).await?;
+        
+        let positions = items
+            .into_iter()
+            .filter_map(|pos| {
+                let size: f64 = pos.position_amt.parse().ok()?;
+                if size == 0.0 {
+                    return None;
+                }
+                
+                let leverage: f64 = pos.leverage.parse().unwrap_or(1.0);
+                let notional: f64 = pos.notional.parse().unwrap_or(0.0);
+                let liquidation_price: f64 = pos.liquidation_price.parse().unwrap_or(0.0);
+                
+                Some(Position {
+                    symbol: pos.symbol,
+                    size,
+                    entry_price: pos.entry_price.parse().unwrap_or(0.0),
+                    mark_price: pos.mark_price.parse().unwrap_or(0.0),
+                    unrealized_pnl: pos.unrealized_profit.parse().unwrap_or(0.0),
+                    realized_pnl: 0.0,
+                    leverage,
+                    margin_used: notional.abs() / leverage.max(1.0),
+                    liquidation_price: (liquidation_price > 0.0).then_some(liquidation_price),
+                    maintenance_margin: None,
+                })
+            })
+            .collect();
+        
+        Ok(positions)
+    }
+    
+    async fn fee_tier(&self) -> Result<FeeTier> {
+        let params = [("symbol", FEE_REFERENCE_SYMBOL.to_string())];
+        let rate: Result<CommissionRate> = self.signed(reqwest::Method::GET, "/fapi/v1/commissionRate", &params).await;
+        
+        Ok(rate
+            .and_then(|rate| rate.to_fee_tier())
+            .unwrap_or_else(|e| fallback_fee_tier(Venue::BinanceFutures, FALLBACK_FEES, &e.to_string())))
+    }
+    
+    async fn leverage(&self) -> Result<f64> {
+        Ok(1.0)
+    }
+}
+
+#[async_trait]
+impl OrderRouter for BinanceAdapter {
+    async fn send_order(&self, order: OrderRequest) -> Result<OrderAck> {
+        if self.dry_run.is_enabled() {
+            return Ok(self.dry_run.send(Venue::BinanceFutures, &order));
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Response {
+            order_id: u64,
+            status: String,
+            update_time: i64,
+            #[serde(default)]
+            avg_price: Option<String>,
+        }
+        
+        let params = order_params(&order)?;
+        let resp: Response = self
+            .signed(reqwest::Method::POST, "/fapi/v1/order", &params)
+            .await
+            .map_err(|e| match e {
+                Error::Venue(msg) => Error::OrderRejected(msg),
+                e => e,
+            })?;
+        
+        let status = map_order_status(&resp.status);
+        if matches!(status, OrderStatus::Accepted | OrderStatus::PartiallyFilled) {
+            self.orders.write().insert(
+                order.client_id.clone(),
+                TrackedOrder { symbol: order.symbol.to_uppercase(), order_id: resp.order_id },
+            );
+        }
+        
+        Ok(OrderAck {
+            venue_order_id: resp.order_id.to_string(),
+            client_id: order.client_id,
+            status,
+            timestamp_ns: resp.update_time * 1_000_000,
+            avg_fill_price: avg_fill_price(resp.avg_price.as_deref()),
+        })
+    }
+    
+    async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        if self.dry_run.is_enabled() {
+            self.dry_run.cancel(Venue::BinanceFutures, order_id);
+            return Ok(());
+        }
+        
+        let order = self.resolve_order(order_id)?;
+        let params = [
+            ("symbol", order.symbol.clone()),
+            ("orderId", order.order_id.to_string()),
+        ];
+        
+        let result: Result<serde_json::Value> = self.signed(reqwest::Method::DELETE, "/fapi/v1/order", &params).await;
+        
+        match result {
+            Ok(_) => {
+                self.forget_order(order.order_id);
+                Ok(())
+            }
+            Err(Error::NotFound(msg)) => {
+                self.forget_order(order.order_id);
+                Err(Error::NotFound(format!("Binance order {} ({}): {}", order_id, order.order_id, msg)))
+            }
+            Err(e) => Err(e),
+        }
+    }
+    
+    async fn cancel_all(&self, symbol: &str) -> Result<()> {
+        if self.dry_run.is_enabled() {
+            self.dry_run.cancel_all(Venue::BinanceFutures, symbol);
+            return Ok(());
+        }
+
+        let symbol = symbol.to_uppercase();
+        let params = [("symbol", symbol.clone())];
+        
+        let _: serde_json::Value = self.signed(reqwest::Method::DELETE, "/fapi/v1/allOpenOrders", &params).await?;
+        self.orders.write().retain(|_, o| o.symbol != symbol);
+        
+        Ok(())
+    }
+    
+    async fn get_order(&self, order_id: &str) -> Result<OrderAck> {
+        if let Some(ack) = self.dry_run.lookup(order_id) {
+            return Ok(ack);
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Response {
+            order_id: u64,
+            client_order_id: String,
+            status: String,
+            update_time: i64,
+            #[serde(default)]
+            avg_price: Option<String>,
+        }
+        
+        let order = self.resolve_order(order_id)?;
+        let params = [
+            ("symbol", order.symbol.clone()),
+            ("orderId", order.order_id.to_string()),
+        ];
+        
+        let resp: Response = self.signed(reqwest::Method::GET, "/fapi/v1/order", &params).await?;
+        
+        let status = map_order_status(&resp.status);
+        if matches!(status, OrderStatus::Filled | OrderStatus::Cancelled | OrderStatus::Rejected) {
+            self.forget_order(resp.order_id);
+        }
+        
+        Ok(OrderAck {
+            venue_order_id: resp.order_id.to_string(),
+            client_id: resp.client_order_id,
+            status,
+            timestamp_ns: resp.update_time * 1_000_000,
+            avg_fill_price: avg_fill_price(resp.avg_price.as_deref()),
+        })
+    }
+    
+    /// Binance's countdown cancel is per symbol; arm it for every subscribed book
+    async fn schedule_cancel(&self, deadline_ms: u64) -> Result<bool> {
+        if self.dry_run.is_enabled() {
+            return Ok(false);
+        }
+
+        let symbols: Vec<String> = self.stream.books.read().await.keys().cloned().collect();
+        if symbols.is_empty() {
+            return Ok(false);
+        }
+        
+        for symbol in symbols {
+            let params = [
+                ("symbol", symbol),
+                ("countdownTime", deadline_ms.to_string()),
+            ];
+            let _: serde_json::Value = self.signed(reqwest::Method::POST, "/fapi/v1/countdownCancelAll", &params).await?;
+        }
+        
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl MarketInfo for BinanceAdapter {
+    async fn list_symbols(&self) -> Result<Vec<String>> {
+        #[derive(Deserialize)]
+        struct Response {
+            symbols: Vec<SymbolItem>,
+        }
+        
+        #[derive(Deserialize)]
+        struct SymbolItem {
+            symbol: String,
+            status: String,
+        }
+        
+        let resp: Response = self.public_get("/fapi/v1/exchangeInfo", &[]).await?;
+        
+        Ok(resp.symbols
+            .into_iter()
+            .filter(|s| s.status == "TRADING")
+            .map(|s| s.symbol)
+            .collect())
+    }
+    
+    async fn search_symbols(&self, prefix: &str) -> Result<Vec<String>> {
+        let all_symbols = self.list_symbols().await?;
+        Ok(all_symbols
+            .into_iter()
+            .filter(|s| s.to_lowercase().starts_with(&prefix.to_lowercase()))
+            .collect())
+    }
+    
+    /// Last funding rate, as a fraction per funding interval
+    async fn funding_rate(&self, symbol: &str) -> Result<f64> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Response {
+            last_funding_rate: String,
+        }
+        
+        let resp: Response = self.public_get("/fapi/v1/premiumIndex", &[("symbol", symbol.to_uppercase())]).await?;
+        parse_num(&resp.last_funding_rate, "lastFundingRate")
+    }
+    
+    async fn open_interest(&self, symbol: &str) -> Result<f64> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Response {
+            open_interest: String,
+        }
+        
+        let resp: Response = self.public_get("/fapi/v1/openInterest", &[("symbol", symbol.to_uppercase())]).await?;
+        parse_num(&resp.open_interest, "openInterest")
+    }
+    
+    /// 24h quote-asset volume
+    async fn volume_24h(&self, symbol: &str) -> Result<f64> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Response {
+            quote_volume: String,
+        }
+        
+        let resp: Response = self.public_get("/fapi/v1/ticker/24hr", &[("symbol", symbol.to_uppercase())]).await?;
+        parse_num(&resp.quote_volume, "quoteVolume")
+    }
+    
+    /// Notional brackets for every symbol, fetched once and cached
+    async fn leverage_tiers(&self, symbol: &str) -> Result<Vec<margin::LeverageTier>> {
+        let symbol = symbol.to_uppercase();
+        
+        if self.leverage_tiers.read().is_empty() {
+            let brackets: Vec<SymbolBrackets> = self
+                .signed(reqwest::Method::GET, "/fapi/v1/leverageBracket", &[])
+                .await?;
+            *self.leverage_tiers.write() = brackets
+                .into_iter()
+                .map(|b| (b.symbol.clone(), tiers_from_brackets(b)))
+                .collect();
+        }
+        
+        self.leverage_tiers
+            .read()
+            .get(&symbol)
+            .cloned()
+            .ok_or_else(|| Error::NotFound(format!("No Binance leverage brackets for {}", symbol)))
+    }
+    
+    async fn symbol_spec(&self, symbol: &str) -> Result<SymbolSpec> {
+        #[derive(Deserialize)]
+        struct Response {
+            symbols: Vec<SpecItem>,
+        }
+        
+        let symbol = symbol.to_uppercase();
+        let resp: Response = self.public_get("/fapi/v1/exchangeInfo", &[("symbol", symbol.clone())]).await?;
+        
+        resp.symbols
+            .into_iter()
+            .find(|s| s.symbol == symbol)
+            .ok_or_else(|| Error::NotFound(format!("No Binance symbol {}", symbol)))
+            .and_then(symbol_spec)
+    }
+}
+
+/// One symbol of `/fapi/v1/exchangeInfo`, filters only
+#[derive(Debug, Deserialize)]
+struct SpecItem {
+    symbol: String,
+    filters: Vec<SymbolFilter>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "filterType")]
+enum SymbolFilter {
+    #[serde(rename = "PRICE_FILTER", rename_all = "camelCase")]
+    Price { tick_size: String },
+    #[serde(rename = "LOT_SIZE", rename_all = "camelCase")]
+    Lot { step_size: String },
+    #[serde(other)]
+    Other,
+}
+
+/// Tick from `PRICE_FILTER`, lot from `LOT_SIZE`; both are required
+fn symbol_spec(item: SpecItem) -> Result<SymbolSpec> {
+    let mut tick_size = None;
+    let mut lot_size = None;
+    
+    for filter in &item.filters {
+        match filter {
+            SymbolFilter::Price { tick_size: tick } => tick_size = Some(parse_num(tick, "tickSize")?),
+            SymbolFilter::Lot { step_size } => lot_size = Some(parse_num(step_size, "stepSize")?),
+            SymbolFilter::Other => {}
+        }
+    }
+    
+    match (tick_size, lot_size) {
+        (Some(tick_size), Some(lot_size)) => Ok(SymbolSpec { symbol: item.symbol, tick_size, lot_size }),
+        _ => Err(Error::Venue(format!("Binance {} is missing PRICE_FILTER or LOT_SIZE", item.symbol))),
+    }
+}
+
+/// One symbol of `/fapi/v1/leverageBracket`
+#[derive(Debug, Deserialize)]
+struct SymbolBrackets {
+    symbol: String,
+    brackets: Vec<Bracket>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Bracket {
+    initial_leverage: f64,
+    notional_floor: f64,
+    maint_margin_ratio: f64,
+    /// Maintenance amount, already continuous across brackets
+    cum: f64,
+}
+
+fn tiers_from_brackets(brackets: SymbolBrackets) -> Vec<margin::LeverageTier> {
+    let mut tiers: Vec<_> = brackets.brackets
+        .into_iter()
+        .map(|b| margin::LeverageTier {
+            notional_floor: b.notional_floor,
+            max_leverage: b.initial_leverage,
+            maintenance_margin_rate: b.maint_margin_ratio,
+            maintenance_amount: b.cum,
+        })
+        .collect();
+    tiers.sort_by(|a, b| a.notional_floor.total_cmp(&b.notional_floor));
+    tiers
+}
+
+#[async_trait]
+impl ExchangeAdapter for BinanceAdapter {
+    fn venue(&self) -> Venue {
+        Venue::BinanceFutures
+    }
+    
+    fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+    
+    async fn connect(&mut self) -> Result<()> {
+        self.connected.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+    
+    async fn disconnect(&mut self) -> Result<()> {
+        self.connected.store(false, Ordering::Relaxed);
+        Ok(())
+    }
+    
+    fn set_disconnect_hook(&self, hook: Arc<dyn DisconnectHook>) {
+        *self.disconnect_hook.write() = Some(hook);
+    }
+}
+
+/// `<symbol>@depth` diff event
+#[derive(Debug, Clone, Deserialize)]
+struct DepthUpdate {
+    #[serde(rename = "E")]
+    event_time: i64,
+    #[serde(rename = "s")]
+    symbol: String,
+    #[serde(rename = "U")]
+    first_update_id: u64,
+    #[serde(rename = "u")]
+    final_update_id: u64,
+    /// Previous event's `u` (futures streams only)
+    #[serde(rename = "pu", default)]
+    prev_final_update_id: Option<u64>,
+    #[serde(rename = "b")]
+    bids: Vec<[String; 2]>,
+    #[serde(rename = "a")]
+    asks: Vec<[String; 2]>,
+}
+
+/// REST `/fapi/v1/depth` snapshot
+#[derive(Debug, Clone, Deserialize)]
+struct DepthSnapshot {
+    #[serde(rename = "lastUpdateId")]
+    last_update_id: u64,
+    #[serde(rename = "T", default)]
+    transaction_time: Option<i64>,
+    bids: Vec<[String; 2]>,
+    asks: Vec<[String; 2]>,
+}
+
+/// `<symbol>@aggTrade` event
+#[derive(Debug, Clone, Deserialize)]
+struct AggTrade {
+    #[serde(rename = "s")]
+    symbol: String,
+    #[serde(rename = "a")]
+    agg_id: u64,
+    #[serde(rename = "p")]
+    price: String,
+    #[serde(rename = "q")]
+    quantity: String,
+    #[serde(rename = "T")]
+    trade_time: i64,
+    /// Buyer was the maker, i.e. the aggressor sold
+    #[serde(rename = "m")]
+    buyer_is_maker: bool,
+}
+
+impl AggTrade {
+    fn into_trade(self) -> Result<Trade> {
+        Ok(Trade {
+            timestamp_ns: self.trade_time * 1_000_000,
+            price: parse_num(&self.price, "aggTrade price")?,
+            quantity: parse_num(&self.quantity, "aggTrade quantity")?,
+            side: if self.buyer_is_maker { Side::Sell } else { Side::Buy },
+            trade_id: self.agg_id.to_string(),
+            symbol: self.symbol,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SyncOutcome {
+    /// Event applied to a synced book
+    Applied,
+    /// Event predates the snapshot and was dropped
+    Stale,
+    /// No snapshot yet; event held for replay
+    Buffered,
+    /// Sequence gap: book reset, event held, snapshot needed
+    Gap,
+}
+
+/// Local book kept in sync with Binance's documented snapshot + diff procedure
+struct DepthSync {
+    book: OrderBookMaintainer,
+    /// `u` of the last applied event (or the snapshot's `lastUpdateId`); `None` until synced
+    last_update_id: Option<u64>,
+    /// Whether an event has been applied since the snapshot
+    bridged: bool,
+    pending: VecDeque<DepthUpdate>,
+    timestamp_ns: i64,
+}
+
+impl DepthSync {
+    fn new(symbol: String) -> Self {
+        Self {
+            book: OrderBookMaintainer::new(symbol),
+            last_update_id: None,
+            bridged: false,
+            pending: VecDeque::new(),
+            timestamp_ns: 0,
+        }
+    }
+    
+    fn reset(&mut self) {
+        self.book.apply_delta(BookDelta::Clear);
+        self.last_update_id = None;
+        self.bridged = false;
+        self.pending.clear();
+    }
+    
+    fn on_update(&mut self, update: DepthUpdate) -> SyncOutcome {
+        let Some(last) = self.last_update_id else {
+            self.buffer(update);
+            return SyncOutcome::Buffered;
+        };
+        
+        if update.final_update_id < last || (self.bridged && update.final_update_id == last) {
+            return SyncOutcome::Stale;
+        }
+        
+        let contiguous = if self.bridged {
+            match update.prev_final_update_id {
+                Some(pu) => pu == last,
+                None => update.first_update_id == last + 1,
+            }
+        } else {
+            // First event must straddle the snapshot
+            update.first_update_id <= last + 1
+        };
+        
+        if !contiguous {
+            self.reset();
+            self.buffer(update);
+            return SyncOutcome::Gap;
+        }
+        
+        self.apply(&update);
+        SyncOutcome::Applied
+    }
+    
+    /// Rebuild from a REST snapshot and replay buffered events; returns whether the book is in sync
+    fn load_snapshot(&mut self, snapshot: DepthSnapshot) -> bool {
+        let pending = std::mem::take(&mut self.pending);
+        
+        self.book.apply_delta(BookDelta::Clear);
+        apply_levels(&mut self.book, Side::Buy, &snapshot.bids);
+        apply_levels(&mut self.book, Side::Sell, &snapshot.asks);
+        self.book.sequence = snapshot.last_update_id;
+        self.last_update_id = Some(snapshot.last_update_id);
+        self.bridged = false;
+        if let Some(t) = snapshot.transaction_time {
+            self.timestamp_ns = t * 1_000_000;
+        }
+        
+        for update in pending {
+            if self.on_update(update) == SyncOutcome::Gap {
+                return false;
+            }
+        }
+        
+        true
+    }
+    
+    fn buffer(&mut self, update: DepthUpdate) {
+        self.pending.push_back(update);
+        while self.pending.len() > MAX_PENDING_UPDATES {
+            self.pending.pop_front();
+        }
+    }
+    
+    fn apply(&mut self, update: &DepthUpdate) {
+        apply_levels(&mut self.book, Side::Buy, &update.bids);
+        apply_levels(&mut self.book, Side::Sell, &update.asks);
+        self.book.sequence = update.final_update_id;
+        self.last_update_id = Some(update.final_update_id);
+        self.bridged = true;
+        self.timestamp_ns = update.event_time * 1_000_000;
+    }
+}
+
+/// Apply absolute level quantities (zero removes the level)
+fn apply_levels(book: &mut OrderBookMaintainer, side: Side, levels: &[[String; 2]]) {
+    for [price, quantity] in levels {
+        let (Ok(price), Ok(quantity)) = (price.parse::<f64>(), quantity.parse::<f64>()) else {
+            tracing::warn!("Skipping malformed Binance level on {}: {} @ {}", book.symbol, quantity, price);
+            continue;
+        };
+        
+        book.apply_delta(BookDelta::Update { side, price, quantity });
+    }
+}
+
+/// `/fapi/v1/commissionRate` response; rates are fractions of notional
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CommissionRate {
+    maker_commission_rate: String,
+    taker_commission_rate: String,
+}
+
+impl CommissionRate {
+    /// The endpoint reports no volume, so `volume_30d` stays 0
+    fn to_fee_tier(&self) -> Result<FeeTier> {
+        Ok(FeeTier {
+            maker_fee_bps: parse_num(&self.maker_commission_rate, "makerCommissionRate")? * 1e4,
+            taker_fee_bps: parse_num(&self.taker_commission_rate, "takerCommissionRate")? * 1e4,
+            volume_30d: 0.0,
+        })
+    }
+}
+
+fn parse_num(value: &str, field: &str) -> Result<f64> {
+    value
+        .parse()
+        .map_err(|_| Error::Venue(format!("Binance returned non-numeric {}: {:?}", field, value)))
+}
+
+fn encode_query(params: &[(&str, String)]) -> String {
+    params
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Append `recvWindow`/`timestamp` and the HMAC-SHA256 `signature` of the query
+fn sign_query(secret: &str, params: &[(&str, String)], timestamp_ms: i64) -> String {
+    let mut query = encode_query(params);
+    if !query.is_empty() {
+        query.push('&');
+    }
+    query.push_str(&format!("recvWindow={}&timestamp={}", RECV_WINDOW_MS, timestamp_ms));
+    
+    let signature = sign_request(secret, &query);
+    format!("{}&signature={}", query, signature)
+}
+
+/// Build `/fapi/v1/order` parameters for an order request
+fn order_params(order: &OrderRequest) -> Result<Vec<(&'static str, String)>> {
+    let side = match order.side {
+        Side::Buy => "BUY",
+        Side::Sell => "SELL",
+    };
+    
+    let mut params = vec![
+        ("symbol", order.symbol.to_uppercase()),
+        ("side", side.to_string()),
+        ("quantity", order.quantity.to_string()),
+        ("newClientOrderId", order.client_id.clone()),
+    ];
+    
+    if order.order_type == OrderType::Market {
+        params.push(("type", "MARKET".to_string()));
+    } else {
+        let price = order.price.ok_or_else(|| {
+            Error::OrderRejected(format!("{:?} order for {} has no price", order.order_type, order.symbol))
+        })?;
+        
+        let tif = match order.order_type {
+            OrderType::PostOnly => TimeInForce::GTX,
+            OrderType::IOC => TimeInForce::IOC,
+            OrderType::FOK => TimeInForce::FOK,
+            _ => order.time_in_force,
+        };
+        
+        params.push(("type", "LIMIT".to_string()));
+        params.push(("price", price.to_string()));
+        params.push(("timeInForce", format!("{:?}", Venue::BinanceFutures.resolve_tif(tif)?)));
+    }
+    
+    if order.reduce_only {
+        params.push(("reduceOnly", "true".to_string()));
+    }
+    
+    Ok(params)
+}
+
+fn api_error(body: &str) -> Error {
+    #[derive(Deserialize)]
+    struct ApiError {
+        code: i64,
+        msg: String,
+    }
+    
+    match serde_json::from_str::<ApiError>(body) {
+        Ok(e) if UNKNOWN_ORDER_CODES.contains(&e.code) => Error::NotFound(format!("Binance order: {}", e.msg)),
+        Ok(e) => Error::Venue(format!("Binance API error {}: {}", e.code, e.msg)),
+        Err(_) => Error::Venue(format!("Binance API error: {}", body)),
+    }
+}
+
+/// `avgPrice` of an order; Binance sends "0" or "0.00000" until something fills
+fn avg_fill_price(avg_price: Option<&str>) -> Option<f64> {
+    avg_price
+        .and_then(|p| p.parse::<f64>().ok())
+        .filter(|p| p.is_finite() && *p > 0.0)
+}
+
+/// Translate Binance order status values into our `OrderStatus`
+fn map_order_status(status: &str) -> OrderStatus {
+    match status {
+        "NEW" => OrderStatus::Accepted,
+        "PARTIALLY_FILLED" => OrderStatus::PartiallyFilled,
+        "FILLED" => OrderStatus::Filled,
+        "CANCELED" | "EXPIRED" | "EXPIRED_IN_MATCH" => OrderStatus::Cancelled,
+        "REJECTED" => OrderStatus::Rejected,
+        _ => OrderStatus::Pending,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn level(price: f64, quantity: f64) -> [String; 2] {
+        [price.to_string(), quantity.to_string()]
+    }
+    
+    fn update(first: u64, last: u64, prev: u64, bids: Vec<[String; 2]>) -> DepthUpdate {
+        DepthUpdate {
+            event_time: 1,
+            symbol: "BTCUSDT".to_string(),
+            first_update_id: first,
+            final_update_id: last,
+            prev_final_update_id: Some(prev),
+            bids,
+            asks: vec![],
+        }
+    }
+    
+    fn snapshot(last_update_id: u64) -> DepthSnapshot {
+        DepthSnapshot {
+            last_update_id,
+            transaction_time: None,
+            bids: vec![level(100.0, 1.0)],
+            asks: vec![level(101.0, 1.0)],
+        }
+    }
+    
+    #[test]
+    fn test_snapshot_then_diff_sync() {
+        let mut sync = DepthSync::new("BTCUSDT".to_string());
+        
+        // Events before the snapshot are buffered, then replayed; stale ones dropped
+        assert_eq!(sync.on_update(update(90, 95, 80, vec![level(99.0, 5.0)])), SyncOutcome::Buffered);
+        assert_eq!(sync.on_update(update(96, 105, 95, vec![level(100.0, 2.0)])), SyncOutcome::Buffered);
+        assert!(sync.load_snapshot(snapshot(100)));
+        
+        let book = sync.book.to_orderbook(0, 10);
+        assert_eq!(book.sequence, 105);
+        assert_eq!(book.bids.len(), 1);
+        assert_eq!(book.bids[0].quantity, 2.0);
+        
+        // Contiguous via `pu`
+        assert_eq!(sync.on_update(update(106, 110, 105, vec![level(100.0, 0.0)])), SyncOutcome::Applied);
+        assert!(sync.book.bids.is_empty());
+        assert_eq!(sync.book.sequence, 110);
+        
+        // Replayed event is stale
+        assert_eq!(sync.on_update(update(106, 110, 105, vec![])), SyncOutcome::Stale);
+    }
+    
+    #[test]
+    fn test_sequence_gap_triggers_resync() {
+        let mut sync = DepthSync::new("BTCUSDT".to_string());
+        sync.load_snapshot(snapshot(100));
+        assert_eq!(sync.on_update(update(95, 102, 90, vec![])), SyncOutcome::Applied);
+        
+        // `pu` doesn't match the last applied `u`: an event was missed
+        assert_eq!(sync.on_update(update(110, 115, 108, vec![level(98.0, 1.0)])), SyncOutcome::Gap);
+        assert!(sync.last_update_id.is_none());
+        assert!(sync.book.bids.is_empty() && sync.book.asks.is_empty());
+        
+        // Fresh snapshot replays the held event
+        assert!(sync.load_snapshot(snapshot(112)));
+        assert_eq!(sync.book.sequence, 115);
+        assert_eq!(sync.book.bids.len(), 2);
+    }
+    
+    #[test]
+    fn test_first_event_must_straddle_snapshot() {
+        let mut sync = DepthSync::new("BTCUSDT".to_string());
+        sync.load_snapshot(snapshot(100));
+        
+        assert_eq!(sync.on_update(update(105, 110, 104, vec![])), SyncOutcome::Gap);
+    }
+    
+    #[test]
+    fn test_sign_query() {
+        // Example from the Binance API documentation
+        let secret = "NhqPtmdSJYdKjVHjA7PZj4Mge3R5YNiP1e3UZjInClVN65XAbvqqM6A7H5fATj0j";
+        let params = [
+            ("symbol", "LTCBTC".to_string()),
+            ("side", "BUY".to_string()),
+            ("type", "LIMIT".to_string()),
+            ("timeInForce", "GTC".to_string()),
+            ("quantity", "1".to_string()),
+            ("price", "0.1".to_string()),
+        ];
+        
+        let query = sign_query(secret, &params, 1499827319559);
+        assert!(query.ends_with("&signature=c8db56825ae71d6d79447849e617115f4a920fa2acdcab2b053c4b2838bd6b71"));
+    }
+    
+    #[test]
+    fn test_order_params() {
+        let order = OrderRequest {
+            client_id: "c1".to_string(),
+            symbol: "btcusdt".to_string(),
+            side: Side::Sell,
+            order_type: OrderType::PostOnly,
+            quantity: 0.5,
+            price: Some(50000.0),
+            reduce_only: true,
+            time_in_force: TimeInForce::GTC,
+        };
+        
+        let params: HashMap<_, _> = order_params(&order).unwrap().into_iter().collect();
+        assert_eq!(params["symbol"], "BTCUSDT");
+        assert_eq!(params["type"], "LIMIT");
+        assert_eq!(params["timeInForce"], "GTX");
+        assert_eq!(params["reduceOnly"], "true");
+        
+        let market = OrderRequest { order_type: OrderType::Market, price: None, ..order };
+        let params: HashMap<_, _> = order_params(&market).unwrap().into_iter().collect();
+        assert_eq!(params["type"], "MARKET");
+        assert!(!params.contains_key("price"));
+    }
+    
+    #[test]
+    fn test_api_error_mapping() {
+        assert!(matches!(api_error(r#"{"code":-2011,"msg":"Unknown order sent."}"#), Error::NotFound(_)));
+        assert!(matches!(api_error(r#"{"code":-1021,"msg":"Timestamp outside recvWindow."}"#), Error::Venue(_)));
+        assert_eq!(map_order_status("EXPIRED"), OrderStatus::Cancelled);
+    }
+    
+    #[test]
+    fn test_tiers_from_brackets() {
+        let brackets: SymbolBrackets = serde_json::from_value(serde_json::json!({
+            "symbol": "ETHUSDT",
+            "brackets": [
+                { "bracket": 2, "initialLeverage": 50, "notionalCap": 250000, "notionalFloor": 50000,
+                  "maintMarginRatio": 0.01, "cum": 250.0 },
+                { "bracket": 1, "initialLeverage": 125, "notionalCap": 50000, "notionalFloor": 0,
+                  "maintMarginRatio": 0.005, "cum": 0.0 }
+            ]
+        })).unwrap();
+        
+        let tiers = tiers_from_brackets(brackets);
+        assert_eq!(tiers[0].max_leverage, 125.0);
+        assert_eq!((tiers[1].notional_floor, tiers[1].maintenance_amount), (50_000.0, 250.0));
+        assert!((margin::maintenance_margin(&tiers, 100_000.0).unwrap() - 750.0).abs() < 1e-9);
+    }
+    
+    #[test]
+    fn test_symbol_spec_from_filters() {
+        let item: SpecItem = serde_json::from_value(serde_json::json!({
+            "symbol": "BTCUSDT",
+            "filters": [
+                { "filterType": "PRICE_FILTER", "minPrice": "556.80", "maxPrice": "4529764", "tickSize": "0.10" },
+                { "filterType": "LOT_SIZE", "minQty": "0.001", "maxQty": "1000", "stepSize": "0.001" },
+                { "filterType": "MIN_NOTIONAL", "notional": "100" }
+            ]
+        })).unwrap();
+        
+        let spec = symbol_spec(item).unwrap();
+        assert_eq!((spec.symbol.as_str(), spec.tick_size, spec.lot_size), ("BTCUSDT", 0.1, 0.001));
+        
+        let bare: SpecItem = serde_json::from_value(serde_json::json!({ "symbol": "X", "filters": [] })).unwrap();
+        assert!(symbol_spec(bare).is_err());
+    }
+    
+    #[test]
+    fn test_commission_rate_to_fee_tier() {
+        let rate: CommissionRate = serde_json::from_value(serde_json::json!({
+            "symbol": "BTCUSDT",
+            "makerCommissionRate": "0.00016",
+            "takerCommissionRate": "0.0004",
+            "rpiCommissionRate": "0.00005"
+        })).unwrap();
+        
+        let tier = rate.to_fee_tier().unwrap();
+        assert!((tier.maker_fee_bps - 1.6).abs() < 1e-9);
+        assert!((tier.taker_fee_bps - 4.0).abs() < 1e-9);
+        
+        let garbled = CommissionRate { taker_commission_rate: "-".to_string(), ..rate };
+        assert!(garbled.to_fee_tier().is_err());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,160 @@
+// crates/adapters/src/cancel.rs - Confirmed, retry-safe cancel-all
+use async_trait::async_trait;
+use common::*;
+use std::time::Duration;
+
+/// Venue operations needed to cancel everything resting on a symbol
+#[async_trait]
+pub trait OpenOrders: Send + Sync {
+    type Order: Send + Sync;
+    
+    /// Orders currently resting on `symbol`, as reported by the venue
+    async fn open_orders(&self, symbol: &str) -> Result<Vec<Self::Order>>;
+    
+    /// Cancel `orders`; orders already gone must count as cancelled
+    async fn cancel_open(&self, orders: &[Self::Order]) -> Result<()>;
+}
+
+/// Attempts and backoff for `cancel_all_confirmed`
+#[derive(Debug, Clone, Copy)]
+pub struct CancelRetry {
+    pub attempts: u32,
+    /// Delay after the first failed attempt, doubled after each further one
+    pub backoff: Duration,
+}
+
+impl Default for CancelRetry {
+    fn default() -> Self {
+        Self {
+            attempts: 4,
+            backoff: Duration::from_millis(250),
+        }
+    }
+}
+
+/// Cancel every open order on `symbol` and only succeed once a re-query shows
+/// none remain. Idempotent: with nothing open it returns `Ok` without
+/// cancelling. Transient failures are retried; non-retryable query errors and
+/// critical cancel errors (auth) abort immediately.
+pub async fn cancel_all_confirmed<V: OpenOrders + ?Sized>(
+    venue: &V,
+    symbol: &str,
+    retry: CancelRetry,
+) -> Result<()> {
+    let mut backoff = retry.backoff;
+    let mut last_error = None;
+    
+    for attempt in 1..=retry.attempts.max(1) {
+        match venue.open_orders(symbol).await {
+            Ok(open) if open.is_empty() => return Ok(()),
+            Ok(open) => {
+                if let Err(e) = venue.cancel_open(&open).await {
+                    if e.is_critical() {
+                        return Err(e);
+                    }
+                    tracing::warn!(
+                        "cancel_all {}: cancelling {} orders failed (attempt {}): {}",
+                        symbol, open.len(), attempt, e
+                    );
+                    last_error = Some(e.to_string());
+                } else {
+                    // Confirm on the next query without waiting
+                    continue;
+                }
+            }
+            Err(e) if e.is_retryable() => {
+                tracing::warn!("cancel_all {}: open order query failed (attempt {}): {}", symbol, attempt, e);
+                last_error = Some(e.to_string());
+            }
+            Err(e) => return Err(e),
+        }
+        
+        if attempt < retry.attempts {
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+        }
+    }
+    
+    // Final confirmation after the last attempt
+    let remaining = venue.open_orders(symbol).await?;
+    if remaining.is_empty() {
+        return Ok(());
+    }
+    
+    Err(Error::Venue(format!(
+        "cancel_all {}: {} orders still open after {} attempts{}",
+        symbol,
+        remaining.len(),
+        retry.attempts,
+        last_error.map(|e| format!(" (last error: {})", e)).unwrap_or_default()
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+    
+    struct MockVenue {
+        open: Mutex<Vec<u64>>,
+        /// Orders the venue refuses to cancel
+        sticky: Vec<u64>,
+        /// Cancel calls that fail transiently before one goes through
+        flaky_cancels: Mutex<u32>,
+        cancelled: Mutex<Vec<u64>>,
+    }
+    
+    impl MockVenue {
+        fn new(open: Vec<u64>, sticky: Vec<u64>, flaky_cancels: u32) -> Self {
+            Self {
+                open: Mutex::new(open),
+                sticky,
+                flaky_cancels: Mutex::new(flaky_cancels),
+                cancelled: Mutex::new(vec![]),
+            }
+        }
+    }
+    
+    #[async_trait]
+    impl OpenOrders for MockVenue {
+        type Order = u64;
+        
+        async fn open_orders(&self, _symbol: &str) -> Result<Vec<u64>> {
+            Ok(self.open.lock().clone())
+        }
+        
+        async fn cancel_open(&self, orders: &[u64]) -> Result<()> {
+            {
+                let mut flaky = self.flaky_cancels.lock();
+                if *flaky > 0 {
+                    *flaky -= 1;
+                    return Err(Error::Timeout("cancel".to_string()));
+                }
+            }
+            self.cancelled.lock().extend_from_slice(orders);
+            self.open.lock().retain(|oid| self.sticky.contains(oid));
+            Ok(())
+        }
+    }
+    
+    fn fast() -> CancelRetry {
+        CancelRetry { attempts: 3, backoff: Duration::from_millis(1) }
+    }
+    
+    #[tokio::test]
+    async fn test_cancel_all_confirms_empty_open_set() {
+        let venue = MockVenue::new(vec![1, 2, 3], vec![], 1);
+        cancel_all_confirmed(&venue, "BTC", fast()).await.unwrap();
+        assert_eq!(*venue.cancelled.lock(), vec![1, 2, 3]);
+        assert!(venue.open.lock().is_empty());
+        
+        // Idempotent: nothing open, nothing cancelled
+        cancel_all_confirmed(&venue, "BTC", fast()).await.unwrap();
+        assert_eq!(venue.cancelled.lock().len(), 3);
+        
+        let venue = MockVenue::new(vec![1, 2], vec![2], 0);
+        let err = cancel_all_confirmed(&venue, "BTC", fast()).await.unwrap_err();
+        assert!(matches!(&err, Error::Venue(m) if m.contains("1 orders still open")), "{}", err);
+        assert!(venue.cancelled.lock().contains(&1));
+    }
+}
@@ -0,0 +1,138 @@
+// crates/adapters/src/dry_run.rs
+use common::*;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// Intents kept for inspection; older ones are only in the logs
+const MAX_INTENTS: usize = 1000;
+
+/// Venue order id prefix for synthetic acks
+pub const DRY_RUN_ID_PREFIX: &str = "dry-";
+
+/// What an adapter would have sent while in dry-run
+#[derive(Debug, Clone)]
+pub enum OrderIntent {
+    Send { venue: Venue, order: OrderRequest },
+    Cancel { venue: Venue, order_id: String },
+    CancelAll { venue: Venue, symbol: String },
+}
+
+/// Order submission switch for live rollouts: unlike paper mode (testnet
+/// endpoints and accounts), dry-run keeps production data and decisions but
+/// stops order traffic at the adapter boundary, logging it and acking locally
+#[derive(Debug, Default)]
+pub struct DryRun {
+    enabled: AtomicBool,
+    next_id: AtomicU64,
+    intents: parking_lot::Mutex<VecDeque<OrderIntent>>,
+    acks: parking_lot::RwLock<HashMap<String, OrderAck>>,
+}
+
+impl DryRun {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled: AtomicBool::new(enabled),
+            ..Default::default()
+        }
+    }
+    
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+    
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+    
+    /// Record an order that would have been sent and return a synthetic ack
+    pub fn send(&self, venue: Venue, order: &OrderRequest) -> OrderAck {
+        tracing::info!(
+            "[dry-run] {:?} would send {:?} {:?} {} {} @ {:?} (client_id {}, reduce_only {}, {:?})",
+            venue, order.order_type, order.side, order.quantity, order.symbol, order.price,
+            order.client_id, order.reduce_only, order.time_in_force
+        );
+        
+        let ack = OrderAck {
+            venue_order_id: format!("{}{}", DRY_RUN_ID_PREFIX, self.next_id.fetch_add(1, Ordering::Relaxed) + 1),
+            client_id: order.client_id.clone(),
+            status: OrderStatus::Accepted,
+            timestamp_ns: chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0),
+            avg_fill_price: None,
+        };
+        
+        self.acks.write().insert(order.client_id.clone(), ack.clone());
+        self.record(OrderIntent::Send { venue, order: order.clone() });
+        ack
+    }
+    
+    /// Record a cancel that would have been sent
+    pub fn cancel(&self, venue: Venue, order_id: &str) {
+        tracing::info!("[dry-run] {:?} would cancel {}", venue, order_id);
+        
+        if let Some(ack) = self.acks.write().get_mut(order_id) {
+            ack.status = OrderStatus::Cancelled;
+        }
+        self.record(OrderIntent::Cancel { venue, order_id: order_id.to_string() });
+    }
+    
+    /// Record a cancel-all that would have been sent
+    pub fn cancel_all(&self, venue: Venue, symbol: &str) {
+        tracing::info!("[dry-run] {:?} would cancel all {} orders", venue, symbol);
+        self.record(OrderIntent::CancelAll { venue, symbol: symbol.to_string() });
+    }
+    
+    /// Synthetic ack for a dry-run order, by client id or synthetic venue id
+    pub fn lookup(&self, order_id: &str) -> Option<OrderAck> {
+        let acks = self.acks.read();
+        
+        acks.get(order_id).cloned().or_else(|| {
+            acks.values().find(|a| a.venue_order_id == order_id).cloned()
+        })
+    }
+    
+    /// Recorded intents, oldest first
+    pub fn intents(&self) -> Vec<OrderIntent> {
+        self.intents.lock().iter().cloned().collect()
+    }
+    
+    fn record(&self, intent: OrderIntent) {
+        let mut intents = self.intents.lock();
+        
+        intents.push_back(intent);
+        while intents.len() > MAX_INTENTS {
+            intents.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_dry_run_acks_and_records() {
+        let dry_run = DryRun::new(true);
+        let order = OrderRequest {
+            client_id: "c1".to_string(),
+            symbol: "BTC".to_string(),
+            side: Side::Buy,
+            order_type: OrderType::Limit,
+            quantity: 0.5,
+            price: Some(100.0),
+            reduce_only: false,
+            time_in_force: TimeInForce::GTC,
+        };
+        
+        let ack = dry_run.send(Venue::Hyperliquid, &order);
+        assert!(ack.venue_order_id.starts_with(DRY_RUN_ID_PREFIX));
+        assert_eq!(ack.status, OrderStatus::Accepted);
+        
+        dry_run.cancel(Venue::Hyperliquid, "c1");
+        assert_eq!(dry_run.lookup(&ack.venue_order_id).unwrap().status, OrderStatus::Cancelled);
+        
+        let intents = dry_run.intents();
+        assert_eq!(intents.len(), 2);
+        assert!(matches!(&intents[0], OrderIntent::Send { order, .. } if order.client_id == "c1"));
+        assert!(matches!(&intents[1], OrderIntent::Cancel { order_id, .. } if order_id == "c1"));
+    }
+}
@@ -0,0 +1,139 @@
+// crates/adapters/src/fallback.rs
+use std::time::{Duration, Instant};
+
+/// REST polling fallback settings
+#[derive(Debug, Clone)]
+pub struct RestFallbackConfig {
+    pub enabled: bool,
+    /// WS downtime tolerated before polling starts
+    pub activate_after: Duration,
+    /// Order book poll interval while active
+    pub poll_interval: Duration,
+}
+
+impl Default for RestFallbackConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            activate_after: Duration::from_secs(10),
+            poll_interval: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Tracks WS health and decides when REST polling should run
+#[derive(Debug)]
+pub struct RestFallback {
+    config: RestFallbackConfig,
+    ws_down_since: Option<Instant>,
+    active: bool,
+}
+
+impl RestFallback {
+    /// The stream starts out disconnected as of `now`
+    pub fn new(config: RestFallbackConfig, now: Instant) -> Self {
+        Self {
+            config,
+            ws_down_since: Some(now),
+            active: false,
+        }
+    }
+    
+    pub fn config(&self) -> &RestFallbackConfig {
+        &self.config
+    }
+    
+    /// WS (re)connected: step down from polling
+    pub fn on_ws_up(&mut self) {
+        if self.active {
+            tracing::info!("WS recovered; stopping REST fallback polling");
+        }
+        
+        self.ws_down_since = None;
+        self.active = false;
+    }
+    
+    /// WS dropped at `now`
+    pub fn on_ws_down(&mut self, now: Instant) {
+        self.ws_down_since.get_or_insert(now);
+    }
+    
+    /// Whether a poll should run at `now`, activating once the WS has been
+    /// down longer than `activate_after`
+    pub fn should_poll(&mut self, now: Instant) -> bool {
+        if !self.config.enabled {
+            return false;
+        }
+        
+        if !self.active {
+            if let Some(since) = self.ws_down_since {
+                if now.duration_since(since) >= self.config.activate_after {
+                    tracing::warn!("WS down for {:?}; starting REST fallback polling", now.duration_since(since));
+                    self.active = true;
+                }
+            }
+        }
+        
+        self.active
+    }
+    
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn config() -> RestFallbackConfig {
+        RestFallbackConfig {
+            enabled: true,
+            activate_after: Duration::from_secs(5),
+            poll_interval: Duration::from_millis(500),
+        }
+    }
+    
+    #[test]
+    fn test_downtime_activates_and_recovery_deactivates() {
+        let t0 = Instant::now();
+        let mut fallback = RestFallback::new(config(), t0);
+        
+        fallback.on_ws_up();
+        assert!(!fallback.should_poll(t0 + Duration::from_secs(60)));
+        
+        // Short blip stays on WS
+        let down = t0 + Duration::from_secs(100);
+        fallback.on_ws_down(down);
+        assert!(!fallback.should_poll(down + Duration::from_secs(2)));
+        
+        // Past the threshold polling kicks in
+        assert!(fallback.should_poll(down + Duration::from_secs(5)));
+        assert!(fallback.is_active());
+        
+        // Repeated drop notifications don't reset the outage start
+        fallback.on_ws_down(down + Duration::from_secs(6));
+        assert!(fallback.should_poll(down + Duration::from_secs(7)));
+        
+        fallback.on_ws_up();
+        assert!(!fallback.is_active());
+        assert!(!fallback.should_poll(down + Duration::from_secs(8)));
+    }
+    
+    #[test]
+    fn test_never_connected_activates() {
+        let t0 = Instant::now();
+        let mut fallback = RestFallback::new(config(), t0);
+        
+        assert!(!fallback.should_poll(t0 + Duration::from_secs(1)));
+        assert!(fallback.should_poll(t0 + Duration::from_secs(5)));
+    }
+    
+    #[test]
+    fn test_disabled_never_polls() {
+        let t0 = Instant::now();
+        let mut fallback = RestFallback::new(RestFallbackConfig::default(), t0);
+        
+        assert!(!fallback.should_poll(t0 + Duration::from_secs(3600)));
+    }
+}
@@ -1,25 +1,101 @@
 // crates/adapters/src/hyperliquid.rs
 use crate::*;
+use crate::hyperliquid_signing::{float_to_wire, ActionSigner, NonceSource, Signature};
 use common::*;
 use common::security::ApiCredentials;
-use futures::{SinkExt, StreamExt};
+use futures::{Sink, SinkExt, Stream, StreamExt};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::{mpsc, RwLock};
-use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tokio_tungstenite::{connect_async, tungstenite::{self, Message}};
 
 const WS_URL: &str = "wss://api.hyperliquid.xyz/ws";
-const REST_URL: &str = "https://api.hyperliquid.xyz/info";
+const REST_BASE: &str = "https://api.hyperliquid.xyz";
+
+/// The adapter trades against `REST_BASE`, so actions are signed for mainnet
+const MAINNET: bool = true;
+
+/// Hyperliquid REST endpoints: reads go to `/info`, signed actions to `/exchange`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Endpoint {
+    Info,
+    Exchange,
+}
+
+impl Endpoint {
+    fn path(self) -> &'static str {
+        match self {
+            Endpoint::Info => "/info",
+            Endpoint::Exchange => "/exchange",
+        }
+    }
+    
+    fn url(self, base: &str) -> String {
+        format!("{}{}", base.trim_end_matches('/'), self.path())
+    }
+}
+
+/// How often the dead-man's switch is refreshed while the stream is healthy
+const DEAD_MAN_REFRESH: std::time::Duration = std::time::Duration::from_secs(15);
+
+/// Trades kept per coin for snapshots
+const MAX_RECENT_TRADES: usize = 100;
+
+/// Market orders go out as aggressive IOC limits this far through the touch
+const MARKET_SLIPPAGE: f64 = 0.05;
+
+/// Assumed when `userFees` can't be read
+const FALLBACK_FEES: FeeTier = FeeTier {
+    maker_fee_bps: 2.0,
+    taker_fee_bps: 5.0,
+    volume_30d: 0.0,
+};
+
+/// Prices carry at most this many significant figures...
+const PRICE_SIG_FIGS: i32 = 5;
+
+/// ...and at most `MAX_PRICE_DECIMALS - szDecimals` decimals
+const MAX_PRICE_DECIMALS: i32 = 6;
+
+type HookSlot = Arc<parking_lot::RwLock<Option<Arc<dyn DisconnectHook>>>>;
+type TradeRing = Arc<parking_lot::Mutex<HashMap<String, VecDeque<Trade>>>>;
+type WsItem = std::result::Result<Message, tungstenite::Error>;
+
+/// Venue identity of an order we placed
+#[derive(Debug, Clone)]
+pub struct RestingOrder {
+    coin: String,
+    oid: u64,
+}
+
+/// Perp asset index and size precision from the `meta` universe
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AssetMeta {
+    index: u32,
+    sz_decimals: u32,
+}
 
 pub struct HyperliquidAdapter {
     credentials: ApiCredentials,
     rate_limiter: RateLimiter,
     snapshot_tx: mpsc::UnboundedSender<MarketSnapshot>,
-    snapshot_rx: Arc<RwLock<Option<mpsc::UnboundedReceiver<MarketSnapshot>>>>,
+    snapshot_rx: std::sync::Mutex<Option<mpsc::UnboundedReceiver<MarketSnapshot>>>,
     books: Arc<RwLock<HashMap<String, OrderBookMaintainer>>>,
+    trades: TradeRing,
     client: reqwest::Client,
-    connected: Arc<RwLock<bool>>,
+    /// Set by `connect` and tracked by the stream task after `subscribe_orderbook`
+    connected: Arc<AtomicBool>,
+    disconnect_hook: HookSlot,
+    fallback: Arc<parking_lot::Mutex<RestFallback>>,
+    orders: parking_lot::RwLock<HashMap<String, RestingOrder>>,
+    assets: parking_lot::RwLock<HashMap<String, AssetMeta>>,
+    leverage_tiers: parking_lot::RwLock<HashMap<String, Vec<margin::LeverageTier>>>,
+    /// Feeds symbols to the running stream after the first subscribe
+    ws_subscriptions: Option<mpsc::UnboundedSender<Vec<String>>>,
+    nonces: NonceSource,
+    dry_run: DryRun,
 }
 
 impl HyperliquidAdapter {
@@ -30,57 +106,175 @@ impl HyperliquidAdapter {
             credentials,
             rate_limiter: RateLimiter::new(100, 10.0), // 10 req/sec
             snapshot_tx,
-            snapshot_rx: Arc::new(RwLock::new(Some(snapshot_rx))),
+            snapshot_rx: std::sync::Mutex::new(Some(snapshot_rx)),
             books: Arc::new(RwLock::new(HashMap::new())),
+            trades: Arc::new(parking_lot::Mutex::new(HashMap::new())),
             client: reqwest::Client::builder()
                 .timeout(std::time::Duration::from_secs(10))
                 .build()
                 .unwrap(),
-            connected: Arc::new(RwLock::new(false)),
+            connected: Arc::new(AtomicBool::new(false)),
+            disconnect_hook: Arc::new(parking_lot::RwLock::new(None)),
+            fallback: Arc::new(parking_lot::Mutex::new(RestFallback::new(
+                RestFallbackConfig::default(),
+                std::time::Instant::now(),
+            ))),
+            orders: parking_lot::RwLock::new(HashMap::new()),
+            assets: parking_lot::RwLock::new(HashMap::new()),
+            leverage_tiers: parking_lot::RwLock::new(HashMap::new()),
+            ws_subscriptions: None,
+            nonces: NonceSource::default(),
+            dry_run: DryRun::default(),
+        }
+    }
+    
+    /// Log and locally ack orders instead of submitting them (see `DryRun`)
+    pub fn with_dry_run(self, enabled: bool) -> Self {
+        self.dry_run.set_enabled(enabled);
+        self
+    }
+    
+    pub fn dry_run(&self) -> &DryRun {
+        &self.dry_run
+    }
+    
+    /// Poll the order book over REST while the WS is down
+    pub fn with_rest_fallback(self, config: RestFallbackConfig) -> Self {
+        *self.fallback.lock() = RestFallback::new(config, std::time::Instant::now());
+        self
+    }
+
+    /// Open the venue stream as a (sink, stream) pair
+    async fn connect_ws() -> Result<(impl Sink<Message, Error = tungstenite::Error>, impl Stream<Item = WsItem>)> {
+        let (ws_stream, _) = connect_async(WS_URL)
+            .await
+            .map_err(|e| Error::WebSocket(format!("Hyperliquid connect failed: {}", e)))?;
+        Ok(ws_stream.split())
+    }
+    
+    /// Subscribe `symbols` on the current connection
+    async fn send_subscriptions<W>(write: &mut W, symbols: &[String]) -> Result<()>
+    where
+        W: Sink<Message> + Unpin,
+        W::Error: std::fmt::Display,
+    {
+        for frame in subscription_frames(symbols) {
+            write
+                .send(Message::Text(frame.into()))
+                .await
+                .map_err(|e| Error::WebSocket(format!("Hyperliquid subscribe failed: {}", e)))?;
         }
+        Ok(())
     }
     
-    async fn ws_loop(
+    /// Stream books for `symbols`, plus any later sent through `added`,
+    /// reconnecting with backoff. Every connection re-sends the subscriptions
+    /// and starts from empty books, since whatever was held when the stream
+    /// dropped is stale.
+    #[allow(clippy::too_many_arguments)]
+    async fn ws_loop<C, F, W, S>(
+        mut connect: C,
+        mut symbols: Vec<String>,
+        mut added: mpsc::UnboundedReceiver<Vec<String>>,
         books: Arc<RwLock<HashMap<String, OrderBookMaintainer>>>,
+        trades: TradeRing,
         snapshot_tx: mpsc::UnboundedSender<MarketSnapshot>,
-    ) {
+        hook_slot: HookSlot,
+        fallback: Arc<parking_lot::Mutex<RestFallback>>,
+        connected: Arc<AtomicBool>,
+        mut backoff: ReconnectBackoff,
+    ) where
+        C: FnMut() -> F,
+        F: std::future::Future<Output = Result<(W, S)>>,
+        W: Sink<Message> + Unpin,
+        W::Error: std::fmt::Display,
+        S: Stream<Item = WsItem> + Unpin,
+    {
         loop {
-            match connect_async(WS_URL).await {
-                Ok((ws_stream, _)) => {
+            match connect().await {
+                Ok((mut write, mut read)) => {
                     tracing::info!("Hyperliquid WS connected");
-                    let (mut write, mut read) = ws_stream.split();
                     
-                    while let Some(msg) = read.next().await {
-                        match msg {
-                            Ok(Message::Text(text)) => {
-                                if let Err(e) = Self::handle_ws_message(&text, &books, &snapshot_tx).await {
-                                    tracing::warn!("Failed to handle WS message: {}", e);
+                    let hook = hook_slot.read().clone();
+                    let mut refresh = tokio::time::interval(DEAD_MAN_REFRESH);
+                    
+                    // Symbols added while disconnected go out with the rest
+                    while let Ok(more) = added.try_recv() {
+                        merge_symbols(&mut symbols, more);
+                    }
+                    
+                    let subscribed = Self::send_subscriptions(&mut write, &symbols).await;
+                    match &subscribed {
+                        Ok(()) => {
+                            connected.store(true, Ordering::Relaxed);
+                            fallback.lock().on_ws_up();
+                        }
+                        Err(e) => tracing::error!("{}", e),
+                    }
+                    
+                    while subscribed.is_ok() {
+                        tokio::select! {
+                            _ = refresh.tick() => {
+                                if let Some(hook) = &hook {
+                                    hook.on_alive(Venue::Hyperliquid).await;
                                 }
                             }
-                            Ok(Message::Close(_)) => {
-                                tracing::warn!("Hyperliquid WS closed");
-                                break;
+                            Some(more) = added.recv() => {
+                                let fresh = merge_symbols(&mut symbols, more);
+                                if let Err(e) = Self::send_subscriptions(&mut write, &fresh).await {
+                                    tracing::error!("{}", e);
+                                    break;
+                                }
                             }
-                            Err(e) => {
-                                tracing::error!("Hyperliquid WS error: {}", e);
-                                break;
+                            msg = read.next() => match msg {
+                                Some(Ok(Message::Text(text))) => {
+                                    // Data is flowing again: the next drop starts from the base delay
+                                    backoff.reset();
+                                    if let Err(e) = Self::handle_ws_message(&text, &books, &trades, &snapshot_tx).await {
+                                        tracing::warn!("Failed to handle WS message: {}", e);
+                                    }
+                                }
+                                Some(Ok(Message::Close(_))) | None => {
+                                    tracing::warn!("Hyperliquid WS closed");
+                                    break;
+                                }
+                                Some(Err(e)) => {
+                                    tracing::error!("Hyperliquid WS error: {}", e);
+                                    break;
+                                }
+                                _ => {}
                             }
-                            _ => {}
                         }
                     }
+                    
+                    connected.store(false, Ordering::Relaxed);
+                    fallback.lock().on_ws_down(std::time::Instant::now());
+                    
+                    // Rebuilt from the first l2Book message after resubscribing
+                    books.write().await.clear();
+                    
+                    // Pull resting orders before the reconnect window
+                    if let Some(hook) = &hook {
+                        hook.on_disconnect(Venue::Hyperliquid, &symbols).await;
+                    }
                 }
                 Err(e) => {
                     tracing::error!("Failed to connect to Hyperliquid WS: {}", e);
+                    fallback.lock().on_ws_down(std::time::Instant::now());
                 }
             }
             
-            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            let delay = backoff.next_delay();
+            metrics::increment_counter!("reconnect_count", "venue" => "hyperliquid");
+            tracing::warn!("Hyperliquid WS reconnecting in {:?} (attempt {})", delay, backoff.attempt());
+            tokio::time::sleep(delay).await;
         }
     }
     
     async fn handle_ws_message(
         text: &str,
         books: &Arc<RwLock<HashMap<String, OrderBookMaintainer>>>,
+        trades: &TradeRing,
         snapshot_tx: &mpsc::UnboundedSender<MarketSnapshot>,
     ) -> Result<()> {
         #[derive(Deserialize)]
@@ -93,47 +287,48 @@ impl HyperliquidAdapter {
         
         match msg.channel.as_str() {
             "l2Book" => {
-                #[derive(Deserialize)]
-                struct L2Book {
-                    coin: String,
-                    levels: Vec<Vec<serde_json::Value>>,
-                    time: i64,
-                }
-                
-                let book: L2Book = serde_json::from_value(msg.data)?;
+                // Parse fully before touching the book so a bad message can't corrupt it
+                let (coin, deltas, timestamp_ns) = parse_l2book(&msg.data)?;
                 let mut books_guard = books.write().await;
                 
                 let maintainer = books_guard
-                    .entry(book.coin.clone())
-                    .or_insert_with(|| OrderBookMaintainer::new(book.coin.clone()));
+                    .entry(coin.clone())
+                    .or_insert_with(|| OrderBookMaintainer::new(coin.clone()));
                 
-                // Process levels
-                for level in book.levels {
-                    if level.len() >= 3 {
-                        let side = if level[0].as_str() == Some("bid") { Side::Buy } else { Side::Sell };
-                        let price = level[1].as_str().and_then(|s| s.parse::<f64>().ok()).unwrap_or(0.0);
-                        let qty = level[2].as_str().and_then(|s| s.parse::<f64>().ok()).unwrap_or(0.0);
-                        
-                        maintainer.apply_delta(BookDelta::Update { side, price, quantity: qty });
-                    }
+                for delta in deltas {
+                    maintainer.apply_delta(delta);
                 }
                 
-                let orderbook = maintainer.to_orderbook(book.time * 1_000_000, 20);
+                let orderbook = maintainer.to_orderbook(timestamp_ns, 20);
+                let recent_trades = trades
+                    .lock()
+                    .get(&coin)
+                    .map(|t| t.iter().cloned().collect())
+                    .unwrap_or_default();
                 
                 let snapshot = MarketSnapshot {
-                    timestamp_ns: book.time * 1_000_000,
-                    symbol: book.coin,
+                    timestamp_ns,
+                    symbol: coin,
                     orderbook,
-                    recent_trades: vec![],
+                    recent_trades,
                     funding_rate_bps: None,
                     open_interest: None,
                     volume_24h: 0.0,
+                    quality: DataQuality::Live,
                 };
                 
                 let _ = snapshot_tx.send(snapshot);
             }
             "trades" => {
-                // Handle trades
+                let mut ring = trades.lock();
+                
+                for trade in parse_trades(&msg.data)? {
+                    let recent = ring.entry(trade.symbol.clone()).or_default();
+                    recent.push_back(trade);
+                    while recent.len() > MAX_RECENT_TRADES {
+                        recent.pop_front();
+                    }
+                }
             }
             _ => {}
         }
@@ -141,320 +336,1839 @@ impl HyperliquidAdapter {
         Ok(())
     }
     
-    async fn post_request<T: Serialize, R: for<'de> Deserialize<'de>>(
-        &self,
-        endpoint: &str,
-        payload: &T,
-    ) -> Result<R> {
-        let _guard = self.rate_limiter.acquire().await;
-        
-        let response = self.client
-            .post(format!("{}/{}", REST_URL, endpoint))
-            .json(payload)
-            .send()
-            .await?;
+    /// Poll order books over REST whenever the fallback is active
+    async fn rest_poll_loop(
+        client: reqwest::Client,
+        rate_limiter: RateLimiter,
+        symbols: Vec<String>,
+        fallback: Arc<parking_lot::Mutex<RestFallback>>,
+        snapshot_tx: mpsc::UnboundedSender<MarketSnapshot>,
+    ) {
+        let poll_interval = fallback.lock().config().poll_interval;
+        let mut ticker = tokio::time::interval(poll_interval);
         
-        if !response.status().is_success() {
-            let error_text = response.text().await?;
-            return Err(Error::Venue(format!("Hyperliquid API error: {}", error_text)));
+        while !snapshot_tx.is_closed() {
+            ticker.tick().await;
+            
+            if !fallback.lock().should_poll(std::time::Instant::now()) {
+                continue;
+            }
+            
+            for symbol in &symbols {
+                match Self::fetch_book(&client, &rate_limiter, symbol).await {
+                    Ok(orderbook) => {
+                        let snapshot = MarketSnapshot {
+                            timestamp_ns: orderbook.timestamp_ns,
+                            symbol: symbol.clone(),
+                            orderbook,
+                            recent_trades: vec![],
+                            funding_rate_bps: None,
+                            open_interest: None,
+                            volume_24h: 0.0,
+                            quality: DataQuality::Degraded,
+                        };
+                        
+                        let _ = snapshot_tx.send(snapshot);
+                    }
+                    Err(e) => tracing::warn!("REST fallback poll failed for {}: {}", symbol, e),
+                }
+            }
         }
-        
-        Ok(response.json().await?)
-    }
-}
-
-#[async_trait]
-impl MarketDataStream for HyperliquidAdapter {
-    async fn subscribe_orderbook(&mut self, symbols: &[String]) -> Result<()> {
-        let books = self.books.clone();
-        let snapshot_tx = self.snapshot_tx.clone();
-        
-        tokio::spawn(async move {
-            Self::ws_loop(books, snapshot_tx).await;
-        });
-        
-        Ok(())
-    }
-    
-    async fn subscribe_trades(&mut self, _symbols: &[String]) -> Result<()> {
-        Ok(())
     }
     
-    fn snapshot_receiver(&self) -> mpsc::UnboundedReceiver<MarketSnapshot> {
-        self.snapshot_rx.write().await.take().expect("Receiver already taken")
-    }
-}
-
-#[async_trait]
-impl AccountData for HyperliquidAdapter {
-    async fn balances(&self) -> Result<HashMap<String, Balance>> {
+    async fn fetch_book(
+        client: &reqwest::Client,
+        rate_limiter: &RateLimiter,
+        symbol: &str,
+    ) -> Result<OrderBook> {
         #[derive(Serialize)]
-        struct Request {
+        struct Request<'a> {
             #[serde(rename = "type")]
-            req_type: String,
-            user: String,
+            req_type: &'a str,
+            coin: &'a str,
         }
         
         #[derive(Deserialize)]
         struct Response {
-            balances: Vec<BalanceItem>,
+            levels: Vec<Vec<RestLevel>>,
+            time: i64,
         }
         
         #[derive(Deserialize)]
-        struct BalanceItem {
-            coin: String,
-            total: String,
+        struct RestLevel {
+            px: String,
+            sz: String,
         }
         
-        let req = Request {
-            req_type: "clearinghouseState".to_string(),
-            user: self.credentials.api_key.clone(),
-        };
+        let req = Request { req_type: "l2Book", coin: symbol };
+        let resp: Response = Self::post_with(client, rate_limiter, Endpoint::Info, &req).await?;
         
-        let resp: Response = self.post_request("info", &req).await?;
-        
-        let mut balances = HashMap::new();
-        for item in resp.balances {
-            let total = item.total.parse::<f64>().unwrap_or(0.0);
-            balances.insert(
-                item.coin.clone(),
-                Balance {
-                    asset: item.coin,
-                    free: total,
-                    locked: 0.0,
-                    total,
-                },
-            );
-        }
+        let to_levels = |side: Option<&Vec<RestLevel>>| -> Result<Vec<Level>> {
+            side.into_iter()
+                .flatten()
+                .map(|l| Ok(Level {
+                    price: ordered_float::OrderedFloat(parse_decimal(&l.px, "l2Book px")?),
+                    quantity: parse_decimal(&l.sz, "l2Book sz")?,
+                }))
+                .collect()
+        };
         
-        Ok(balances)
+        Ok(OrderBook {
+            symbol: symbol.to_string(),
+            timestamp_ns: resp.time * 1_000_000,
+            bids: to_levels(resp.levels.first())?,
+            asks: to_levels(resp.levels.get(1))?,
+            sequence: 0,
+        })
     }
     
-    async fn positions(&self) -> Result<Vec<Position>> {
-        #[derive(Serialize)]
-        struct Request {
-            #[serde(rename = "type")]
-            req_type: String,
-            user: String,
+    /// Resolve a client id (or raw venue oid) to the order's venue identity
+    fn resolve_order(&self, order_id: &str) -> Result<RestingOrder> {
+        let orders = self.orders.read();
+        
+        if let Some(order) = orders.get(order_id) {
+            return Ok(order.clone());
         }
         
-        #[derive(Deserialize)]
-        struct Response {
-            #[serde(rename = "assetPositions")]
-            asset_positions: Vec<PositionItem>,
+        if let Ok(oid) = order_id.parse::<u64>() {
+            if let Some(order) = orders.values().find(|o| o.oid == oid) {
+                return Ok(order.clone());
+            }
         }
         
+        Err(Error::NotFound(format!("Unknown Hyperliquid order: {}", order_id)))
+    }
+    
+    /// Cancel a set of orders in one exchange call, returning per-order outcomes
+    async fn cancel_orders(&self, orders: &[RestingOrder]) -> Result<Vec<std::result::Result<(), String>>> {
         #[derive(Deserialize)]
-        struct PositionItem {
-            position: PositionData,
+        #[serde(untagged)]
+        enum CancelStatus {
+            Success(String),
+            Error { error: String },
         }
         
-        #[derive(Deserialize)]
-        struct PositionData {
-            coin: String,
-            szi: String,
-            #[serde(rename = "entryPx")]
-            entry_px: String,
-            #[serde(rename = "positionValue")]
-            position_value: String,
-            #[serde(rename = "unrealizedPnl")]
-            unrealized_pnl: String,
+        let mut cancels = Vec::with_capacity(orders.len());
+        for order in orders {
+            cancels.push(CancelWire { a: self.asset(&order.coin).await?.index, o: order.oid });
         }
         
-        let req = Request {
-            req_type: "clearinghouseState".to_string(),
-            user: self.credentials.api_key.clone(),
-        };
+        let action = CancelAction { action_type: "cancel", cancels };
+        let raw = self.post_action(&action).await?;
+        let statuses: Vec<CancelStatus> = exchange_statuses(raw)?;
         
-        let resp: Response = self.post_request("info", &req).await?;
+        if statuses.len() != orders.len() {
+            return Err(Error::Venue(format!(
+                "Hyperliquid cancel returned {} statuses for {} orders",
+                statuses.len(), orders.len()
+            )));
+        }
         
-        let positions = resp.asset_positions
+        let outcomes = statuses
             .into_iter()
-            .map(|item| {
-                let pos = item.position;
-                Position {
-                    symbol: pos.coin,
-                    size: pos.szi.parse().unwrap_or(0.0),
-                    entry_price: pos.entry_px.parse().unwrap_or(0.0),
-                    mark_price: 0.0,
-                    unrealized_pnl: pos.unrealized_pnl.parse().unwrap_or(0.0),
-                    realized_pnl: 0.0,
-                    leverage: 1.0,
-                    margin_used: pos.position_value.parse().unwrap_or(0.0),
-                    liquidation_price: None,
-                }
+            .map(|s| match s {
+                CancelStatus::Success(_) => Ok(()),
+                CancelStatus::Error { error } => Err(error),
             })
             .collect();
         
-        Ok(positions)
+        Ok(outcomes)
     }
     
-    async fn fee_tier(&self) -> Result<FeeTier> {
-        Ok(FeeTier {
-            maker_fee_bps: 2.0,
-            taker_fee_bps: 5.0,
-            volume_30d: 0.0,
-        })
+    fn forget_order(&self, oid: u64) {
+        self.orders.write().retain(|_, o| o.oid != oid);
     }
     
-    async fn leverage(&self) -> Result<f64> {
-        Ok(1.0)
-    }
-}
-
-#[async_trait]
-impl OrderRouter for HyperliquidAdapter {
-    async fn send_order(&self, order: OrderRequest) -> Result<OrderAck> {
-        #[derive(Serialize)]
-        struct OrderPayload {
-            coin: String,
-            is_buy: bool,
-            sz: f64,
-            limit_px: f64,
-            order_type: OrderTypePayload,
-            reduce_only: bool,
+    /// Asset index and size precision of a coin, loading `meta` on a miss
+    async fn asset(&self, coin: &str) -> Result<AssetMeta> {
+        if let Some(asset) = self.assets.read().get(coin) {
+            return Ok(*asset);
         }
         
+        self.refresh_assets().await?;
+        self.assets
+            .read()
+            .get(coin)
+            .copied()
+            .ok_or_else(|| Error::NotFound(format!("Unknown Hyperliquid coin: {}", coin)))
+    }
+    
+    /// Reload the perp universe into the asset cache, returning coin names in index order
+    async fn refresh_assets(&self) -> Result<Vec<String>> {
         #[derive(Serialize)]
-        struct OrderTypePayload {
-            limit: LimitOrder,
+        struct Request {
+            #[serde(rename = "type")]
+            req_type: String,
         }
         
-        #[derive(Serialize)]
-        struct LimitOrder {
-            tif: String,
-        }
-        
-        let payload = OrderPayload {
-            coin: order.symbol.clone(),
-            is_buy: matches!(order.side, Side::Buy),
-            sz: order.quantity,
-            limit_px: order.price.unwrap_or(0.0),
-            order_type: OrderTypePayload {
-                limit: LimitOrder {
-                    tif: "Gtc".to_string(),
-                },
-            },
-            reduce_only: order.reduce_only,
-        };
-        
         #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
         struct Response {
-            status: String,
-            response: ResponseData,
+            universe: Vec<UniverseItem>,
+            #[serde(default)]
+            margin_tables: Vec<(u32, MarginTable)>,
         }
         
         #[derive(Deserialize)]
-        struct ResponseData {
-            #[serde(rename = "type")]
-            response_type: String,
-            data: Option<OrderData>,
+        #[serde(rename_all = "camelCase")]
+        struct UniverseItem {
+            name: String,
+            sz_decimals: u32,
+            max_leverage: f64,
+            #[serde(default)]
+            margin_table_id: Option<u32>,
         }
         
-        #[derive(Deserialize)]
-        struct OrderData {
-            statuses: Vec<OrderStatusData>,
+        let req = Request {
+            req_type: "meta".to_string(),
+        };
+        
+        let resp: Response = self.post_request(Endpoint::Info, &req).await?;
+        let tables: HashMap<u32, MarginTable> = resp.margin_tables.into_iter().collect();
+        
+        let mut tiers = HashMap::with_capacity(resp.universe.len());
+        for item in &resp.universe {
+            let table = item.margin_table_id.and_then(|id| tables.get(&id));
+            tiers.insert(item.name.clone(), leverage_tiers_from_meta(item.max_leverage, table)?);
         }
+        *self.leverage_tiers.write() = tiers;
         
-        #[derive(Deserialize)]
-        struct OrderStatusData {
-            filled: bool,
+        let mut assets = self.assets.write();
+        assets.clear();
+        for (index, item) in resp.universe.iter().enumerate() {
+            assets.insert(item.name.clone(), AssetMeta { index: index as u32, sz_decimals: item.sz_decimals });
         }
         
-        let resp: Response = self.post_request("exchange", &payload).await?;
+        Ok(resp.universe.into_iter().map(|item| item.name).collect())
+    }
+    
+    /// Limit price for an order; market orders are priced off the local book
+    async fn limit_price(&self, order: &OrderRequest) -> Result<f64> {
+        if let Some(price) = order.price {
+            return Ok(price);
+        }
         
-        Ok(OrderAck {
-            venue_order_id: order.client_id.clone(),
-            client_id: order.client_id,
-            status: OrderStatus::Accepted,
-            timestamp_ns: chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0),
-        })
+        let books = self.books.read().await;
+        let book = books.get(&order.symbol);
+        let price = match order.side {
+            Side::Buy => book.and_then(|b| b.asks.keys().next()).map(|p| p.0 * (1.0 + MARKET_SLIPPAGE)),
+            Side::Sell => book.and_then(|b| b.bids.keys().next_back()).map(|p| p.0 * (1.0 - MARKET_SLIPPAGE)),
+        };
+        
+        price.ok_or_else(|| Error::OrderRejected(format!("No {} book to price a market order", order.symbol)))
     }
     
-    async fn cancel_order(&self, _order_id: &str) -> Result<()> {
-        Ok(())
+    /// Sign an action with the account key and submit it to `/exchange`
+    async fn post_action<A: Serialize + Sync>(&self, action: &A) -> Result<serde_json::Value> {
+        let signer = ActionSigner::from_hex(&self.credentials.api_secret, MAINNET)?;
+        let nonce = self.nonces.next(chrono::Utc::now().timestamp_millis() as u64);
+        let signature = signer.sign(action, nonce, None)?;
+        
+        let request = ExchangeRequest {
+            action,
+            nonce,
+            signature,
+            vault_address: None,
+        };
+        
+        self.post_request(Endpoint::Exchange, &request).await
     }
     
-    async fn cancel_all(&self, _symbol: &str) -> Result<()> {
-        Ok(())
+    async fn post_request<T: Serialize, R: for<'de> Deserialize<'de>>(
+        &self,
+        endpoint: Endpoint,
+        payload: &T,
+    ) -> Result<R> {
+        Self::post_with(&self.client, &self.rate_limiter, endpoint, payload).await
     }
     
-    async fn get_order(&self, order_id: &str) -> Result<OrderAck> {
-        Ok(OrderAck {
-            venue_order_id: order_id.to_string(),
-            client_id: order_id.to_string(),
-            status: OrderStatus::Filled,
-            timestamp_ns: chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0),
-        })
+    async fn post_with<T: Serialize, R: for<'de> Deserialize<'de>>(
+        client: &reqwest::Client,
+        rate_limiter: &RateLimiter,
+        endpoint: Endpoint,
+        payload: &T,
+    ) -> Result<R> {
+        let _guard = rate_limiter.acquire().await;
+        
+        let response = client
+            .post(endpoint.url(REST_BASE))
+            .json(payload)
+            .send()
+            .await?;
+        
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await?;
+            return Err(api_error(status.as_u16(), &error_text));
+        }
+        
+        Ok(response.json().await?)
     }
 }
 
 #[async_trait]
-impl MarketInfo for HyperliquidAdapter {
-    async fn list_symbols(&self) -> Result<Vec<String>> {
+impl OpenOrders for HyperliquidAdapter {
+    type Order = RestingOrder;
+    
+    async fn open_orders(&self, symbol: &str) -> Result<Vec<RestingOrder>> {
         #[derive(Serialize)]
         struct Request {
             #[serde(rename = "type")]
             req_type: String,
+            user: String,
         }
         
         #[derive(Deserialize)]
-        struct Response {
-            universe: Vec<UniverseItem>,
-        }
-        
-        #[derive(Deserialize)]
-        struct UniverseItem {
-            name: String,
+        struct OpenOrder {
+            coin: String,
+            oid: u64,
         }
         
         let req = Request {
-            req_type: "meta".to_string(),
+            req_type: "openOrders".to_string(),
+            user: self.credentials.api_key.clone(),
         };
         
-        let resp: Response = self.post_request("info", &req).await?;
-        
-        Ok(resp.universe.into_iter().map(|item| item.name).collect())
-    }
-    
-    async fn search_symbols(&self, prefix: &str) -> Result<Vec<String>> {
-        let all_symbols = self.list_symbols().await?;
-        Ok(all_symbols
+        let open: Vec<OpenOrder> = self.post_request(Endpoint::Info, &req).await?;
+        Ok(open
             .into_iter()
-            .filter(|s| s.to_lowercase().starts_with(&prefix.to_lowercase()))
+            .filter(|o| o.coin == symbol)
+            .map(|o| RestingOrder { coin: o.coin, oid: o.oid })
             .collect())
     }
     
-    async fn funding_rate(&self, _symbol: &str) -> Result<f64> {
-        Ok(0.0)
-    }
-    
-    async fn open_interest(&self, _symbol: &str) -> Result<f64> {
-        Ok(0.0)
-    }
-    
-    async fn volume_24h(&self, _symbol: &str) -> Result<f64> {
-        Ok(0.0)
+    async fn cancel_open(&self, orders: &[RestingOrder]) -> Result<()> {
+        let outcomes = self.cancel_orders(orders).await?;
+        let mut failures = Vec::new();
+        
+        for (order, outcome) in orders.iter().zip(outcomes) {
+            match outcome {
+                Ok(()) => self.forget_order(order.oid),
+                // Already gone: nothing left to cancel
+                Err(msg) if is_unknown_order(&msg) => self.forget_order(order.oid),
+                Err(msg) => failures.push(format!("{}: {}", order.oid, msg)),
+            }
+        }
+        
+        if !failures.is_empty() {
+            return Err(Error::Venue(format!(
+                "Hyperliquid cancel failed for {} orders: {}",
+                failures.len(), failures.join("; ")
+            )));
+        }
+        
+        Ok(())
     }
 }
 
 #[async_trait]
-impl ExchangeAdapter for HyperliquidAdapter {
-    fn venue(&self) -> Venue {
-        Venue::Hyperliquid
+impl MarketDataStream for HyperliquidAdapter {
+    async fn subscribe_orderbook(&mut self, symbols: &[String]) -> Result<()> {
+        let books = self.books.clone();
+        let trades = self.trades.clone();
+        let snapshot_tx = self.snapshot_tx.clone();
+        let hook_slot = self.disconnect_hook.clone();
+        let fallback = self.fallback.clone();
+        let connected = self.connected.clone();
+        
+        // One stream per adapter: later calls subscribe on the running loop
+        match &self.ws_subscriptions {
+            Some(added) => added
+                .send(symbols.to_vec())
+                .map_err(|_| Error::WebSocket("Hyperliquid stream task has stopped".to_string()))?,
+            None => {
+                let (added_tx, added_rx) = mpsc::unbounded_channel();
+                let ws_symbols = symbols.to_vec();
+                
+                tokio::spawn(async move {
+                    Self::ws_loop(
+                        Self::connect_ws,
+                        ws_symbols,
+                        added_rx,
+                        books,
+                        trades,
+                        snapshot_tx,
+                        hook_slot,
+                        fallback,
+                        connected,
+                        ReconnectBackoff::default(),
+                    ).await;
+                });
+                self.ws_subscriptions = Some(added_tx);
+            }
+        }
+        
+        if self.fallback.lock().config().enabled {
+            let client = self.client.clone();
+            let rate_limiter = self.rate_limiter.clone();
+            let symbols = symbols.to_vec();
+            let fallback = self.fallback.clone();
+            let snapshot_tx = self.snapshot_tx.clone();
+            
+            tokio::spawn(async move {
+                Self::rest_poll_loop(client, rate_limiter, symbols, fallback, snapshot_tx).await;
+            });
+        }
+        
+        Ok(())
     }
     
-    fn is_connected(&self) -> bool {
-        *self.connected.blocking_read()
+    /// Trades share the book stream, so this subscribes the books as well
+    async fn subscribe_trades(&mut self, symbols: &[String]) -> Result<()> {
+        match &self.ws_subscriptions {
+            Some(added) => added
+                .send(symbols.to_vec())
+                .map_err(|_| Error::WebSocket("Hyperliquid stream task has stopped".to_string())),
+            None => self.subscribe_orderbook(symbols).await,
+        }
     }
     
-    async fn connect(&mut self) -> Result<()> {
-        *self.connected.write().await = true;
-        Ok(())
+    fn snapshot_receiver(&self) -> Result<mpsc::UnboundedReceiver<MarketSnapshot>> {
+        take_receiver(&self.snapshot_rx, Venue::Hyperliquid)
+    }
+}
+
+#[async_trait]
+impl AccountData for HyperliquidAdapter {
+    async fn balances(&self) -> Result<HashMap<String, Balance>> {
+        #[derive(Serialize)]
+        struct Request {
+            #[serde(rename = "type")]
+            req_type: String,
+            user: String,
+        }
+        
+        let req = Request {
+            req_type: "clearinghouseState".to_string(),
+            user: self.credentials.api_key.clone(),
+        };
+        
+        let state: serde_json::Value = self.post_request(Endpoint::Info, &req).await?;
+        let balance = parse_margin_balance(&state)?;
+        Ok(HashMap::from([(balance.asset.clone(), balance)]))
+    }
+    
+    async fn positions(&self) -> Result<Vec<Position>> {
+        #[derive(Serialize)]
+        struct Request {
+            #[serde(rename = "type")]
+            req_type: String,
+            user: String,
+        }
+        
+        let req = Request {
+            req_type: "clearinghouseState".to_string(),
+            user: self.credentials.api_key.clone(),
+        };
+        let mids_req = serde_json::json!({ "type": "allMids" });
+        
+        let (state, mids): (serde_json::Value, serde_json::Value) = tokio::try_join!(
+            self.post_request(Endpoint::Info, &req),
+            self.post_request(Endpoint::Info, &mids_req),
+        )?;
+        parse_positions(&state, &parse_mids(&mids)?)
+    }
+    
+    async fn fee_tier(&self) -> Result<FeeTier> {
+        let req = serde_json::json!({ "type": "userFees", "user": self.credentials.api_key });
+        
+        let fees: Result<serde_json::Value> = self.post_request(Endpoint::Info, &req).await;
+        Ok(fees
+            .and_then(|fees| parse_user_fees(&fees))
+            .unwrap_or_else(|e| fallback_fee_tier(Venue::Hyperliquid, FALLBACK_FEES, &e.to_string())))
+    }
+    
+    async fn leverage(&self) -> Result<f64> {
+        Ok(1.0)
+    }
+}
+
+#[async_trait]
+impl OrderRouter for HyperliquidAdapter {
+    async fn send_order(&self, order: OrderRequest) -> Result<OrderAck> {
+        if self.dry_run.is_enabled() {
+            return Ok(self.dry_run.send(Venue::Hyperliquid, &order));
+        }
+
+        let asset = self.asset(&order.symbol).await?;
+        let limit_px = self.limit_price(&order).await?;
+        
+        let action = OrderAction {
+            action_type: "order",
+            orders: vec![order_wire(&order, asset, limit_px)?],
+            grouping: "na",
+        };
+        
+        let raw = self.post_action(&action).await?;
+        let (oid, status, avg_fill_price) = parse_order_status(raw)?;
+        
+        if status == OrderStatus::Accepted {
+            self.orders.write().insert(
+                order.client_id.clone(),
+                RestingOrder { coin: order.symbol.clone(), oid },
+            );
+        }
+        
+        Ok(OrderAck {
+            venue_order_id: oid.to_string(),
+            client_id: order.client_id,
+            status,
+            timestamp_ns: chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0),
+            avg_fill_price,
+        })
+    }
+    
+    async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        if self.dry_run.is_enabled() {
+            self.dry_run.cancel(Venue::Hyperliquid, order_id);
+            return Ok(());
+        }
+        
+        let order = self.resolve_order(order_id)?;
+        
+        let outcome = self.cancel_orders(std::slice::from_ref(&order)).await?
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| Err("no status".to_string()));
+        
+        match outcome {
+            Ok(()) => {
+                self.forget_order(order.oid);
+                Ok(())
+            }
+            Err(msg) if is_unknown_order(&msg) => {
+                self.forget_order(order.oid);
+                Err(Error::NotFound(format!("Hyperliquid order {} ({}): {}", order_id, order.oid, msg)))
+            }
+            Err(msg) => Err(Error::Venue(format!("Hyperliquid cancel failed for {}: {}", order_id, msg))),
+        }
+    }
+    
+    async fn cancel_all(&self, symbol: &str) -> Result<()> {
+        if self.dry_run.is_enabled() {
+            self.dry_run.cancel_all(Venue::Hyperliquid, symbol);
+            return Ok(());
+        }
+        
+        cancel_all_confirmed(self, symbol, CancelRetry::default()).await
+    }
+    
+    async fn get_order(&self, order_id: &str) -> Result<OrderAck> {
+        if let Some(ack) = self.dry_run.lookup(order_id) {
+            return Ok(ack);
+        }
+
+        #[derive(Serialize)]
+        struct Request {
+            #[serde(rename = "type")]
+            req_type: String,
+            user: String,
+            oid: u64,
+        }
+        
+        #[derive(Deserialize)]
+        struct Response {
+            status: String,
+            order: Option<OrderEnvelope>,
+        }
+        
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct OrderEnvelope {
+            status: String,
+            status_timestamp: i64,
+        }
+        
+        let oid = match self.resolve_order(order_id) {
+            Ok(order) => order.oid,
+            Err(e) => order_id.parse::<u64>().map_err(|_| e)?,
+        };
+        
+        let req = Request {
+            req_type: "orderStatus".to_string(),
+            user: self.credentials.api_key.clone(),
+            oid,
+        };
+        
+        let resp: Response = self.post_request(Endpoint::Info, &req).await?;
+        
+        let Some(order) = resp.order else {
+            return Err(Error::NotFound(format!(
+                "Hyperliquid order {} ({}): {}", order_id, oid, resp.status
+            )));
+        };
+        
+        let status = map_order_status(&order.status);
+        if matches!(status, OrderStatus::Filled | OrderStatus::Cancelled | OrderStatus::Rejected) {
+            self.forget_order(oid);
+        }
+        
+        Ok(OrderAck {
+            venue_order_id: oid.to_string(),
+            client_id: order_id.to_string(),
+            status,
+            timestamp_ns: order.status_timestamp * 1_000_000,
+            avg_fill_price: None,
+        })
+    }
+    
+    async fn schedule_cancel(&self, deadline_ms: u64) -> Result<bool> {
+        if self.dry_run.is_enabled() {
+            return Ok(false);
+        }
+
+        let action = ScheduleCancelAction {
+            action_type: "scheduleCancel",
+            time: chrono::Utc::now().timestamp_millis() as u64 + deadline_ms,
+        };
+        
+        let raw = self.post_action(&action).await?;
+        exchange_statuses::<serde_json::Value>(raw)?;
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl MarketInfo for HyperliquidAdapter {
+    async fn list_symbols(&self) -> Result<Vec<String>> {
+        self.refresh_assets().await
+    }
+    
+    async fn search_symbols(&self, prefix: &str) -> Result<Vec<String>> {
+        let all_symbols = self.list_symbols().await?;
+        Ok(all_symbols
+            .into_iter()
+            .filter(|s| s.to_lowercase().starts_with(&prefix.to_lowercase()))
+            .collect())
+    }
+    
+    async fn funding_rate(&self, _symbol: &str) -> Result<f64> {
+        Ok(0.0)
+    }
+    
+    async fn open_interest(&self, _symbol: &str) -> Result<f64> {
+        Ok(0.0)
+    }
+    
+    async fn volume_24h(&self, _symbol: &str) -> Result<f64> {
+        Ok(0.0)
+    }
+    
+    /// Margin tables ride along with `meta`, so one fetch caches every coin
+    async fn leverage_tiers(&self, symbol: &str) -> Result<Vec<margin::LeverageTier>> {
+        if self.leverage_tiers.read().is_empty() {
+            self.refresh_assets().await?;
+        }
+        
+        self.leverage_tiers
+            .read()
+            .get(symbol)
+            .cloned()
+            .ok_or_else(|| Error::NotFound(format!("No Hyperliquid margin table for {}", symbol)))
+    }
+    
+    async fn symbol_spec(&self, symbol: &str) -> Result<SymbolSpec> {
+        Ok(symbol_spec(symbol, self.asset(symbol).await?))
+    }
+}
+
+#[async_trait]
+impl ExchangeAdapter for HyperliquidAdapter {
+    fn venue(&self) -> Venue {
+        Venue::Hyperliquid
+    }
+    
+    fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+    
+    async fn connect(&mut self) -> Result<()> {
+        self.connected.store(true, Ordering::Relaxed);
+        Ok(())
     }
     
     async fn disconnect(&mut self) -> Result<()> {
-        *self.connected.write().await = false;
+        self.connected.store(false, Ordering::Relaxed);
         Ok(())
     }
+    
+    fn set_disconnect_hook(&self, hook: Arc<dyn DisconnectHook>) {
+        *self.disconnect_hook.write() = Some(hook);
+    }
+}
+
+/// Non-2xx REST response. Hyperliquid replies with either a JSON
+/// `{"status":"err","response":...}` envelope, `{"code":..,"msg":..}` or plain text.
+fn api_error(http_status: u16, body: &str) -> Error {
+    #[derive(Deserialize)]
+    struct ErrBody {
+        code: Option<serde_json::Value>,
+        #[serde(alias = "msg", alias = "error", alias = "response")]
+        message: Option<serde_json::Value>,
+    }
+    
+    let text = |v: serde_json::Value| match v {
+        serde_json::Value::String(s) => s,
+        other => other.to_string(),
+    };
+    
+    match serde_json::from_str::<ErrBody>(body) {
+        Ok(ErrBody { code, message: Some(message) }) => {
+            let code = code.map_or_else(|| http_status.to_string(), text);
+            Error::Venue(format!("Hyperliquid API error {}: {}", code, text(message)))
+        }
+        _ => Error::Venue(format!("Hyperliquid API error {}: {}", http_status, body.trim())),
+    }
+}
+
+/// Body of a signed `/exchange` request
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExchangeRequest<'a, A> {
+    action: &'a A,
+    nonce: u64,
+    signature: Signature,
+    vault_address: Option<String>,
+}
+
+// Wire actions. Field order is part of the signed msgpack payload.
+
+#[derive(Serialize)]
+struct OrderAction {
+    #[serde(rename = "type")]
+    action_type: &'static str,
+    orders: Vec<OrderWire>,
+    grouping: &'static str,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct OrderWire {
+    a: u32,
+    b: bool,
+    p: String,
+    s: String,
+    r: bool,
+    t: OrderTypeWire,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct OrderTypeWire {
+    limit: LimitWire,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct LimitWire {
+    tif: &'static str,
+}
+
+#[derive(Serialize)]
+struct CancelAction {
+    #[serde(rename = "type")]
+    action_type: &'static str,
+    cancels: Vec<CancelWire>,
+}
+
+#[derive(Serialize)]
+struct CancelWire {
+    a: u32,
+    o: u64,
+}
+
+#[derive(Serialize)]
+struct ScheduleCancelAction {
+    #[serde(rename = "type")]
+    action_type: &'static str,
+    time: u64,
+}
+
+/// Wire form of an order with price and size rounded to the asset's precision
+fn order_wire(order: &OrderRequest, asset: AssetMeta, limit_px: f64) -> Result<OrderWire> {
+    let size = round_to(order.quantity, asset.sz_decimals as i32);
+    if size <= 0.0 {
+        return Err(Error::OrderRejected(format!(
+            "{} {} rounds to zero at {} decimals",
+            order.quantity, order.symbol, asset.sz_decimals
+        )));
+    }
+    
+    let price = round_to(
+        round_sig_figs(limit_px, PRICE_SIG_FIGS),
+        MAX_PRICE_DECIMALS - asset.sz_decimals as i32,
+    );
+    
+    Ok(OrderWire {
+        a: asset.index,
+        b: matches!(order.side, Side::Buy),
+        p: float_to_wire(price)?,
+        s: float_to_wire(size)?,
+        r: order.reduce_only,
+        t: OrderTypeWire {
+            limit: LimitWire { tif: hyperliquid_tif(order)? },
+        },
+    })
+}
+
+/// Lot from `szDecimals`; the tick is the finest the decimal cap allows, which
+/// the significant-figure limit coarsens at higher prices
+fn symbol_spec(coin: &str, asset: AssetMeta) -> SymbolSpec {
+    SymbolSpec {
+        symbol: coin.to_string(),
+        tick_size: 10f64.powi(-(MAX_PRICE_DECIMALS - asset.sz_decimals as i32).max(0)),
+        lot_size: 10f64.powi(-(asset.sz_decimals as i32)),
+    }
+}
+
+fn round_to(value: f64, decimals: i32) -> f64 {
+    let scale = 10f64.powi(decimals.max(0));
+    (value * scale).round() / scale
+}
+
+fn round_sig_figs(value: f64, figures: i32) -> f64 {
+    if value == 0.0 || !value.is_finite() {
+        return value;
+    }
+    let magnitude = value.abs().log10().floor() as i32;
+    let scale = 10f64.powi(figures - 1 - magnitude);
+    (value * scale).round() / scale
+}
+
+/// Venue oid and status of a single order placement. A per-order `error`
+/// status is the venue rejecting the order and surfaces its message.
+fn parse_order_status(raw: serde_json::Value) -> Result<(u64, OrderStatus, Option<f64>)> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    enum OrderStatusData {
+        Resting { oid: u64 },
+        Filled {
+            oid: u64,
+            #[serde(rename = "avgPx")]
+            avg_px: Option<String>,
+        },
+        Error(String),
+    }
+    
+    let status = exchange_statuses::<OrderStatusData>(raw)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| Error::Venue("Hyperliquid order response had no status".to_string()))?;
+    
+    match status {
+        OrderStatusData::Resting { oid } => Ok((oid, OrderStatus::Accepted, None)),
+        OrderStatusData::Filled { oid, avg_px } => {
+            let avg_px = parse_optional_decimal(avg_px.as_deref(), "filled avgPx")?;
+            Ok((oid, OrderStatus::Filled, avg_px))
+        }
+        OrderStatusData::Error(msg) => Err(Error::OrderRejected(msg)),
+    }
+}
+
+/// Per-action statuses from an exchange response, erroring on `status != "ok"`
+fn exchange_statuses<T: for<'de> Deserialize<'de>>(raw: serde_json::Value) -> Result<Vec<T>> {
+    #[derive(Deserialize)]
+    struct Envelope {
+        status: String,
+        response: serde_json::Value,
+    }
+    
+    let envelope: Envelope = serde_json::from_value(raw)?;
+    if envelope.status != "ok" {
+        return Err(Error::Venue(format!("Hyperliquid exchange error: {}", envelope.response)));
+    }
+    
+    let statuses = envelope.response
+        .pointer("/data/statuses")
+        .cloned()
+        .unwrap_or_else(|| serde_json::Value::Array(vec![]));
+    
+    Ok(serde_json::from_value(statuses)?)
+}
+
+/// Entry of `meta.marginTables`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MarginTable {
+    margin_tiers: Vec<MarginTierWire>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MarginTierWire {
+    lower_bound: String,
+    max_leverage: f64,
+}
+
+/// Leverage tiers of a coin. Hyperliquid's maintenance rate is half the
+/// initial margin at max leverage; coins without a tiered table have a single
+/// tier at the universe `maxLeverage`.
+fn leverage_tiers_from_meta(max_leverage: f64, table: Option<&MarginTable>) -> Result<Vec<margin::LeverageTier>> {
+    let tier = |notional_floor: f64, max_leverage: f64| -> Result<margin::LeverageTier> {
+        if max_leverage.is_nan() || max_leverage < 1.0 {
+            return Err(Error::Venue(format!("Hyperliquid returned invalid maxLeverage: {}", max_leverage)));
+        }
+        Ok(margin::LeverageTier {
+            notional_floor,
+            max_leverage,
+            maintenance_margin_rate: 1.0 / (2.0 * max_leverage),
+            maintenance_amount: 0.0,
+        })
+    };
+    
+    let mut tiers = match table {
+        Some(table) if !table.margin_tiers.is_empty() => table.margin_tiers
+            .iter()
+            .map(|t| tier(parse_decimal(&t.lower_bound, "marginTiers lowerBound")?, t.max_leverage))
+            .collect::<Result<Vec<_>>>()?,
+        _ => vec![tier(0.0, max_leverage)?],
+    };
+    
+    tiers.sort_by(|a, b| a.notional_floor.total_cmp(&b.notional_floor));
+    margin::fill_maintenance_amounts(&mut tiers);
+    Ok(tiers)
+}
+
+/// Add `more` to `symbols`, returning the ones not already subscribed
+fn merge_symbols(symbols: &mut Vec<String>, more: Vec<String>) -> Vec<String> {
+    let mut fresh = Vec::new();
+    for symbol in more {
+        if !symbols.contains(&symbol) {
+            symbols.push(symbol.clone());
+            fresh.push(symbol);
+        }
+    }
+    fresh
+}
+
+/// `subscribe` frames for the `l2Book` and `trades` channels of each coin.
+/// Snapshots carry the coin's recent trades, so books are never streamed
+/// without them.
+pub fn subscription_frames(symbols: &[String]) -> Vec<String> {
+    symbols
+        .iter()
+        .flat_map(|coin| {
+            ["l2Book", "trades"].map(|channel| {
+                serde_json::json!({
+                    "method": "subscribe",
+                    "subscription": { "type": channel, "coin": coin },
+                })
+                .to_string()
+            })
+        })
+        .collect()
+}
+
+/// Parse an `l2Book` payload into `(coin, deltas, timestamp_ns)`. The payload is a
+/// full snapshot, so the deltas are a `Clear` followed by one `Insert` per level.
+/// Any malformed level, missing side or crossed book rejects the whole message.
+pub fn parse_l2book(data: &serde_json::Value) -> Result<(String, Vec<BookDelta>, i64)> {
+    #[derive(Deserialize)]
+    struct L2Book {
+        coin: String,
+        levels: Vec<Vec<WireLevel>>,
+        time: i64,
+    }
+    
+    #[derive(Deserialize)]
+    struct WireLevel {
+        px: String,
+        sz: String,
+    }
+    
+    let book = L2Book::deserialize(data)
+        .map_err(|e| Error::Venue(format!("Malformed Hyperliquid l2Book: {}", e)))?;
+    
+    let [bids, asks] = book.levels.as_slice() else {
+        return Err(Error::Venue(format!(
+            "Malformed Hyperliquid l2Book for {}: expected 2 sides, got {}",
+            book.coin,
+            book.levels.len()
+        )));
+    };
+    if book.time <= 0 {
+        return Err(Error::Venue(format!("Malformed Hyperliquid l2Book for {}: time {}", book.coin, book.time)));
+    }
+    
+    let mut deltas = Vec::with_capacity(1 + bids.len() + asks.len());
+    deltas.push(BookDelta::Clear);
+    
+    let mut best_bid = f64::NEG_INFINITY;
+    let mut best_ask = f64::INFINITY;
+    
+    for (side, levels) in [(Side::Buy, bids), (Side::Sell, asks)] {
+        for level in levels {
+            let price = parse_decimal(&level.px, "l2Book px")?;
+            let quantity = parse_decimal(&level.sz, "l2Book sz")?;
+            if price <= 0.0 || quantity < 0.0 {
+                return Err(Error::Venue(format!(
+                    "Malformed Hyperliquid l2Book for {}: level {}@{}",
+                    book.coin, level.sz, level.px
+                )));
+            }
+            
+            match side {
+                Side::Buy => best_bid = best_bid.max(price),
+                Side::Sell => best_ask = best_ask.min(price),
+            }
+            deltas.push(BookDelta::Insert { side, price, quantity });
+        }
+    }
+    
+    if best_bid >= best_ask {
+        return Err(Error::Venue(format!(
+            "Crossed Hyperliquid l2Book for {}: bid {} >= ask {}",
+            book.coin, best_bid, best_ask
+        )));
+    }
+    
+    Ok((book.coin, deltas, book.time * 1_000_000))
+}
+
+/// Parse a `trades` payload; `side` is the aggressor (`B` buy, `A` sell)
+pub fn parse_trades(data: &serde_json::Value) -> Result<Vec<Trade>> {
+    #[derive(Deserialize)]
+    struct WireTrade {
+        coin: String,
+        side: String,
+        px: String,
+        sz: String,
+        time: i64,
+        tid: u64,
+    }
+    
+    let wire = Vec::<WireTrade>::deserialize(data)
+        .map_err(|e| Error::Venue(format!("Malformed Hyperliquid trades: {}", e)))?;
+    
+    wire.into_iter()
+        .map(|t| {
+            let side = match t.side.as_str() {
+                "B" => Side::Buy,
+                "A" => Side::Sell,
+                other => return Err(Error::Venue(format!("Malformed Hyperliquid trade side {:?}", other))),
+            };
+            
+            let price = parse_decimal(&t.px, "trade px")?;
+            let quantity = parse_decimal(&t.sz, "trade sz")?;
+            if price <= 0.0 || quantity <= 0.0 {
+                return Err(Error::Venue(format!("Malformed Hyperliquid trade {}: {}@{}", t.tid, t.sz, t.px)));
+            }
+            
+            Ok(Trade {
+                symbol: t.coin,
+                timestamp_ns: t.time * 1_000_000,
+                price,
+                quantity,
+                side,
+                trade_id: t.tid.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Parse the `allMids` info payload: coin -> mid
+pub fn parse_mids(mids: &serde_json::Value) -> Result<HashMap<String, f64>> {
+    let mids = HashMap::<String, String>::deserialize(mids)
+        .map_err(|e| Error::Venue(format!("Malformed Hyperliquid allMids: {}", e)))?;
+    
+    mids.into_iter()
+        .map(|(coin, mid)| {
+            let mid = parse_decimal(&mid, &format!("{} mid", coin))?;
+            Ok((coin, mid))
+        })
+        .collect()
+}
+
+/// Parse `clearinghouseState.assetPositions`, marked at `mids` (from
+/// `allMids`). A malformed number fails the whole response rather than
+/// reading as a zero position. Coins missing from `mids` are marked at
+/// positionValue / |szi|.
+pub fn parse_positions(state: &serde_json::Value, mids: &HashMap<String, f64>) -> Result<Vec<Position>> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct ClearinghouseState {
+        asset_positions: Vec<PositionItem>,
+    }
+    
+    #[derive(Deserialize)]
+    struct PositionItem {
+        position: PositionData,
+    }
+    
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct PositionData {
+        coin: String,
+        szi: String,
+        entry_px: String,
+        position_value: String,
+        unrealized_pnl: String,
+        /// Null when the position can't be liquidated
+        liquidation_px: Option<String>,
+        margin_used: Option<String>,
+        leverage: Option<LeverageData>,
+        cum_funding: Option<CumFunding>,
+    }
+    
+    #[derive(Deserialize)]
+    struct LeverageData {
+        value: f64,
+    }
+    
+    /// Funding paid (positive) or received since the position opened
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct CumFunding {
+        since_open: String,
+    }
+
+    let state = ClearinghouseState::deserialize(state)
+        .map_err(|e| Error::Venue(format!("Malformed Hyperliquid clearinghouseState: {}", e)))?;
+    
+    state.asset_positions
+        .into_iter()
+        .map(|item| {
+            let pos = item.position;
+            let field = |name: &str| format!("position {} {}", pos.coin, name);
+            
+            let size = parse_decimal(&pos.szi, &field("szi"))?;
+            let position_value = parse_decimal(&pos.position_value, &field("positionValue"))?;
+            let leverage = pos.leverage.map_or(1.0, |l| l.value);
+            if !(leverage.is_finite() && leverage > 0.0) {
+                return Err(Error::Venue(format!("Hyperliquid returned invalid {}: {}", field("leverage"), leverage)));
+            }
+            
+            let mark_price = match mids.get(&pos.coin) {
+                Some(mid) => *mid,
+                None if size != 0.0 => position_value / size.abs(),
+                None => 0.0,
+            };
+            let margin_used = match pos.margin_used.as_deref() {
+                Some(margin) => parse_decimal(margin, &field("marginUsed"))?,
+                None => position_value / leverage,
+            };
+            // The only realized leg the venue reports per position is funding
+            let funding_paid = match &pos.cum_funding {
+                Some(funding) => parse_decimal(&funding.since_open, &field("cumFunding.sinceOpen"))?,
+                None => 0.0,
+            };
+            
+            Ok(Position {
+                size,
+                entry_price: parse_decimal(&pos.entry_px, &field("entryPx"))?,
+                mark_price,
+                unrealized_pnl: parse_decimal(&pos.unrealized_pnl, &field("unrealizedPnl"))?,
+                realized_pnl: -funding_paid,
+                leverage,
+                margin_used,
+                liquidation_price: parse_optional_decimal(pos.liquidation_px.as_deref(), &field("liquidationPx"))?,
+                maintenance_margin: None,
+                symbol: pos.coin,
+            })
+        })
+        .collect()
+}
+
+/// The perp account's USDC collateral from `clearinghouseState`: account
+/// value (unrealized PnL included) as the total, `withdrawable` as free, and
+/// the margin held by positions as locked. Reads `marginSummary`, or
+/// `crossMarginSummary` where that's all the response has.
+pub fn parse_margin_balance(state: &serde_json::Value) -> Result<Balance> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct ClearinghouseState {
+        margin_summary: Option<MarginSummary>,
+        cross_margin_summary: Option<MarginSummary>,
+        withdrawable: String,
+    }
+    
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct MarginSummary {
+        account_value: String,
+    }
+    
+    let state = ClearinghouseState::deserialize(state)
+        .map_err(|e| Error::Venue(format!("Malformed Hyperliquid clearinghouseState: {}", e)))?;
+    let summary = state.margin_summary
+        .or(state.cross_margin_summary)
+        .ok_or_else(|| Error::Venue("Hyperliquid clearinghouseState has no margin summary".to_string()))?;
+    
+    let total = parse_decimal(&summary.account_value, "marginSummary.accountValue")?;
+    let free = parse_decimal(&state.withdrawable, "withdrawable")?.min(total);
+    
+    Ok(Balance {
+        asset: "USDC".to_string(),
+        free,
+        locked: (total - free).max(0.0),
+        total,
+    })
+}
+
+/// The account's perp rates from `userFees`, which come as fractions (a
+/// negative add rate is a maker rebate), and its volume summed over the
+/// daily entries the response carries
+pub fn parse_user_fees(fees: &serde_json::Value) -> Result<FeeTier> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct UserFees {
+        user_cross_rate: String,
+        user_add_rate: String,
+        #[serde(default)]
+        daily_user_vlm: Vec<DailyVolume>,
+    }
+    
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct DailyVolume {
+        user_cross: String,
+        user_add: String,
+    }
+    
+    let fees = UserFees::deserialize(fees)
+        .map_err(|e| Error::Venue(format!("Malformed Hyperliquid userFees: {}", e)))?;
+    
+    let mut volume = 0.0;
+    for day in &fees.daily_user_vlm {
+        volume += parse_decimal(&day.user_cross, "dailyUserVlm.userCross")?;
+        volume += parse_decimal(&day.user_add, "dailyUserVlm.userAdd")?;
+    }
+    
+    Ok(FeeTier {
+        maker_fee_bps: parse_decimal(&fees.user_add_rate, "userAddRate")? * 1e4,
+        taker_fee_bps: parse_decimal(&fees.user_cross_rate, "userCrossRate")? * 1e4,
+        volume_30d: volume,
+    })
+}
+
+/// Finite decimal from one of Hyperliquid's string-encoded numbers
+fn parse_decimal(value: &str, field: &str) -> Result<f64> {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite())
+        .ok_or_else(|| Error::Venue(format!("Hyperliquid returned non-numeric {}: {:?}", field, value)))
+}
+
+/// Like `parse_decimal` for fields the venue may omit or send as null: an
+/// absent value is `None`, a present but malformed one is still an error
+fn parse_optional_decimal(value: Option<&str>, field: &str) -> Result<Option<f64>> {
+    value.map(|v| parse_decimal(v, field)).transpose()
+}
+
+/// Hyperliquid `tif` for an order; the order type wins over the requested TIF
+fn hyperliquid_tif(order: &OrderRequest) -> Result<&'static str> {
+    let tif = match order.order_type {
+        OrderType::PostOnly => TimeInForce::GTX,
+        OrderType::IOC | OrderType::Market => TimeInForce::IOC,
+        OrderType::FOK => TimeInForce::FOK,
+        OrderType::Limit => order.time_in_force,
+    };
+    
+    Ok(match Venue::Hyperliquid.resolve_tif(tif)? {
+        TimeInForce::IOC => "Ioc",
+        TimeInForce::GTX => "Alo",
+        _ => "Gtc",
+    })
+}
+
+/// Venue error text for orders that no longer exist
+fn is_unknown_order(msg: &str) -> bool {
+    let msg = msg.to_lowercase();
+    msg.contains("never placed") || msg.contains("already canceled") || msg.contains("unknown")
+}
+
+/// Translate Hyperliquid `orderStatus` values into our `OrderStatus`
+fn map_order_status(status: &str) -> OrderStatus {
+    match status {
+        "open" | "triggered" => OrderStatus::Accepted,
+        "filled" => OrderStatus::Filled,
+        "rejected" => OrderStatus::Rejected,
+        s if s.to_lowercase().contains("canceled") => OrderStatus::Cancelled,
+        _ => OrderStatus::Pending,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_endpoint_urls() {
+        assert_eq!(Endpoint::Info.url(REST_BASE), "https://api.hyperliquid.xyz/info");
+        assert_eq!(Endpoint::Exchange.url(REST_BASE), "https://api.hyperliquid.xyz/exchange");
+        assert_eq!(Endpoint::Info.url("http://localhost:3001/"), "http://localhost:3001/info");
+    }
+    
+    #[test]
+    fn test_api_error_surfaces_venue_code() {
+        let err = api_error(400, r#"{"code":"E101","msg":"Insufficient margin"}"#);
+        assert!(matches!(&err, Error::Venue(m) if m.contains("E101") && m.contains("Insufficient margin")));
+        
+        let err = api_error(422, r#"{"status":"err","response":"User or API Wallet does not exist."}"#);
+        assert!(matches!(&err, Error::Venue(m) if m.contains("422") && m.contains("does not exist")));
+        
+        let err = api_error(500, "Internal Server Error\n");
+        assert!(matches!(&err, Error::Venue(m) if m.ends_with("500: Internal Server Error")));
+    }
+
+    #[test]
+    fn test_snapshot_receiver_taken_once() {
+        let adapter = HyperliquidAdapter::new(ApiCredentials::new(
+            "key".to_string(),
+            "secret".to_string(),
+            true,
+        ));
+        
+        assert!(adapter.snapshot_receiver().is_ok());
+        assert!(adapter.snapshot_receiver().is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_dry_run_send_skips_venue() {
+        let adapter = HyperliquidAdapter::new(ApiCredentials::new(
+            "key".to_string(),
+            "secret".to_string(),
+            false,
+        ))
+        .with_dry_run(true);
+        
+        let order = OrderRequest {
+            client_id: "c1".to_string(),
+            symbol: "BTC".to_string(),
+            side: Side::Sell,
+            order_type: OrderType::Limit,
+            quantity: 0.1,
+            price: Some(67000.0),
+            reduce_only: true,
+            time_in_force: TimeInForce::GTC,
+        };
+        
+        // A real submission would need the network and a signed payload
+        let ack = adapter.send_order(order).await.unwrap();
+        assert!(ack.venue_order_id.starts_with(DRY_RUN_ID_PREFIX));
+        assert!(adapter.orders.read().is_empty());
+        assert_eq!(adapter.get_order("c1").await.unwrap().venue_order_id, ack.venue_order_id);
+        
+        adapter.cancel_order("c1").await.unwrap();
+        adapter.cancel_all("BTC").await.unwrap();
+        assert!(!adapter.schedule_cancel(5000).await.unwrap());
+        
+        let intents = adapter.dry_run().intents();
+        assert_eq!(intents.len(), 3);
+        assert!(matches!(&intents[0], OrderIntent::Send { venue: Venue::Hyperliquid, order } if order.reduce_only));
+    }
+    
+    #[test]
+    fn test_hyperliquid_tif() {
+        let mut order = OrderRequest {
+            client_id: "c1".to_string(),
+            symbol: "BTC".to_string(),
+            side: Side::Buy,
+            order_type: OrderType::PostOnly,
+            quantity: 1.0,
+            price: Some(100.0),
+            reduce_only: false,
+            time_in_force: TimeInForce::GTC,
+        };
+        assert_eq!(hyperliquid_tif(&order).unwrap(), "Alo");
+        
+        order.order_type = OrderType::Limit;
+        order.time_in_force = TimeInForce::IOC;
+        assert_eq!(hyperliquid_tif(&order).unwrap(), "Ioc");
+        
+        // No fill-or-kill on Hyperliquid
+        order.order_type = OrderType::FOK;
+        assert!(hyperliquid_tif(&order).is_err());
+    }
+    
+    #[test]
+    fn test_map_order_status() {
+        assert_eq!(map_order_status("open"), OrderStatus::Accepted);
+        assert_eq!(map_order_status("filled"), OrderStatus::Filled);
+        assert_eq!(map_order_status("canceled"), OrderStatus::Cancelled);
+        assert_eq!(map_order_status("marginCanceled"), OrderStatus::Cancelled);
+        assert_eq!(map_order_status("rejected"), OrderStatus::Rejected);
+    }
+    
+    #[test]
+    fn test_order_action_signing_vector() {
+        let order = OrderRequest {
+            client_id: "c1".to_string(),
+            symbol: "ETH".to_string(),
+            side: Side::Buy,
+            order_type: OrderType::IOC,
+            quantity: 0.0147,
+            price: Some(1670.1),
+            reduce_only: false,
+            time_in_force: TimeInForce::GTC,
+        };
+        let asset = AssetMeta { index: 4, sz_decimals: 4 };
+        
+        let action = OrderAction {
+            action_type: "order",
+            orders: vec![order_wire(&order, asset, 1670.1).unwrap()],
+            grouping: "na",
+        };
+        assert_eq!(
+            serde_json::to_value(&action).unwrap(),
+            serde_json::json!({
+                "type": "order",
+                "orders": [{ "a": 4, "b": true, "p": "1670.1", "s": "0.0147", "r": false, "t": { "limit": { "tif": "Ioc" } } }],
+                "grouping": "na"
+            })
+        );
+        
+        let nonce = 1_677_777_606_040;
+        let hash = crate::hyperliquid_signing::action_hash(&action, nonce, None).unwrap();
+        assert_eq!(hex::encode(hash), "0fcbeda5ae3c4950a548021552a4fea2226858c4453571bf3f24ba017eac2908");
+        
+        let signer = ActionSigner::from_hex(
+            "0x0123456789012345678901234567890123456789012345678901234567890123",
+            true,
+        ).unwrap();
+        let signature = signer.sign(&action, nonce, None).unwrap();
+        assert_eq!(signature.r, "0xe302c107c0048633293299b066317048f69f685aa4f536fe56c050efaf04996c");
+        assert_eq!(signature.s, "0x70e01d4d26e3d724fb8f8ab54b3f2544a37ea1b092f83532349405274f3f3962");
+        assert_eq!(signature.v, 28);
+    }
+    
+    #[test]
+    fn test_order_wire_rounds_to_asset_precision() {
+        let mut order = OrderRequest {
+            client_id: "c1".to_string(),
+            symbol: "BTC".to_string(),
+            side: Side::Sell,
+            order_type: OrderType::Limit,
+            quantity: 0.123456,
+            price: None,
+            reduce_only: true,
+            time_in_force: TimeInForce::GTC,
+        };
+        
+        let wire = order_wire(&order, AssetMeta { index: 0, sz_decimals: 5 }, 67123.456).unwrap();
+        assert_eq!((wire.p.as_str(), wire.s.as_str(), wire.b, wire.r), ("67123", "0.12346", false, true));
+        
+        order.quantity = 3.2;
+        let wire = order_wire(&order, AssetMeta { index: 7, sz_decimals: 0 }, 0.000123456).unwrap();
+        assert_eq!((wire.a, wire.p.as_str(), wire.s.as_str()), (7, "0.000123", "3"));
+        
+        order.quantity = 0.4;
+        assert!(matches!(
+            order_wire(&order, AssetMeta { index: 7, sz_decimals: 0 }, 1.0),
+            Err(Error::OrderRejected(_))
+        ));
+        
+        // The spec matches the precision orders are rounded to
+        let spec = symbol_spec("BTC", AssetMeta { index: 0, sz_decimals: 5 });
+        assert_eq!((spec.tick_size, spec.lot_size), (0.1, 1e-5));
+    }
+    
+    #[test]
+    fn test_parse_order_status() {
+        let statuses = |status: serde_json::Value| serde_json::json!({
+            "status": "ok",
+            "response": { "type": "order", "data": { "statuses": [status] } }
+        });
+        
+        let resting = parse_order_status(statuses(serde_json::json!({ "resting": { "oid": 77738308 } }))).unwrap();
+        assert_eq!(resting, (77738308, OrderStatus::Accepted, None));
+        
+        let filled = parse_order_status(statuses(serde_json::json!({
+            "filled": { "totalSz": "0.02", "avgPx": "1891.4", "oid": 77747314 }
+        }))).unwrap();
+        assert_eq!(filled, (77747314, OrderStatus::Filled, Some(1891.4)));
+        
+        let rejected = parse_order_status(statuses(serde_json::json!({
+            "error": "Order must have minimum value of $10."
+        })));
+        assert!(matches!(rejected, Err(Error::OrderRejected(m)) if m.contains("minimum value")));
+    }
+    
+    #[test]
+    fn test_exchange_statuses() {
+        let ok = serde_json::json!({
+            "status": "ok",
+            "response": { "type": "cancel", "data": { "statuses": ["success", { "error": "Order was never placed, already canceled, or filled." }] } }
+        });
+        let statuses: Vec<serde_json::Value> = exchange_statuses(ok).unwrap();
+        assert_eq!(statuses.len(), 2);
+        assert!(is_unknown_order(statuses[1]["error"].as_str().unwrap()));
+        
+        let err = serde_json::json!({ "status": "err", "response": "User or API Wallet does not exist." });
+        assert!(exchange_statuses::<serde_json::Value>(err).is_err());
+    }
+    
+    const L2BOOK_FIXTURE: &str = include_str!("../fixtures/hyperliquid/l2book.json");
+    const TRADES_FIXTURE: &str = include_str!("../fixtures/hyperliquid/trades.json");
+    
+    fn data(fixture: &str) -> serde_json::Value {
+        let msg: serde_json::Value = serde_json::from_str(fixture).unwrap();
+        msg["data"].clone()
+    }
+    
+    #[test]
+    fn test_parse_l2book_fixture() {
+        let (coin, deltas, ts) = parse_l2book(&data(L2BOOK_FIXTURE)).unwrap();
+        assert_eq!(coin, "BTC");
+        assert_eq!(ts, 1_718_035_200_123_000_000);
+        assert!(matches!(deltas[0], BookDelta::Clear));
+        assert_eq!(deltas.len(), 6);
+        
+        let mut maintainer = OrderBookMaintainer::new(coin);
+        maintainer.apply_delta(BookDelta::Insert { side: Side::Buy, price: 1.0, quantity: 9.0 });
+        for delta in deltas {
+            maintainer.apply_delta(delta);
+        }
+        
+        let book = maintainer.to_orderbook(ts, 20);
+        assert_eq!(book.bids.len(), 3);
+        assert_eq!(book.asks.len(), 2);
+        assert_eq!(book.bids[0].price.0, 67012.0);
+        assert_eq!(book.bids[0].quantity, 1.25431);
+        assert_eq!(book.asks[0].price.0, 67013.0);
+        assert_eq!(book.asks[1].quantity, 2.0);
+    }
+    
+    #[test]
+    fn test_parse_l2book_rejects_malformed() {
+        let good = data(L2BOOK_FIXTURE);
+        let variant = |path: &str, value: serde_json::Value| {
+            let mut v = good.clone();
+            *v.pointer_mut(path).unwrap() = value;
+            v
+        };
+        
+        let cases = [
+            variant("/levels", serde_json::json!([[{ "px": "1", "sz": "1", "n": 1 }]])),
+            variant("/levels/0/0/px", serde_json::json!("abc")),
+            variant("/levels/0/0/px", serde_json::json!(67012.0)),
+            variant("/levels/1/0/sz", serde_json::json!("-1")),
+            variant("/levels/1/0/px", serde_json::json!("NaN")),
+            variant("/levels/0/0/px", serde_json::json!("67020.0")),
+            variant("/time", serde_json::json!(0)),
+            serde_json::json!({ "coin": "BTC", "time": 1 }),
+            serde_json::json!([1, 2, 3]),
+        ];
+        
+        for case in cases {
+            assert!(parse_l2book(&case).is_err(), "accepted {}", case);
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_malformed_l2book_keeps_previous_book() {
+        let books = Arc::new(RwLock::new(HashMap::new()));
+        let trades: TradeRing = Arc::new(parking_lot::Mutex::new(HashMap::new()));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        
+        HyperliquidAdapter::handle_ws_message(TRADES_FIXTURE, &books, &trades, &tx).await.unwrap();
+        HyperliquidAdapter::handle_ws_message(L2BOOK_FIXTURE, &books, &trades, &tx).await.unwrap();
+        let snapshot = rx.try_recv().unwrap();
+        assert_eq!(snapshot.recent_trades.len(), 2);
+        
+        let truncated = &L2BOOK_FIXTURE[..L2BOOK_FIXTURE.len() / 2];
+        assert!(HyperliquidAdapter::handle_ws_message(truncated, &books, &trades, &tx).await.is_err());
+        
+        let crossed = L2BOOK_FIXTURE.replace("\"67013.0\"", "\"67000.0\"");
+        assert!(HyperliquidAdapter::handle_ws_message(&crossed, &books, &trades, &tx).await.is_err());
+        assert!(rx.try_recv().is_err());
+        
+        let guard = books.read().await;
+        let book = guard["BTC"].to_orderbook(0, 20);
+        assert_eq!(book.asks[0].price.0, 67013.0);
+        assert_eq!(book.bids.len(), 3);
+    }
+    
+    #[test]
+    fn test_leverage_tiers_from_meta() {
+        let table: MarginTable = serde_json::from_value(serde_json::json!({
+            "description": "tiered 40x",
+            "marginTiers": [
+                { "lowerBound": "0.0", "maxLeverage": 40 },
+                { "lowerBound": "150000000.0", "maxLeverage": 20 }
+            ]
+        })).unwrap();
+        
+        let tiers = leverage_tiers_from_meta(40.0, Some(&table)).unwrap();
+        assert_eq!(tiers.len(), 2);
+        assert_eq!((tiers[0].max_leverage, tiers[0].maintenance_margin_rate), (40.0, 0.0125));
+        assert_eq!((tiers[1].notional_floor, tiers[1].maintenance_margin_rate), (150_000_000.0, 0.025));
+        assert!((tiers[1].maintenance_amount - 1_875_000.0).abs() < 1e-6);
+        
+        let single = leverage_tiers_from_meta(3.0, None).unwrap();
+        assert_eq!(single.len(), 1);
+        assert!((single[0].maintenance_margin_rate - 1.0 / 6.0).abs() < 1e-12);
+        
+        assert!(leverage_tiers_from_meta(0.0, None).is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_ws_loop_resubscribes_after_drop() {
+        let (frames_tx, mut frames_rx) = futures::channel::mpsc::unbounded::<Message>();
+        let connects = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        
+        // Every connection delivers one book and then drops
+        let connect = {
+            let connects = connects.clone();
+            move || {
+                connects.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                let write = frames_tx.clone();
+                let read = futures::stream::iter(vec![Ok(Message::Text(L2BOOK_FIXTURE.into()))]);
+                async move { Ok((write, read)) }
+            }
+        };
+        
+        let books = Arc::new(RwLock::new(HashMap::new()));
+        let (snapshot_tx, mut snapshot_rx) = mpsc::unbounded_channel();
+        let backoff = ReconnectBackoff::new(std::time::Duration::from_millis(1), std::time::Duration::from_millis(5));
+        let (_added_tx, added_rx) = mpsc::unbounded_channel();
+        let ws = tokio::spawn(HyperliquidAdapter::ws_loop(
+            connect,
+            vec!["BTC".to_string(), "ETH".to_string()],
+            added_rx,
+            books,
+            Arc::new(parking_lot::Mutex::new(HashMap::new())),
+            snapshot_tx,
+            Arc::new(parking_lot::RwLock::new(None)),
+            Arc::new(parking_lot::Mutex::new(RestFallback::new(RestFallbackConfig::default(), std::time::Instant::now()))),
+            Arc::new(AtomicBool::new(false)),
+            backoff,
+        ));
+        
+        let mut frames = Vec::new();
+        while frames.len() < 8 {
+            match frames_rx.next().await {
+                Some(Message::Text(text)) => frames.push(text.to_string()),
+                other => panic!("unexpected frame {:?}", other),
+            }
+        }
+        ws.abort();
+        
+        // The second connection subscribed again, in the same order
+        let expected = subscription_frames(&["BTC".to_string(), "ETH".to_string()]);
+        assert_eq!(frames[..4], expected[..]);
+        assert_eq!(frames[4..], expected[..]);
+        assert!(frames[0].contains(r#""type":"l2Book""#) && frames[1].contains(r#""type":"trades""#));
+        assert!(frames[2].contains(r#""coin":"ETH""#));
+        assert!(connects.load(std::sync::atomic::Ordering::SeqCst) >= 2);
+        assert!(snapshot_rx.try_recv().is_ok());
+    }
+    
+    #[tokio::test]
+    async fn test_subscribe_sends_book_and_trades_frames() {
+        let (frames_tx, mut frames_rx) = futures::channel::mpsc::unbounded::<Message>();
+        let connect = move || {
+            let write = frames_tx.clone();
+            async move { Ok((write, futures::stream::pending::<WsItem>())) }
+        };
+        
+        let (added_tx, added_rx) = mpsc::unbounded_channel();
+        let (snapshot_tx, _snapshot_rx) = mpsc::unbounded_channel();
+        let connected = Arc::new(AtomicBool::new(false));
+        let ws = tokio::spawn(HyperliquidAdapter::ws_loop(
+            connect,
+            vec!["BTC".to_string(), "ETH".to_string()],
+            added_rx,
+            Arc::new(RwLock::new(HashMap::new())),
+            Arc::new(parking_lot::Mutex::new(HashMap::new())),
+            snapshot_tx,
+            Arc::new(parking_lot::RwLock::new(None)),
+            Arc::new(parking_lot::Mutex::new(RestFallback::new(RestFallbackConfig::default(), std::time::Instant::now()))),
+            connected.clone(),
+            ReconnectBackoff::default(),
+        ));
+        
+        async fn next_frame(rx: &mut futures::channel::mpsc::UnboundedReceiver<Message>) -> serde_json::Value {
+            match rx.next().await {
+                Some(Message::Text(text)) => serde_json::from_str(&text).unwrap(),
+                other => panic!("unexpected frame {:?}", other),
+            }
+        }
+        
+        for coin in ["BTC", "ETH"] {
+            for channel in ["l2Book", "trades"] {
+                assert_eq!(next_frame(&mut frames_rx).await, serde_json::json!({
+                    "method": "subscribe",
+                    "subscription": { "type": channel, "coin": coin },
+                }));
+            }
+        }
+        
+        // Later symbols go out on the live connection; known ones aren't repeated
+        added_tx.send(vec!["ETH".to_string(), "SOL".to_string()]).unwrap();
+        for channel in ["l2Book", "trades"] {
+            let frame = next_frame(&mut frames_rx).await;
+            assert_eq!(frame["subscription"]["coin"], "SOL");
+            assert_eq!(frame["subscription"]["type"], channel);
+        }
+        assert!(connected.load(Ordering::Relaxed));
+        
+        ws.abort();
+    }
+    
+    #[test]
+    fn test_parse_trades_fixture() {
+        let trades = parse_trades(&data(TRADES_FIXTURE)).unwrap();
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[0].side, Side::Buy);
+        assert_eq!(trades[0].price, 67013.0);
+        assert_eq!(trades[0].trade_id, "90123456789012");
+        assert_eq!(trades[1].side, Side::Sell);
+        assert_eq!(trades[1].timestamp_ns, 1_718_035_200_461_000_000);
+        
+        let mut bad = data(TRADES_FIXTURE);
+        bad[1]["side"] = serde_json::json!("X");
+        assert!(parse_trades(&bad).is_err());
+        
+        let mut bad = data(TRADES_FIXTURE);
+        bad[0].as_object_mut().unwrap().remove("px");
+        assert!(parse_trades(&bad).is_err());
+    }
+    
+    #[test]
+    fn test_parse_positions_rejects_malformed_size() {
+        let state = |szi: &str, liquidation_px: serde_json::Value| serde_json::json!({
+            "assetPositions": [{
+                "position": {
+                    "coin": "ETH",
+                    "szi": szi,
+                    "entryPx": "2986.3",
+                    "positionValue": "100.02765",
+                    "unrealizedPnl": "-0.0079",
+                    "liquidationPx": liquidation_px
+                },
+                "type": "oneWay"
+            }]
+        });
+        
+        let no_mids = HashMap::new();
+        
+        let positions = parse_positions(&state("-0.0335", serde_json::json!("2866.26936529")), &no_mids).unwrap();
+        assert_eq!(positions[0].size, -0.0335);
+        assert_eq!(positions[0].liquidation_price, Some(2866.26936529));
+        
+        // Null is absent, not zero
+        let positions = parse_positions(&state("0.5", serde_json::Value::Null), &no_mids).unwrap();
+        assert_eq!(positions[0].liquidation_price, None);
+        
+        let err = parse_positions(&state("0.5x", serde_json::Value::Null), &no_mids).unwrap_err();
+        assert!(matches!(&err, Error::Venue(m) if m.contains("position ETH szi") && m.contains("0.5x")), "{}", err);
+        
+        assert!(parse_positions(&state("0.5", serde_json::json!("n/a")), &no_mids).is_err());
+        assert_eq!(parse_optional_decimal(None, "x").unwrap(), None);
+    }
+    
+    /// Recorded `clearinghouseState` response (one cross, one isolated position)
+    const CLEARINGHOUSE_FIXTURE: &str = r#"{
+        "assetPositions": [
+            {
+                "position": {
+                    "coin": "ETH",
+                    "cumFunding": { "allTime": "514.085417", "sinceChange": "0.0", "sinceOpen": "-1.25" },
+                    "entryPx": "2986.3",
+                    "leverage": { "rawUsd": "-95.059824", "type": "isolated", "value": 20 },
+                    "liquidationPx": "2866.26936529",
+                    "marginUsed": "4.967826",
+                    "maxLeverage": 50,
+                    "positionValue": "100.02765",
+                    "returnOnEquity": "-0.0026789",
+                    "szi": "0.0335",
+                    "unrealizedPnl": "-0.0079"
+                },
+                "type": "oneWay"
+            },
+            {
+                "position": {
+                    "coin": "BTC",
+                    "cumFunding": { "allTime": "2.5", "sinceChange": "0.4", "sinceOpen": "0.4" },
+                    "entryPx": "64000.0",
+                    "leverage": { "type": "cross", "value": 5 },
+                    "liquidationPx": null,
+                    "marginUsed": "1290.0",
+                    "maxLeverage": 40,
+                    "positionValue": "6450.0",
+                    "returnOnEquity": "-0.03875",
+                    "szi": "-0.1",
+                    "unrealizedPnl": "-50.0"
+                },
+                "type": "oneWay"
+            }
+        ],
+        "crossMarginSummary": { "accountValue": "13109.482328", "totalMarginUsed": "1294.967826", "totalNtlPos": "6550.02765", "totalRawUsd": "13009.454678" },
+        "withdrawable": "11814.514502"
+    }"#;
+    
+    #[test]
+    fn test_parse_clearinghouse_state() {
+        let state: serde_json::Value = serde_json::from_str(CLEARINGHOUSE_FIXTURE).unwrap();
+        let mids = parse_mids(&serde_json::json!({ "ETH": "2985.9", "SOL": "142.1" })).unwrap();
+        let positions = parse_positions(&state, &mids).unwrap();
+        
+        let eth = &positions[0];
+        assert_eq!(eth.symbol, "ETH");
+        assert_eq!(eth.size, 0.0335);
+        assert_eq!(eth.entry_price, 2986.3);
+        assert_eq!(eth.mark_price, 2985.9);
+        assert_eq!(eth.unrealized_pnl, -0.0079);
+        assert_eq!(eth.realized_pnl, 1.25);
+        assert_eq!(eth.leverage, 20.0);
+        assert_eq!(eth.margin_used, 4.967826);
+        assert_eq!(eth.liquidation_price, Some(2866.26936529));
+        
+        // No mid for BTC: marked at positionValue / |szi|
+        let btc = &positions[1];
+        assert_eq!(btc.size, -0.1);
+        assert!((btc.mark_price - 64_500.0).abs() < 1e-6);
+        assert_eq!(btc.realized_pnl, -0.4);
+        assert_eq!(btc.leverage, 5.0);
+        assert_eq!(btc.margin_used, 1290.0);
+        assert_eq!(btc.liquidation_price, None);
+        
+        assert!(parse_mids(&serde_json::json!({ "ETH": "n/a" })).is_err());
+    }
+    
+    #[test]
+    fn test_parse_margin_balance() {
+        let mut state: serde_json::Value = serde_json::from_str(CLEARINGHOUSE_FIXTURE).unwrap();
+        
+        // Cross summary only
+        let balance = parse_margin_balance(&state).unwrap();
+        assert_eq!(balance.asset, "USDC");
+        assert_eq!(balance.total, 13109.482328);
+        assert_eq!(balance.free, 11814.514502);
+        assert!((balance.locked - 1294.967826).abs() < 1e-6);
+        
+        // The full margin summary wins when present
+        state["marginSummary"] = serde_json::json!({ "accountValue": "13200.0", "totalMarginUsed": "1300.0" });
+        assert_eq!(parse_margin_balance(&state).unwrap().total, 13200.0);
+        
+        state["withdrawable"] = serde_json::json!("n/a");
+        assert!(parse_margin_balance(&state).is_err());
+        
+        let spot_shape = serde_json::json!({ "balances": [{ "coin": "USDC", "total": "100.0" }] });
+        assert!(parse_margin_balance(&spot_shape).is_err());
+    }
+    
+    #[test]
+    fn test_parse_user_fees() {
+        let mut fees = serde_json::json!({
+            "dailyUserVlm": [
+                { "date": "2026-10-12", "userCross": "120000.5", "userAdd": "30000.0", "exchange": "9.1e9" },
+                { "date": "2026-10-13", "userCross": "0.0", "userAdd": "49999.5", "exchange": "8.7e9" }
+            ],
+            "userCrossRate": "0.00035",
+            "userAddRate": "0.0001",
+            "activeReferralDiscount": "0.0"
+        });
+        
+        let tier = parse_user_fees(&fees).unwrap();
+        assert!((tier.taker_fee_bps - 3.5).abs() < 1e-9);
+        assert!((tier.maker_fee_bps - 1.0).abs() < 1e-9);
+        assert_eq!(tier.volume_30d, 200_000.0);
+        
+        // Rebate tiers pay makers
+        fees["userAddRate"] = serde_json::json!("-0.00002");
+        assert!((parse_user_fees(&fees).unwrap().maker_fee_bps + 0.2).abs() < 1e-9);
+        
+        fees["userCrossRate"] = serde_json::json!(null);
+        assert!(parse_user_fees(&fees).is_err());
+    }
 }
\ No newline at end of file
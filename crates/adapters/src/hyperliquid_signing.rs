@@ -0,0 +1,207 @@
+// crates/adapters/src/hyperliquid_signing.rs - EIP-712 signing of Hyperliquid exchange actions
+use common::{Error, Result};
+use k256::ecdsa::SigningKey;
+use serde::Serialize;
+use sha3::{Digest, Keccak256};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// `chainId` of the L1 action domain, the same on mainnet and testnet
+const L1_CHAIN_ID: u64 = 1337;
+
+const DOMAIN_TYPE: &str = "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+const AGENT_TYPE: &str = "Agent(string source,bytes32 connectionId)";
+
+fn keccak(bytes: &[u8]) -> [u8; 32] {
+    Keccak256::digest(bytes).into()
+}
+
+fn u256(value: u64) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+/// Signature in the `{r, s, v}` shape the exchange endpoint expects
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Signature {
+    pub r: String,
+    pub s: String,
+    pub v: u8,
+}
+
+/// Signs L1 actions with the account (or API wallet) private key
+pub struct ActionSigner {
+    key: SigningKey,
+    mainnet: bool,
+}
+
+impl ActionSigner {
+    /// Parse a hex private key, with or without a `0x` prefix
+    pub fn from_hex(secret: &str, mainnet: bool) -> Result<Self> {
+        let bytes = hex::decode(secret.trim().trim_start_matches("0x"))
+            .map_err(|_| Error::Authentication("Hyperliquid secret is not a hex private key".to_string()))?;
+        let key = SigningKey::from_slice(&bytes)
+            .map_err(|_| Error::Authentication("Hyperliquid secret is not a valid secp256k1 key".to_string()))?;
+        
+        Ok(Self { key, mainnet })
+    }
+    
+    /// Checksum-free lowercase address of the signing key
+    pub fn address(&self) -> String {
+        let point = self.key.verifying_key().to_encoded_point(false);
+        let hash = keccak(&point.as_bytes()[1..]);
+        format!("0x{}", hex::encode(&hash[12..]))
+    }
+    
+    /// Sign `action` for submission with `nonce`, optionally on behalf of a vault
+    pub fn sign<A: Serialize>(&self, action: &A, nonce: u64, vault: Option<&str>) -> Result<Signature> {
+        let connection_id = action_hash(action, nonce, vault)?;
+        self.sign_digest(&agent_digest(&connection_id, self.mainnet))
+    }
+    
+    fn sign_digest(&self, digest: &[u8; 32]) -> Result<Signature> {
+        let (signature, recovery_id) = self.key
+            .sign_prehash_recoverable(digest)
+            .map_err(|e| Error::Internal(format!("Hyperliquid signing failed: {}", e)))?;
+        let (r, s) = signature.split_bytes();
+        
+        Ok(Signature {
+            r: format!("0x{}", hex::encode(r)),
+            s: format!("0x{}", hex::encode(s)),
+            v: 27 + recovery_id.to_byte(),
+        })
+    }
+}
+
+/// `connectionId` of the phantom agent: keccak of the msgpack-encoded action,
+/// the big-endian nonce and the vault marker
+pub fn action_hash<A: Serialize>(action: &A, nonce: u64, vault: Option<&str>) -> Result<[u8; 32]> {
+    let mut data = rmp_serde::to_vec_named(action)
+        .map_err(|e| Error::Internal(format!("Hyperliquid action msgpack encoding failed: {}", e)))?;
+    data.extend_from_slice(&nonce.to_be_bytes());
+    
+    match vault {
+        None => data.push(0),
+        Some(address) => {
+            let address = hex::decode(address.trim_start_matches("0x"))
+                .map_err(|_| Error::Config(format!("Invalid vault address: {}", address)))?;
+            data.push(1);
+            data.extend_from_slice(&address);
+        }
+    }
+    
+    Ok(keccak(&data))
+}
+
+/// EIP-712 digest of `Agent { source, connectionId }` in the `Exchange` domain
+fn agent_digest(connection_id: &[u8; 32], mainnet: bool) -> [u8; 32] {
+    let mut domain = Vec::with_capacity(5 * 32);
+    domain.extend_from_slice(&keccak(DOMAIN_TYPE.as_bytes()));
+    domain.extend_from_slice(&keccak(b"Exchange"));
+    domain.extend_from_slice(&keccak(b"1"));
+    domain.extend_from_slice(&u256(L1_CHAIN_ID));
+    domain.extend_from_slice(&[0u8; 32]);
+    
+    let source: &[u8] = if mainnet { b"a" } else { b"b" };
+    let mut agent = Vec::with_capacity(3 * 32);
+    agent.extend_from_slice(&keccak(AGENT_TYPE.as_bytes()));
+    agent.extend_from_slice(&keccak(source));
+    agent.extend_from_slice(connection_id);
+    
+    let mut message = vec![0x19, 0x01];
+    message.extend_from_slice(&keccak(&domain));
+    message.extend_from_slice(&keccak(&agent));
+    keccak(&message)
+}
+
+/// Millisecond nonces that never repeat, even for several actions in one ms
+#[derive(Debug, Default)]
+pub struct NonceSource {
+    last: AtomicU64,
+}
+
+impl NonceSource {
+    pub fn next(&self, now_ms: u64) -> u64 {
+        let previous = self.last
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |last| Some(now_ms.max(last + 1)))
+            .unwrap_or_default();
+        now_ms.max(previous + 1)
+    }
+}
+
+/// Decimal string for prices and sizes: at most 8 decimals, no trailing zeros
+pub fn float_to_wire(value: f64) -> Result<String> {
+    let rounded = format!("{:.8}", value);
+    if !value.is_finite() || (rounded.parse::<f64>().unwrap_or(f64::NAN) - value).abs() >= 1e-12 {
+        return Err(Error::OrderRejected(format!("{} has more than 8 decimals", value)));
+    }
+    
+    let trimmed = rounded.trim_end_matches('0').trim_end_matches('.');
+    Ok(match trimmed {
+        "-0" | "" => "0".to_string(),
+        other => other.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const TEST_KEY: &str = "0x0123456789012345678901234567890123456789012345678901234567890123";
+    
+    #[derive(Serialize)]
+    struct Dummy {
+        #[serde(rename = "type")]
+        action_type: &'static str,
+        num: u64,
+    }
+    
+    #[test]
+    fn test_sign_matches_reference_vector() {
+        let signer = ActionSigner::from_hex(TEST_KEY, true).unwrap();
+        assert_eq!(signer.address(), "0x14791697260e4c9a71f18484c9f997b308e59325");
+        
+        let action = Dummy { action_type: "dummy", num: 100_000_000_000 };
+        let signature = signer.sign(&action, 0, None).unwrap();
+        assert_eq!(signature, Signature {
+            r: "0x053749d5b30552aeb2fca34b530185976545bb22d0b3ce6f62e31be961a59298".to_string(),
+            s: "0x755c40ba9bf05223521753995abb2f73ab3229be8ec921f350cb447e384d8ed8".to_string(),
+            v: 27,
+        });
+        
+        // Testnet signs a different phantom agent source
+        let testnet = ActionSigner::from_hex(TEST_KEY, false).unwrap();
+        assert_ne!(testnet.sign(&action, 0, None).unwrap(), signature);
+        
+        assert!(matches!(ActionSigner::from_hex("secret", true), Err(Error::Authentication(_))));
+    }
+    
+    #[test]
+    fn test_action_hash_covers_nonce_and_vault() {
+        let action = Dummy { action_type: "dummy", num: 1 };
+        let base = action_hash(&action, 1, None).unwrap();
+        
+        assert_ne!(base, action_hash(&action, 2, None).unwrap());
+        assert_ne!(base, action_hash(&action, 1, Some("0x1719884eb866cb12b2287399b15f7db5e7d775ea")).unwrap());
+        assert!(action_hash(&action, 1, Some("not-hex")).is_err());
+    }
+    
+    #[test]
+    fn test_nonces_strictly_increase() {
+        let nonces = NonceSource::default();
+        assert_eq!(nonces.next(1_000), 1_000);
+        assert_eq!(nonces.next(1_000), 1_001);
+        assert_eq!(nonces.next(999), 1_002);
+        assert_eq!(nonces.next(5_000), 5_000);
+    }
+    
+    #[test]
+    fn test_float_to_wire() {
+        assert_eq!(float_to_wire(1670.1).unwrap(), "1670.1");
+        assert_eq!(float_to_wire(0.0147).unwrap(), "0.0147");
+        assert_eq!(float_to_wire(100.0).unwrap(), "100");
+        assert_eq!(float_to_wire(-0.0).unwrap(), "0");
+        assert!(float_to_wire(0.123456789).is_err());
+        assert!(float_to_wire(f64::NAN).is_err());
+    }
+}
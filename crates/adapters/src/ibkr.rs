@@ -1,4 +1,1244 @@
-// TODO: Implement IBKR TWS API integration
+// crates/adapters/src/ibkr.rs - Interactive Brokers via the Client Portal gateway
+use crate::*;
+use common::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// The gateway session expires after a few idle minutes
+const TICKLE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Snapshot poll cadence; the gateway allows ~10 requests/sec in total
+const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Precautionary order warnings confirmed before giving up on an order
+const MAX_ORDER_REPLIES: usize = 5;
+
+/// Snapshot field codes
+const FIELD_LAST: &str = "31";
+const FIELD_BID: &str = "84";
+const FIELD_ASK_SIZE: &str = "85";
+const FIELD_ASK: &str = "86";
+const FIELD_BID_SIZE: &str = "88";
+const FIELD_VOLUME: &str = "7762";
+
+/// `/portfolio/{account}/positions/{page}` page size
+const POSITIONS_PAGE: usize = 100;
+
+type HookSlot = Arc<parking_lot::RwLock<Option<Arc<dyn DisconnectHook>>>>;
+
+/// IB contract ids (`conid`) and the symbols we trade them as
+#[derive(Debug, Default)]
+pub struct ContractMap {
+    by_symbol: HashMap<String, u64>,
+    by_conid: HashMap<u64, String>,
+}
+
+impl ContractMap {
+    pub fn insert(&mut self, symbol: &str, conid: u64) {
+        self.by_symbol.insert(symbol.to_string(), conid);
+        self.by_conid.insert(conid, symbol.to_string());
+    }
+    
+    pub fn conid(&self, symbol: &str) -> Option<u64> {
+        self.by_symbol.get(symbol).copied()
+    }
+    
+    pub fn symbol(&self, conid: u64) -> Option<&str> {
+        self.by_conid.get(&conid).map(String::as_str)
+    }
+    
+    pub fn symbols(&self) -> Vec<String> {
+        let mut symbols: Vec<String> = self.by_symbol.keys().cloned().collect();
+        symbols.sort();
+        symbols
+    }
+}
+
+/// Venue identity of an order we placed
+#[derive(Debug, Clone)]
+pub struct IbkrOrder {
+    symbol: String,
+    order_id: String,
+}
+
+/// HTTP access to the gateway, shared with the keepalive and snapshot tasks
+#[derive(Clone)]
+struct Gateway {
+    client: reqwest::Client,
+    base_url: String,
+    rate_limiter: RateLimiter,
+}
+
+impl Gateway {
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+    
+    async fn get<R: DeserializeOwned>(&self, path: &str, query: &[(&str, &str)]) -> Result<R> {
+        self.send(self.client.get(self.url(path)).query(query)).await
+    }
+    
+    async fn post<T: Serialize + ?Sized, R: DeserializeOwned>(&self, path: &str, body: &T) -> Result<R> {
+        self.send(self.client.post(self.url(path)).json(body)).await
+    }
+    
+    async fn delete<R: DeserializeOwned>(&self, path: &str) -> Result<R> {
+        self.send(self.client.delete(self.url(path))).await
+    }
+    
+    async fn send<R: DeserializeOwned>(&self, request: reqwest::RequestBuilder) -> Result<R> {
+        let _guard = self.rate_limiter.acquire().await;
+        
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await?;
+            return Err(api_error(status.as_u16(), &error_text));
+        }
+        
+        Ok(response.json().await?)
+    }
+}
+
 pub struct IbkrAdapter {
-    // Implementation needed
+    gateway: Gateway,
+    account_id: Option<String>,
+    contracts: Arc<parking_lot::RwLock<ContractMap>>,
+    snapshot_tx: mpsc::UnboundedSender<MarketSnapshot>,
+    snapshot_rx: std::sync::Mutex<Option<mpsc::UnboundedReceiver<MarketSnapshot>>>,
+    connected: Arc<AtomicBool>,
+    disconnect_hook: HookSlot,
+    orders: parking_lot::RwLock<HashMap<String, IbkrOrder>>,
+    /// IB warning message ids `send_order` may confirm unattended
+    confirm_warnings: HashSet<String>,
+    tasks: parking_lot::Mutex<Vec<tokio::task::JoinHandle<()>>>,
+    dry_run: DryRun,
+}
+
+impl IbkrAdapter {
+    /// Adapter for a Client Portal gateway at `https://{host}:{port}`. The
+    /// brokerage login happens in the gateway; `connect` only checks it.
+    pub fn new(gateway_host: &str, gateway_port: u16) -> Self {
+        let (snapshot_tx, snapshot_rx) = mpsc::unbounded_channel();
+        
+        Self {
+            gateway: Gateway {
+                // The gateway serves a self-signed certificate on localhost
+                client: reqwest::Client::builder()
+                    .timeout(Duration::from_secs(10))
+                    .danger_accept_invalid_certs(true)
+                    .build()
+                    .unwrap(),
+                base_url: format!("https://{}:{}/v1/api", gateway_host, gateway_port),
+                rate_limiter: RateLimiter::new(50, 10.0), // 10 req/sec
+            },
+            account_id: None,
+            contracts: Arc::new(parking_lot::RwLock::new(ContractMap::default())),
+            snapshot_tx,
+            snapshot_rx: std::sync::Mutex::new(Some(snapshot_rx)),
+            connected: Arc::new(AtomicBool::new(false)),
+            disconnect_hook: Arc::new(parking_lot::RwLock::new(None)),
+            orders: parking_lot::RwLock::new(HashMap::new()),
+            confirm_warnings: HashSet::new(),
+            tasks: parking_lot::Mutex::new(Vec::new()),
+            dry_run: DryRun::default(),
+        }
+    }
+    
+    /// Trade this account instead of the gateway's selected one
+    pub fn with_account(mut self, account_id: String) -> Self {
+        self.account_id = Some(account_id);
+        self
+    }
+    
+    /// Log and locally ack orders instead of submitting them (see `DryRun`)
+    pub fn with_dry_run(self, enabled: bool) -> Self {
+        self.dry_run.set_enabled(enabled);
+        self
+    }
+    
+    /// Confirm order warnings with these IB message ids (e.g. `o163`);
+    /// any other warning rejects the order
+    pub fn with_confirmed_warnings(mut self, message_ids: impl IntoIterator<Item = String>) -> Self {
+        self.confirm_warnings = message_ids.into_iter().collect();
+        self
+    }
+    
+    pub fn dry_run(&self) -> &DryRun {
+        &self.dry_run
+    }
+    
+    fn account(&self) -> Result<&str> {
+        self.account_id
+            .as_deref()
+            .ok_or_else(|| Error::Venue("IBKR adapter is not connected".to_string()))
+    }
+    
+    /// Contract id of an equity symbol, searching the gateway on a miss
+    async fn resolve_conid(&self, symbol: &str) -> Result<u64> {
+        if let Some(conid) = self.contracts.read().conid(symbol) {
+            return Ok(conid);
+        }
+        
+        let raw: serde_json::Value = self.gateway
+            .get("/iserver/secdef/search", &[("symbol", symbol), ("secType", "STK")])
+            .await?;
+        let conid = parse_search(&raw)?
+            .into_iter()
+            .find(|(found, _)| found.eq_ignore_ascii_case(symbol))
+            .map(|(_, conid)| conid)
+            .ok_or_else(|| Error::NotFound(format!("No IBKR stock contract for {}", symbol)))?;
+        
+        self.contracts.write().insert(symbol, conid);
+        Ok(conid)
+    }
+    
+    /// Resolve a client id (or raw IB order id) to the order's venue identity
+    fn resolve_order(&self, order_id: &str) -> Result<IbkrOrder> {
+        let orders = self.orders.read();
+        
+        if let Some(order) = orders.get(order_id) {
+            return Ok(order.clone());
+        }
+        
+        orders
+            .values()
+            .find(|o| o.order_id == order_id)
+            .cloned()
+            .ok_or_else(|| Error::NotFound(format!("Unknown IBKR order: {}", order_id)))
+    }
+    
+    fn forget_order(&self, order_id: &str) {
+        self.orders.write().retain(|_, o| o.order_id != order_id);
+    }
+    
+    /// Cancel one order; `Err` carries the venue's message
+    async fn cancel_by_id(&self, order_id: &str) -> Result<std::result::Result<(), String>> {
+        let path = format!("/iserver/account/{}/order/{}", self.account()?, order_id);
+        let reply: serde_json::Value = match self.gateway.delete(&path).await {
+            Ok(reply) => reply,
+            Err(Error::Venue(msg)) => return Ok(Err(msg)),
+            Err(e) => return Err(e),
+        };
+        
+        Ok(match reply_error(&reply) {
+            Some(msg) => Err(msg),
+            None => Ok(()),
+        })
+    }
+    
+    /// Keep the gateway session alive and report when it stops being authenticated
+    async fn keepalive_loop(
+        gateway: Gateway,
+        connected: Arc<AtomicBool>,
+        disconnect_hook: HookSlot,
+        contracts: Arc<parking_lot::RwLock<ContractMap>>,
+    ) {
+        #[derive(Deserialize)]
+        struct Tickle {
+            iserver: Option<TickleServer>,
+        }
+        
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct TickleServer {
+            auth_status: AuthStatus,
+        }
+        
+        let mut interval = tokio::time::interval(TICKLE_INTERVAL);
+        interval.tick().await;
+        
+        loop {
+            interval.tick().await;
+            let hook = disconnect_hook.read().clone();
+            
+            let status = gateway
+                .post::<_, Tickle>("/tickle", &serde_json::json!({}))
+                .await
+                .and_then(|t| check_auth(&t.iserver.map(|s| s.auth_status).unwrap_or_default()));
+            
+            match status {
+                Ok(()) => {
+                    if let Some(hook) = hook {
+                        hook.on_alive(Venue::IBKR).await;
+                    }
+                }
+                Err(e) if e.is_critical() => {
+                    tracing::error!("IB Gateway session lost: {}", e);
+                    connected.store(false, Ordering::Relaxed);
+                    if let Some(hook) = hook {
+                        let symbols = contracts.read().symbols();
+                        hook.on_disconnect(Venue::IBKR, &symbols).await;
+                    }
+                    break;
+                }
+                Err(e) => tracing::warn!("IB Gateway keepalive failed: {}", e),
+            }
+        }
+    }
+    
+    /// Poll top-of-book snapshots for `conids` and publish them
+    async fn snapshot_loop(
+        gateway: Gateway,
+        conids: Vec<u64>,
+        contracts: Arc<parking_lot::RwLock<ContractMap>>,
+        snapshot_tx: mpsc::UnboundedSender<MarketSnapshot>,
+    ) {
+        let fields = [FIELD_LAST, FIELD_BID, FIELD_ASK_SIZE, FIELD_ASK, FIELD_BID_SIZE, FIELD_VOLUME].join(",");
+        let conid_list = conids.iter().map(u64::to_string).collect::<Vec<_>>().join(",");
+        let mut interval = tokio::time::interval(SNAPSHOT_INTERVAL);
+        
+        loop {
+            interval.tick().await;
+            
+            let rows: Vec<serde_json::Value> = match gateway
+                .get("/iserver/marketdata/snapshot", &[("conids", &conid_list), ("fields", &fields)])
+                .await
+            {
+                Ok(rows) => rows,
+                Err(e) => {
+                    tracing::warn!("IBKR snapshot poll failed: {}", e);
+                    continue;
+                }
+            };
+            
+            let now_ns = chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0);
+            for row in &rows {
+                let Some(symbol) = row["conid"].as_u64().and_then(|c| contracts.read().symbol(c).map(String::from)) else {
+                    continue;
+                };
+                
+                match parse_snapshot(row, &symbol, now_ns) {
+                    Ok(Some(snapshot)) => {
+                        if snapshot_tx.send(snapshot).is_err() {
+                            return;
+                        }
+                    }
+                    // Fields arrive after the first request for a conid
+                    Ok(None) => {}
+                    Err(e) => tracing::warn!("Dropping IBKR snapshot for {}: {}", symbol, e),
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl MarketDataStream for IbkrAdapter {
+    async fn subscribe_orderbook(&mut self, symbols: &[String]) -> Result<()> {
+        let mut conids = Vec::with_capacity(symbols.len());
+        for symbol in symbols {
+            conids.push(self.resolve_conid(symbol).await?);
+        }
+        
+        let task = tokio::spawn(Self::snapshot_loop(
+            self.gateway.clone(),
+            conids,
+            self.contracts.clone(),
+            self.snapshot_tx.clone(),
+        ));
+        self.tasks.lock().push(task);
+        
+        tracing::info!("Polling IBKR snapshots for {} symbols", symbols.len());
+        Ok(())
+    }
+    
+    async fn subscribe_trades(&mut self, _symbols: &[String]) -> Result<()> {
+        Ok(())
+    }
+    
+    fn snapshot_receiver(&self) -> Result<mpsc::UnboundedReceiver<MarketSnapshot>> {
+        take_receiver(&self.snapshot_rx, Venue::IBKR)
+    }
+}
+
+#[async_trait]
+impl AccountData for IbkrAdapter {
+    async fn balances(&self) -> Result<HashMap<String, Balance>> {
+        #[derive(Deserialize)]
+        struct LedgerEntry {
+            cashbalance: f64,
+            settledcash: f64,
+        }
+        
+        let path = format!("/portfolio/{}/ledger", self.account()?);
+        let ledger: HashMap<String, LedgerEntry> = self.gateway.get(&path, &[]).await?;
+        
+        Ok(ledger
+            .into_iter()
+            // `BASE` aggregates every currency in the account's base currency
+            .filter(|(currency, _)| currency != "BASE")
+            .map(|(currency, entry)| {
+                let balance = Balance {
+                    asset: currency.clone(),
+                    free: entry.settledcash,
+                    locked: (entry.cashbalance - entry.settledcash).max(0.0),
+                    total: entry.cashbalance,
+                };
+                (currency, balance)
+            })
+            .collect())
+    }
+    
+    async fn positions(&self) -> Result<Vec<Position>> {
+        let account = self.account()?;
+        let mut positions = Vec::new();
+        
+        for page in 0.. {
+            let path = format!("/portfolio/{}/positions/{}", account, page);
+            let rows: Vec<IbPosition> = self.gateway.get(&path, &[]).await?;
+            let full = rows.len() == POSITIONS_PAGE;
+            
+            let mut contracts = self.contracts.write();
+            positions.extend(rows.into_iter().map(|row| position_from_ib(row, &mut contracts)));
+            
+            if !full {
+                break;
+            }
+        }
+        
+        Ok(positions)
+    }
+    
+    async fn fee_tier(&self) -> Result<FeeTier> {
+        // IBKR charges per share and the gateway has no commission endpoint;
+        // this is the fixed tier on a typical US stock
+        let tier = FeeTier {
+            maker_fee_bps: 1.0,
+            taker_fee_bps: 1.0,
+            volume_30d: 0.0,
+        };
+        Ok(fallback_fee_tier(Venue::IBKR, tier, "no commission endpoint"))
+    }
+    
+    async fn leverage(&self) -> Result<f64> {
+        Ok(1.0)
+    }
+}
+
+#[async_trait]
+impl OrderRouter for IbkrAdapter {
+    async fn send_order(&self, order: OrderRequest) -> Result<OrderAck> {
+        if self.dry_run.is_enabled() {
+            return Ok(self.dry_run.send(Venue::IBKR, &order));
+        }
+        
+        let conid = self.resolve_conid(&order.symbol).await?;
+        let ticket = order_ticket(&order, conid)?;
+        
+        let path = format!("/iserver/account/{}/orders", self.account()?);
+        let mut raw: serde_json::Value = self.gateway
+            .post(&path, &serde_json::json!({ "orders": [ticket] }))
+            .await?;
+        
+        // IB answers risky-looking orders with warnings that must be confirmed;
+        // only allowlisted ones are confirmed without a human
+        for _ in 0..MAX_ORDER_REPLIES {
+            match parse_order_reply(&raw)? {
+                OrderReply::Placed { order_id, status } => {
+                    if !matches!(status, OrderStatus::Filled | OrderStatus::Cancelled | OrderStatus::Rejected) {
+                        self.orders.write().insert(
+                            order.client_id.clone(),
+                            IbkrOrder { symbol: order.symbol.clone(), order_id: order_id.clone() },
+                        );
+                    }
+                    
+                    return Ok(OrderAck {
+                        venue_order_id: order_id,
+                        client_id: order.client_id,
+                        status,
+                        timestamp_ns: chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0),
+                        avg_fill_price: None,
+                    });
+                }
+                OrderReply::Confirm { reply_id, messages, message_ids } => {
+                    if !auto_confirmable(&message_ids, &self.confirm_warnings) {
+                        tracing::warn!("Not confirming IBKR warning {:?} for {}", message_ids, order.client_id);
+                        return Err(Error::OrderRejected(messages.join(" | ")));
+                    }
+                    tracing::warn!("Confirming IBKR order warning for {}: {}", order.client_id, messages.join(" | "));
+                    raw = self.gateway
+                        .post(&format!("/iserver/reply/{}", reply_id), &serde_json::json!({ "confirmed": true }))
+                        .await?;
+                }
+                OrderReply::Rejected(msg) => return Err(Error::OrderRejected(msg)),
+            }
+        }
+        
+        Err(Error::OrderRejected(format!(
+            "IBKR order {} still needed confirmation after {} replies",
+            order.client_id, MAX_ORDER_REPLIES
+        )))
+    }
+    
+    async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        if self.dry_run.is_enabled() {
+            self.dry_run.cancel(Venue::IBKR, order_id);
+            return Ok(());
+        }
+        
+        let order = self.resolve_order(order_id)?;
+        
+        match self.cancel_by_id(&order.order_id).await? {
+            Ok(()) => {
+                self.forget_order(&order.order_id);
+                Ok(())
+            }
+            Err(msg) if is_unknown_order(&msg) => {
+                self.forget_order(&order.order_id);
+                Err(Error::NotFound(format!("IBKR order {} ({}): {}", order_id, order.order_id, msg)))
+            }
+            Err(msg) => Err(Error::Venue(format!("IBKR cancel failed for {}: {}", order_id, msg))),
+        }
+    }
+    
+    async fn cancel_all(&self, symbol: &str) -> Result<()> {
+        if self.dry_run.is_enabled() {
+            self.dry_run.cancel_all(Venue::IBKR, symbol);
+            return Ok(());
+        }
+        
+        cancel_all_confirmed(self, symbol, CancelRetry::default()).await
+    }
+    
+    async fn get_order(&self, order_id: &str) -> Result<OrderAck> {
+        if let Some(ack) = self.dry_run.lookup(order_id) {
+            return Ok(ack);
+        }
+        
+        #[derive(Deserialize)]
+        struct StatusReply {
+            order_status: String,
+        }
+        
+        let venue_id = match self.resolve_order(order_id) {
+            Ok(order) => order.order_id,
+            Err(e) => {
+                order_id.parse::<u64>().map_err(|_| e)?;
+                order_id.to_string()
+            }
+        };
+        
+        let reply: StatusReply = self.gateway
+            .get(&format!("/iserver/account/order/status/{}", venue_id), &[])
+            .await?;
+        
+        let status = map_order_status(&reply.order_status);
+        if matches!(status, OrderStatus::Filled | OrderStatus::Cancelled | OrderStatus::Rejected) {
+            self.forget_order(&venue_id);
+        }
+        
+        Ok(OrderAck {
+            venue_order_id: venue_id,
+            client_id: order_id.to_string(),
+            status,
+            timestamp_ns: chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0),
+            avg_fill_price: None,
+        })
+    }
+}
+
+#[async_trait]
+impl OpenOrders for IbkrAdapter {
+    type Order = IbkrOrder;
+    
+    async fn open_orders(&self, symbol: &str) -> Result<Vec<IbkrOrder>> {
+        #[derive(Deserialize)]
+        struct LiveOrders {
+            #[serde(default)]
+            orders: Vec<LiveOrder>,
+        }
+        
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct LiveOrder {
+            order_id: u64,
+            conid: u64,
+            status: String,
+        }
+        
+        let conid = self.resolve_conid(symbol).await?;
+        let live: LiveOrders = self.gateway.get("/iserver/account/orders", &[]).await?;
+        
+        Ok(live.orders
+            .into_iter()
+            .filter(|o| o.conid == conid)
+            .filter(|o| !matches!(
+                map_order_status(&o.status),
+                OrderStatus::Filled | OrderStatus::Cancelled | OrderStatus::Rejected
+            ))
+            .map(|o| IbkrOrder { symbol: symbol.to_string(), order_id: o.order_id.to_string() })
+            .collect())
+    }
+    
+    async fn cancel_open(&self, orders: &[IbkrOrder]) -> Result<()> {
+        let mut failures = Vec::new();
+        
+        for order in orders {
+            match self.cancel_by_id(&order.order_id).await? {
+                Ok(()) => self.forget_order(&order.order_id),
+                // Already gone: nothing left to cancel
+                Err(msg) if is_unknown_order(&msg) => self.forget_order(&order.order_id),
+                Err(msg) => failures.push(format!("{}: {}", order.order_id, msg)),
+            }
+        }
+        
+        if !failures.is_empty() {
+            return Err(Error::Venue(format!(
+                "IBKR cancel failed for {} orders: {}",
+                failures.len(), failures.join("; ")
+            )));
+        }
+        
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl MarketInfo for IbkrAdapter {
+    /// IB has no instrument listing; these are the contracts resolved so far
+    async fn list_symbols(&self) -> Result<Vec<String>> {
+        Ok(self.contracts.read().symbols())
+    }
+    
+    async fn search_symbols(&self, prefix: &str) -> Result<Vec<String>> {
+        let raw: serde_json::Value = self.gateway
+            .get("/iserver/secdef/search", &[("symbol", prefix), ("secType", "STK"), ("name", "false")])
+            .await?;
+        
+        let mut symbols: Vec<String> = parse_search(&raw)?
+            .into_iter()
+            .map(|(symbol, _)| symbol)
+            .filter(|s| s.to_lowercase().starts_with(&prefix.to_lowercase()))
+            .collect();
+        symbols.dedup();
+        Ok(symbols)
+    }
+    
+    async fn funding_rate(&self, _symbol: &str) -> Result<f64> {
+        Ok(0.0)
+    }
+    
+    async fn open_interest(&self, _symbol: &str) -> Result<f64> {
+        Ok(0.0)
+    }
+    
+    async fn volume_24h(&self, _symbol: &str) -> Result<f64> {
+        Ok(0.0)
+    }
+    
+    /// Reg T margin on US stocks: no leverage beyond 2x, 25% maintenance
+    async fn leverage_tiers(&self, _symbol: &str) -> Result<Vec<margin::LeverageTier>> {
+        Ok(vec![margin::LeverageTier {
+            notional_floor: 0.0,
+            max_leverage: 2.0,
+            maintenance_margin_rate: 0.25,
+            maintenance_amount: 0.0,
+        }])
+    }
+    
+    async fn symbol_spec(&self, symbol: &str) -> Result<SymbolSpec> {
+        let conid = self.resolve_conid(symbol).await?;
+        let raw: serde_json::Value = self.gateway
+            .get(&format!("/iserver/contract/{}/info-and-rules", conid), &[("isBuy", "true")])
+            .await?;
+        
+        parse_rules(symbol, &raw)
+    }
+}
+
+#[async_trait]
+impl ExchangeAdapter for IbkrAdapter {
+    fn venue(&self) -> Venue {
+        Venue::IBKR
+    }
+    
+    fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+    
+    /// Check the gateway's brokerage session, pick the account and start the keepalive
+    async fn connect(&mut self) -> Result<()> {
+        let status: AuthStatus = self.gateway.post("/iserver/auth/status", &serde_json::json!({})).await?;
+        check_auth(&status)?;
+        
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Accounts {
+            accounts: Vec<String>,
+            selected_account: Option<String>,
+        }
+        
+        // Portfolio endpoints require the account list to have been read first
+        let _: serde_json::Value = self.gateway.get("/portfolio/accounts", &[]).await?;
+        let accounts: Accounts = self.gateway.get("/iserver/accounts", &[]).await?;
+        
+        let account_id = match self.account_id.take() {
+            Some(id) if accounts.accounts.contains(&id) => id,
+            Some(id) => {
+                return Err(Error::Authentication(format!("IBKR session has no access to account {}", id)));
+            }
+            None => accounts.selected_account
+                .or_else(|| accounts.accounts.first().cloned())
+                .ok_or_else(|| Error::Authentication("IBKR session has no accounts".to_string()))?,
+        };
+        self.account_id = Some(account_id);
+        
+        let task = tokio::spawn(Self::keepalive_loop(
+            self.gateway.clone(),
+            self.connected.clone(),
+            self.disconnect_hook.clone(),
+            self.contracts.clone(),
+        ));
+        self.tasks.lock().push(task);
+        
+        self.connected.store(true, Ordering::Relaxed);
+        tracing::info!("IBKR connected to account {}", self.account_id.as_deref().unwrap_or_default());
+        Ok(())
+    }
+    
+    async fn disconnect(&mut self) -> Result<()> {
+        for task in self.tasks.lock().drain(..) {
+            task.abort();
+        }
+        self.connected.store(false, Ordering::Relaxed);
+        
+        let _: serde_json::Value = self.gateway.post("/logout", &serde_json::json!({})).await?;
+        Ok(())
+    }
+    
+    fn set_disconnect_hook(&self, hook: Arc<dyn DisconnectHook>) {
+        *self.disconnect_hook.write() = Some(hook);
+    }
+}
+
+/// `/iserver/auth/status` and the `iserver.authStatus` part of `/tickle`
+#[derive(Debug, Default, Deserialize)]
+struct AuthStatus {
+    #[serde(default)]
+    authenticated: bool,
+    #[serde(default)]
+    competing: bool,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+/// The brokerage session must be authenticated and not displaced by another login
+fn check_auth(status: &AuthStatus) -> Result<()> {
+    if status.competing {
+        return Err(Error::Authentication(
+            "IBKR session is competing with another login for the same user".to_string(),
+        ));
+    }
+    if !status.authenticated {
+        return Err(Error::Authentication(format!(
+            "IB Gateway is not logged in{}",
+            status.message.as_deref().map(|m| format!(": {}", m)).unwrap_or_default()
+        )));
+    }
+    Ok(())
+}
+
+/// Non-2xx gateway response; 401/403 mean the brokerage session is gone
+fn api_error(http_status: u16, body: &str) -> Error {
+    let message = serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|v| reply_error(&v))
+        .unwrap_or_else(|| body.trim().to_string());
+    
+    match http_status {
+        401 | 403 => Error::Authentication(format!("IBKR gateway {}: {}", http_status, message)),
+        _ => Error::Venue(format!("IBKR API error {}: {}", http_status, message)),
+    }
+}
+
+/// `error` field of a gateway reply, if any
+fn reply_error(reply: &serde_json::Value) -> Option<String> {
+    reply.get("error").and_then(|e| e.as_str()).map(String::from)
+}
+
+/// Venue error text for orders that no longer exist
+fn is_unknown_order(msg: &str) -> bool {
+    let msg = msg.to_lowercase();
+    msg.contains("doesn't exist") || msg.contains("not found") || msg.contains("already cancelled")
+}
+
+/// `(symbol, conid)` pairs from `/iserver/secdef/search`; `conid` may be a string
+fn parse_search(raw: &serde_json::Value) -> Result<Vec<(String, u64)>> {
+    if let Some(msg) = reply_error(raw) {
+        return Err(Error::Venue(format!("IBKR contract search failed: {}", msg)));
+    }
+    
+    let rows = raw.as_array().map(Vec::as_slice).unwrap_or_default();
+    rows.iter()
+        .map(|row| {
+            let symbol = row["symbol"].as_str().unwrap_or_default();
+            let conid = match &row["conid"] {
+                serde_json::Value::Number(n) => n.as_u64(),
+                serde_json::Value::String(s) => s.parse().ok(),
+                _ => None,
+            };
+            
+            match conid {
+                Some(conid) if !symbol.is_empty() => Ok((symbol.to_string(), conid)),
+                _ => Err(Error::Venue(format!("Malformed IBKR contract search row: {}", row))),
+            }
+        })
+        .collect()
+}
+
+/// Tick and lot from `/iserver/contract/{conid}/info-and-rules`; stocks
+/// without a `sizeIncrement` trade in whole shares
+fn parse_rules(symbol: &str, raw: &serde_json::Value) -> Result<SymbolSpec> {
+    if let Some(msg) = reply_error(raw) {
+        return Err(Error::Venue(format!("IBKR contract rules for {} failed: {}", symbol, msg)));
+    }
+    
+    let rules = &raw["rules"];
+    let tick_size = parse_ib_number(&rules["increment"], "increment")?;
+    let lot_size = match &rules["sizeIncrement"] {
+        serde_json::Value::Null => 1.0,
+        value => parse_ib_number(value, "sizeIncrement")?,
+    };
+    
+    Ok(SymbolSpec { symbol: symbol.to_string(), tick_size, lot_size })
+}
+
+/// Snapshot field value. IB prefixes closing/halted prices with `C`/`H`,
+/// groups thousands with commas and abbreviates sizes (`1.2K`, `3.4M`).
+fn parse_ib_number(value: &serde_json::Value, field: &str) -> Result<f64> {
+    let malformed = || Error::Venue(format!("IBKR returned non-numeric {}: {}", field, value));
+    
+    let text = match value {
+        serde_json::Value::Number(n) => return n.as_f64().ok_or_else(malformed),
+        serde_json::Value::String(s) => s.trim().trim_start_matches(['C', 'H']).replace(',', ""),
+        _ => return Err(malformed()),
+    };
+    
+    let (digits, scale) = match text.chars().last() {
+        Some('K') => (&text[..text.len() - 1], 1e3),
+        Some('M') => (&text[..text.len() - 1], 1e6),
+        Some('B') => (&text[..text.len() - 1], 1e9),
+        _ => (text.as_str(), 1.0),
+    };
+    
+    digits
+        .parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite())
+        .map(|v| v * scale)
+        .ok_or_else(malformed)
+}
+
+/// Absent snapshot fields are `None`; present but malformed ones are errors
+fn snapshot_field(row: &serde_json::Value, code: &str, field: &str) -> Result<Option<f64>> {
+    row.get(code).map(|v| parse_ib_number(v, field)).transpose()
+}
+
+/// Top-of-book snapshot from one `/iserver/marketdata/snapshot` row, or
+/// `None` while the gateway hasn't delivered the quote fields yet
+pub fn parse_snapshot(row: &serde_json::Value, symbol: &str, now_ns: i64) -> Result<Option<MarketSnapshot>> {
+    let quote = (
+        snapshot_field(row, FIELD_BID, "bid")?,
+        snapshot_field(row, FIELD_BID_SIZE, "bid size")?,
+        snapshot_field(row, FIELD_ASK, "ask")?,
+        snapshot_field(row, FIELD_ASK_SIZE, "ask size")?,
+    );
+    let (Some(bid), Some(bid_size), Some(ask), Some(ask_size)) = quote else {
+        return Ok(None);
+    };
+    if bid <= 0.0 || ask <= 0.0 || bid >= ask {
+        return Err(Error::Venue(format!("Crossed or empty IBKR quote for {}: {} / {}", symbol, bid, ask)));
+    }
+    
+    let timestamp_ns = row["_updated"].as_i64().map_or(now_ns, |ms| ms * 1_000_000);
+    let volume = snapshot_field(row, FIELD_VOLUME, "volume")?;
+    
+    Ok(Some(MarketSnapshot {
+        timestamp_ns,
+        symbol: symbol.to_string(),
+        orderbook: OrderBook {
+            symbol: symbol.to_string(),
+            timestamp_ns,
+            bids: vec![Level { price: ordered_float::OrderedFloat(bid), quantity: bid_size }],
+            asks: vec![Level { price: ordered_float::OrderedFloat(ask), quantity: ask_size }],
+            sequence: 0,
+        },
+        recent_trades: Vec::new(),
+        funding_rate_bps: None,
+        open_interest: None,
+        volume_24h: volume.unwrap_or(0.0),
+        // The snapshot poll is IBKR's primary feed, not a fallback
+        quality: DataQuality::Live,
+    }))
+}
+
+/// One row of `/portfolio/{account}/positions/{page}`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IbPosition {
+    conid: u64,
+    #[serde(default)]
+    ticker: Option<String>,
+    contract_desc: String,
+    position: f64,
+    mkt_price: f64,
+    mkt_value: f64,
+    avg_cost: f64,
+    unrealized_pnl: f64,
+    realized_pnl: f64,
+}
+
+/// Map a portfolio row to our `Position`, naming it by its known symbol, else
+/// its ticker, and remembering the conid for later orders
+fn position_from_ib(row: IbPosition, contracts: &mut ContractMap) -> Position {
+    let symbol = match contracts.symbol(row.conid) {
+        Some(symbol) => symbol.to_string(),
+        None => {
+            let symbol = row.ticker.unwrap_or(row.contract_desc);
+            contracts.insert(&symbol, row.conid);
+            symbol
+        }
+    };
+    
+    Position {
+        symbol,
+        size: row.position,
+        entry_price: row.avg_cost,
+        mark_price: row.mkt_price,
+        unrealized_pnl: row.unrealized_pnl,
+        realized_pnl: row.realized_pnl,
+        leverage: 1.0,
+        margin_used: row.mkt_value.abs(),
+        liquidation_price: None,
+        maintenance_margin: None,
+    }
+}
+
+/// Order ticket for `/iserver/account/{account}/orders`
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OrderTicket {
+    conid: u64,
+    #[serde(rename = "cOID")]
+    client_order_id: String,
+    order_type: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    price: Option<f64>,
+    side: &'static str,
+    quantity: f64,
+    tif: &'static str,
+}
+
+/// IB has no reduce-only flag for stocks, so exits rely on their size
+fn order_ticket(order: &OrderRequest, conid: u64) -> Result<OrderTicket> {
+    let (order_type, tif) = match order.order_type {
+        OrderType::Market => ("MKT", TimeInForce::IOC),
+        OrderType::IOC => ("LMT", TimeInForce::IOC),
+        OrderType::FOK => ("LMT", TimeInForce::FOK),
+        OrderType::PostOnly => ("LMT", TimeInForce::GTX),
+        OrderType::Limit => ("LMT", order.time_in_force),
+    };
+    
+    let tif = match Venue::IBKR.resolve_tif(tif)? {
+        TimeInForce::IOC => "IOC",
+        TimeInForce::FOK => "FOK",
+        TimeInForce::DAY => "DAY",
+        _ => "GTC",
+    };
+    
+    let price = match (order_type, order.price) {
+        ("MKT", _) => None,
+        (_, Some(price)) => Some(price),
+        (_, None) => {
+            return Err(Error::OrderRejected(format!("IBKR limit order {} needs a price", order.client_id)));
+        }
+    };
+    
+    Ok(OrderTicket {
+        conid,
+        client_order_id: order.client_id.clone(),
+        order_type,
+        price,
+        side: match order.side {
+            Side::Buy => "BUY",
+            Side::Sell => "SELL",
+        },
+        quantity: order.quantity,
+        tif,
+    })
+}
+
+/// Outcome of an order submission or reply step
+#[derive(Debug, PartialEq)]
+enum OrderReply {
+    Placed { order_id: String, status: OrderStatus },
+    /// Precautionary warning to confirm via `/iserver/reply/{reply_id}`
+    Confirm { reply_id: String, messages: Vec<String>, message_ids: Vec<String> },
+    Rejected(String),
+}
+
+fn parse_order_reply(raw: &serde_json::Value) -> Result<OrderReply> {
+    if let Some(msg) = reply_error(raw) {
+        return Ok(OrderReply::Rejected(msg));
+    }
+    
+    let first = raw
+        .as_array()
+        .and_then(|rows| rows.first())
+        .ok_or_else(|| Error::Venue(format!("Unexpected IBKR order reply: {}", raw)))?;
+    
+    if let Some(msg) = reply_error(first) {
+        return Ok(OrderReply::Rejected(msg));
+    }
+    
+    if let Some(order_id) = first.get("order_id") {
+        let order_id = match order_id {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        let status = first["order_status"].as_str().map_or(OrderStatus::Pending, map_order_status);
+        return Ok(OrderReply::Placed { order_id, status });
+    }
+    
+    if let Some(reply_id) = first["id"].as_str() {
+        let messages = first["message"]
+            .as_array()
+            .map(|m| m.iter().filter_map(|s| s.as_str().map(String::from)).collect())
+            .unwrap_or_default();
+        let message_ids = first["messageIds"]
+            .as_array()
+            .map(|m| m.iter().filter_map(|s| s.as_str().map(String::from)).collect())
+            .unwrap_or_default();
+        return Ok(OrderReply::Confirm { reply_id: reply_id.to_string(), messages, message_ids });
+    }
+    
+    Err(Error::Venue(format!("Unexpected IBKR order reply: {}", raw)))
+}
+
+/// Untagged warnings are never confirmed, nor are any with an unlisted id
+fn auto_confirmable(message_ids: &[String], allowed: &HashSet<String>) -> bool {
+    !message_ids.is_empty() && message_ids.iter().all(|id| allowed.contains(id))
+}
+
+/// Translate IB order statuses into our `OrderStatus`
+fn map_order_status(status: &str) -> OrderStatus {
+    match status.to_lowercase().as_str() {
+        "submitted" | "pendingcancel" => OrderStatus::Accepted,
+        "filled" => OrderStatus::Filled,
+        "cancelled" | "apicancelled" => OrderStatus::Cancelled,
+        "inactive" | "rejected" => OrderStatus::Rejected,
+        // PendingSubmit, PreSubmitted, ApiPending
+        _ => OrderStatus::Pending,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn order(order_type: OrderType, price: Option<f64>) -> OrderRequest {
+        OrderRequest {
+            client_id: "c1".to_string(),
+            symbol: "AAPL".to_string(),
+            side: Side::Buy,
+            order_type,
+            quantity: 10.0,
+            price,
+            reduce_only: false,
+            time_in_force: TimeInForce::GTC,
+        }
+    }
+    
+    #[test]
+    fn test_check_auth_and_api_errors() {
+        assert!(check_auth(&AuthStatus { authenticated: true, ..Default::default() }).is_ok());
+        
+        let logged_out = AuthStatus { message: Some("session expired".to_string()), ..Default::default() };
+        assert!(matches!(check_auth(&logged_out), Err(Error::Authentication(m)) if m.contains("session expired")));
+        
+        let competing = AuthStatus { authenticated: true, competing: true, message: None };
+        assert!(matches!(check_auth(&competing), Err(Error::Authentication(_))));
+        
+        assert!(matches!(api_error(401, ""), Error::Authentication(_)));
+        let err = api_error(500, r#"{"error":"Bad Request: no bridge"}"#);
+        assert!(matches!(&err, Error::Venue(m) if m.contains("500") && m.contains("no bridge")));
+    }
+    
+    #[test]
+    fn test_parse_search_maps_conids() {
+        let raw = serde_json::json!([
+            { "conid": "265598", "symbol": "AAPL", "description": "NASDAQ" },
+            { "conid": 38708077, "symbol": "AAPL", "description": "MEXI" }
+        ]);
+        let found = parse_search(&raw).unwrap();
+        assert_eq!(found, vec![("AAPL".to_string(), 265598), ("AAPL".to_string(), 38708077)]);
+        
+        assert!(parse_search(&serde_json::json!({ "error": "no contracts" })).is_err());
+        assert!(parse_search(&serde_json::json!([{ "symbol": "AAPL" }])).is_err());
+        
+        let mut contracts = ContractMap::default();
+        contracts.insert("AAPL", 265598);
+        assert_eq!(contracts.conid("AAPL"), Some(265598));
+        assert_eq!(contracts.symbol(265598), Some("AAPL"));
+    }
+    
+    #[test]
+    fn test_parse_rules() {
+        let raw = serde_json::json!({ "rules": { "orderTypes": ["limit"], "increment": 0.01, "sizeIncrement": 1 } });
+        let spec = parse_rules("AAPL", &raw).unwrap();
+        assert_eq!((spec.tick_size, spec.lot_size), (0.01, 1.0));
+        
+        let whole_shares = parse_rules("AAPL", &serde_json::json!({ "rules": { "increment": "0.005" } })).unwrap();
+        assert_eq!(whole_shares.lot_size, 1.0);
+        
+        assert!(parse_rules("AAPL", &serde_json::json!({ "error": "no rules" })).is_err());
+    }
+    
+    #[test]
+    fn test_parse_ib_number() {
+        assert_eq!(parse_ib_number(&serde_json::json!("189.25"), "bid").unwrap(), 189.25);
+        assert_eq!(parse_ib_number(&serde_json::json!("C189.25"), "last").unwrap(), 189.25);
+        assert_eq!(parse_ib_number(&serde_json::json!("1,200"), "bid size").unwrap(), 1200.0);
+        assert_eq!(parse_ib_number(&serde_json::json!("3.4M"), "volume").unwrap(), 3_400_000.0);
+        assert_eq!(parse_ib_number(&serde_json::json!(7), "ask size").unwrap(), 7.0);
+        assert!(parse_ib_number(&serde_json::json!("N/A"), "bid").is_err());
+        assert!(parse_ib_number(&serde_json::Value::Null, "bid").is_err());
+    }
+    
+    #[test]
+    fn test_parse_snapshot() {
+        let row = serde_json::json!({
+            "conid": 265598,
+            "_updated": 1_718_035_200_123i64,
+            "31": "189.30",
+            "84": "189.25",
+            "85": "300",
+            "86": "189.31",
+            "88": "1,200",
+            "7762": "45.2M"
+        });
+        
+        let snapshot = parse_snapshot(&row, "AAPL", 0).unwrap().unwrap();
+        assert_eq!(snapshot.symbol, "AAPL");
+        assert_eq!(snapshot.timestamp_ns, 1_718_035_200_123_000_000);
+        assert_eq!(snapshot.orderbook.bids[0].price.0, 189.25);
+        assert_eq!(snapshot.orderbook.bids[0].quantity, 1200.0);
+        assert_eq!(snapshot.orderbook.asks[0].quantity, 300.0);
+        assert_eq!(snapshot.volume_24h, 45_200_000.0);
+        
+        // First response for a conid carries no fields yet
+        assert!(parse_snapshot(&serde_json::json!({ "conid": 265598 }), "AAPL", 0).unwrap().is_none());
+        
+        let mut bad = row.clone();
+        bad["88"] = serde_json::json!("lots");
+        assert!(parse_snapshot(&bad, "AAPL", 0).is_err());
+        
+        let mut crossed = row;
+        crossed["84"] = serde_json::json!("190.00");
+        assert!(parse_snapshot(&crossed, "AAPL", 0).is_err());
+    }
+    
+    #[test]
+    fn test_order_ticket() {
+        let ticket = order_ticket(&order(OrderType::Limit, Some(189.25)), 265598).unwrap();
+        assert_eq!(
+            serde_json::to_value(&ticket).unwrap(),
+            serde_json::json!({
+                "conid": 265598, "cOID": "c1", "orderType": "LMT", "price": 189.25,
+                "side": "BUY", "quantity": 10.0, "tif": "GTC"
+            })
+        );
+        
+        let market = order_ticket(&order(OrderType::Market, None), 265598).unwrap();
+        assert_eq!((market.order_type, market.price, market.tif), ("MKT", None, "IOC"));
+        
+        let fok = order_ticket(&order(OrderType::FOK, Some(189.0)), 265598).unwrap();
+        assert_eq!(fok.tif, "FOK");
+        
+        let day = OrderRequest { time_in_force: TimeInForce::DAY, ..order(OrderType::Limit, Some(189.0)) };
+        assert_eq!(order_ticket(&day, 265598).unwrap().tif, "DAY");
+        
+        // No post-only on IBKR, and limits need a price
+        assert!(order_ticket(&order(OrderType::PostOnly, Some(189.0)), 265598).is_err());
+        assert!(order_ticket(&order(OrderType::Limit, None), 265598).is_err());
+    }
+    
+    #[test]
+    fn test_parse_order_reply() {
+        let placed = serde_json::json!([{ "order_id": "1234567", "order_status": "Submitted", "encrypt_message": "1" }]);
+        assert_eq!(
+            parse_order_reply(&placed).unwrap(),
+            OrderReply::Placed { order_id: "1234567".to_string(), status: OrderStatus::Accepted }
+        );
+        
+        let confirm = serde_json::json!([{
+            "id": "07a13a5a-4a48-44a5-bb25-5ab37b79186c",
+            "message": ["The following order exceeds the price percentage limit"],
+            "isSuppressed": false,
+            "messageIds": ["o163"]
+        }]);
+        assert!(matches!(
+            parse_order_reply(&confirm).unwrap(),
+            OrderReply::Confirm { reply_id, messages, message_ids }
+                if reply_id.starts_with("07a13a5a") && messages.len() == 1 && message_ids == ["o163"]
+        ));
+        
+        let rejected = serde_json::json!({ "error": "Order couldn't be submitted: insufficient funds" });
+        assert!(matches!(parse_order_reply(&rejected).unwrap(), OrderReply::Rejected(m) if m.contains("insufficient")));
+        
+        assert!(parse_order_reply(&serde_json::json!([])).is_err());
+    }
+    
+    #[test]
+    fn test_only_allowlisted_warnings_are_confirmed() {
+        let adapter = IbkrAdapter::new("127.0.0.1", 5000).with_confirmed_warnings(vec!["o163".to_string()]);
+        let ids = |ids: &[&str]| ids.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+        
+        assert!(auto_confirmable(&ids(&["o163"]), &adapter.confirm_warnings));
+        
+        // An unlisted id, even next to a listed one, leaves the order unconfirmed
+        assert!(!auto_confirmable(&ids(&["o354"]), &adapter.confirm_warnings));
+        assert!(!auto_confirmable(&ids(&["o163", "o354"]), &adapter.confirm_warnings));
+        assert!(!auto_confirmable(&[], &adapter.confirm_warnings));
+        
+        // Nothing is confirmed by default
+        assert!(!auto_confirmable(&ids(&["o163"]), &IbkrAdapter::new("127.0.0.1", 5000).confirm_warnings));
+    }
+    
+    #[test]
+    fn test_map_order_status_and_positions() {
+        assert_eq!(map_order_status("PreSubmitted"), OrderStatus::Pending);
+        assert_eq!(map_order_status("Submitted"), OrderStatus::Accepted);
+        assert_eq!(map_order_status("Filled"), OrderStatus::Filled);
+        assert_eq!(map_order_status("ApiCancelled"), OrderStatus::Cancelled);
+        assert_eq!(map_order_status("Inactive"), OrderStatus::Rejected);
+        
+        let row: IbPosition = serde_json::from_value(serde_json::json!({
+            "acctId": "U1234567", "conid": 265598, "contractDesc": "AAPL", "position": -25.0,
+            "mktPrice": 189.3, "mktValue": -4732.5, "avgCost": 190.1, "avgPrice": 190.1,
+            "realizedPnl": 0.0, "unrealizedPnl": 20.0, "currency": "USD"
+        })).unwrap();
+        
+        let mut contracts = ContractMap::default();
+        let position = position_from_ib(row, &mut contracts);
+        assert_eq!((position.symbol.as_str(), position.size, position.margin_used), ("AAPL", -25.0, 4732.5));
+        assert_eq!(contracts.conid("AAPL"), Some(265598));
+    }
+    
+    #[tokio::test]
+    async fn test_dry_run_and_unconnected_account() {
+        let adapter = IbkrAdapter::new("127.0.0.1", 5000).with_dry_run(true);
+        assert!(!adapter.is_connected());
+        assert!(adapter.account().is_err());
+        
+        let ack = adapter.send_order(order(OrderType::Limit, Some(189.0))).await.unwrap();
+        assert!(ack.venue_order_id.starts_with(DRY_RUN_ID_PREFIX));
+        adapter.cancel_all("AAPL").await.unwrap();
+    }
 }
\ No newline at end of file
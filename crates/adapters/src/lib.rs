@@ -2,17 +2,32 @@
 use async_trait::async_trait;
 use common::*;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
 use tokio::sync::mpsc;
 
+pub use common::ImpactCurve;
+
 pub mod hyperliquid;
 pub mod binance;
 pub mod ibkr;
+pub mod replay;
+mod backoff;
+mod cancel;
+mod hyperliquid_signing;
 mod rate_limiter;
+mod fallback;
+mod dry_run;
 
 pub use hyperliquid::HyperliquidAdapter;
 pub use binance::BinanceAdapter;
 pub use ibkr::IbkrAdapter;
+pub use replay::{ReplayAdapter, ReplayFill};
+pub use backoff::ReconnectBackoff;
+pub use cancel::{cancel_all_confirmed, CancelRetry, OpenOrders};
 pub use rate_limiter::RateLimiter;
+pub use fallback::{RestFallback, RestFallbackConfig};
+pub use dry_run::{DryRun, OrderIntent, DRY_RUN_ID_PREFIX};
 
 /// Market data stream interface
 #[async_trait]
@@ -23,8 +38,8 @@ pub trait MarketDataStream: Send + Sync {
     /// Subscribe to trade stream
     async fn subscribe_trades(&mut self, symbols: &[String]) -> Result<()>;
     
-    /// Get the receiver for market snapshots
-    fn snapshot_receiver(&self) -> mpsc::UnboundedReceiver<MarketSnapshot>;
+    /// Take the receiver for market snapshots; errors if it was already taken
+    fn snapshot_receiver(&self) -> Result<mpsc::UnboundedReceiver<MarketSnapshot>>;
 }
 
 /// Account data interface
@@ -52,11 +67,17 @@ pub trait OrderRouter: Send + Sync {
     /// Cancel an order
     async fn cancel_order(&self, order_id: &str) -> Result<()>;
     
-    /// Cancel all orders for a symbol
+    /// Cancel all orders for a symbol, succeeding only once none remain
     async fn cancel_all(&self, symbol: &str) -> Result<()>;
     
     /// Get order status
     async fn get_order(&self, order_id: &str) -> Result<OrderAck>;
+    
+    /// Arm the venue's dead-man's switch so all orders are cancelled if it is
+    /// not refreshed within `deadline_ms`. Returns `false` if unsupported.
+    async fn schedule_cancel(&self, _deadline_ms: u64) -> Result<bool> {
+        Ok(false)
+    }
 }
 
 /// Market info interface
@@ -76,6 +97,12 @@ pub trait MarketInfo: Send + Sync {
     
     /// Get 24h volume
     async fn volume_24h(&self, symbol: &str) -> Result<f64>;
+    
+    /// Notional-tiered leverage table, cached after the first fetch
+    async fn leverage_tiers(&self, symbol: &str) -> Result<Vec<margin::LeverageTier>>;
+    
+    /// Price tick and size lot of a symbol
+    async fn symbol_spec(&self, symbol: &str) -> Result<SymbolSpec>;
 }
 
 /// Complete exchange adapter
@@ -87,19 +114,100 @@ pub trait ExchangeAdapter:
     fn is_connected(&self) -> bool;
     async fn connect(&mut self) -> Result<()>;
     async fn disconnect(&mut self) -> Result<()>;
+    
+    /// Register a hook driven by the market-data connection state
+    fn set_disconnect_hook(&self, _hook: Arc<dyn DisconnectHook>) {}
 }
 
-/// Impact curve parameters (A * notional^beta)
-#[derive(Debug, Clone, Copy)]
-pub struct ImpactCurve {
-    pub a: f64,
-    pub beta: f64,
+/// Safety hook driven by a venue's stream connection state
+#[async_trait]
+pub trait DisconnectHook: Send + Sync {
+    /// Called after connecting and periodically while the stream is healthy
+    async fn on_alive(&self, venue: Venue);
+    
+    /// Called when the stream drops, before reconnecting
+    async fn on_disconnect(&self, venue: Venue, symbols: &[String]);
+}
+
+/// Cancel-on-disconnect: keeps the venue's dead-man's switch armed where
+/// supported, otherwise cancels resting orders over REST when the stream drops
+pub struct CancelOnDisconnect<R: OrderRouter + ?Sized> {
+    router: Weak<R>,
+    deadline_ms: u64,
+    native_armed: AtomicBool,
+}
+
+impl<R: OrderRouter + ?Sized> CancelOnDisconnect<R> {
+    pub fn new(router: Weak<R>, deadline_ms: u64) -> Self {
+        Self {
+            router,
+            deadline_ms,
+            native_armed: AtomicBool::new(false),
+        }
+    }
+    
+    /// Whether the venue-native dead-man's switch is currently armed
+    pub fn native_armed(&self) -> bool {
+        self.native_armed.load(Ordering::Relaxed)
+    }
 }
 
-impl ImpactCurve {
-    pub fn compute_bps(&self, notional: f64) -> f64 {
-        self.a * notional.powf(self.beta) * 10000.0
+#[async_trait]
+impl<R: OrderRouter + ?Sized + 'static> DisconnectHook for CancelOnDisconnect<R> {
+    async fn on_alive(&self, venue: Venue) {
+        let Some(router) = self.router.upgrade() else {
+            return;
+        };
+        
+        match router.schedule_cancel(self.deadline_ms).await {
+            Ok(armed) => self.native_armed.store(armed, Ordering::Relaxed),
+            Err(e) => {
+                tracing::warn!("{:?}: failed to arm dead-man's switch: {}", venue, e);
+                self.native_armed.store(false, Ordering::Relaxed);
+            }
+        }
     }
+    
+    async fn on_disconnect(&self, venue: Venue, symbols: &[String]) {
+        if self.native_armed() {
+            tracing::warn!(
+                "{:?} stream dropped; venue dead-man's switch will cancel resting orders within {}ms",
+                venue, self.deadline_ms
+            );
+            return;
+        }
+        
+        let Some(router) = self.router.upgrade() else {
+            return;
+        };
+        
+        tracing::warn!("{:?} stream dropped; cancelling resting orders on {} symbols", venue, symbols.len());
+        
+        for symbol in symbols {
+            if let Err(e) = router.cancel_all(symbol).await {
+                tracing::error!("{:?}: cancel-on-disconnect failed for {}: {}", venue, symbol, e);
+            }
+        }
+    }
+}
+
+/// Fees to use in place of an account tier the venue didn't give us,
+/// counted so a cost model running on assumed fees shows up
+pub(crate) fn fallback_fee_tier(venue: Venue, tier: FeeTier, reason: &str) -> FeeTier {
+    tracing::warn!("{:?}: account fee tier unavailable ({}), assuming {:?}", venue, reason, tier);
+    metrics::increment_counter!("fee_tier_fallback", "venue" => format!("{:?}", venue));
+    tier
+}
+
+/// Take a snapshot receiver out of its slot exactly once
+pub(crate) fn take_receiver(
+    slot: &std::sync::Mutex<Option<mpsc::UnboundedReceiver<MarketSnapshot>>>,
+    venue: Venue,
+) -> Result<mpsc::UnboundedReceiver<MarketSnapshot>> {
+    slot.lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .take()
+        .ok_or_else(|| Error::Internal(format!("{:?} snapshot receiver already taken", venue)))
 }
 
 /// Order book delta
@@ -160,6 +268,24 @@ impl OrderBookMaintainer {
         self.sequence += 1;
     }
     
+    /// Replace both sides with a full snapshot of `(price, quantity)` levels;
+    /// levels absent from the snapshot are dropped
+    pub fn replace_snapshot(&mut self, bids: &[(f64, f64)], asks: &[(f64, f64)]) {
+        use ordered_float::OrderedFloat;
+        
+        let side = |levels: &[(f64, f64)]| -> std::collections::BTreeMap<OrderedFloat<f64>, f64> {
+            levels
+                .iter()
+                .filter(|(_, q)| *q > 0.0)
+                .map(|&(p, q)| (OrderedFloat(p), q))
+                .collect()
+        };
+        
+        self.bids = side(bids);
+        self.asks = side(asks);
+        self.sequence += 1;
+    }
+    
     pub fn to_orderbook(&self, timestamp_ns: i64, depth: usize) -> OrderBook {
         use ordered_float::OrderedFloat;
         
@@ -184,4 +310,84 @@ impl OrderBookMaintainer {
             sequence: self.sequence,
         }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+    
+    struct MockRouter {
+        native: bool,
+        cancelled: Mutex<Vec<String>>,
+    }
+    
+    #[async_trait]
+    impl OrderRouter for MockRouter {
+        async fn send_order(&self, _order: OrderRequest) -> Result<OrderAck> {
+            Err(Error::Internal("unused".to_string()))
+        }
+        
+        async fn cancel_order(&self, _order_id: &str) -> Result<()> {
+            Ok(())
+        }
+        
+        async fn cancel_all(&self, symbol: &str) -> Result<()> {
+            self.cancelled.lock().push(symbol.to_string());
+            Ok(())
+        }
+        
+        async fn get_order(&self, _order_id: &str) -> Result<OrderAck> {
+            Err(Error::Internal("unused".to_string()))
+        }
+        
+        async fn schedule_cancel(&self, _deadline_ms: u64) -> Result<bool> {
+            Ok(self.native)
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_disconnect_triggers_cancel_all() {
+        let router = Arc::new(MockRouter { native: false, cancelled: Mutex::new(vec![]) });
+        let hook = CancelOnDisconnect::new(Arc::downgrade(&router), 60_000);
+        
+        hook.on_alive(Venue::Hyperliquid).await;
+        assert!(!hook.native_armed());
+        
+        let symbols = vec!["BTC".to_string(), "ETH".to_string()];
+        hook.on_disconnect(Venue::Hyperliquid, &symbols).await;
+        
+        assert_eq!(*router.cancelled.lock(), symbols);
+    }
+    
+    #[tokio::test]
+    async fn test_native_dead_mans_switch_skips_rest_cancel() {
+        let router = Arc::new(MockRouter { native: true, cancelled: Mutex::new(vec![]) });
+        let hook = CancelOnDisconnect::new(Arc::downgrade(&router), 60_000);
+        
+        hook.on_alive(Venue::Hyperliquid).await;
+        assert!(hook.native_armed());
+        
+        hook.on_disconnect(Venue::Hyperliquid, &["BTC".to_string()]).await;
+        assert!(router.cancelled.lock().is_empty());
+    }
+    
+    #[test]
+    fn test_replace_snapshot_drops_missing_levels() {
+        let mut book = OrderBookMaintainer::new("BTC".to_string());
+        
+        book.replace_snapshot(&[(100.0, 1.0), (99.0, 2.0)], &[(101.0, 1.0), (102.0, 3.0)]);
+        assert_eq!(book.to_orderbook(0, 10).bids.len(), 2);
+        
+        // Second snapshot no longer has 99.0 or 102.0
+        book.replace_snapshot(&[(100.0, 1.5)], &[(101.0, 1.0)]);
+        let snapshot = book.to_orderbook(0, 10);
+        
+        assert_eq!(snapshot.bids.len(), 1);
+        assert_eq!(snapshot.asks.len(), 1);
+        assert!(snapshot.bids.iter().all(|l| l.price.0 != 99.0));
+        assert!(snapshot.asks.iter().all(|l| l.price.0 != 102.0));
+        assert_eq!(snapshot.bids[0].quantity, 1.5);
+        assert_eq!(snapshot.sequence, 2);
+    }
 }
\ No newline at end of file
@@ -4,7 +4,8 @@ use tokio::sync::Semaphore;
 use std::time::{Duration, Instant};
 use parking_lot::Mutex;
 
-/// Token bucket rate limiter
+/// Token bucket rate limiter (clones share the same bucket)
+#[derive(Clone)]
 pub struct RateLimiter {
     tokens: Arc<Mutex<TokenBucket>>,
     semaphore: Arc<Semaphore>,
@@ -0,0 +1,361 @@
+// crates/adapters/src/replay.rs
+use crate::*;
+use arrow_array::{Array, Float64Array, Int64Array, RecordBatch, StringArray};
+use ordered_float::OrderedFloat;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::sync::{mpsc, watch};
+
+/// Training shards carry no depth, so each replayed side is one level of this size
+pub const REPLAY_LEVEL_QTY: f64 = 1.0;
+
+/// Fill recorded by the replay router
+#[derive(Debug, Clone)]
+pub struct ReplayFill {
+    pub client_id: String,
+    pub symbol: String,
+    pub side: Side,
+    pub quantity: f64,
+    pub price: f64,
+    pub timestamp_ns: i64,
+}
+
+/// Backtest feed: replays the parquet shards written by the engine's
+/// `S3Writer` as `MarketSnapshot`s in timestamp order. Orders never leave the
+/// process; marketable ones fill at the last replayed touch.
+pub struct ReplayAdapter {
+    paths: Vec<PathBuf>,
+    /// Replay speed relative to recorded time (2.0 = twice as fast); zero or
+    /// infinite replays as fast as the receiver drains
+    speed: f64,
+    snapshot_tx: mpsc::UnboundedSender<MarketSnapshot>,
+    snapshot_rx: parking_lot::Mutex<Option<mpsc::UnboundedReceiver<MarketSnapshot>>>,
+    end_tx: Arc<watch::Sender<bool>>,
+    /// Last replayed book per symbol, the price reference for fills
+    books: Arc<parking_lot::RwLock<HashMap<String, OrderBook>>>,
+    fills: parking_lot::Mutex<Vec<ReplayFill>>,
+    acks: parking_lot::RwLock<HashMap<String, OrderAck>>,
+    next_id: AtomicU64,
+}
+
+impl ReplayAdapter {
+    /// `paths` are shard files or directories searched recursively for `.parquet`
+    pub fn new(paths: Vec<PathBuf>, speed: f64) -> Self {
+        let (snapshot_tx, snapshot_rx) = mpsc::unbounded_channel();
+        let (end_tx, _) = watch::channel(false);
+        
+        Self {
+            paths,
+            speed,
+            snapshot_tx,
+            snapshot_rx: parking_lot::Mutex::new(Some(snapshot_rx)),
+            end_tx: Arc::new(end_tx),
+            books: Arc::new(parking_lot::RwLock::new(HashMap::new())),
+            fills: parking_lot::Mutex::new(Vec::new()),
+            acks: parking_lot::RwLock::new(HashMap::new()),
+            next_id: AtomicU64::new(0),
+        }
+    }
+    
+    /// Flips to `true` once the last snapshot has been sent
+    pub fn end_of_data(&self) -> watch::Receiver<bool> {
+        self.end_tx.subscribe()
+    }
+    
+    /// Fills recorded so far, oldest first
+    pub fn fills(&self) -> Vec<ReplayFill> {
+        self.fills.lock().clone()
+    }
+    
+    async fn replay(
+        snapshots: Vec<MarketSnapshot>,
+        speed: f64,
+        snapshot_tx: mpsc::UnboundedSender<MarketSnapshot>,
+        books: Arc<parking_lot::RwLock<HashMap<String, OrderBook>>>,
+        end_tx: Arc<watch::Sender<bool>>,
+    ) {
+        let paced = speed.is_finite() && speed > 0.0;
+        let mut prev_ts = None;
+        let count = snapshots.len();
+        
+        for snapshot in snapshots {
+            if let Some(prev) = prev_ts.filter(|_| paced) {
+                let gap_ns = (snapshot.timestamp_ns - prev).max(0) as f64 / speed;
+                tokio::time::sleep(std::time::Duration::from_nanos(gap_ns as u64)).await;
+            }
+            prev_ts = Some(snapshot.timestamp_ns);
+            
+            books.write().insert(snapshot.symbol.clone(), snapshot.orderbook.clone());
+            if snapshot_tx.send(snapshot).is_err() {
+                tracing::warn!("Replay receiver dropped, stopping");
+                break;
+            }
+        }
+        
+        tracing::info!("Replay finished after {} snapshots", count);
+        end_tx.send_replace(true);
+    }
+}
+
+#[async_trait]
+impl MarketDataStream for ReplayAdapter {
+    /// Starts the replay of `symbols` (all symbols if empty)
+    async fn subscribe_orderbook(&mut self, symbols: &[String]) -> Result<()> {
+        let snapshots = load_snapshots(&self.paths, symbols)?;
+        tracing::info!("Replaying {} snapshots at {}x", snapshots.len(), self.speed);
+        
+        tokio::spawn(Self::replay(
+            snapshots,
+            self.speed,
+            self.snapshot_tx.clone(),
+            self.books.clone(),
+            self.end_tx.clone(),
+        ));
+        Ok(())
+    }
+    
+    async fn subscribe_trades(&mut self, _symbols: &[String]) -> Result<()> {
+        // Shards hold no trade prints
+        Ok(())
+    }
+    
+    fn snapshot_receiver(&self) -> Result<mpsc::UnboundedReceiver<MarketSnapshot>> {
+        self.snapshot_rx
+            .lock()
+            .take()
+            .ok_or_else(|| Error::Internal("Replay snapshot receiver already taken".to_string()))
+    }
+}
+
+#[async_trait]
+impl OrderRouter for ReplayAdapter {
+    async fn send_order(&self, order: OrderRequest) -> Result<OrderAck> {
+        let book = self.books.read().get(&order.symbol).cloned().ok_or_else(|| {
+            Error::OrderRejected(format!("No replayed price for {} yet", order.symbol))
+        })?;
+        
+        let touch = match order.side {
+            Side::Buy => book.best_ask(),
+            Side::Sell => book.best_bid(),
+        }
+        .map(|l| l.price.0)
+        .ok_or_else(|| Error::OrderRejected(format!("Replayed {} book is empty", order.symbol)))?;
+        
+        let marketable = match (order.order_type, order.price, order.side) {
+            (OrderType::Market, _, _) | (_, None, _) => true,
+            (_, Some(limit), Side::Buy) => limit >= touch,
+            (_, Some(limit), Side::Sell) => limit <= touch,
+        };
+        
+        let status = if marketable {
+            self.fills.lock().push(ReplayFill {
+                client_id: order.client_id.clone(),
+                symbol: order.symbol.clone(),
+                side: order.side,
+                quantity: order.quantity,
+                price: touch,
+                timestamp_ns: book.timestamp_ns,
+            });
+            OrderStatus::Filled
+        } else if matches!(order.time_in_force, TimeInForce::GTC | TimeInForce::DAY) {
+            OrderStatus::Accepted
+        } else {
+            OrderStatus::Cancelled
+        };
+        
+        let ack = OrderAck {
+            venue_order_id: format!("replay-{}", self.next_id.fetch_add(1, Ordering::Relaxed) + 1),
+            client_id: order.client_id.clone(),
+            status,
+            timestamp_ns: book.timestamp_ns,
+            avg_fill_price: (status == OrderStatus::Filled).then_some(touch),
+        };
+        self.acks.write().insert(order.client_id, ack.clone());
+        Ok(ack)
+    }
+    
+    async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        if let Some(ack) = self.acks.write().get_mut(order_id) {
+            if ack.status == OrderStatus::Accepted {
+                ack.status = OrderStatus::Cancelled;
+            }
+        }
+        Ok(())
+    }
+    
+    async fn cancel_all(&self, _symbol: &str) -> Result<()> {
+        // Acks don't carry the symbol; resting replay orders never fill anyway
+        for ack in self.acks.write().values_mut() {
+            if ack.status == OrderStatus::Accepted {
+                ack.status = OrderStatus::Cancelled;
+            }
+        }
+        Ok(())
+    }
+    
+    async fn get_order(&self, order_id: &str) -> Result<OrderAck> {
+        self.acks
+            .read()
+            .get(order_id)
+            .cloned()
+            .ok_or_else(|| Error::NotFound(format!("Replay order {}", order_id)))
+    }
+}
+
+/// Read every shard under `paths` into snapshots sorted by timestamp
+/// (the sort is stable, so same-timestamp rows keep file order)
+pub fn load_snapshots(paths: &[PathBuf], symbols: &[String]) -> Result<Vec<MarketSnapshot>> {
+    let mut files = Vec::new();
+    for path in paths {
+        collect_shards(path, &mut files)?;
+    }
+    files.sort();
+    
+    let mut snapshots = Vec::new();
+    for file in &files {
+        let reader = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(file)?)
+            .and_then(|b| b.build())
+            .map_err(|e| Error::Internal(format!("Cannot read shard {:?}: {}", file, e)))?;
+        
+        for batch in reader {
+            let batch = batch.map_err(|e| Error::Internal(format!("Cannot read shard {:?}: {}", file, e)))?;
+            snapshots.extend(batch_snapshots(&batch, file)?);
+        }
+    }
+    
+    if !symbols.is_empty() {
+        snapshots.retain(|s| symbols.contains(&s.symbol));
+    }
+    snapshots.sort_by_key(|s| s.timestamp_ns);
+    
+    Ok(snapshots)
+}
+
+fn collect_shards(path: &Path, out: &mut Vec<PathBuf>) -> Result<()> {
+    if path.is_dir() {
+        for entry in std::fs::read_dir(path)? {
+            collect_shards(&entry?.path(), out)?;
+        }
+    } else if path.extension().is_some_and(|ext| ext == "parquet") {
+        out.push(path.to_path_buf());
+    }
+    Ok(())
+}
+
+/// One snapshot per shard row: a single-level book at mid +/- half the spread
+fn batch_snapshots(batch: &RecordBatch, file: &Path) -> Result<Vec<MarketSnapshot>> {
+    fn column<'a, T: Array + 'static>(batch: &'a RecordBatch, name: &str, file: &Path) -> Result<&'a T> {
+        batch
+            .column_by_name(name)
+            .and_then(|c| c.as_any().downcast_ref::<T>())
+            .ok_or_else(|| Error::Internal(format!("Shard {:?} has no usable {} column", file, name)))
+    }
+    
+    let timestamps = column::<Int64Array>(batch, "timestamp_ns", file)?;
+    let symbols = column::<StringArray>(batch, "symbol", file)?;
+    let mids = column::<Float64Array>(batch, "mid_price", file)?;
+    let spreads = column::<Float64Array>(batch, "spread_bps", file)?;
+    let funding = column::<Float64Array>(batch, "funding_bps_8h", file)?;
+    
+    let snapshots = (0..batch.num_rows())
+        .map(|i| {
+            let timestamp_ns = timestamps.value(i);
+            let symbol = symbols.value(i).to_string();
+            let mid = mids.value(i);
+            let half_spread = mid * spreads.value(i) / 20_000.0;
+            let level = |price: f64| Level { price: OrderedFloat(price), quantity: REPLAY_LEVEL_QTY };
+            
+            MarketSnapshot {
+                timestamp_ns,
+                symbol: symbol.clone(),
+                orderbook: OrderBook {
+                    symbol,
+                    timestamp_ns,
+                    bids: vec![level(mid - half_spread)],
+                    asks: vec![level(mid + half_spread)],
+                    sequence: i as u64,
+                },
+                recent_trades: Vec::new(),
+                funding_rate_bps: Some(funding.value(i)),
+                open_interest: None,
+                volume_24h: 0.0,
+                quality: DataQuality::default(),
+            }
+        })
+        .collect();
+    
+    Ok(snapshots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::ArrayRef;
+    use arrow_schema::{DataType, Field, Schema};
+    use parquet::arrow::ArrowWriter;
+    
+    /// Minimal shard with the replayed columns of the engine's training schema
+    fn write_fixture(path: &Path, rows: &[(i64, &str, f64)]) {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("timestamp_ns", DataType::Int64, false),
+            Field::new("symbol", DataType::Utf8, false),
+            Field::new("mid_price", DataType::Float64, false),
+            Field::new("spread_bps", DataType::Float64, false),
+            Field::new("funding_bps_8h", DataType::Float64, false),
+        ]));
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(Int64Array::from_iter_values(rows.iter().map(|r| r.0))),
+            Arc::new(StringArray::from_iter_values(rows.iter().map(|r| r.1))),
+            Arc::new(Float64Array::from_iter_values(rows.iter().map(|r| r.2))),
+            Arc::new(Float64Array::from_iter_values(rows.iter().map(|_| 2.0))),
+            Arc::new(Float64Array::from_iter_values(rows.iter().map(|_| 0.1))),
+        ];
+        let batch = RecordBatch::try_new(schema.clone(), columns).unwrap();
+        
+        let mut writer = ArrowWriter::try_new(std::fs::File::create(path).unwrap(), schema, None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_replay_in_timestamp_order() {
+        let dir = std::env::temp_dir().join(format!("replay_test_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("BTC")).unwrap();
+        write_fixture(&dir.join("BTC/a.parquet"), &[(3_000, "BTC", 100.0), (1_000, "BTC", 99.0)]);
+        write_fixture(&dir.join("eth.parquet"), &[(2_000, "ETH", 10.0), (4_000, "ETH", 11.0)]);
+        
+        let mut adapter = ReplayAdapter::new(vec![dir.clone()], 0.0);
+        let mut rx = adapter.snapshot_receiver().unwrap();
+        let mut end = adapter.end_of_data();
+        adapter.subscribe_orderbook(&[]).await.unwrap();
+        
+        end.wait_for(|done| *done).await.unwrap();
+        let mut timestamps = Vec::new();
+        while let Ok(snapshot) = rx.try_recv() {
+            timestamps.push(snapshot.timestamp_ns);
+        }
+        assert_eq!(timestamps, vec![1_000, 2_000, 3_000, 4_000]);
+        
+        // Market buy fills at the last replayed BTC ask: 100 * (1 + 1bp)
+        let ack = adapter.send_order(OrderRequest {
+            client_id: "c1".to_string(),
+            symbol: "BTC".to_string(),
+            side: Side::Buy,
+            order_type: OrderType::Market,
+            quantity: 0.5,
+            price: None,
+            reduce_only: false,
+            time_in_force: TimeInForce::IOC,
+        }).await.unwrap();
+        assert_eq!(ack.status, OrderStatus::Filled);
+        
+        let fills = adapter.fills();
+        assert_eq!(fills.len(), 1);
+        assert!((fills[0].price - 100.01).abs() < 1e-9);
+        
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
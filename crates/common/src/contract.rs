@@ -0,0 +1,91 @@
+// crates/common/src/contract.rs - Engine ↔ model bundle contract
+use crate::{Error, Result};
+use std::path::Path;
+
+/// Version of the feature/model contract the engine speaks. Bump on any
+/// breaking change to the feature layout, normalization or model I/O, and
+/// record it below.
+///
+/// Changelog:
+/// - 1: 100-slot feature vector (`features::layout`), raw depth ladder at 60,
+///   edge/IDEC/transformer/GBDT take `[1, 100]` f32 input
+/// - 2: realized vol, ATR, depth-curve a/beta and 1% impact at 22..=26
+/// - 3: depth-curve a/beta carry `ImpactCurve::PRIOR` instead of 0 when the
+///   book can't be fit
+/// - 4: rolling window vol, ATR, book OFI and mean OBI at 27..=30, on both
+///   CPU and GPU batches
+/// - 5: depth-curve a/beta switch from the book fit to the curve calibrated
+///   on realized trade slippage once a symbol has `impact::MIN_SAMPLES`
+///   trades, so the same slots change source mid-session (feeds must stream
+///   trades; Hyperliquid subscribes them with each book)
+/// - 6: window OFI at 29 sums the multi-level OFI over `layout::OFI_LEVELS`
+///   instead of the top of book
+pub const FEATURE_CONTRACT_VERSION: u32 = 6;
+
+/// Sidecar file in each model bundle holding the contract version it was built for
+pub const CONTRACT_FILE: &str = "CONTRACT";
+
+/// Contract version a model bundle was built against
+pub fn read_contract(dir: &Path) -> Result<u32> {
+    let path = dir.join(CONTRACT_FILE);
+    let raw = std::fs::read_to_string(&path).map_err(|e| {
+        Error::Model(format!(
+            "Model bundle {:?} has no {} tag ({}). Re-export it against contract v{}.",
+            dir, CONTRACT_FILE, e, FEATURE_CONTRACT_VERSION
+        ))
+    })?;
+    
+    raw.trim().parse().map_err(|_| {
+        Error::Model(format!("Invalid contract version in {:?}: {:?}", path, raw.trim()))
+    })
+}
+
+/// Refuse model bundles built for a different contract
+pub fn verify_contract(dir: &Path) -> Result<()> {
+    let version = read_contract(dir)?;
+    
+    if version != FEATURE_CONTRACT_VERSION {
+        return Err(Error::Model(format!(
+            "Model bundle {:?} was built for feature contract v{}, engine requires v{}",
+            dir, version, FEATURE_CONTRACT_VERSION
+        )));
+    }
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn bundle(name: &str, tag: Option<&str>) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("contract-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        
+        let path = dir.join(CONTRACT_FILE);
+        match tag {
+            Some(tag) => std::fs::write(&path, tag).unwrap(),
+            None => { let _ = std::fs::remove_file(&path); }
+        }
+        
+        dir
+    }
+    
+    #[test]
+    fn test_matching_contract_accepted() {
+        let dir = bundle("ok", Some(&format!("{}\n", FEATURE_CONTRACT_VERSION)));
+        assert!(verify_contract(&dir).is_ok());
+    }
+    
+    #[test]
+    fn test_mismatched_contract_rejected() {
+        let dir = bundle("mismatch", Some(&(FEATURE_CONTRACT_VERSION + 1).to_string()));
+        
+        let err = verify_contract(&dir).unwrap_err().to_string();
+        assert!(err.contains(&format!("built for feature contract v{}", FEATURE_CONTRACT_VERSION + 1)), "{}", err);
+        assert!(err.contains(&format!("requires v{}", FEATURE_CONTRACT_VERSION)), "{}", err);
+        
+        assert!(verify_contract(&bundle("missing", None)).is_err());
+        assert!(verify_contract(&bundle("garbage", Some("v-one"))).is_err());
+    }
+}
@@ -0,0 +1,152 @@
+// crates/common/src/currency.rs - Base-currency conversion for risk aggregates
+use crate::{Error, Result};
+use std::collections::HashMap;
+
+/// Quote assets recognized as a suffix of concatenated symbols (`ETHUSDT`);
+/// the longest match wins so `FDUSD` beats `USD`
+const KNOWN_QUOTES: &[&str] = &["FDUSD", "USDT", "USDC", "BUSD", "USD", "EUR", "BTC", "ETH"];
+
+/// Assets whose names end in a quote asset, so `WETH` isn't read as `W`/`ETH`
+const QUOTE_SUFFIXED_ASSETS: &[&str] = &["WETH", "STETH", "WSTETH", "CBETH", "RETH", "WBTC", "TBTC"];
+
+/// Contract-type tags venues append to the pair (`BTC-PERP`, `BTC-USD-SWAP`)
+const VENUE_SUFFIXES: &[&str] = &["-PERP", "_PERP", "-SWAP", "_SWAP", ".P"];
+
+/// Split an explicit pair symbol into (base asset, quote asset).
+///
+/// Accepts `BTC-USD`, `BTC/USD` and concatenated `BTCUSDT`, after stripping
+/// venue suffixes. Bare symbols (Hyperliquid coins, `BTC-PERP`, equity
+/// tickers) carry no quote asset and return `None`.
+pub fn split_pair(symbol: &str) -> Option<(&str, &str)> {
+    let symbol = VENUE_SUFFIXES
+        .iter()
+        .find_map(|suffix| symbol.strip_suffix(suffix))
+        .unwrap_or(symbol);
+    
+    if let Some((base, quote)) = symbol.split_once(['-', '/']) {
+        return (!base.is_empty() && !quote.is_empty()).then_some((base, quote));
+    }
+    
+    if QUOTE_SUFFIXED_ASSETS.contains(&symbol) {
+        return None;
+    }
+    
+    KNOWN_QUOTES
+        .iter()
+        .filter_map(|quote| {
+            symbol
+                .strip_suffix(quote)
+                .filter(|base| !base.is_empty())
+                .map(|base| (base, *quote))
+        })
+        .max_by_key(|(_, quote)| quote.len())
+}
+
+/// Reference rates into a single base currency
+#[derive(Debug, Clone)]
+pub struct CurrencyConverter {
+    base: String,
+    /// Units of base currency per unit of asset
+    rates: HashMap<String, f64>,
+}
+
+impl CurrencyConverter {
+    pub fn new(base: impl Into<String>, reference_rates: &HashMap<String, f64>) -> Self {
+        let mut converter = Self {
+            base: base.into(),
+            rates: HashMap::new(),
+        };
+        for (asset, rate) in reference_rates {
+            converter.set_rate(asset, *rate);
+        }
+        converter
+    }
+    
+    pub fn base(&self) -> &str {
+        &self.base
+    }
+    
+    /// Set the value of one unit of `asset` in base currency. Non-positive or
+    /// non-finite rates are ignored.
+    pub fn set_rate(&mut self, asset: &str, rate: f64) {
+        if asset == self.base || !rate.is_finite() || rate <= 0.0 {
+            return;
+        }
+        self.rates.insert(asset.to_string(), rate);
+    }
+    
+    pub fn rate(&self, asset: &str) -> Option<f64> {
+        if asset == self.base {
+            Some(1.0)
+        } else {
+            self.rates.get(asset).copied()
+        }
+    }
+    
+    /// Convert an amount denominated in `asset` into base currency
+    pub fn to_base(&self, amount: f64, asset: &str) -> Result<f64> {
+        self.rate(asset).map(|rate| amount * rate).ok_or_else(|| {
+            Error::NotFound(format!("No {} reference rate for {}", self.base, asset))
+        })
+    }
+    
+    /// Update reference rates from a traded pair's mid price. A pair quoted in
+    /// base sets its base asset; a pair with base as its base asset sets the
+    /// quote asset; otherwise the base asset is chained through a known quote
+    /// rate (`ETH-BTC` via `BTC`).
+    pub fn observe_pair(&mut self, symbol: &str, mid: f64) {
+        if !mid.is_finite() || mid <= 0.0 {
+            return;
+        }
+        let Some((asset, quote)) = split_pair(symbol) else {
+            return;
+        };
+        
+        if quote == self.base {
+            self.set_rate(asset, mid);
+        } else if asset == self.base {
+            self.set_rate(quote, 1.0 / mid);
+        } else if let Some(quote_rate) = self.rate(quote) {
+            self.set_rate(asset, mid * quote_rate);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_split_and_observe() {
+        assert_eq!(split_pair("BTC-USD"), Some(("BTC", "USD")));
+        assert_eq!(split_pair("ETHUSDT"), Some(("ETH", "USDT")));
+        assert_eq!(split_pair("ETHBTC"), Some(("ETH", "BTC")));
+        assert_eq!(split_pair("BTC"), None);
+        assert_eq!(split_pair("AAPL"), None);
+        assert_eq!(split_pair("BTCFDUSD"), Some(("BTC", "FDUSD")));
+        
+        // Assets ending in a quote asset stay whole
+        assert_eq!(split_pair("WETH"), None);
+        assert_eq!(split_pair("STETH"), None);
+        assert_eq!(split_pair("WETHUSDT"), Some(("WETH", "USDT")));
+        assert_eq!(split_pair("STETHETH"), Some(("STETH", "ETH")));
+        assert_eq!(split_pair("WBTC-BTC"), Some(("WBTC", "BTC")));
+        
+        // Venue suffixes aren't quote assets
+        assert_eq!(split_pair("BTC-PERP"), None);
+        assert_eq!(split_pair("ETH-USD-PERP"), Some(("ETH", "USD")));
+        assert_eq!(split_pair("ETHUSDT_PERP"), Some(("ETH", "USDT")));
+        
+        let mut fx = CurrencyConverter::new("USD", &HashMap::new());
+        assert!(fx.to_base(1.0, "BTC").is_err());
+        
+        fx.observe_pair("BTC-USD", 50_000.0);
+        fx.observe_pair("ETH-BTC", 0.05);
+        fx.observe_pair("USD-EUR", 0.8);
+        
+        assert_eq!(fx.to_base(2.0, "BTC").unwrap(), 100_000.0);
+        assert!((fx.to_base(1.0, "ETH").unwrap() - 2_500.0).abs() < 1e-9);
+        assert!((fx.to_base(8.0, "EUR").unwrap() - 10.0).abs() < 1e-9);
+        assert_eq!(fx.to_base(5.0, "USD").unwrap(), 5.0);
+    }
+}
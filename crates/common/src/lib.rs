@@ -1,5 +1,6 @@
 // crates/common/src/lib.rs
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt;
 use ordered_float::OrderedFloat;
 
@@ -7,20 +8,26 @@ pub mod security;
 pub mod error;
 pub mod metrics;
 pub mod config;
+pub mod contract;
+pub mod currency;
+pub mod margin;
 
 pub use error::{Result, Error};
 
-/// Asset categories
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// Asset categories. Defaults to crypto futures, which trade around the
+/// clock and need no gateway session, so a fresh form is usable as is.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum AssetCategory {
     Equity,
+    #[default]
     CryptoFutures,
 }
 
-/// Supported venues
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// Supported venues. Defaults to Hyperliquid, a venue of the default category.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Venue {
     IBKR,
+    #[default]
     Hyperliquid,
     BinanceFutures,
 }
@@ -32,6 +39,57 @@ impl Venue {
             Venue::Hyperliquid | Venue::BinanceFutures => AssetCategory::CryptoFutures,
         }
     }
+    
+    /// The account form offers a passphrase field for the venue; one given
+    /// is stored alongside the key
+    pub fn allows_passphrase(&self) -> bool {
+        match self {
+            Venue::BinanceFutures => true,
+            Venue::IBKR | Venue::Hyperliquid => false,
+        }
+    }
+    
+    /// API keys for the venue can't be saved without a passphrase. None of
+    /// the current venues issue passphrase-bound keys.
+    pub fn requires_passphrase(&self) -> bool {
+        match self {
+            Venue::IBKR | Venue::Hyperliquid | Venue::BinanceFutures => false,
+        }
+    }
+    
+    /// Time-in-force values the venue accepts
+    pub fn supported_tifs(&self) -> &'static [TimeInForce] {
+        match self {
+            Venue::IBKR => &[TimeInForce::GTC, TimeInForce::IOC, TimeInForce::FOK, TimeInForce::DAY],
+            // Hyperliquid: Gtc, Ioc and Alo (post-only)
+            Venue::Hyperliquid => &[TimeInForce::GTC, TimeInForce::IOC, TimeInForce::GTX],
+            Venue::BinanceFutures => &[TimeInForce::GTC, TimeInForce::IOC, TimeInForce::FOK, TimeInForce::GTX],
+        }
+    }
+    
+    /// Validate a TIF for this venue. Unsupported values are rejected rather
+    /// than substituted: swapping post-only or all-or-none for something
+    /// looser would change what the order is allowed to do.
+    pub fn resolve_tif(&self, tif: TimeInForce) -> Result<TimeInForce> {
+        if self.supported_tifs().contains(&tif) {
+            Ok(tif)
+        } else {
+            Err(Error::OrderRejected(format!("{:?} does not support {:?}", self, tif)))
+        }
+    }
+    
+    /// Like `resolve_tif`, but post-only falls back to a resting limit (DAY,
+    /// else GTC) on venues without it; the caller prices it so it can't take
+    pub fn nearest_tif(&self, tif: TimeInForce) -> Result<TimeInForce> {
+        let supported = self.supported_tifs();
+        match tif {
+            TimeInForce::GTX if !supported.contains(&tif) => [TimeInForce::DAY, TimeInForce::GTC]
+                .into_iter()
+                .find(|t| supported.contains(t))
+                .ok_or_else(|| Error::OrderRejected(format!("{:?} has no resting TIF", self))),
+            _ => self.resolve_tif(tif),
+        }
+    }
 }
 
 /// Trading mode
@@ -61,7 +119,7 @@ pub enum OrderType {
 }
 
 /// Order style for routing
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum OrderStyle {
     MakerPassive,
     TakerNow,
@@ -87,6 +145,19 @@ pub enum TimeInForce {
     IOC,
     FOK,
     GTX,
+    /// Rests until the session closes
+    DAY,
+}
+
+impl TimeInForce {
+    /// Intended TIF for a routing style: takers must not rest, makers must not take
+    pub fn for_style(style: OrderStyle) -> Self {
+        match style {
+            OrderStyle::TakerNow => TimeInForce::IOC,
+            OrderStyle::MakerPassive => TimeInForce::GTX,
+            OrderStyle::Sniper => TimeInForce::GTC,
+        }
+    }
 }
 
 /// Order acknowledgment
@@ -96,6 +167,9 @@ pub struct OrderAck {
     pub client_id: String,
     pub status: OrderStatus,
     pub timestamp_ns: i64,
+    /// Average fill price, when the venue reports one with the ack
+    #[serde(default)]
+    pub avg_fill_price: Option<f64>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -120,6 +194,9 @@ pub struct Position {
     pub leverage: f64,
     pub margin_used: f64,
     pub liquidation_price: Option<f64>,
+    /// Maintenance margin at the mark from the venue's leverage tiers
+    #[serde(default)]
+    pub maintenance_margin: Option<f64>,
 }
 
 /// Balance
@@ -181,6 +258,64 @@ impl OrderBook {
             _ => None,
         }
     }
+    
+    /// Size imbalance over the top `levels` of each side, in [-1, 1].
+    /// Positive means more resting bid size; 0.0 when both sides are empty.
+    pub fn imbalance(&self, levels: usize) -> f64 {
+        let bid_vol: f64 = self.bids.iter().take(levels).map(|l| l.quantity).sum();
+        let ask_vol: f64 = self.asks.iter().take(levels).map(|l| l.quantity).sum();
+        let total = bid_vol + ask_vol;
+        
+        if total > 0.0 { (bid_vol - ask_vol) / total } else { 0.0 }
+    }
+    
+    /// Size-weighted mid over the top `levels` of each side.
+    ///
+    /// Each side collapses to its VWAP and total size, and each VWAP is
+    /// weighted by the opposite side's size (the microprice for `levels == 1`).
+    /// Falls back to the plain mid when both sides carry zero size.
+    pub fn weighted_mid(&self, levels: usize) -> Option<f64> {
+        let levels = levels.max(1);
+        let (bid_px, bid_sz) = side_vwap(&self.bids, levels)?;
+        let (ask_px, ask_sz) = side_vwap(&self.asks, levels)?;
+        
+        let total = bid_sz + ask_sz;
+        if total <= 0.0 {
+            return self.mid_price();
+        }
+        
+        Some((bid_px * ask_sz + ask_px * bid_sz) / total)
+    }
+    
+    /// Top-of-book microprice: (bid_px * ask_sz + ask_px * bid_sz) / (bid_sz + ask_sz).
+    /// `None` when either side is empty; the plain mid when both touches are sizeless.
+    pub fn microprice(&self) -> Option<f64> {
+        self.weighted_mid(1)
+    }
+}
+
+/// (VWAP, total size) over the first `levels` of one book side
+fn side_vwap(side: &[Level], levels: usize) -> Option<(f64, f64)> {
+    let first = side.first()?;
+    
+    let (notional, size) = side
+        .iter()
+        .take(levels)
+        .fold((0.0, 0.0), |(n, s), l| (n + l.price.0 * l.quantity, s + l.quantity));
+    
+    if size <= 0.0 {
+        return Some((first.price.0, 0.0));
+    }
+    
+    Some((notional / size, size))
+}
+
+/// Per-symbol instrument specification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolSpec {
+    pub symbol: String,
+    pub tick_size: f64,
+    pub lot_size: f64,
 }
 
 /// Trade execution
@@ -204,6 +339,18 @@ pub struct MarketSnapshot {
     pub funding_rate_bps: Option<f64>,
     pub open_interest: Option<f64>,
     pub volume_24h: f64,
+    #[serde(default)]
+    pub quality: DataQuality,
+}
+
+/// Provenance of a market snapshot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DataQuality {
+    /// Streamed over the venue WebSocket
+    #[default]
+    Live,
+    /// Polled over REST while the stream is down
+    Degraded,
 }
 
 /// Risk limits per account
@@ -214,6 +361,42 @@ pub struct RiskLimits {
     pub max_leverage: f64,
     pub max_loss_per_day: f64,
     pub max_position_concentration: f64, // % of portfolio
+    #[serde(default = "default_max_open_positions")]
+    pub max_open_positions: usize,
+    /// Currency all notional, PnL and loss limits are expressed in
+    #[serde(default = "default_base_currency")]
+    pub base_currency: String,
+    /// Static reference rates (base currency per unit of asset), overridden
+    /// by live prices of pairs the engine trades
+    #[serde(default = "default_reference_rates")]
+    pub reference_rates: HashMap<String, f64>,
+    /// Hour (UTC) the daily loss limit rolls over at
+    #[serde(default)]
+    pub reset_hour_utc: u32,
+    /// Uniform pairwise return correlation assumed by VaR (0 = independent)
+    #[serde(default)]
+    pub var_correlation: f64,
+    /// Kill switch trips on its own once daily loss reaches this multiple of
+    /// `max_loss_per_day`
+    #[serde(default = "default_kill_switch_loss_multiple")]
+    pub kill_switch_loss_multiple: f64,
+}
+
+fn default_max_open_positions() -> usize {
+    20
+}
+
+fn default_kill_switch_loss_multiple() -> f64 {
+    1.0
+}
+
+fn default_base_currency() -> String {
+    "USD".to_string()
+}
+
+/// Stablecoins at par until a live rate is observed
+fn default_reference_rates() -> HashMap<String, f64> {
+    HashMap::from([("USDT".to_string(), 1.0), ("USDC".to_string(), 1.0)])
 }
 
 impl Default for RiskLimits {
@@ -224,10 +407,63 @@ impl Default for RiskLimits {
             max_leverage: 3.0,
             max_loss_per_day: 10_000.0,
             max_position_concentration: 0.25,
+            max_open_positions: default_max_open_positions(),
+            base_currency: default_base_currency(),
+            reference_rates: default_reference_rates(),
+            reset_hour_utc: 0,
+            var_correlation: 0.0,
+            kill_switch_loss_multiple: default_kill_switch_loss_multiple(),
         }
     }
 }
 
+impl RiskLimits {
+    /// Reject limits that contradict each other or can't be enforced
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |msg: String| Err(Error::Config(format!("Invalid risk limits: {}", msg)));
+        let positive = |v: f64| v.is_finite() && v > 0.0;
+        
+        if !positive(self.max_notional_per_symbol) {
+            return invalid(format!("max_notional_per_symbol must be > 0, got {}", self.max_notional_per_symbol));
+        }
+        if !positive(self.max_total_notional) {
+            return invalid(format!("max_total_notional must be > 0, got {}", self.max_total_notional));
+        }
+        if self.max_notional_per_symbol > self.max_total_notional {
+            return invalid(format!(
+                "max_notional_per_symbol ({}) exceeds max_total_notional ({})",
+                self.max_notional_per_symbol, self.max_total_notional
+            ));
+        }
+        if !(self.max_leverage.is_finite() && self.max_leverage >= 1.0) {
+            return invalid(format!("max_leverage must be >= 1, got {}", self.max_leverage));
+        }
+        if !positive(self.max_loss_per_day) {
+            return invalid(format!("max_loss_per_day must be > 0, got {}", self.max_loss_per_day));
+        }
+        if !(positive(self.max_position_concentration) && self.max_position_concentration <= 1.0) {
+            return invalid(format!(
+                "max_position_concentration must be in (0, 1], got {}",
+                self.max_position_concentration
+            ));
+        }
+        if self.reset_hour_utc >= 24 {
+            return invalid(format!("reset_hour_utc must be in 0..24, got {}", self.reset_hour_utc));
+        }
+        if !(0.0..=1.0).contains(&self.var_correlation) {
+            return invalid(format!("var_correlation must be in [0, 1], got {}", self.var_correlation));
+        }
+        if !positive(self.kill_switch_loss_multiple) {
+            return invalid(format!(
+                "kill_switch_loss_multiple must be > 0, got {}",
+                self.kill_switch_loss_multiple
+            ));
+        }
+        
+        Ok(())
+    }
+}
+
 /// Account configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccountConfig {
@@ -289,6 +525,23 @@ pub struct FeatureVec {
     pub vwap_ratio: f64,
 }
 
+/// Impact curve parameters (A * notional^beta)
+#[derive(Debug, Clone, Copy)]
+pub struct ImpactCurve {
+    pub a: f64,
+    pub beta: f64,
+}
+
+impl ImpactCurve {
+    /// Square-root impact costing ~10 bps at $100k, stood in when no curve can
+    /// be fit. Pessimistic for liquid books, where zero would claim free fills.
+    pub const PRIOR: ImpactCurve = ImpactCurve { a: 3.2e-6, beta: 0.5 };
+    
+    pub fn compute_bps(&self, notional: f64) -> f64 {
+        self.a * notional.powf(self.beta) * 10000.0
+    }
+}
+
 /// Model prediction
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Prediction {
@@ -308,9 +561,50 @@ pub struct RouteDecision {
     pub hold_duration_s: f64,
     pub urgency: f64, // 0.0 = patient, 1.0 = urgent
     pub should_trade: bool,
+    /// Intended direction, when the decision source determines one
+    #[serde(default)]
+    pub side: Option<Side>,
+    /// Exit: may only shrink the open position, never flip it
+    #[serde(default)]
+    pub reduce_only: bool,
     pub reason: String,
 }
 
+/// Symbol autocomplete request on the engine's `/symbols` socket
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SymbolQuery {
+    pub venue: Venue,
+    pub prefix: String,
+}
+
+/// Reply to a `SymbolQuery`; `error` explains why a venue couldn't be searched
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolReply {
+    pub venue: Venue,
+    pub prefix: String,
+    pub symbols: Vec<String>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// Live balance request on the engine's `/balances` socket for a saved account
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceQuery {
+    pub venue: Venue,
+    pub label: String,
+    pub live: bool,
+}
+
+/// Reply to a `BalanceQuery`: total per asset, or why the account couldn't be read
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BalanceReply {
+    pub venue: Venue,
+    pub label: String,
+    pub balances: HashMap<String, f64>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
 /// Performance metrics
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PerformanceMetrics {
@@ -346,6 +640,8 @@ pub struct RiskSnapshot {
     pub net_notional: f64,
     pub num_positions: usize,
     pub total_margin_used: f64,
+    #[serde(default)]
+    pub total_maintenance_margin: f64,
     pub available_margin: f64,
     pub unrealized_pnl: f64,
     pub realized_pnl: f64,
@@ -371,4 +667,183 @@ pub struct Alert {
     pub source: String,
     pub message: String,
     pub metadata: serde_json::Value,
-}
\ No newline at end of file
+}
+
+/// Frame on the engine's metrics, risk, alert and universe sockets, tagged so
+/// clients dispatch on `type` rather than guessing from the shape
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum WsMsg {
+    Metrics(PerformanceMetrics),
+    Risk(RiskSnapshot),
+    Alert(Alert),
+    Universe(Vec<UniverseAsset>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_risk_limits_validate() {
+        assert!(RiskLimits::default().validate().is_ok());
+        
+        let cases = [
+            (RiskLimits { max_notional_per_symbol: 0.0, ..RiskLimits::default() }, "max_notional_per_symbol must be > 0"),
+            (RiskLimits { max_notional_per_symbol: -1.0, ..RiskLimits::default() }, "max_notional_per_symbol must be > 0"),
+            (RiskLimits { max_total_notional: f64::NAN, ..RiskLimits::default() }, "max_total_notional must be > 0"),
+            (RiskLimits { max_notional_per_symbol: 600_000.0, ..RiskLimits::default() }, "exceeds max_total_notional"),
+            (RiskLimits { max_leverage: 0.5, ..RiskLimits::default() }, "max_leverage must be >= 1"),
+            (RiskLimits { max_loss_per_day: 0.0, ..RiskLimits::default() }, "max_loss_per_day must be > 0"),
+            (RiskLimits { max_position_concentration: 0.0, ..RiskLimits::default() }, "max_position_concentration"),
+            (RiskLimits { max_position_concentration: 1.5, ..RiskLimits::default() }, "max_position_concentration"),
+            (RiskLimits { reset_hour_utc: 24, ..RiskLimits::default() }, "reset_hour_utc"),
+        ];
+        
+        for (limits, expected) in cases {
+            let err = limits.validate().unwrap_err();
+            assert!(matches!(&err, Error::Config(m) if m.contains(expected)), "{}", err);
+        }
+        
+        // Boundaries are allowed
+        let edge = RiskLimits {
+            max_notional_per_symbol: 500_000.0,
+            max_leverage: 1.0,
+            max_position_concentration: 1.0,
+            ..RiskLimits::default()
+        };
+        assert!(edge.validate().is_ok());
+    }
+    
+    fn book(bids: &[(f64, f64)], asks: &[(f64, f64)]) -> OrderBook {
+        let to_levels = |side: &[(f64, f64)]| {
+            side.iter()
+                .map(|&(p, q)| Level { price: OrderedFloat(p), quantity: q })
+                .collect()
+        };
+        
+        OrderBook {
+            symbol: "BTC".to_string(),
+            timestamp_ns: 0,
+            bids: to_levels(bids),
+            asks: to_levels(asks),
+            sequence: 1,
+        }
+    }
+    
+    #[test]
+    fn test_imbalance() {
+        let b = book(&[(100.0, 3.0), (99.0, 5.0)], &[(101.0, 1.0), (102.0, 1.0)]);
+        
+        assert!((b.imbalance(1) - 0.5).abs() < 1e-12);
+        assert!((b.imbalance(2) - 0.6).abs() < 1e-12);
+        assert!((b.imbalance(10) - b.imbalance(2)).abs() < 1e-12);
+        assert_eq!(b.imbalance(0), 0.0);
+    }
+    
+    #[test]
+    fn test_imbalance_empty_side() {
+        assert_eq!(book(&[(100.0, 2.0)], &[]).imbalance(5), 1.0);
+        assert_eq!(book(&[], &[(101.0, 2.0)]).imbalance(5), -1.0);
+        assert_eq!(book(&[], &[]).imbalance(5), 0.0);
+    }
+    
+    #[test]
+    fn test_weighted_mid() {
+        // Single level: microprice 100 * 1/10 + 101 * 9/10
+        let b = book(&[(100.0, 9.0)], &[(101.0, 1.0)]);
+        assert!((b.weighted_mid(1).unwrap() - 100.9).abs() < 1e-9);
+        assert!((b.weighted_mid(5).unwrap() - 100.9).abs() < 1e-9);
+        
+        // Two levels: bid VWAP 99.5 (size 4), ask VWAP 101.5 (size 4)
+        let b = book(&[(100.0, 2.0), (99.0, 2.0)], &[(101.0, 2.0), (102.0, 2.0)]);
+        assert!((b.weighted_mid(2).unwrap() - 100.5).abs() < 1e-9);
+    }
+    
+    #[test]
+    fn test_weighted_mid_edge_cases() {
+        assert!(book(&[(100.0, 1.0)], &[]).weighted_mid(1).is_none());
+        assert!(book(&[], &[]).weighted_mid(1).is_none());
+        
+        // Zero size on both sides falls back to the plain mid
+        let b = book(&[(100.0, 0.0)], &[(101.0, 0.0)]);
+        assert_eq!(b.weighted_mid(1), Some(100.5));
+    }
+    
+    #[test]
+    fn test_microprice() {
+        // Heavy bids lean the price toward the ask: 100 * 1/4 + 101 * 3/4
+        let b = book(&[(100.0, 3.0), (99.0, 50.0)], &[(101.0, 1.0)]);
+        assert!((b.microprice().unwrap() - 100.75).abs() < 1e-9);
+        assert!(b.microprice().unwrap() > b.mid_price().unwrap());
+        
+        let b = book(&[(100.0, 1.0)], &[(101.0, 3.0)]);
+        assert!((b.microprice().unwrap() - 100.25).abs() < 1e-9);
+        
+        assert!(book(&[], &[(101.0, 1.0)]).microprice().is_none());
+        assert!(book(&[], &[]).microprice().is_none());
+        assert_eq!(book(&[(100.0, 0.0)], &[(101.0, 0.0)]).microprice(), Some(100.5));
+    }
+    
+    #[test]
+    fn test_tif_for_style() {
+        assert_eq!(TimeInForce::for_style(OrderStyle::TakerNow), TimeInForce::IOC);
+        assert_eq!(TimeInForce::for_style(OrderStyle::MakerPassive), TimeInForce::GTX);
+        assert_eq!(TimeInForce::for_style(OrderStyle::Sniper), TimeInForce::GTC);
+    }
+    
+    #[test]
+    fn test_unsupported_tif_rejected() {
+        assert_eq!(Venue::Hyperliquid.resolve_tif(TimeInForce::GTX).unwrap(), TimeInForce::GTX);
+        assert!(matches!(Venue::Hyperliquid.resolve_tif(TimeInForce::FOK), Err(Error::OrderRejected(_))));
+        assert!(Venue::IBKR.resolve_tif(TimeInForce::GTX).is_err());
+        assert!(Venue::BinanceFutures.resolve_tif(TimeInForce::FOK).is_ok());
+        
+        // Post-only degrades to a resting limit only where it's missing
+        assert_eq!(Venue::IBKR.nearest_tif(TimeInForce::GTX).unwrap(), TimeInForce::DAY);
+        assert_eq!(Venue::Hyperliquid.nearest_tif(TimeInForce::GTX).unwrap(), TimeInForce::GTX);
+        assert!(Venue::Hyperliquid.nearest_tif(TimeInForce::FOK).is_err());
+    }
+    
+    #[test]
+    fn test_venue_defaults_agree() {
+        assert_eq!(AssetCategory::default(), AssetCategory::CryptoFutures);
+        assert_eq!(Venue::default(), Venue::Hyperliquid);
+        assert_eq!(Venue::default().category(), AssetCategory::default());
+    }
+    
+    #[test]
+    fn test_ws_msg_roundtrip() {
+        let roundtrip = |msg: WsMsg| -> WsMsg {
+            serde_json::from_str(&serde_json::to_string(&msg).unwrap()).unwrap()
+        };
+        
+        let perf = PerformanceMetrics { orders_per_sec: 12.0, ..PerformanceMetrics::default() };
+        assert!(matches!(roundtrip(WsMsg::Metrics(perf)), WsMsg::Metrics(p) if p.orders_per_sec == 12.0));
+        
+        // Identical shapes no longer decide the route: an empty risk frame stays risk
+        assert!(matches!(roundtrip(WsMsg::Risk(RiskSnapshot::default())), WsMsg::Risk(_)));
+        
+        let alert = Alert {
+            timestamp_ns: 1,
+            level: AlertLevel::Critical,
+            source: "risk".to_string(),
+            message: "Kill switch".to_string(),
+            metadata: serde_json::Value::Null,
+        };
+        assert!(matches!(roundtrip(WsMsg::Alert(alert)), WsMsg::Alert(a) if a.message == "Kill switch"));
+        
+        let asset = UniverseAsset {
+            symbol: "SOL".to_string(),
+            venue: Venue::Hyperliquid,
+            category: AssetCategory::CryptoFutures,
+            score: 0.8,
+            rank: 1,
+            metrics: AssetMetrics::default(),
+        };
+        assert!(matches!(roundtrip(WsMsg::Universe(vec![asset])), WsMsg::Universe(u) if u[0].symbol == "SOL"));
+        
+        let json = serde_json::to_value(WsMsg::Universe(Vec::new())).unwrap();
+        assert_eq!(json["type"], "Universe");
+    }
+}
@@ -0,0 +1,121 @@
+// crates/common/src/margin.rs - Notional-tiered leverage and maintenance margin
+use crate::Position;
+use serde::{Deserialize, Serialize};
+
+/// One notional bracket of a venue's leverage table. Tables are sorted by
+/// `notional_floor`; a tier applies from its floor up to the next one's.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LeverageTier {
+    pub notional_floor: f64,
+    pub max_leverage: f64,
+    pub maintenance_margin_rate: f64,
+    /// Deducted from `notional * rate` so margin is continuous across tiers
+    pub maintenance_amount: f64,
+}
+
+/// Tier that applies to a position of `notional`
+pub fn tier_for(tiers: &[LeverageTier], notional: f64) -> Option<&LeverageTier> {
+    tiers
+        .iter()
+        .take_while(|t| t.notional_floor <= notional)
+        .last()
+        .or(tiers.first())
+}
+
+/// Fill in `maintenance_amount` for venues that only publish rates, so the
+/// margin requirement doesn't jump at tier boundaries
+pub fn fill_maintenance_amounts(tiers: &mut [LeverageTier]) {
+    let mut amount = 0.0;
+    let mut previous_rate = tiers.first().map_or(0.0, |t| t.maintenance_margin_rate);
+    
+    for tier in tiers.iter_mut() {
+        amount += tier.notional_floor * (tier.maintenance_margin_rate - previous_rate);
+        tier.maintenance_amount = amount;
+        previous_rate = tier.maintenance_margin_rate;
+    }
+}
+
+/// Maintenance margin required at `notional`
+pub fn maintenance_margin(tiers: &[LeverageTier], notional: f64) -> Option<f64> {
+    let notional = notional.abs();
+    tier_for(tiers, notional).map(|t| (notional * t.maintenance_margin_rate - t.maintenance_amount).max(0.0))
+}
+
+/// Isolated-margin liquidation price: where the position's collateral
+/// (entry notional over its leverage) plus PnL falls to the maintenance
+/// margin. The tier is taken at the current mark notional. `None` when the
+/// position can't be liquidated, e.g. an unlevered long.
+pub fn liquidation_price(position: &Position, tiers: &[LeverageTier]) -> Option<f64> {
+    let size = position.size;
+    if size == 0.0 || position.entry_price <= 0.0 {
+        return None;
+    }
+    
+    let tier = tier_for(tiers, (size * position.mark_price).abs())?;
+    let collateral = size.abs() * position.entry_price / position.leverage.max(1.0);
+    
+    // collateral + size * (P - entry) = |size| * P * rate - amount
+    let price = (size * position.entry_price - collateral - tier.maintenance_amount)
+        / (size - size.abs() * tier.maintenance_margin_rate);
+    
+    (price.is_finite() && price > 0.0).then_some(price)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn tiers() -> Vec<LeverageTier> {
+        let tier = |notional_floor: f64, max_leverage: f64, maintenance_margin_rate: f64| LeverageTier {
+            notional_floor,
+            max_leverage,
+            maintenance_margin_rate,
+            maintenance_amount: 0.0,
+        };
+        let mut tiers = vec![tier(0.0, 50.0, 0.01), tier(100_000.0, 20.0, 0.025), tier(1_000_000.0, 10.0, 0.05)];
+        fill_maintenance_amounts(&mut tiers);
+        tiers
+    }
+    
+    #[test]
+    fn test_maintenance_margin_is_tiered_and_continuous() {
+        let tiers = tiers();
+        assert!((tiers[1].maintenance_amount - 1_500.0).abs() < 1e-9);
+        assert!((tiers[2].maintenance_amount - 26_500.0).abs() < 1e-9);
+        
+        assert!((maintenance_margin(&tiers, 50_000.0).unwrap() - 500.0).abs() < 1e-9);
+        assert!((maintenance_margin(&tiers, 2_000_000.0).unwrap() - 73_500.0).abs() < 1e-9);
+        
+        // No jump at the boundary
+        let below = maintenance_margin(&tiers, 99_999.99).unwrap();
+        let above = maintenance_margin(&tiers, 100_000.0).unwrap();
+        assert!((above - below).abs() < 1e-3);
+        assert_eq!(maintenance_margin(&[], 1.0), None);
+    }
+    
+    #[test]
+    fn test_liquidation_price() {
+        let position = |size: f64, leverage: f64| Position {
+            symbol: "BTC".to_string(),
+            size,
+            entry_price: 100.0,
+            mark_price: 100.0,
+            unrealized_pnl: 0.0,
+            realized_pnl: 0.0,
+            leverage,
+            margin_used: 0.0,
+            liquidation_price: None,
+            maintenance_margin: None,
+        };
+        let tiers = tiers();
+        
+        // 10x long: collateral 100, liquidated at (1000 - 100) / (10 * 0.99)
+        let long = liquidation_price(&position(10.0, 10.0), &tiers).unwrap();
+        assert!((long - 90.0 / 0.99).abs() < 1e-9);
+        
+        let short = liquidation_price(&position(-10.0, 10.0), &tiers).unwrap();
+        assert!((short - 110.0 / 1.01).abs() < 1e-9);
+        
+        assert_eq!(liquidation_price(&position(10.0, 1.0), &tiers), None);
+    }
+}
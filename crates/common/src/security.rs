@@ -1,7 +1,7 @@
 // crates/common/src/security.rs
 use crate::{Error, Result, Venue};
 use aes_gcm::{
-    aead::{Aead, KeyInit, OsRng},
+    aead::{Aead, AeadCore, KeyInit, OsRng},
     Aes256Gcm, Nonce,
 };
 use keyring::Entry;
@@ -11,6 +11,18 @@ use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
 
 const SERVICE_NAME: &str = "com.yourco.hft";
 const APP_KEY_ACCOUNT: &str = "app_master_key";
+/// Keychain entry listing the saved accounts (keyring can't enumerate)
+const INDEX_ACCOUNT: &str = "account_index";
+
+/// Serializes index read-modify-writes across stores in this process
+static INDEX_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// AES-GCM nonce length; stored blobs are base64(nonce || ciphertext)
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+
+/// Fixed nonce used by the original storage format (read-only, for migration)
+const LEGACY_NONCE: &[u8; NONCE_LEN] = b"unique nonce";
 
 /// API credentials with automatic zeroing on drop
 #[derive(Clone, Serialize, Deserialize, ZeroizeOnDrop)]
@@ -37,6 +49,14 @@ impl ApiCredentials {
     }
 }
 
+/// A saved account as recorded in the credential index; no secrets
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredAccount {
+    pub venue: Venue,
+    pub label: String,
+    pub live: bool,
+}
+
 /// Secure credential store
 pub struct CredentialStore {
     cipher: Option<Aes256Gcm>,
@@ -90,11 +110,7 @@ impl CredentialStore {
             .map_err(|e| Error::Serialization(e))?;
         
         let data = if let Some(cipher) = &self.cipher {
-            // Encrypt with app key
-            let nonce = Nonce::from_slice(b"unique nonce"); // In production, use random nonce + store
-            let ciphertext = cipher.encrypt(nonce, json.as_bytes())
-                .map_err(|e| Error::Internal(format!("Encryption failed: {}", e)))?;
-            BASE64.encode(&ciphertext)
+            encrypt_blob(cipher, json.as_bytes())?
         } else {
             json
         };
@@ -102,6 +118,14 @@ impl CredentialStore {
         entry.set_password(&data)
             .map_err(|e| Error::Internal(format!("Failed to save: {:?}", e)))?;
         
+        let stored = StoredAccount { venue, label: label.to_string(), live: !creds.is_paper };
+        let _guard = INDEX_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut index = self.read_index()?;
+        if !index.contains(&stored) {
+            index.push(stored);
+            self.write_index(&index)?;
+        }
+        
         Ok(())
     }
     
@@ -115,11 +139,15 @@ impl CredentialStore {
             .map_err(|e| Error::NotFound(format!("Credentials not found: {:?}", e)))?;
         
         let json = if let Some(cipher) = &self.cipher {
-            let ciphertext = BASE64.decode(&data)
-                .map_err(|e| Error::Internal(format!("Invalid encrypted data: {}", e)))?;
-            let nonce = Nonce::from_slice(b"unique nonce");
-            let plaintext = cipher.decrypt(nonce, ciphertext.as_ref())
-                .map_err(|e| Error::Internal(format!("Decryption failed: {}", e)))?;
+            let (plaintext, legacy) = decrypt_blob(cipher, &data)?;
+            
+            // Re-encrypt fixed-nonce entries with a fresh nonce (best effort)
+            if legacy {
+                if let Ok(migrated) = encrypt_blob(cipher, &plaintext) {
+                    let _ = entry.set_password(&migrated);
+                }
+            }
+            
             String::from_utf8(plaintext)
                 .map_err(|e| Error::Internal(format!("Invalid UTF-8: {}", e)))?
         } else {
@@ -136,22 +164,98 @@ impl CredentialStore {
         let entry = Entry::new(SERVICE_NAME, &account)
             .map_err(|e| Error::Internal(format!("Keychain init: {:?}", e)))?;
         
-        entry.delete_password()
-            .map_err(|e| Error::Internal(format!("Failed to delete: {:?}", e)))?;
+        // Already gone counts as deleted; any other failure leaves the
+        // secret, so the account stays indexed
+        match entry.delete_password() {
+            Ok(()) | Err(keyring::Error::NoEntry) => {}
+            Err(e) => return Err(Error::Internal(format!("Failed to delete: {:?}", e))),
+        }
+        
+        let _guard = INDEX_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut index = self.read_index()?;
+        let before = index.len();
+        index.retain(|a| !(a.venue == venue && a.label == label && a.live == live));
+        if index.len() != before {
+            self.write_index(&index)?;
+        }
         
         Ok(())
     }
     
-    /// List all stored accounts
-    pub fn list_accounts(&self) -> Vec<String> {
-        // Note: keyring crate doesn't provide list functionality
-        // In production, maintain a separate index
-        vec![]
+    /// Every saved account, in the order first saved
+    pub fn list_accounts(&self) -> Result<Vec<StoredAccount>> {
+        self.read_index()
     }
+    
+    fn index_entry() -> Result<Entry> {
+        Entry::new(SERVICE_NAME, INDEX_ACCOUNT)
+            .map_err(|e| Error::Internal(format!("Keychain init: {:?}", e)))
+    }
+    
+    /// The account index; empty until the first save
+    fn read_index(&self) -> Result<Vec<StoredAccount>> {
+        let data = match Self::index_entry()?.get_password() {
+            Ok(data) => data,
+            Err(keyring::Error::NoEntry) => return Ok(Vec::new()),
+            Err(e) => return Err(Error::Internal(format!("Failed to read account index: {:?}", e))),
+        };
+        
+        let json = match &self.cipher {
+            Some(cipher) => decrypt_blob(cipher, &data)?.0,
+            None => data.into_bytes(),
+        };
+        
+        serde_json::from_slice(&json)
+            .map_err(|e| Error::Serialization(e))
+    }
+    
+    fn write_index(&self, index: &[StoredAccount]) -> Result<()> {
+        let json = serde_json::to_string(index)?;
+        let data = match &self.cipher {
+            Some(cipher) => encrypt_blob(cipher, json.as_bytes())?,
+            None => json,
+        };
+        
+        Self::index_entry()?
+            .set_password(&data)
+            .map_err(|e| Error::Internal(format!("Failed to save account index: {:?}", e)))
+    }
+}
+
+/// Encrypt with a fresh random nonce: base64(nonce || ciphertext)
+fn encrypt_blob(cipher: &Aes256Gcm, plaintext: &[u8]) -> Result<String> {
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher.encrypt(&nonce, plaintext)
+        .map_err(|e| Error::Internal(format!("Encryption failed: {}", e)))?;
+    
+    let mut blob = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+    blob.extend_from_slice(&nonce);
+    blob.extend_from_slice(&ciphertext);
+    Ok(BASE64.encode(blob))
+}
+
+/// Decrypt a stored blob; the flag is set when it was in the legacy
+/// fixed-nonce format and should be rewritten
+fn decrypt_blob(cipher: &Aes256Gcm, blob: &str) -> Result<(Vec<u8>, bool)> {
+    let bytes = BASE64.decode(blob)
+        .map_err(|e| Error::Internal(format!("Invalid encrypted data: {}", e)))?;
+    
+    // Too short to carry a nonce prefix: can only be the legacy format.
+    // Otherwise GCM authentication tells the two layouts apart.
+    if bytes.len() >= NONCE_LEN + TAG_LEN {
+        let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+        if let Ok(plaintext) = cipher.decrypt(Nonce::from_slice(nonce), ciphertext) {
+            return Ok((plaintext, false));
+        }
+    }
+    
+    let plaintext = cipher.decrypt(Nonce::from_slice(LEGACY_NONCE), bytes.as_ref())
+        .map_err(|e| Error::Internal(format!("Decryption failed: {}", e)))?;
+    Ok((plaintext, true))
 }
 
 /// Data source API keys
-#[derive(Clone, Serialize, Deserialize, ZeroizeOnDrop)]
+#[derive(Clone, Default, Serialize, Deserialize, ZeroizeOnDrop)]
 pub struct DataSourceKeys {
     pub gecko_terminal: Option<String>,
     pub birdeye: Option<String>,
@@ -163,12 +267,18 @@ pub struct DataSourceKeys {
 impl DataSourceKeys {
     const KEY_ACCOUNT: &'static str = "data_source_keys";
     
+    /// Save the keys, encrypted when `store` has an app key
     pub fn save(&self, store: &CredentialStore) -> Result<()> {
         let entry = Entry::new(SERVICE_NAME, Self::KEY_ACCOUNT)
             .map_err(|e| Error::Internal(format!("Keychain init: {:?}", e)))?;
         
         let json = serde_json::to_string(&self)?;
-        entry.set_password(&json)
+        let data = match &store.cipher {
+            Some(cipher) => encrypt_blob(cipher, json.as_bytes())?,
+            None => json,
+        };
+        
+        entry.set_password(&data)
             .map_err(|e| Error::Internal(format!("Failed to save: {:?}", e)))?;
         
         Ok(())
@@ -178,10 +288,15 @@ impl DataSourceKeys {
         let entry = Entry::new(SERVICE_NAME, Self::KEY_ACCOUNT)
             .map_err(|e| Error::Internal(format!("Keychain init: {:?}", e)))?;
         
-        let json = entry.get_password()
+        let data = entry.get_password()
             .map_err(|e| Error::NotFound(format!("Keys not found: {:?}", e)))?;
         
-        serde_json::from_str(&json)
+        let json = match &store.cipher {
+            Some(cipher) => decrypt_blob(cipher, &data)?.0,
+            None => data.into_bytes(),
+        };
+        
+        serde_json::from_slice(&json)
             .map_err(|e| Error::Serialization(e))
     }
 }
@@ -223,6 +338,100 @@ mod tests {
         store.delete(Venue::Hyperliquid, "test", false).unwrap();
     }
     
+    #[test]
+    fn test_saved_accounts_listed_after_restart() {
+        let store = CredentialStore::new_simple();
+        let paper = ApiCredentials::new("k1".to_string(), "s1".to_string(), true);
+        let live = ApiCredentials::new("k2".to_string(), "s2".to_string(), false);
+        store.save(Venue::Hyperliquid, "index-test-a", &paper).unwrap();
+        store.save(Venue::BinanceFutures, "index-test-b", &live).unwrap();
+        drop(store);
+        
+        let store = CredentialStore::new_simple();
+        let accounts = store.list_accounts().unwrap();
+        let a = StoredAccount { venue: Venue::Hyperliquid, label: "index-test-a".to_string(), live: false };
+        let b = StoredAccount { venue: Venue::BinanceFutures, label: "index-test-b".to_string(), live: true };
+        assert!(accounts.contains(&a));
+        assert!(accounts.contains(&b));
+        
+        // Saving again doesn't duplicate; deleting drops it from the index
+        store.save(Venue::Hyperliquid, "index-test-a", &paper).unwrap();
+        assert_eq!(store.list_accounts().unwrap().iter().filter(|x| **x == a).count(), 1);
+        store.delete(Venue::Hyperliquid, "index-test-a", false).unwrap();
+        store.delete(Venue::BinanceFutures, "index-test-b", true).unwrap();
+        let accounts = store.list_accounts().unwrap();
+        assert!(!accounts.contains(&a) && !accounts.contains(&b));
+    }
+    
+    #[test]
+    fn test_data_source_keys_roundtrip() {
+        let store = CredentialStore::new_simple();
+        let previous = DataSourceKeys::load(&store).ok();
+        
+        let keys = DataSourceKeys {
+            gecko_terminal: Some("gt-key".to_string()),
+            birdeye: Some("be-key".to_string()),
+            the_graph: None,
+            crypto_panic: Some("cp-key".to_string()),
+            flipside: None,
+        };
+        keys.save(&store).unwrap();
+        
+        let loaded = DataSourceKeys::load(&CredentialStore::new_simple()).unwrap();
+        assert_eq!(loaded.gecko_terminal.as_deref(), Some("gt-key"));
+        assert_eq!(loaded.birdeye.as_deref(), Some("be-key"));
+        assert_eq!(loaded.crypto_panic.as_deref(), Some("cp-key"));
+        assert!(loaded.the_graph.is_none() && loaded.flipside.is_none());
+        
+        // Leave whatever keys the machine had before
+        match previous {
+            Some(previous) => previous.save(&store).unwrap(),
+            None => {
+                let _ = Entry::new(SERVICE_NAME, DataSourceKeys::KEY_ACCOUNT).unwrap().delete_password();
+            }
+        }
+    }
+    
+    #[test]
+    fn test_encrypted_blobs_use_fresh_nonces() {
+        // An encrypting store that leaves the machine's app key alone
+        let cipher = Aes256Gcm::new(&Aes256Gcm::generate_key(&mut OsRng));
+        let store = CredentialStore { cipher: Some(cipher.clone()) };
+        let creds = ApiCredentials::new("test_key".to_string(), "test_secret".to_string(), true);
+        let entry = Entry::new(SERVICE_NAME, &CredentialStore::account_key(&Venue::Hyperliquid, "nonce-test", false)).unwrap();
+        
+        store.save(Venue::Hyperliquid, "nonce-test", &creds).unwrap();
+        let first = entry.get_password().unwrap();
+        assert_eq!(store.load(Venue::Hyperliquid, "nonce-test", false).unwrap().api_key, "test_key");
+        assert_eq!(entry.get_password().unwrap(), first);
+        
+        store.save(Venue::Hyperliquid, "nonce-test", &creds).unwrap();
+        let second = entry.get_password().unwrap();
+        assert_ne!(first, second);
+        assert!(!decrypt_blob(&cipher, &second).unwrap().1);
+        
+        // Loading a fixed-nonce entry rewrites it under a fresh nonce
+        let json = serde_json::to_vec(&creds).unwrap();
+        let legacy = BASE64.encode(cipher.encrypt(Nonce::from_slice(LEGACY_NONCE), json.as_ref()).unwrap());
+        entry.set_password(&legacy).unwrap();
+        assert_eq!(store.load(Venue::Hyperliquid, "nonce-test", false).unwrap().api_secret, "test_secret");
+        let migrated = entry.get_password().unwrap();
+        assert_ne!(migrated, legacy);
+        assert!(!decrypt_blob(&cipher, &migrated).unwrap().1);
+        
+        store.delete(Venue::Hyperliquid, "nonce-test", false).unwrap();
+    }
+    
+    #[test]
+    fn test_legacy_fixed_nonce_blob_is_readable() {
+        let cipher = Aes256Gcm::new(&Aes256Gcm::generate_key(&mut OsRng));
+        let legacy = cipher.encrypt(Nonce::from_slice(LEGACY_NONCE), b"{}".as_ref()).unwrap();
+        
+        let (plaintext, is_legacy) = decrypt_blob(&cipher, &BASE64.encode(legacy)).unwrap();
+        assert!(is_legacy);
+        assert_eq!(plaintext, b"{}");
+    }
+    
     #[test]
     fn test_hmac_signing() {
         let signature = sign_request("secret", "message");
@@ -0,0 +1,304 @@
+// crates/engine/src/advanced_features.rs
+use crate::rl_agent::RLAgentConfig;
+use crate::s3_writer::{LocalSink, S3Writer};
+use common::*;
+use features::DeviceType;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Key prefix of exported training shards, locally and on S3
+const EXPORT_PREFIX: &str = "training";
+
+/// Optional subsystems enabled under `[advanced]`
+#[derive(Debug, Clone)]
+pub struct AdvancedConfig {
+    // Multi-threaded order book
+    pub enable_mt_orderbook: bool,
+    pub orderbook_workers: usize,
+    
+    // Parquet export
+    pub enable_parquet_export: bool,
+    pub parquet_output_dir: String,
+    /// Samples buffered by the export task before they are handed to the writer
+    pub parquet_batch_size: usize,
+    pub parquet_samples_per_shard: usize,
+    pub enable_s3_upload: bool,
+    pub s3_bucket: Option<String>,
+    
+    // RL Agent
+    pub enable_rl_agent: bool,
+    pub rl_actor_path: String,
+    pub rl_critic_path: Option<String>,
+    pub rl_config: RLAgentConfig,
+    
+    // GPU acceleration
+    pub enable_gpu: bool,
+    pub gpu_device: DeviceType,
+    pub gpu_batch_size: usize,
+    pub gpu_model_path: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AdvancedStats {
+    pub export_enabled: bool,
+    pub samples_queued: u64,
+    pub samples_exported: u64,
+}
+
+#[derive(Default)]
+struct ExportCounters {
+    queued: AtomicU64,
+    exported: AtomicU64,
+}
+
+/// Owner of the advanced subsystems. Shared behind an `Arc`; `shutdown` takes
+/// `&self` so it works no matter how many clones are still alive.
+pub struct AdvancedFeaturesManager {
+    config: AdvancedConfig,
+    export_tx: parking_lot::Mutex<Option<mpsc::UnboundedSender<(FeatureVec, Prediction)>>>,
+    export_task: parking_lot::Mutex<Option<JoinHandle<()>>>,
+    counters: Arc<ExportCounters>,
+}
+
+impl AdvancedFeaturesManager {
+    /// `s3_client` is required when `enable_s3_upload` is set
+    pub async fn new(config: AdvancedConfig, s3_client: Option<aws_sdk_s3::Client>) -> Result<Self> {
+        let writer = if config.enable_parquet_export {
+            Some(Self::export_writer(&config, s3_client)?)
+        } else {
+            None
+        };
+        
+        Ok(Self::with_writer(config, writer))
+    }
+    
+    fn export_writer(config: &AdvancedConfig, s3_client: Option<aws_sdk_s3::Client>) -> Result<S3Writer> {
+        let shard = config.parquet_samples_per_shard;
+        
+        match (config.enable_s3_upload, &config.s3_bucket, s3_client) {
+            (true, Some(bucket), Some(client)) => {
+                Ok(S3Writer::new(client, bucket.clone(), EXPORT_PREFIX.to_string(), shard))
+            }
+            (true, None, _) => Err(Error::Config("parquet.enable_s3_upload needs parquet.s3_bucket".to_string())),
+            (true, _, None) => Err(Error::Config("parquet.enable_s3_upload needs AWS enabled".to_string())),
+            (false, _, _) => Ok(S3Writer::with_sink(
+                Arc::new(LocalSink::new(&config.parquet_output_dir)),
+                EXPORT_PREFIX.to_string(),
+                shard,
+            )),
+        }
+    }
+    
+    fn with_writer(config: AdvancedConfig, writer: Option<S3Writer>) -> Self {
+        let counters = Arc::new(ExportCounters::default());
+        let (export_tx, export_task) = match writer {
+            Some(writer) => {
+                let (tx, rx) = mpsc::unbounded_channel();
+                let task = tokio::spawn(run_export(writer, rx, config.parquet_batch_size.max(1), counters.clone()));
+                (Some(tx), Some(task))
+            }
+            None => (None, None),
+        };
+        
+        Self {
+            config,
+            export_tx: parking_lot::Mutex::new(export_tx),
+            export_task: parking_lot::Mutex::new(export_task),
+            counters,
+        }
+    }
+    
+    pub fn config(&self) -> &AdvancedConfig {
+        &self.config
+    }
+    
+    /// Queue a training sample for export; dropped (with a debug log) once
+    /// export is disabled or shut down
+    pub fn record_sample(&self, sample: FeatureVec, prediction: Prediction) {
+        let sent = self.export_tx
+            .lock()
+            .as_ref()
+            .is_some_and(|tx| tx.send((sample, prediction)).is_ok());
+        
+        if sent {
+            self.counters.queued.fetch_add(1, Ordering::Relaxed);
+        } else {
+            tracing::debug!("Training export inactive, sample dropped");
+        }
+    }
+    
+    pub fn stats(&self) -> AdvancedStats {
+        AdvancedStats {
+            export_enabled: self.export_tx.lock().is_some(),
+            samples_queued: self.counters.queued.load(Ordering::Relaxed),
+            samples_exported: self.counters.exported.load(Ordering::Relaxed),
+        }
+    }
+    
+    /// Close the export queue, then wait for the writer to flush every pending
+    /// sample and shard. Idempotent.
+    pub async fn shutdown(&self) {
+        // Dropping the sender ends the export task once the queue is drained
+        self.export_tx.lock().take();
+        
+        let task = self.export_task.lock().take();
+        if let Some(task) = task {
+            if let Err(e) = task.await {
+                tracing::error!("Training export task failed: {}", e);
+            }
+        }
+        
+        let stats = self.stats();
+        tracing::info!(
+            "Advanced features shut down ({} of {} samples exported)",
+            stats.samples_exported, stats.samples_queued
+        );
+    }
+}
+
+impl Drop for AdvancedFeaturesManager {
+    fn drop(&mut self) {
+        // Without `shutdown` the export task still flushes on its own, but
+        // only if the runtime outlives it
+        if self.export_tx.get_mut().is_some() {
+            tracing::warn!("AdvancedFeaturesManager dropped without shutdown; pending exports may be lost");
+        }
+    }
+}
+
+/// Batch queued samples into the writer; on close, write the remainder and
+/// flush partial shards
+async fn run_export(
+    writer: S3Writer,
+    mut rx: mpsc::UnboundedReceiver<(FeatureVec, Prediction)>,
+    batch_size: usize,
+    counters: Arc<ExportCounters>,
+) {
+    let mut samples = Vec::with_capacity(batch_size);
+    let mut predictions = Vec::with_capacity(batch_size);
+    
+    loop {
+        let next = rx.recv().await;
+        let closed = next.is_none();
+        if let Some((sample, prediction)) = next {
+            samples.push(sample);
+            predictions.push(prediction);
+        }
+        
+        if !samples.is_empty() && (closed || samples.len() >= batch_size) {
+            let rows = samples.len() as u64;
+            match writer.write_batch(&samples, &predictions).await {
+                Ok(()) => {
+                    counters.exported.fetch_add(rows, Ordering::Relaxed);
+                }
+                Err(e) => tracing::error!("Training export failed: {}", e),
+            }
+            samples.clear();
+            predictions.clear();
+        }
+        
+        if closed {
+            break;
+        }
+    }
+    
+    if let Err(e) = writer.flush().await {
+        tracing::error!("Training export flush failed: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rl_agent::ActionType;
+    
+    fn config(output_dir: &std::path::Path) -> AdvancedConfig {
+        AdvancedConfig {
+            enable_mt_orderbook: false,
+            orderbook_workers: 1,
+            enable_parquet_export: true,
+            parquet_output_dir: output_dir.to_string_lossy().into_owned(),
+            parquet_batch_size: 1000,
+            parquet_samples_per_shard: 1000,
+            enable_s3_upload: false,
+            s3_bucket: None,
+            enable_rl_agent: false,
+            rl_actor_path: String::new(),
+            rl_critic_path: None,
+            rl_config: RLAgentConfig {
+                action_type: ActionType::MultiDiscrete,
+                sequence_length: 1,
+                use_recurrent: false,
+                epsilon: 0.0,
+                temperature: 1.0,
+            },
+            enable_gpu: false,
+            gpu_device: DeviceType::CPU,
+            gpu_batch_size: 1,
+            gpu_model_path: String::new(),
+        }
+    }
+    
+    fn sample(i: i64) -> (FeatureVec, Prediction) {
+        let sample = FeatureVec {
+            timestamp_ns: 1_760_400_000_000_000_000 + i,
+            symbol: "BTC".to_string(),
+            mid_price: 100.0,
+            spread_bps: 1.0,
+            ofi_1s: 0.0,
+            obi_1s: 0.0,
+            depth_imbalance: 0.0,
+            depth_a: 0.0,
+            depth_beta: 0.0,
+            realized_vol_5s: 0.0,
+            atr_30s: 0.0,
+            funding_bps_8h: 0.0,
+            impact_bps_1pct: 0.0,
+            microprice: 100.0,
+            vwap_ratio: 1.0,
+        };
+        let prediction = Prediction {
+            timestamp_ns: sample.timestamp_ns,
+            symbol: "BTC".to_string(),
+            edge_bps: 1.0,
+            confidence: 0.5,
+            horizon_ms: 1000,
+            model_version: "v1".to_string(),
+        };
+        (sample, prediction)
+    }
+    
+    #[tokio::test]
+    async fn test_shutdown_flushes_pending_samples() {
+        let dir = std::env::temp_dir().join(format!("advanced_export_{}", std::process::id()));
+        let manager = Arc::new(AdvancedFeaturesManager::new(config(&dir), None).await.unwrap());
+        let _outstanding = manager.clone();
+        
+        // Well below both the batch and the shard size, so nothing is written yet
+        for i in 0..5 {
+            let (s, p) = sample(i);
+            manager.record_sample(s, p);
+        }
+        
+        manager.shutdown().await;
+        
+        let stats = manager.stats();
+        assert!(!stats.export_enabled);
+        assert_eq!(stats.samples_queued, 5);
+        assert_eq!(stats.samples_exported, 5);
+        
+        let day = dir.join("training/BTC/2025/10/14");
+        let shards: Vec<_> = std::fs::read_dir(&day).unwrap().collect();
+        assert_eq!(shards.len(), 1);
+        
+        // Samples after shutdown are dropped, a second shutdown is a no-op
+        let (s, p) = sample(5);
+        manager.record_sample(s, p);
+        manager.shutdown().await;
+        assert_eq!(manager.stats().samples_queued, 5);
+        
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
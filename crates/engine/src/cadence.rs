@@ -0,0 +1,147 @@
+// crates/engine/src/cadence.rs - Timer-driven decisions decoupled from the data rate
+use common::*;
+use features::ComputedFeatures;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Finest timer resolution for scheduled decisions
+pub const MIN_TICK_MS: u64 = 10;
+
+/// Decision interval per symbol: a symbol override wins over a category
+/// override, which wins over `default_ms`. An interval of 0 decides on every
+/// batch flush, as before.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct CadenceConfig {
+    pub default_ms: u64,
+    pub by_category: HashMap<AssetCategory, u64>,
+    pub by_symbol: HashMap<String, u64>,
+}
+
+impl CadenceConfig {
+    /// Decision interval for `symbol`, `None` when it decides per flush
+    pub fn interval_ms(&self, symbol: &str, category: Option<AssetCategory>) -> Option<u64> {
+        let ms = self.by_symbol
+            .get(symbol)
+            .or_else(|| category.and_then(|c| self.by_category.get(&c)))
+            .copied()
+            .unwrap_or(self.default_ms);
+        (ms > 0).then_some(ms)
+    }
+    
+    /// Timer period that serves every configured interval, `None` if none is set
+    pub fn tick_ms(&self) -> Option<u64> {
+        std::iter::once(self.default_ms)
+            .chain(self.by_category.values().copied())
+            .chain(self.by_symbol.values().copied())
+            .filter(|ms| *ms > 0)
+            .min()
+            .map(|ms| ms.min(MIN_TICK_MS))
+    }
+}
+
+struct Slot {
+    interval_ns: i64,
+    next_due_ns: i64,
+    /// Latest features, taken when a decision is made on them
+    latest: Option<ComputedFeatures>,
+}
+
+/// Latest features per timed symbol, released once per interval.
+/// Features keep updating on every flush; only the newest survive until the
+/// next decision, and symbols without fresh features since their last
+/// decision are skipped rather than re-decided on stale data.
+#[derive(Default)]
+pub struct DecisionScheduler {
+    slots: HashMap<String, Slot>,
+}
+
+impl DecisionScheduler {
+    /// Keep `features` as the symbol's latest; the first update starts its clock
+    pub fn update(&mut self, features: ComputedFeatures, interval_ms: u64, now_ns: i64) {
+        let interval_ns = interval_ms as i64 * 1_000_000;
+        let slot = self.slots.entry(features.symbol.clone()).or_insert(Slot {
+            interval_ns,
+            next_due_ns: now_ns + interval_ns,
+            latest: None,
+        });
+        
+        // Interval changed through config: restart the clock from now
+        if slot.interval_ns != interval_ns {
+            slot.interval_ns = interval_ns;
+            slot.next_due_ns = now_ns + interval_ns;
+        }
+        slot.latest = Some(features);
+    }
+    
+    /// Features of every symbol whose interval has elapsed at `now_ns`
+    pub fn due(&mut self, now_ns: i64) -> Vec<ComputedFeatures> {
+        let mut due = Vec::new();
+        
+        for slot in self.slots.values_mut() {
+            if slot.next_due_ns > now_ns {
+                continue;
+            }
+            
+            // Stay on the interval grid even if a tick ran late
+            let missed = (now_ns - slot.next_due_ns) / slot.interval_ns;
+            slot.next_due_ns += (missed + 1) * slot.interval_ns;
+            due.extend(slot.latest.take());
+        }
+        
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn computed(symbol: &str, timestamp_ns: i64) -> ComputedFeatures {
+        ComputedFeatures {
+            symbol: symbol.to_string(),
+            timestamp_ns,
+            features: ndarray::Array1::zeros(4),
+            computed_on: features::Device::CPU,
+        }
+    }
+    
+    #[test]
+    fn test_decisions_follow_interval_not_feed_rate() {
+        let ms = 1_000_000;
+        let mut scheduler = DecisionScheduler::default();
+        let mut decisions = Vec::new();
+        
+        // 2s of updates every 5ms, timer ticking every 10ms
+        for t in (0..=2_000).step_by(5) {
+            scheduler.update(computed("BTC", t * ms), 500, t * ms);
+            if t % 10 == 0 {
+                decisions.extend(scheduler.due(t * ms).into_iter().map(|f| f.timestamp_ns / ms));
+            }
+        }
+        
+        // 401 updates, 4 decisions, each on the latest features at the tick
+        assert_eq!(decisions, vec![500, 1_000, 1_500, 2_000]);
+        
+        // No fresh features: the next interval passes without a decision
+        assert!(scheduler.due(2_500 * ms).is_empty());
+        scheduler.update(computed("BTC", 2_600 * ms), 500, 2_600 * ms);
+        assert!(scheduler.due(2_700 * ms).is_empty());
+        assert_eq!(scheduler.due(3_000 * ms).len(), 1);
+    }
+    
+    #[test]
+    fn test_interval_resolution() {
+        let config = CadenceConfig {
+            default_ms: 0,
+            by_category: HashMap::from([(AssetCategory::Equity, 1_000)]),
+            by_symbol: HashMap::from([("BTC-USD".to_string(), 500)]),
+        };
+        
+        assert_eq!(config.interval_ms("BTC-USD", Some(AssetCategory::CryptoFutures)), Some(500));
+        assert_eq!(config.interval_ms("AAPL", Some(AssetCategory::Equity)), Some(1_000));
+        assert_eq!(config.interval_ms("ETH-USD", Some(AssetCategory::CryptoFutures)), None);
+        assert_eq!(config.tick_ms(), Some(MIN_TICK_MS));
+        assert_eq!(CadenceConfig::default().tick_ms(), None);
+    }
+}
@@ -0,0 +1,137 @@
+// crates/engine/src/control.rs - Operator commands (mode, kill switch) from the control socket
+use common::*;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::{mpsc, oneshot};
+
+/// Header carrying the shared control token on the WebSocket upgrade
+pub const CONTROL_TOKEN_HEADER: &str = "x-control-token";
+
+/// Commands accepted on `/control`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+pub enum ControlCommand {
+    SetMode { mode: TradingMode },
+    KillSwitch { active: bool },
+}
+
+/// State after a command was applied, echoed back to the client
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ControlReply {
+    pub ok: bool,
+    pub mode: TradingMode,
+    pub kill_switch_active: bool,
+}
+
+/// What the control loop drives; `TradingEngine` in production
+pub trait ControlTarget: Send + Sync {
+    fn set_mode(&self, mode: TradingMode);
+    fn set_kill_switch(&self, active: bool);
+    fn control_state(&self) -> ControlReply;
+}
+
+impl ControlTarget for crate::TradingEngine {
+    fn set_mode(&self, mode: TradingMode) {
+        crate::TradingEngine::set_mode(self, mode);
+    }
+    
+    fn set_kill_switch(&self, active: bool) {
+        if active {
+            self.activate_kill_switch();
+        } else {
+            self.deactivate_kill_switch();
+        }
+    }
+    
+    fn control_state(&self) -> ControlReply {
+        ControlReply {
+            ok: true,
+            mode: self.get_mode(),
+            kill_switch_active: self.router.get_risk_manager().read().get_state().kill_switch_active,
+        }
+    }
+}
+
+/// A command plus the channel its result goes back on
+pub struct ControlRequest {
+    pub command: ControlCommand,
+    pub reply: oneshot::Sender<ControlReply>,
+}
+
+/// Sending half handed to the WebSocket server
+#[derive(Clone)]
+pub struct ControlHandle {
+    tx: mpsc::Sender<ControlRequest>,
+    token: Arc<str>,
+}
+
+impl ControlHandle {
+    /// Whether `presented` matches the shared token (constant time)
+    pub fn authorize(&self, presented: &str) -> bool {
+        let (a, b) = (self.token.as_bytes(), presented.as_bytes());
+        a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+    }
+    
+    /// Queue a command and wait for the control loop to apply it
+    pub async fn send(&self, command: ControlCommand) -> Result<ControlReply> {
+        let (reply, rx) = oneshot::channel();
+        
+        self.tx
+            .send(ControlRequest { command, reply })
+            .await
+            .map_err(|_| Error::Internal("control loop stopped".to_string()))?;
+        
+        rx.await.map_err(|_| Error::Internal("control loop dropped the command".to_string()))
+    }
+}
+
+/// Control channel guarded by `token`; run the returned receiver with `run_control_loop`
+pub fn control_channel(token: impl Into<Arc<str>>) -> Result<(ControlHandle, mpsc::Receiver<ControlRequest>)> {
+    let token = token.into();
+    if token.is_empty() {
+        return Err(Error::Config("control token must not be empty".to_string()));
+    }
+    
+    let (tx, rx) = mpsc::channel(32);
+    Ok((ControlHandle { tx, token }, rx))
+}
+
+/// Apply operator commands in arrival order until every handle is dropped
+pub async fn run_control_loop(mut rx: mpsc::Receiver<ControlRequest>, target: Arc<dyn ControlTarget>) {
+    while let Some(request) = rx.recv().await {
+        tracing::warn!("Operator command: {:?}", request.command);
+        
+        match request.command {
+            ControlCommand::SetMode { mode } => target.set_mode(mode),
+            ControlCommand::KillSwitch { active } => target.set_kill_switch(active),
+        }
+        
+        let _ = request.reply.send(target.control_state());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_commands() {
+        let cmd: ControlCommand = serde_json::from_str(r#"{"cmd":"set_mode","mode":"Paused"}"#).unwrap();
+        assert_eq!(cmd, ControlCommand::SetMode { mode: TradingMode::Paused });
+        
+        let cmd: ControlCommand = serde_json::from_str(r#"{"cmd":"kill_switch","active":true}"#).unwrap();
+        assert_eq!(cmd, ControlCommand::KillSwitch { active: true });
+        
+        assert!(serde_json::from_str::<ControlCommand>(r#"{"cmd":"flatten_all"}"#).is_err());
+        assert!(serde_json::from_str::<ControlCommand>(r#"{"cmd":"set_mode","mode":"YOLO"}"#).is_err());
+    }
+    
+    #[test]
+    fn test_authorize() {
+        let (handle, _rx) = control_channel("s3cret").unwrap();
+        assert!(handle.authorize("s3cret"));
+        assert!(!handle.authorize("s3cre"));
+        assert!(!handle.authorize("S3cret"));
+        assert!(control_channel("").is_err());
+    }
+}
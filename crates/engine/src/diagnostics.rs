@@ -0,0 +1,280 @@
+// crates/engine/src/diagnostics.rs - Redacted state dump for bug reports
+use common::*;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::{BTreeMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Key segments (or runs of them) whose values are never written to a bundle
+const SECRET_MARKERS: &[&[&str]] = &[
+    &["secret"], &["password"], &["passphrase"], &["token"], &["api", "key"], &["apikey"],
+    &["private", "key"], &["credential"], &["credentials"], &["signature"], &["arn"],
+];
+
+const REDACTED: &str = "[redacted]";
+
+/// Symbols without an update for this long are reported stale
+pub const STALE_AFTER_MS: i64 = 5_000;
+
+/// Engine health summary
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    pub status: String,
+    pub mode: TradingMode,
+    pub decision_mode: String,
+    pub kill_switch_active: bool,
+    pub daily_loss_exceeded: bool,
+    pub adapters: Vec<AdapterInfo>,
+    pub stale_symbols: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AdapterInfo {
+    pub label: String,
+    pub venue: Venue,
+}
+
+/// Loaded model and the version it was loaded from
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelInfo {
+    pub name: String,
+    pub path: String,
+    pub version: String,
+    pub loaded: bool,
+}
+
+/// Running counts of routing decisions
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DecisionStats {
+    pub total: u64,
+    pub traded: u64,
+    pub skipped: u64,
+    pub by_reason: BTreeMap<String, u64>,
+    pub last_reason: Option<String>,
+}
+
+impl DecisionStats {
+    pub fn record(&mut self, decision: &RouteDecision) {
+        self.total += 1;
+        
+        if decision.should_trade {
+            self.traded += 1;
+        } else {
+            self.skipped += 1;
+            *self.by_reason.entry(reason_key(&decision.reason)).or_default() += 1;
+        }
+        
+        self.last_reason = Some(decision.reason.clone());
+    }
+}
+
+/// Group reasons by their prefix so numeric detail doesn't explode the map
+fn reason_key(reason: &str) -> String {
+    reason.split(':').next().unwrap_or(reason).trim().to_string()
+}
+
+/// Bounded log of the most recent alerts
+pub struct AlertLog {
+    alerts: Mutex<VecDeque<Alert>>,
+    capacity: usize,
+}
+
+impl AlertLog {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            alerts: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity,
+        }
+    }
+    
+    pub fn record(&self, alert: Alert) {
+        let mut alerts = self.alerts.lock();
+        if alerts.len() == self.capacity {
+            alerts.pop_front();
+        }
+        alerts.push_back(alert);
+    }
+    
+    pub fn recent(&self) -> Vec<Alert> {
+        self.alerts.lock().iter().cloned().collect()
+    }
+}
+
+/// Everything captured by `dump-diagnostics`
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagnosticsBundle {
+    pub generated_at: String,
+    pub config: serde_json::Value,
+    pub health: HealthReport,
+    pub recent_alerts: Vec<Alert>,
+    pub symbol_update_age_ms: BTreeMap<String, i64>,
+    pub models: Vec<ModelInfo>,
+    pub performance: PerformanceMetrics,
+    pub decisions: DecisionStats,
+}
+
+impl DiagnosticsBundle {
+    /// Serialize with every secret-looking field replaced
+    pub fn to_redacted_json(&self) -> Result<serde_json::Value> {
+        let mut value = serde_json::to_value(self)?;
+        redact(&mut value);
+        Ok(value)
+    }
+    
+    /// Write the redacted bundle to `dir/diagnostics-<timestamp>.json`
+    pub fn write_to(&self, dir: &Path) -> Result<PathBuf> {
+        std::fs::create_dir_all(dir)?;
+        
+        let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%S%.3fZ");
+        let path = dir.join(format!("diagnostics-{}.json", stamp));
+        
+        let json = serde_json::to_string_pretty(&self.to_redacted_json()?)?;
+        std::fs::write(&path, json)?;
+        
+        tracing::info!("Diagnostics written to {:?}", path);
+        Ok(path)
+    }
+}
+
+/// Strip secrets from a JSON tree in place, matching on key names
+pub fn redact(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, v) in map.iter_mut() {
+                if is_secret_key(key) {
+                    *v = serde_json::Value::String(REDACTED.to_string());
+                } else {
+                    redact(v);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+/// Matches whole segments so `topic_arn` is a secret but `learning_rate` isn't
+fn is_secret_key(key: &str) -> bool {
+    let segments = key_segments(key);
+    SECRET_MARKERS.iter().any(|marker| {
+        segments.windows(marker.len()).any(|run| run.iter().zip(marker.iter()).all(|(s, m)| s == m))
+    })
+}
+
+/// Lowercase segments of a snake, kebab, dotted or camelCase key
+fn key_segments(key: &str) -> Vec<String> {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut prev_lower = false;
+    
+    for c in key.chars() {
+        if !c.is_alphanumeric() {
+            segments.push(std::mem::take(&mut current));
+            prev_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && prev_lower {
+            segments.push(std::mem::take(&mut current));
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        current.extend(c.to_lowercase());
+    }
+    segments.push(current);
+    
+    segments.retain(|s| !s.is_empty());
+    segments
+}
+
+/// Source for the diagnostics endpoint
+#[derive(Clone)]
+pub struct DiagnosticsHandle {
+    pub engine: Arc<crate::TradingEngine>,
+    pub alerts: Arc<AlertLog>,
+    pub config: serde_json::Value,
+    pub output_dir: PathBuf,
+}
+
+impl DiagnosticsHandle {
+    pub fn dump(&self) -> Result<PathBuf> {
+        self.engine
+            .diagnostics(self.config.clone(), self.alerts.recent())
+            .write_to(&self.output_dir)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn bundle() -> DiagnosticsBundle {
+        let config = serde_json::json!({
+            "engine": { "mode": "paper", "batch_size": 32, "learning_rate": 0.001, "warn_after_s": 5 },
+            "venues": {
+                "hyperliquid": { "enabled": true, "api_secret": "hl-secret-XYZ" },
+                "binance": { "apiKey": "bn-key-123", "passphrase": "hunter2" },
+            },
+            "sns": { "topic_arn": "arn:aws:sns:us-east-1:123456789012:alerts" },
+            "accounts": [{ "label": "main", "private_key": "0xdeadbeef" }],
+        });
+        
+        let mut decisions = DecisionStats::default();
+        decisions.record(&RouteDecision {
+            style: OrderStyle::MakerPassive,
+            size_fraction: 0.0,
+            hold_duration_s: 0.0,
+            urgency: 0.0,
+            should_trade: false,
+            side: None,
+            reduce_only: false,
+            reason: "Risk check failed: Kill switch active".to_string(),
+        });
+        
+        DiagnosticsBundle {
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            config,
+            health: HealthReport {
+                status: "degraded".to_string(),
+                mode: TradingMode::Paper,
+                decision_mode: "Hybrid".to_string(),
+                kill_switch_active: true,
+                daily_loss_exceeded: false,
+                adapters: vec![AdapterInfo { label: "hyperliquid".to_string(), venue: Venue::Hyperliquid }],
+                stale_symbols: vec!["ETH".to_string()],
+            },
+            recent_alerts: vec![],
+            symbol_update_age_ms: BTreeMap::from([("BTC".to_string(), 12), ("ETH".to_string(), 9000)]),
+            models: vec![ModelInfo {
+                name: "crypto".to_string(),
+                path: "models/crypto".to_string(),
+                version: "2024-06-01".to_string(),
+                loaded: true,
+            }],
+            performance: PerformanceMetrics::default(),
+            decisions,
+        }
+    }
+    
+    #[test]
+    fn test_dump_redacts_secrets() {
+        let dir = std::env::temp_dir().join(format!("diag-test-{}", std::process::id()));
+        let path = bundle().write_to(&dir).unwrap();
+        let dump = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+        
+        let parsed: serde_json::Value = serde_json::from_str(&dump).unwrap();
+        assert_eq!(parsed["health"]["status"], "degraded");
+        assert_eq!(parsed["models"][0]["version"], "2024-06-01");
+        assert_eq!(parsed["decisions"]["by_reason"]["Risk check failed"], 1);
+        assert_eq!(parsed["config"]["engine"]["batch_size"], 32);
+        
+        // Markers match whole key segments, not substrings
+        assert_eq!(parsed["config"]["engine"]["learning_rate"], 0.001);
+        assert_eq!(parsed["config"]["engine"]["warn_after_s"], 5);
+        assert_eq!(parsed["config"]["venues"]["binance"]["apiKey"], REDACTED);
+        
+        for secret in ["hl-secret-XYZ", "bn-key-123", "hunter2", "123456789012", "0xdeadbeef"] {
+            assert!(!dump.contains(secret), "secret leaked: {}", secret);
+        }
+    }
+}
@@ -0,0 +1,319 @@
+// crates/engine/src/execution.rs - Parent/child order scheduling (immediate vs TWAP)
+use common::*;
+use features::layout;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// When and how large orders are worked instead of sent whole
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SlicingConfig {
+    /// Orders below this notional always go out as one order
+    pub min_slice_notional: f64,
+    /// Urgency at or above which orders go out at once
+    pub aggressive_urgency: f64,
+    /// Largest share of displayed opposite-side depth a single child may take
+    pub max_participation: f64,
+    /// Share of the expected hold the schedule may use (scaled down by urgency)
+    pub horizon_fraction: f64,
+    /// Minimum spacing between child orders
+    pub min_interval_ms: u64,
+    pub max_children: usize,
+}
+
+impl Default for SlicingConfig {
+    fn default() -> Self {
+        Self {
+            min_slice_notional: 10_000.0,
+            aggressive_urgency: 0.7,
+            max_participation: 0.25,
+            horizon_fraction: 0.25,
+            min_interval_ms: 1_000,
+            max_children: 20,
+        }
+    }
+}
+
+/// How a parent order is worked
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExecAlgo {
+    /// Whole size now (small or urgent)
+    Immediate,
+    /// Equal children every `interval`, the first one now
+    Twap { interval: Duration },
+}
+
+/// One slice of a parent order, sent `delay` after the parent was planned
+#[derive(Debug, Clone)]
+pub struct ChildOrder {
+    pub seq: usize,
+    pub delay: Duration,
+    pub order: OrderRequest,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExecutionPlan {
+    pub parent_id: String,
+    pub algo: ExecAlgo,
+    pub children: Vec<ChildOrder>,
+}
+
+/// Displayed quantity on the side `side` would trade against, from the raw
+/// depth ladder (zero when the ladder wasn't filled, e.g. on the GPU path)
+pub fn opposite_depth(features: &[f32], side: Side) -> f64 {
+    // Bid (price, qty) pairs, then ask pairs
+    let start = match side {
+        Side::Buy => layout::DEPTH_LADDER_START + 2 * layout::DEPTH_LADDER_LEVELS,
+        Side::Sell => layout::DEPTH_LADDER_START,
+    };
+    
+    (0..layout::DEPTH_LADDER_LEVELS)
+        .filter_map(|i| features.get(start + 2 * i + 1))
+        .map(|&q| q as f64)
+        .sum()
+}
+
+/// Split `order` according to the decision's urgency and hold, the book's
+/// displayed `depth` against the order and `mid`
+pub fn plan(
+    order: OrderRequest,
+    decision: &RouteDecision,
+    mid: f64,
+    depth: f64,
+    config: &SlicingConfig,
+) -> ExecutionPlan {
+    let parent_id = order.client_id.clone();
+    let quantity = order.quantity;
+    let notional = quantity * mid;
+    
+    let immediate = |order: OrderRequest| ExecutionPlan {
+        parent_id: parent_id.clone(),
+        algo: ExecAlgo::Immediate,
+        children: vec![ChildOrder { seq: 0, delay: Duration::ZERO, order }],
+    };
+    
+    if decision.urgency >= config.aggressive_urgency || notional < config.min_slice_notional || quantity <= 0.0 {
+        return immediate(order);
+    }
+    
+    // Children needed to stay within the participation cap
+    let by_depth = if depth > 0.0 {
+        (quantity / (depth * config.max_participation)).ceil()
+    } else {
+        (notional / config.min_slice_notional).ceil()
+    };
+    
+    // Children that fit the schedule at the minimum spacing
+    let horizon_s = decision.hold_duration_s.max(0.0) * config.horizon_fraction * (1.0 - decision.urgency).max(0.0);
+    let by_time = (horizon_s * 1000.0 / config.min_interval_ms.max(1) as f64).floor() + 1.0;
+    
+    let n = (by_depth.max(2.0).min(by_time) as usize).min(config.max_children.max(1));
+    if n <= 1 {
+        return immediate(order);
+    }
+    
+    let interval = Duration::from_secs_f64(horizon_s / (n - 1) as f64);
+    let child_quantity = quantity / n as f64;
+    
+    let children = (0..n)
+        .map(|seq| {
+            let mut child = order.clone();
+            child.client_id = format!("{}-{}", parent_id, seq);
+            child.quantity = if seq + 1 == n {
+                quantity - child_quantity * (n - 1) as f64
+            } else {
+                child_quantity
+            };
+            
+            ChildOrder { seq, delay: interval * seq as u32, order: child }
+        })
+        .collect();
+    
+    ExecutionPlan {
+        parent_id,
+        algo: ExecAlgo::Twap { interval },
+        children,
+    }
+}
+
+/// Progress of one parent order
+#[derive(Debug, Clone)]
+pub struct ParentOrder {
+    pub symbol: String,
+    pub algo: ExecAlgo,
+    pub total_quantity: f64,
+    pub sent_quantity: f64,
+    pub children: Vec<String>,
+    pub sent: usize,
+    pub cancelled: bool,
+}
+
+impl ParentOrder {
+    pub fn is_done(&self) -> bool {
+        self.cancelled || self.sent == self.children.len()
+    }
+}
+
+/// Parent ↔ child bookkeeping for worked orders
+#[derive(Debug, Default)]
+pub struct ParentOrders {
+    parents: HashMap<String, ParentOrder>,
+    child_to_parent: HashMap<String, String>,
+}
+
+impl ParentOrders {
+    pub fn register(&mut self, plan: &ExecutionPlan) {
+        let children: Vec<String> = plan.children.iter().map(|c| c.order.client_id.clone()).collect();
+        for child in &children {
+            self.child_to_parent.insert(child.clone(), plan.parent_id.clone());
+        }
+        
+        self.parents.insert(plan.parent_id.clone(), ParentOrder {
+            symbol: plan.children.first().map(|c| c.order.symbol.clone()).unwrap_or_default(),
+            algo: plan.algo,
+            total_quantity: plan.children.iter().map(|c| c.order.quantity).sum(),
+            sent_quantity: 0.0,
+            children,
+            sent: 0,
+            cancelled: false,
+        });
+    }
+    
+    pub fn record_sent(&mut self, child_id: &str, quantity: f64) {
+        let Some(parent_id) = self.child_to_parent.get(child_id) else {
+            return;
+        };
+        
+        if let Some(parent) = self.parents.get_mut(parent_id) {
+            parent.sent += 1;
+            parent.sent_quantity += quantity;
+        }
+    }
+    
+    /// Stop working a parent; unsent children are dropped
+    pub fn cancel(&mut self, parent_id: &str) {
+        if let Some(parent) = self.parents.get_mut(parent_id) {
+            parent.cancelled = true;
+        }
+    }
+    
+    pub fn parent_of(&self, child_id: &str) -> Option<String> {
+        self.child_to_parent.get(child_id).cloned()
+    }
+    
+    pub fn get(&self, parent_id: &str) -> Option<&ParentOrder> {
+        self.parents.get(parent_id)
+    }
+    
+    /// Parents still sending children
+    pub fn active(&self) -> Vec<String> {
+        self.parents.iter().filter(|(_, p)| !p.is_done()).map(|(id, _)| id.clone()).collect()
+    }
+    
+    /// Forget finished parents
+    pub fn prune(&mut self) {
+        let done: Vec<String> = self.parents.iter().filter(|(_, p)| p.is_done()).map(|(id, _)| id.clone()).collect();
+        for id in done {
+            if let Some(parent) = self.parents.remove(&id) {
+                for child in parent.children {
+                    self.child_to_parent.remove(&child);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn order(quantity: f64) -> OrderRequest {
+        OrderRequest {
+            client_id: "BTC_1".to_string(),
+            symbol: "BTC".to_string(),
+            side: Side::Buy,
+            order_type: OrderType::PostOnly,
+            quantity,
+            price: None,
+            reduce_only: false,
+            time_in_force: TimeInForce::GTX,
+        }
+    }
+    
+    fn decision(urgency: f64, hold_duration_s: f64) -> RouteDecision {
+        RouteDecision {
+            style: OrderStyle::MakerPassive,
+            size_fraction: 1.0,
+            hold_duration_s,
+            urgency,
+            should_trade: true,
+            side: None,
+            reduce_only: false,
+            reason: String::new(),
+        }
+    }
+    
+    #[test]
+    fn test_large_patient_order_is_sliced() {
+        let config = SlicingConfig::default();
+        
+        // 2 BTC @ 50k against 2 BTC displayed: 25% cap → 4 children
+        let plan = plan(order(2.0), &decision(0.2, 100.0), 50_000.0, 2.0, &config);
+        
+        // Horizon 100s * 0.25 * (1 - 0.2) = 20s, children at 0, 6.67, 13.3, 20s
+        assert_eq!(plan.children.len(), 4);
+        assert!(matches!(plan.algo, ExecAlgo::Twap { interval } if (interval.as_secs_f64() - 20.0 / 3.0).abs() < 1e-6));
+        assert_eq!(plan.children[3].delay.as_secs_f64().round(), 20.0);
+        
+        let total: f64 = plan.children.iter().map(|c| c.order.quantity).sum();
+        assert!((total - 2.0).abs() < 1e-12);
+        for child in &plan.children {
+            assert!(child.order.quantity <= 2.0 * config.max_participation + 1e-12);
+            assert_eq!(child.order.client_id, format!("BTC_1-{}", child.seq));
+        }
+        
+        let mut parents = ParentOrders::default();
+        parents.register(&plan);
+        assert_eq!(parents.parent_of("BTC_1-2").as_deref(), Some("BTC_1"));
+        
+        for child in &plan.children {
+            parents.record_sent(&child.order.client_id, child.order.quantity);
+        }
+        assert!(parents.get("BTC_1").unwrap().is_done());
+        parents.prune();
+        assert!(parents.parent_of("BTC_1-2").is_none());
+    }
+    
+    #[test]
+    fn test_small_or_urgent_goes_immediately() {
+        let config = SlicingConfig::default();
+        
+        let urgent = plan(order(2.0), &decision(0.9, 100.0), 50_000.0, 2.0, &config);
+        assert_eq!(urgent.algo, ExecAlgo::Immediate);
+        assert_eq!(urgent.children[0].order.quantity, 2.0);
+        assert_eq!(urgent.children[0].order.client_id, "BTC_1");
+        
+        let small = plan(order(0.1), &decision(0.2, 100.0), 50_000.0, 2.0, &config);
+        assert_eq!(small.children.len(), 1);
+        
+        // No time to work it inside a 2s hold
+        let short = plan(order(2.0), &decision(0.2, 2.0), 50_000.0, 2.0, &config);
+        assert_eq!(short.algo, ExecAlgo::Immediate);
+    }
+    
+    #[test]
+    fn test_opposite_depth_reads_ladder_side() {
+        let mut features = vec![0.0f32; layout::NUM_FEATURES];
+        for i in 0..layout::DEPTH_LADDER_LEVELS {
+            features[layout::DEPTH_LADDER_START + 2 * i + 1] = 1.0;
+            features[layout::DEPTH_LADDER_START + 2 * (layout::DEPTH_LADDER_LEVELS + i) + 1] = 2.0;
+        }
+        
+        // Buys take the asks, sells hit the bids
+        let levels = layout::DEPTH_LADDER_LEVELS as f64;
+        assert_eq!(opposite_depth(&features, Side::Buy), 2.0 * levels);
+        assert_eq!(opposite_depth(&features, Side::Sell), levels);
+        assert_eq!(opposite_depth(&[], Side::Buy), 0.0);
+    }
+}
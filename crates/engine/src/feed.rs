@@ -0,0 +1,370 @@
+// crates/engine/src/feed.rs - Adapter market data into the batching loop
+use common::*;
+use features::FeatureComputer;
+use futures::StreamExt;
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, Notify};
+
+/// Seconds to wait for the first snapshot from any adapter
+pub const DEFAULT_GRACE_S: u64 = 30;
+
+/// Snapshots buffered between the feed and the batching loop
+pub const DEFAULT_QUEUE_CAPACITY: usize = 4_096;
+
+/// Bounded snapshot hand-off from the feed to the batching loop
+pub fn snapshot_queue(capacity: usize) -> (SnapshotSender, SnapshotReceiver) {
+    let shared = Arc::new(Shared {
+        queue: Mutex::new(Queue::default()),
+        notify: Notify::new(),
+        capacity: capacity.max(1),
+        dropped: AtomicU64::new(0),
+    });
+    (SnapshotSender { shared: shared.clone() }, SnapshotReceiver { shared })
+}
+
+struct Shared {
+    queue: Mutex<Queue>,
+    notify: Notify,
+    capacity: usize,
+    dropped: AtomicU64,
+}
+
+#[derive(Default)]
+struct Queue {
+    snapshots: VecDeque<MarketSnapshot>,
+    /// Queued snapshots per symbol
+    queued: HashMap<String, usize>,
+    sender_closed: bool,
+    receiver_closed: bool,
+}
+
+impl Queue {
+    fn push(&mut self, snapshot: MarketSnapshot) {
+        *self.queued.entry(snapshot.symbol.clone()).or_default() += 1;
+        self.snapshots.push_back(snapshot);
+    }
+    
+    fn pop(&mut self) -> Option<MarketSnapshot> {
+        let snapshot = self.snapshots.pop_front()?;
+        self.forget(&snapshot.symbol);
+        Some(snapshot)
+    }
+    
+    /// Make room for a `symbol` book: drop the oldest snapshot that it or a
+    /// later queued book supersedes, or the oldest overall when every queued
+    /// symbol is distinct (capacity below the symbol count)
+    fn evict_for(&mut self, symbol: &str) -> Option<MarketSnapshot> {
+        let index = self.snapshots
+            .iter()
+            .position(|s| s.symbol == symbol || self.queued.get(&s.symbol).copied().unwrap_or(0) > 1)
+            .unwrap_or(0);
+        let evicted = self.snapshots.remove(index)?;
+        self.forget(&evicted.symbol);
+        Some(evicted)
+    }
+    
+    fn forget(&mut self, symbol: &str) {
+        if let Some(count) = self.queued.get_mut(symbol) {
+            *count -= 1;
+            if *count == 0 {
+                self.queued.remove(symbol);
+            }
+        }
+    }
+}
+
+/// Feed side of `snapshot_queue`. Never blocks: when the queue is full an
+/// older snapshot is dropped instead, so the newest book per symbol is kept.
+pub struct SnapshotSender {
+    shared: Arc<Shared>,
+}
+
+impl SnapshotSender {
+    /// Queue `snapshot`; fails once the receiver is gone
+    pub fn send(&self, snapshot: MarketSnapshot) -> Result<()> {
+        let mut queue = self.shared.queue.lock();
+        if queue.receiver_closed {
+            return Err(Error::ChannelSend);
+        }
+        
+        if queue.snapshots.len() >= self.shared.capacity {
+            if let Some(evicted) = queue.evict_for(&snapshot.symbol) {
+                self.shared.dropped.fetch_add(1, Ordering::Relaxed);
+                metrics::increment_counter!("market_snapshots_dropped", "symbol" => evicted.symbol);
+            }
+        }
+        queue.push(snapshot);
+        drop(queue);
+        
+        self.shared.notify.notify_one();
+        Ok(())
+    }
+}
+
+impl Drop for SnapshotSender {
+    fn drop(&mut self) {
+        self.shared.queue.lock().sender_closed = true;
+        self.shared.notify.notify_one();
+    }
+}
+
+/// Batching-loop side of `snapshot_queue`
+pub struct SnapshotReceiver {
+    shared: Arc<Shared>,
+}
+
+impl SnapshotReceiver {
+    /// Oldest queued snapshot; `None` once the sender is gone and the queue
+    /// has drained
+    pub async fn recv(&mut self) -> Option<MarketSnapshot> {
+        loop {
+            let notified = self.shared.notify.notified();
+            {
+                let mut queue = self.shared.queue.lock();
+                if let Some(snapshot) = queue.pop() {
+                    return Some(snapshot);
+                }
+                if queue.sender_closed {
+                    return None;
+                }
+            }
+            notified.await;
+        }
+    }
+    
+    /// Snapshots dropped to keep the queue bounded, since it was created
+    pub fn dropped(&self) -> u64 {
+        self.shared.dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for SnapshotReceiver {
+    fn drop(&mut self) {
+        self.shared.queue.lock().receiver_closed = true;
+    }
+}
+
+/// One batch window's snapshots, coalesced to the latest per symbol (kept
+/// in first-seen order) so each symbol's features run once per flush
+#[derive(Debug, Default)]
+pub struct SnapshotBatch {
+    snapshots: Vec<MarketSnapshot>,
+    slots: HashMap<String, usize>,
+}
+
+impl SnapshotBatch {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            snapshots: Vec::with_capacity(capacity),
+            slots: HashMap::with_capacity(capacity),
+        }
+    }
+    
+    /// Add `snapshot`; returns `true` when it replaced an earlier book for
+    /// its symbol
+    pub fn push(&mut self, snapshot: MarketSnapshot) -> bool {
+        match self.slots.get(&snapshot.symbol) {
+            Some(&slot) => {
+                self.snapshots[slot] = snapshot;
+                true
+            }
+            None => {
+                self.slots.insert(snapshot.symbol.clone(), self.snapshots.len());
+                self.snapshots.push(snapshot);
+                false
+            }
+        }
+    }
+    
+    /// Distinct symbols in the window
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+    
+    pub fn snapshots(&self) -> &[MarketSnapshot] {
+        &self.snapshots
+    }
+    
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+        self.slots.clear();
+    }
+}
+
+/// Merge every venue's snapshot stream into `market_tx`, feeding each book
+/// to the feature computer's rolling windows on the way. Fails if no feed
+/// delivers a snapshot within `grace` or once every feed has closed; returns
+/// `Ok` when the processing loop hangs up.
+pub async fn forward_snapshots(
+    feeds: Vec<(Venue, mpsc::UnboundedReceiver<MarketSnapshot>)>,
+    feature_computer: Arc<FeatureComputer>,
+    market_tx: SnapshotSender,
+    grace: Duration,
+) -> Result<()> {
+    if feeds.is_empty() {
+        return Err(Error::Config("No adapter supplied a market data feed".to_string()));
+    }
+    
+    let mut merged = futures::stream::select_all(feeds.into_iter().map(|(venue, mut rx)| {
+        futures::stream::poll_fn(move |cx| rx.poll_recv(cx))
+            .map(move |snapshot| (venue, snapshot))
+            .boxed()
+    }));
+    
+    let mut next = tokio::time::timeout(grace, merged.next())
+        .await
+        .map_err(|_| Error::Timeout(format!("No market data from any adapter within {:?}", grace)))?;
+    
+    while let Some((venue, snapshot)) = next {
+        feature_computer.update_book(&snapshot.orderbook);
+        metrics::increment_counter!("market_snapshots_received", "venue" => format!("{:?}", venue));
+        
+        if market_tx.send(snapshot).is_err() {
+            return Ok(());
+        }
+        next = merged.next().await;
+    }
+    
+    Err(Error::Internal("All market data feeds closed".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn snapshot(symbol: &str, sequence: u64) -> MarketSnapshot {
+        let level = |price: f64| Level { price: price.into(), quantity: 1.0 };
+        MarketSnapshot {
+            timestamp_ns: sequence as i64,
+            symbol: symbol.to_string(),
+            orderbook: OrderBook {
+                symbol: symbol.to_string(),
+                timestamp_ns: sequence as i64,
+                bids: vec![level(99.0)],
+                asks: vec![level(101.0)],
+                sequence,
+            },
+            recent_trades: vec![],
+            funding_rate_bps: None,
+            open_interest: None,
+            volume_24h: 0.0,
+            quality: DataQuality::Live,
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_snapshots_from_every_feed_reach_the_batcher() {
+        // What each adapter's `snapshot_receiver` hands the engine
+        let (hl_tx, hl_rx) = mpsc::unbounded_channel();
+        let (bn_tx, bn_rx) = mpsc::unbounded_channel();
+        let feeds = vec![(Venue::Hyperliquid, hl_rx), (Venue::BinanceFutures, bn_rx)];
+        let computer = Arc::new(FeatureComputer::cpu_only());
+        computer.add_symbol("BTC".to_string(), 8);
+        computer.add_symbol("ETH".to_string(), 8);
+        
+        let (market_tx, mut market_rx) = snapshot_queue(16);
+        let forward = tokio::spawn(forward_snapshots(feeds, computer.clone(), market_tx, Duration::from_secs(1)));
+        
+        hl_tx.send(snapshot("BTC", 1)).unwrap();
+        bn_tx.send(snapshot("ETH", 1)).unwrap();
+        hl_tx.send(snapshot("BTC", 2)).unwrap();
+        
+        let mut received = Vec::new();
+        for _ in 0..3 {
+            let snap = market_rx.recv().await.unwrap();
+            received.push((snap.symbol, snap.orderbook.sequence));
+        }
+        received.sort();
+        assert_eq!(received, vec![("BTC".to_string(), 1), ("BTC".to_string(), 2), ("ETH".to_string(), 1)]);
+        
+        // Forwarded books already sit in the rolling windows
+        assert!(computer.compute_batch(&[snapshot("BTC", 2)]).is_ok());
+        
+        // Every feed closing is an error, not a silent stall
+        drop((hl_tx, bn_tx));
+        assert!(matches!(forward.await.unwrap(), Err(Error::Internal(_))));
+    }
+    
+    #[tokio::test]
+    async fn test_silent_feeds_fail_after_grace() {
+        let (_tx, rx) = mpsc::unbounded_channel();
+        let feeds = vec![(Venue::Hyperliquid, rx)];
+        let (market_tx, _market_rx) = snapshot_queue(16);
+        
+        let res = forward_snapshots(
+            feeds,
+            Arc::new(FeatureComputer::cpu_only()),
+            market_tx,
+            Duration::from_millis(20),
+        ).await;
+        assert!(matches!(res, Err(Error::Timeout(_))));
+        
+        let (market_tx, _market_rx) = snapshot_queue(16);
+        let res = forward_snapshots(vec![], Arc::new(FeatureComputer::cpu_only()), market_tx, Duration::from_millis(20)).await;
+        assert!(matches!(res, Err(Error::Config(_))));
+    }
+    
+    #[tokio::test]
+    async fn test_full_queue_drops_superseded_books() {
+        let (market_tx, mut market_rx) = snapshot_queue(4);
+        market_tx.send(snapshot("ETH", 1)).unwrap();
+        for sequence in 1..=10 {
+            market_tx.send(snapshot("BTC", sequence)).unwrap();
+        }
+        market_tx.send(snapshot("SOL", 1)).unwrap();
+        
+        // 12 books into 4 slots: every drop is counted, none of them the
+        // only or newest book of a symbol
+        assert_eq!(market_rx.dropped(), 8);
+        drop(market_tx);
+        
+        let mut received = Vec::new();
+        while let Some(snap) = market_rx.recv().await {
+            received.push((snap.symbol, snap.orderbook.sequence));
+        }
+        assert_eq!(received, vec![
+            ("ETH".to_string(), 1),
+            ("BTC".to_string(), 9),
+            ("BTC".to_string(), 10),
+            ("SOL".to_string(), 1),
+        ]);
+        
+        // The receiver hanging up stops the feed
+        let (market_tx, market_rx) = snapshot_queue(4);
+        drop(market_rx);
+        assert!(matches!(market_tx.send(snapshot("BTC", 1)), Err(Error::ChannelSend)));
+    }
+    
+    #[test]
+    fn test_batch_keeps_latest_book_per_symbol() {
+        let computer = FeatureComputer::cpu_only();
+        computer.add_symbol("BTC".to_string(), 8);
+        computer.add_symbol("ETH".to_string(), 8);
+        
+        let mut batch = SnapshotBatch::with_capacity(4);
+        assert!(!batch.push(snapshot("ETH", 1)));
+        let coalesced = (1..=5).filter(|&sequence| batch.push(snapshot("BTC", sequence))).count();
+        assert_eq!(coalesced, 4);
+        assert_eq!(batch.len(), 2);
+        
+        // Features run once per symbol, on its latest book
+        let features = computer.compute_batch(batch.snapshots()).unwrap();
+        assert_eq!(features.len(), 2);
+        let btc: Vec<_> = features.iter().filter(|f| f.symbol == "BTC").collect();
+        assert_eq!(btc.len(), 1);
+        assert_eq!(btc[0].timestamp_ns, 5);
+        assert_eq!(features[0].symbol, "ETH");
+        
+        batch.clear();
+        assert!(batch.is_empty());
+        assert!(!batch.push(snapshot("BTC", 6)));
+    }
+}
@@ -0,0 +1,201 @@
+// crates/engine/src/holds.rs - Close positions once their intended hold elapses
+use common::*;
+use std::collections::HashMap;
+
+/// How often the engine looks for holds past their deadline
+pub const SWEEP_INTERVAL_MS: u64 = 1_000;
+
+/// A position the engine opened, with the hold its entry decision asked for
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpenHold {
+    pub side: Side,
+    pub quantity: f64,
+    pub entry_ns: i64,
+    pub deadline_ns: i64,
+    /// A close has been sent and not yet confirmed
+    closing: bool,
+}
+
+impl OpenHold {
+    fn new(side: Side, quantity: f64, entry_ns: i64, deadline_ns: i64) -> Self {
+        Self { side, quantity, entry_ns, deadline_ns, closing: false }
+    }
+    
+    /// Reduce-only taker order that flattens the hold
+    fn exit(&self, reason: String) -> RouteDecision {
+        RouteDecision {
+            style: OrderStyle::TakerNow,
+            size_fraction: self.quantity,
+            hold_duration_s: 0.0,
+            urgency: 1.0,
+            should_trade: true,
+            side: Some(match self.side {
+                Side::Buy => Side::Sell,
+                Side::Sell => Side::Buy,
+            }),
+            reduce_only: true,
+            reason,
+        }
+    }
+}
+
+/// Open positions per symbol, keyed by entry time and intended hold.
+/// Entries on the held side add to it and keep its clock; opposite entries
+/// offset it, and any excess starts a new hold.
+#[derive(Debug, Default)]
+pub struct HoldTracker {
+    holds: HashMap<String, OpenHold>,
+}
+
+impl HoldTracker {
+    pub fn get(&self, symbol: &str) -> Option<&OpenHold> {
+        self.holds.get(symbol)
+    }
+    
+    /// Record an opening order sent at `now_ns`
+    pub fn on_entry(&mut self, symbol: &str, side: Side, quantity: f64, hold_duration_s: f64, now_ns: i64) {
+        let deadline_ns = now_ns + (hold_duration_s.max(0.0) * 1e9) as i64;
+        
+        let Some(open) = self.holds.get_mut(symbol) else {
+            self.holds.insert(symbol.to_string(), OpenHold::new(side, quantity, now_ns, deadline_ns));
+            return;
+        };
+        
+        if open.side == side {
+            open.quantity += quantity;
+            open.deadline_ns = open.deadline_ns.max(deadline_ns);
+            return;
+        }
+        
+        let excess = quantity - open.quantity;
+        if excess < 0.0 {
+            open.quantity = -excess;
+            return;
+        }
+        
+        self.on_closed(symbol, now_ns);
+        if excess > 0.0 {
+            self.holds.insert(symbol.to_string(), OpenHold::new(side, excess, now_ns, deadline_ns));
+        }
+    }
+    
+    /// Exits for every hold past its deadline at `now_ns`. They stay tracked
+    /// (and aren't issued again) until `on_closed` or `on_close_failed`.
+    pub fn expired(&mut self, now_ns: i64) -> Vec<(String, RouteDecision)> {
+        let mut exits = Vec::new();
+        
+        for (symbol, hold) in self.holds.iter_mut() {
+            if hold.closing || hold.deadline_ns > now_ns {
+                continue;
+            }
+            
+            hold.closing = true;
+            let held_s = (now_ns - hold.entry_ns) as f64 / 1e9;
+            exits.push((symbol.clone(), hold.exit(format!("Hold elapsed after {:.1}s", held_s))));
+        }
+        
+        exits
+    }
+    
+    /// Exit for `symbol` ahead of its deadline, `None` when nothing is held
+    /// or a close is already in flight
+    pub fn close(&self, symbol: &str, reason: String) -> Option<RouteDecision> {
+        self.holds
+            .get(symbol)
+            .filter(|hold| !hold.closing)
+            .map(|hold| hold.exit(reason))
+    }
+    
+    /// Exits for every hold without a close in flight, e.g. at shutdown.
+    /// Like `expired`, they stay tracked until confirmed.
+    pub fn close_all(&mut self, reason: &str) -> Vec<(String, RouteDecision)> {
+        self.holds
+            .iter_mut()
+            .filter(|(_, hold)| !hold.closing)
+            .map(|(symbol, hold)| {
+                hold.closing = true;
+                (symbol.clone(), hold.exit(reason.to_string()))
+            })
+            .collect()
+    }
+    
+    /// The position was flattened: forget it and record how long it was held
+    pub fn on_closed(&mut self, symbol: &str, now_ns: i64) {
+        let Some(hold) = self.holds.remove(symbol) else {
+            return;
+        };
+        
+        let realized_s = (now_ns - hold.entry_ns) as f64 / 1e9;
+        let intended_s = (hold.deadline_ns - hold.entry_ns) as f64 / 1e9;
+        metrics::histogram!("position_hold_realized_s", realized_s, "symbol" => symbol.to_string());
+        metrics::histogram!("position_hold_intended_s", intended_s, "symbol" => symbol.to_string());
+    }
+    
+    /// The close didn't go out: let the next sweep retry it
+    pub fn on_close_failed(&mut self, symbol: &str) {
+        if let Some(hold) = self.holds.get_mut(symbol) {
+            hold.closing = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const SEC: i64 = 1_000_000_000;
+    
+    #[test]
+    fn test_hold_expiry_closes_reduce_only() {
+        let mut holds = HoldTracker::default();
+        let t0 = 1_700_000_000 * SEC;
+        holds.on_entry("BTC", Side::Buy, 0.5, 30.0, t0);
+        
+        assert!(holds.expired(t0 + 29 * SEC).is_empty());
+        
+        let exits = holds.expired(t0 + 31 * SEC);
+        assert_eq!(exits.len(), 1);
+        let (symbol, exit) = &exits[0];
+        assert_eq!(symbol, "BTC");
+        assert!(exit.should_trade);
+        assert!(exit.reduce_only);
+        assert_eq!(exit.side, Some(Side::Sell));
+        assert_eq!(exit.size_fraction, 0.5);
+        
+        // In flight: not issued again; a failed send is retried
+        assert!(holds.expired(t0 + 32 * SEC).is_empty());
+        holds.on_close_failed("BTC");
+        assert_eq!(holds.expired(t0 + 33 * SEC).len(), 1);
+        
+        holds.on_closed("BTC", t0 + 33 * SEC);
+        assert!(holds.get("BTC").is_none());
+        assert!(holds.expired(t0 + 60 * SEC).is_empty());
+    }
+    
+    #[test]
+    fn test_entries_net_against_hold() {
+        let mut holds = HoldTracker::default();
+        holds.on_entry("ETH", Side::Sell, 2.0, 10.0, 0);
+        holds.on_entry("ETH", Side::Sell, 1.0, 30.0, 5 * SEC);
+        
+        // Adding keeps the entry time and takes the later deadline
+        let hold = holds.get("ETH").unwrap();
+        assert_eq!((hold.quantity, hold.entry_ns, hold.deadline_ns), (3.0, 0, 35 * SEC));
+        
+        holds.on_entry("ETH", Side::Buy, 1.0, 10.0, 6 * SEC);
+        assert_eq!(holds.get("ETH").unwrap().quantity, 2.0);
+        
+        // Flipping starts a fresh hold on the other side
+        holds.on_entry("ETH", Side::Buy, 3.0, 10.0, 7 * SEC);
+        let hold = holds.get("ETH").unwrap();
+        assert_eq!((hold.side, hold.quantity, hold.entry_ns), (Side::Buy, 1.0, 7 * SEC));
+        
+        assert_eq!(holds.close("ETH", "RL flat".to_string()).unwrap().side, Some(Side::Sell));
+        assert!(holds.close("SOL", "RL flat".to_string()).is_none());
+        
+        let exits = holds.close_all("Shutdown");
+        assert_eq!(exits.len(), 1);
+        assert_eq!(exits[0].1.reason, "Shutdown");
+        assert!(holds.close_all("Shutdown").is_empty());
+    }
+}
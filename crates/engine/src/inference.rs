@@ -1,12 +1,17 @@
 // crates/engine/src/inference.rs - MANDATORY models (no fallbacks)
 use common::*;
-use ndarray::{Array1, Array2};
+use features::DeviceType;
+use ndarray::{Array1, Array2, ArrayD, Axis};
+use ort::execution_providers::{CUDAExecutionProviderOptions, ROCmExecutionProviderOptions};
 use ort::{Environment, ExecutionProvider, Session, SessionBuilder, Value};
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use parking_lot::RwLock;
+use tokio::sync::Semaphore;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ModelType {
     IDEC,
     Transformer,
@@ -19,12 +24,225 @@ pub struct ModelSet {
     pub transformer: Arc<Session>,
     pub gbdt: Arc<Session>,
     pub edge: Arc<Session>,
+    /// Where each model's edge and confidence outputs are read from
+    pub layouts: HashMap<ModelType, OutputLayout>,
+    /// Feature length each model's input expects (`None` if the axis is dynamic)
+    pub input_lens: HashMap<ModelType, Option<usize>>,
+    /// Execution provider the sessions were built for
+    pub provider: Provider,
+    pub dir: PathBuf,
+    pub version: String,
+}
+
+/// ONNX Runtime execution provider for a session
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provider {
+    CPU,
+    CUDA(usize),
+    ROCm(usize),
+    TensorRT,
+}
+
+impl Provider {
+    /// Providers to try for a configured device, best first; CPU is always last
+    pub fn preference(device: DeviceType) -> Vec<Self> {
+        match device {
+            DeviceType::CPU => vec![Self::CPU],
+            DeviceType::CUDA(id) => vec![Self::CUDA(id), Self::CPU],
+            DeviceType::ROCm(id) => vec![Self::ROCm(id), Self::CPU],
+            DeviceType::TensorRT => vec![Self::TensorRT, Self::CUDA(0), Self::CPU],
+        }
+    }
+    
+    fn execution_provider(self) -> ExecutionProvider {
+        match self {
+            Self::CPU => ExecutionProvider::CPU(Default::default()),
+            Self::CUDA(id) => ExecutionProvider::CUDA(CUDAExecutionProviderOptions {
+                device_id: id as u32,
+                ..Default::default()
+            }),
+            Self::ROCm(id) => ExecutionProvider::ROCm(ROCmExecutionProviderOptions {
+                device_id: id as i32,
+                ..Default::default()
+            }),
+            Self::TensorRT => ExecutionProvider::TensorRT(Default::default()),
+        }
+    }
+    
+    /// Whether this ORT build and machine can run the provider
+    pub fn is_available(self) -> bool {
+        self == Self::CPU || self.execution_provider().is_available()
+    }
+}
+
+/// First provider for `device` that `available` accepts, CPU if none does
+pub fn select_provider(device: DeviceType, available: impl Fn(Provider) -> bool) -> Provider {
+    Provider::preference(device)
+        .into_iter()
+        .find(|p| available(*p))
+        .unwrap_or(Provider::CPU)
+}
+
+/// Output name mapped to edge when a model exports named tensors
+pub const EDGE_OUTPUT: &str = "edge_bps";
+/// Output name mapped to confidence when a model exports named tensors
+pub const CONFIDENCE_OUTPUT: &str = "confidence";
+
+/// Where a model's edge and confidence live among its outputs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputLayout {
+    /// One `[batch, >= 2]` tensor: edge in column 0, confidence in column 1
+    Packed { output: usize },
+    /// Separate `edge_bps` and `confidence` tensors, one value per row
+    Named { edge: usize, confidence: usize },
+}
+
+impl OutputLayout {
+    /// Resolve the layout from session output metadata (name and dims,
+    /// `None` for dynamic axes). Named outputs win over position.
+    pub fn resolve(model_type: ModelType, outputs: &[(String, Vec<Option<u32>>)]) -> Result<Self> {
+        let find = |name: &str| outputs.iter().position(|(n, _)| n == name);
+        
+        if let (Some(edge), Some(confidence)) = (find(EDGE_OUTPUT), find(CONFIDENCE_OUTPUT)) {
+            for i in [edge, confidence] {
+                let (name, dims) = &outputs[i];
+                if !matches!(dims.as_slice(), [_] | [_, None] | [_, Some(1)]) {
+                    return Err(Error::Model(format!(
+                        "{:?} model output '{}' has shape {}, expected [batch] or [batch, 1]",
+                        model_type, name, format_dims(dims)
+                    )));
+                }
+            }
+            return Ok(Self::Named { edge, confidence });
+        }
+        
+        let (name, dims) = outputs.first().ok_or_else(|| {
+            Error::Model(format!("{:?} model has no outputs", model_type))
+        })?;
+        match dims.as_slice() {
+            [_, cols] if cols.map_or(true, |c| c >= 2) => Ok(Self::Packed { output: 0 }),
+            _ => Err(Error::Model(format!(
+                "{:?} model output '{}' has shape {}, expected [batch, 2] or named '{}'/'{}' outputs",
+                model_type, name, format_dims(dims), EDGE_OUTPUT, CONFIDENCE_OUTPUT
+            ))),
+        }
+    }
+    
+    /// Read (edge_bps, confidence) for the first row of `outputs`
+    pub fn read(&self, outputs: &[ArrayD<f32>]) -> Result<(f64, f64)> {
+        self.read_row(outputs, 0)
+    }
+    
+    /// Read (edge_bps, confidence) for batch row `row` of `outputs`
+    pub fn read_row(&self, outputs: &[ArrayD<f32>], row: usize) -> Result<(f64, f64)> {
+        match *self {
+            Self::Packed { output } => Ok((
+                row_value(outputs, output, row, 0)?,
+                row_value(outputs, output, row, 1)?,
+            )),
+            Self::Named { edge, confidence } => Ok((
+                row_value(outputs, edge, row, 0)?,
+                row_value(outputs, confidence, row, 0)?,
+            )),
+        }
+    }
+}
+
+/// `column` of batch row `row` of output `index`, erroring instead of panicking
+fn row_value(outputs: &[ArrayD<f32>], index: usize, row: usize, column: usize) -> Result<f64> {
+    let array = outputs.get(index).ok_or_else(|| {
+        Error::Model(format!("Model returned {} outputs, expected output {}", outputs.len(), index))
+    })?;
+    let cols = if array.ndim() >= 2 { array.shape()[array.ndim() - 1] } else { 1 };
+    let rows = if array.ndim() >= 1 { array.shape()[0] } else { 1 };
+    
+    if column >= cols || row >= rows || array.is_empty() {
+        return Err(Error::Model(format!(
+            "Model output {} has shape {:?}, cannot read row {} column {}",
+            index, array.shape(), row, column
+        )));
+    }
+    Ok(array.iter().nth(row * cols + column).copied().unwrap_or(f32::NAN) as f64)
+}
+
+/// Stack equal-length feature vectors into one `[N, D]` model input
+pub fn stack_features(features: &[Array1<f32>]) -> Result<Array2<f32>> {
+    let dim = features.first().map_or(0, |f| f.len());
+    if let Some((i, f)) = features.iter().enumerate().find(|(_, f)| f.len() != dim) {
+        return Err(Error::Feature(format!(
+            "Batch row {} has {} features, row 0 has {}",
+            i, f.len(), dim
+        )));
+    }
+    
+    let views: Vec<_> = features.iter().map(|f| f.view()).collect();
+    ndarray::stack(Axis(0), &views)
+        .map_err(|e| Error::Model(format!("Failed to stack batch input: {}", e)))
+}
+
+fn to_prediction((edge_bps, confidence): (f64, f64)) -> Prediction {
+    Prediction {
+        timestamp_ns: chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0),
+        symbol: String::new(),
+        edge_bps,
+        confidence,
+        horizon_ms: 5000,
+        model_version: "v1.0".to_string(),
+    }
+}
+
+/// Feature axis length of a `[batch, features]` input, `None` if dynamic
+pub fn expected_input_len(model_type: ModelType, dims: &[Option<u32>]) -> Result<Option<usize>> {
+    match dims {
+        [_, features] => Ok(features.map(|n| n as usize)),
+        _ => Err(Error::Model(format!(
+            "{:?} model input has shape {}, expected [batch, features]",
+            model_type, format_dims(dims)
+        ))),
+    }
+}
+
+/// Reject a feature vector the model wasn't built for before ORT sees it
+pub fn check_input_len(model_type: ModelType, expected: Option<usize>, actual: usize) -> Result<()> {
+    match expected {
+        Some(expected) if expected != actual => Err(Error::Feature(format!(
+            "{:?} model expects {} features, got {}",
+            model_type, expected, actual
+        ))),
+        _ => Ok(()),
+    }
+}
+
+fn format_dims(dims: &[Option<u32>]) -> String {
+    let dims: Vec<String> = dims.iter()
+        .map(|d| d.map_or("?".to_string(), |d| d.to_string()))
+        .collect();
+    format!("[{}]", dims.join(", "))
+}
+
+/// Contents of `dir/VERSION`, or "unversioned" if absent
+pub fn read_model_version(dir: &Path) -> String {
+    std::fs::read_to_string(dir.join("VERSION"))
+        .map(|v| v.trim().to_string())
+        .unwrap_or_else(|_| "unversioned".to_string())
 }
 
 impl ModelSet {
-    pub fn load(env: &Arc<Environment>, models_dir: &Path) -> Result<Self> {
+    pub fn load(env: &Arc<Environment>, models_dir: &Path, device: DeviceType) -> Result<Self> {
         tracing::info!("Loading models from {:?} (MANDATORY)", models_dir);
+        common::contract::verify_contract(models_dir)?;
         
+        // Unavailable providers are skipped; CPU stays in the list as the fallback
+        let provider = select_provider(device, Provider::is_available);
+        if Provider::preference(device).first() != Some(&provider) {
+            tracing::warn!("⚠️  {:?} execution provider unavailable, models fall back to {:?}", device, provider);
+        }
+        let providers: Vec<_> = Provider::preference(device)
+            .into_iter()
+            .skip_while(|p| *p != provider)
+            .map(Provider::execution_provider)
+            .collect();
+
         let load_model = |name: &str| -> Result<Arc<Session>> {
             let path = models_dir.join(format!("{}.onnx", name));
             
@@ -38,7 +256,7 @@ impl ModelSet {
             tracing::info!("Loading model: {:?}", path);
             
             let session = SessionBuilder::new(env)?
-                .with_execution_providers([ExecutionProvider::CPU])?
+                .with_execution_providers(providers.clone())?
                 .with_optimization_level(ort::GraphOptimizationLevel::Level3)?
                 .with_intra_threads(2)?
                 .with_model_from_file(&path)
@@ -47,7 +265,7 @@ impl ModelSet {
                     path, e
                 )))?;
             
-            tracing::info!("✅ Loaded: {:?}", path);
+            tracing::info!("✅ Loaded: {:?} on {:?}", path, provider);
             Ok(Arc::new(session))
         };
         
@@ -57,13 +275,254 @@ impl ModelSet {
         let gbdt = load_model("gbdt")?;
         let edge = load_model("edge")?;
         
+        // Reject signatures we can't feed or read before any inference runs
+        let mut layouts = HashMap::new();
+        let mut input_lens = HashMap::new();
+        for (model_type, session) in [
+            (ModelType::IDEC, &idec),
+            (ModelType::Transformer, &transformer),
+            (ModelType::GBDT, &gbdt),
+            (ModelType::Edge, &edge),
+        ] {
+            let outputs: Vec<_> = session.outputs
+                .iter()
+                .map(|o| (o.name.clone(), o.dimensions.clone()))
+                .collect();
+            layouts.insert(model_type, OutputLayout::resolve(model_type, &outputs)?);
+            
+            let input = session.inputs.first().ok_or_else(|| {
+                Error::Model(format!("{:?} model has no inputs", model_type))
+            })?;
+            input_lens.insert(model_type, expected_input_len(model_type, &input.dimensions)?);
+        }
+        
         Ok(Self {
             idec,
             transformer,
             gbdt,
             edge,
+            layouts,
+            input_lens,
+            provider,
+            dir: models_dir.to_path_buf(),
+            version: read_model_version(models_dir),
         })
     }
+    
+    pub fn session(&self, model_type: ModelType) -> &Arc<Session> {
+        match model_type {
+            ModelType::IDEC => &self.idec,
+            ModelType::Transformer => &self.transformer,
+            ModelType::GBDT => &self.gbdt,
+            ModelType::Edge => &self.edge,
+        }
+    }
+}
+
+/// Dummy runs per model in `InferencePool::warmup`
+const WARMUP_RUNS: usize = 3;
+
+/// One blocking `session.run` on a `[rows, features]` input
+fn run_session(session: &Session, input: &Array2<f32>) -> Result<Vec<ArrayD<f32>>> {
+    let input_value = Value::from_array(session.allocator(), input)
+        .map_err(|e| Error::Model(format!("Failed to create ONNX value: {}", e)))?;
+    
+    let outputs = session.run(vec![input_value])
+        .map_err(|e| Error::Model(format!("Inference execution failed: {}", e)))?;
+    
+    outputs.iter()
+        .map(|output| {
+            output.try_extract::<f32>()
+                .map(|tensor| tensor.view().to_owned())
+                .map_err(|e| Error::Model(format!("Failed to extract output: {}", e)))
+        })
+        .collect()
+}
+
+/// Inference concurrency caps, applied to each model type separately
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct InferenceLimits {
+    /// Concurrent runs of one model; more would oversubscribe the cores
+    pub max_concurrency: usize,
+    /// Requests allowed to wait for a slot before new ones are rejected
+    pub max_queue: usize,
+}
+
+impl Default for InferenceLimits {
+    fn default() -> Self {
+        Self {
+            max_concurrency: 4,
+            max_queue: 256,
+        }
+    }
+}
+
+/// How `predict_ensemble` combines the IDEC, Transformer and GBDT models.
+/// A model's edge counts in proportion to weight * confidence; a weight of
+/// 0 leaves the model out.
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct EnsembleConfig {
+    pub idec_weight: f64,
+    pub transformer_weight: f64,
+    pub gbdt_weight: f64,
+    /// Fewer successful models than this fails the prediction
+    pub min_models_required: usize,
+    /// Weighted std-dev of model edges above which confidence is scaled down
+    /// by `max_edge_std_bps / std`; 0 disables
+    pub max_edge_std_bps: f64,
+}
+
+impl Default for EnsembleConfig {
+    fn default() -> Self {
+        Self {
+            idec_weight: 1.0,
+            transformer_weight: 1.0,
+            gbdt_weight: 1.0,
+            min_models_required: 2,
+            max_edge_std_bps: 10.0,
+        }
+    }
+}
+
+impl EnsembleConfig {
+    pub fn weight(&self, model_type: ModelType) -> f64 {
+        match model_type {
+            ModelType::IDEC => self.idec_weight,
+            ModelType::Transformer => self.transformer_weight,
+            ModelType::GBDT => self.gbdt_weight,
+            ModelType::Edge => 0.0,
+        }
+    }
+    
+    /// Spread of model edges around their weighted mean
+    pub fn edge_std_bps(&self, predictions: &[(ModelType, Prediction)]) -> f64 {
+        let total_weight: f64 = predictions.iter().map(|(m, _)| self.weight(*m)).sum();
+        if total_weight <= 0.0 {
+            return 0.0;
+        }
+        
+        let mean = predictions.iter()
+            .map(|(m, p)| p.edge_bps * self.weight(*m))
+            .sum::<f64>() / total_weight;
+        let variance = predictions.iter()
+            .map(|(m, p)| self.weight(*m) * (p.edge_bps - mean).powi(2))
+            .sum::<f64>() / total_weight;
+        variance.sqrt()
+    }
+    
+    /// Weighted ensemble of per-model predictions: edge weighted by
+    /// weight * confidence, confidence by weight alone and cut when the
+    /// models disagree
+    pub fn combine(&self, predictions: &[(ModelType, Prediction)]) -> Result<Prediction> {
+        if predictions.len() < self.min_models_required.max(1) {
+            return Err(Error::Model(format!(
+                "Only {} ensemble models succeeded, {} required",
+                predictions.len(), self.min_models_required
+            )));
+        }
+        
+        let total_weight: f64 = predictions.iter().map(|(m, _)| self.weight(*m)).sum();
+        let edge_weight: f64 = predictions.iter().map(|(m, p)| self.weight(*m) * p.confidence).sum();
+        if total_weight <= 0.0 || edge_weight <= 0.0 {
+            return Err(Error::Model("Ensemble weights sum to zero".to_string()));
+        }
+        
+        let edge_bps = predictions.iter()
+            .map(|(m, p)| p.edge_bps * self.weight(*m) * p.confidence)
+            .sum::<f64>() / edge_weight;
+        let mut confidence = predictions.iter()
+            .map(|(m, p)| p.confidence * self.weight(*m))
+            .sum::<f64>() / total_weight;
+        
+        // Models pulling in different directions make the average unreliable
+        let edge_std = self.edge_std_bps(predictions);
+        if self.max_edge_std_bps > 0.0 && edge_std > self.max_edge_std_bps {
+            confidence *= self.max_edge_std_bps / edge_std;
+            metrics::increment_counter!("ensemble_disagreement");
+        }
+
+        Ok(Prediction {
+            timestamp_ns: chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0),
+            symbol: String::new(),
+            edge_bps,
+            confidence,
+            horizon_ms: 5000,
+            model_version: "ensemble-v1.0".to_string(),
+        })
+    }
+}
+
+/// Semaphore-bounded executor for blocking model runs: at most
+/// `max_concurrency` run at once, up to `max_queue` more wait their turn
+#[derive(Clone)]
+pub struct InferenceLimiter {
+    name: &'static str,
+    permits: Arc<Semaphore>,
+    queued: Arc<AtomicUsize>,
+    max_queue: usize,
+}
+
+/// Counts a request as queued until it gets a permit or is dropped
+struct QueueSlot<'a>(&'a InferenceLimiter);
+
+impl Drop for QueueSlot<'_> {
+    fn drop(&mut self) {
+        let depth = self.0.queued.fetch_sub(1, Ordering::Relaxed) - 1;
+        metrics::gauge!("inference_queue_depth", depth as f64, "model" => self.0.name);
+    }
+}
+
+impl InferenceLimiter {
+    pub fn new(name: &'static str, limits: InferenceLimits) -> Self {
+        Self {
+            name,
+            permits: Arc::new(Semaphore::new(limits.max_concurrency.max(1))),
+            queued: Arc::new(AtomicUsize::new(0)),
+            max_queue: limits.max_queue,
+        }
+    }
+    
+    /// Requests currently waiting for a slot
+    pub fn queue_depth(&self) -> usize {
+        self.queued.load(Ordering::Relaxed)
+    }
+    
+    /// Run `f` on the blocking pool once a slot is free
+    pub async fn run<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let permit = match self.permits.clone().try_acquire_owned() {
+            Ok(permit) => permit,
+            Err(_) => {
+                let depth = self.queued.fetch_add(1, Ordering::Relaxed) + 1;
+                let slot = QueueSlot(self);
+                if depth > self.max_queue {
+                    metrics::increment_counter!("inference_queue_rejected", "model" => self.name);
+                    return Err(Error::RateLimit(format!(
+                        "{} inference queue full ({} waiting)",
+                        self.name, self.max_queue
+                    )));
+                }
+                metrics::gauge!("inference_queue_depth", depth as f64, "model" => self.name);
+                
+                let permit = self.permits.clone().acquire_owned().await
+                    .map_err(|_| Error::Internal(format!("{} inference limiter closed", self.name)))?;
+                drop(slot);
+                permit
+            }
+        };
+        
+        tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            f()
+        })
+        .await
+        .map_err(|e| Error::Model(format!("Inference task failed: {}", e)))
+    }
 }
 
 pub struct InferencePool {
@@ -71,6 +530,21 @@ pub struct InferencePool {
     pub crypto: Arc<RwLock<Option<ModelSet>>>,
     pub equity: Arc<RwLock<Option<ModelSet>>>,
     timeout_ms: u64,
+    limiters: HashMap<ModelType, InferenceLimiter>,
+    ensemble: EnsembleConfig,
+    device: DeviceType,
+}
+
+fn limiters(limits: InferenceLimits) -> HashMap<ModelType, InferenceLimiter> {
+    [
+        (ModelType::IDEC, "idec"),
+        (ModelType::Transformer, "transformer"),
+        (ModelType::GBDT, "gbdt"),
+        (ModelType::Edge, "edge"),
+    ]
+    .into_iter()
+    .map(|(model, name)| (model, InferenceLimiter::new(name, limits)))
+    .collect()
 }
 
 impl InferencePool {
@@ -87,12 +561,33 @@ impl InferencePool {
             crypto: Arc::new(RwLock::new(None)),
             equity: Arc::new(RwLock::new(None)),
             timeout_ms,
+            limiters: limiters(InferenceLimits::default()),
+            ensemble: EnsembleConfig::default(),
+            device: DeviceType::CPU,
         })
     }
     
+    /// Replace the default concurrency caps
+    pub fn with_limits(mut self, limits: InferenceLimits) -> Self {
+        self.limiters = limiters(limits);
+        self
+    }
+    
+    /// Replace the default ensemble weighting
+    pub fn with_ensemble(mut self, ensemble: EnsembleConfig) -> Self {
+        self.ensemble = ensemble;
+        self
+    }
+    
+    /// Run sessions on `device` (with CPU fallback) instead of the CPU
+    pub fn with_device(mut self, device: DeviceType) -> Self {
+        self.device = device;
+        self
+    }
+    
     /// Load crypto models - FAILS if models missing
     pub fn load_crypto(&self, models_dir: &Path) -> Result<()> {
-        let models = ModelSet::load(&self.env, models_dir)?;
+        let models = ModelSet::load(&self.env, models_dir, self.device)?;
         *self.crypto.write() = Some(models);
         tracing::info!("✅ Crypto models loaded and verified");
         Ok(())
@@ -100,7 +595,7 @@ impl InferencePool {
     
     /// Load equity models - FAILS if models missing
     pub fn load_equity(&self, models_dir: &Path) -> Result<()> {
-        let models = ModelSet::load(&self.env, models_dir)?;
+        let models = ModelSet::load(&self.env, models_dir, self.device)?;
         *self.equity.write() = Some(models);
         tracing::info!("✅ Equity models loaded and verified");
         Ok(())
@@ -116,6 +611,27 @@ impl InferencePool {
         self.equity.read().is_some()
     }
     
+    /// Loaded model sets and their versions
+    pub fn model_info(&self) -> Vec<crate::diagnostics::ModelInfo> {
+        [("crypto", &self.crypto), ("equity", &self.equity)]
+            .into_iter()
+            .map(|(name, set)| match set.read().as_ref() {
+                Some(models) => crate::diagnostics::ModelInfo {
+                    name: name.to_string(),
+                    path: models.dir.display().to_string(),
+                    version: models.version.clone(),
+                    loaded: true,
+                },
+                None => crate::diagnostics::ModelInfo {
+                    name: name.to_string(),
+                    path: String::new(),
+                    version: String::new(),
+                    loaded: false,
+                },
+            })
+            .collect()
+    }
+    
     /// Run inference - FAILS if models not loaded (no fallback)
     pub async fn predict(
         &self,
@@ -137,17 +653,16 @@ impl InferencePool {
             ))
         })?;
         
-        let session = match model_type {
-            ModelType::IDEC => &model_set.idec,
-            ModelType::Transformer => &model_set.transformer,
-            ModelType::GBDT => &model_set.gbdt,
-            ModelType::Edge => &model_set.edge,
-        };
+        let session = model_set.session(model_type).clone();
+        let layout = model_set.layouts[&model_type];
+        let input_len = model_set.input_lens.get(&model_type).copied().flatten();
+        drop(models);
+        check_input_len(model_type, input_len, features.len())?;
         
-        // Run inference with timeout - FAILS if timeout
+        // Run inference with timeout - FAILS if timeout (queueing counts)
         let prediction = tokio::time::timeout(
             std::time::Duration::from_millis(self.timeout_ms),
-            self.run_inference(session.clone(), features)
+            self.run_inference(model_type, session, layout, features)
         ).await.map_err(|_| {
             Error::Timeout(format!(
                 "Inference timeout after {}ms. Model: {:?}. This is CRITICAL.",
@@ -177,61 +692,132 @@ impl InferencePool {
     
     async fn run_inference(
         &self,
+        model_type: ModelType,
         session: Arc<Session>,
+        layout: OutputLayout,
         features: &Array1<f32>,
     ) -> Result<Prediction> {
         let features_owned = features.clone();
         
-        let result = tokio::task::spawn_blocking(move || {
+        let result = self.limiters[&model_type].run(move || {
             let input_shape = vec![1, features_owned.len()];
             let input_array = Array2::from_shape_vec(
                 (input_shape[0], input_shape[1]),
                 features_owned.to_vec()
             ).map_err(|e| Error::Model(format!("Failed to reshape input: {}", e)))?;
             
-            let input_value = Value::from_array(session.allocator(), &input_array)
-                .map_err(|e| Error::Model(format!("Failed to create ONNX value: {}", e)))?;
-            
-            let outputs = session.run(vec![input_value])
-                .map_err(|e| Error::Model(format!("Inference execution failed: {}", e)))?;
-            
-            let output = &outputs[0];
-            let output_array: Array2<f32> = output.try_extract()
-                .map_err(|e| Error::Model(format!("Failed to extract output: {}", e)))?
-                .view()
-                .to_owned();
-            
-            let edge_bps = output_array[[0, 0]] as f64;
-            let confidence = output_array[[0, 1]] as f64;
+            layout.read(&run_session(&session, &input_array)?)
+        }).await??;
+        
+        Ok(to_prediction(result))
+    }
+    
+    /// Batched inference: one `[N, D]` run for all of `features`, split back
+    /// into predictions in input order. The timeout covers the whole batch.
+    pub async fn predict_batch(
+        &self,
+        category: AssetCategory,
+        features: &[Array1<f32>],
+        model_type: ModelType,
+    ) -> Result<Vec<Prediction>> {
+        if features.is_empty() {
+            return Ok(Vec::new());
+        }
+        let start = std::time::Instant::now();
+        
+        let models = match category {
+            AssetCategory::CryptoFutures => self.crypto.read(),
+            AssetCategory::Equity => self.equity.read(),
+        };
+        let model_set = models.as_ref().ok_or_else(|| {
+            Error::Model(format!(
+                "Models NOT loaded for {:?}. REQUIRED: Load models before trading.",
+                category
+            ))
+        })?;
+        let session = model_set.session(model_type).clone();
+        let layout = model_set.layouts[&model_type];
+        let input_len = model_set.input_lens.get(&model_type).copied().flatten();
+        drop(models);
+        
+        let input = stack_features(features)?;
+        check_input_len(model_type, input_len, input.ncols())?;
+        let rows = input.nrows();
+        
+        let run = self.limiters[&model_type].run(move || {
+            let outputs = run_session(&session, &input)?;
+            (0..rows).map(|row| layout.read_row(&outputs, row)).collect::<Result<Vec<_>>>()
+        });
+        let results = tokio::time::timeout(std::time::Duration::from_millis(self.timeout_ms), run)
+            .await
+            .map_err(|_| {
+                Error::Timeout(format!(
+                    "Batch inference timeout after {}ms for {} rows. Model: {:?}.",
+                    self.timeout_ms, rows, model_type
+                ))
+            })???;
+        
+        metrics::histogram!("inference_batch_duration_us", start.elapsed().as_micros() as f64,
+            "category" => format!("{:?}", category),
+            "model" => format!("{:?}", model_type)
+        );
+        metrics::histogram!("inference_batch_size", rows as f64);
+        
+        Ok(results.into_iter().map(to_prediction).collect())
+    }
+    
+    /// Run zero inputs through every model of `category` so the first live
+    /// signal doesn't pay ORT's one-time allocation and graph setup. Blocking,
+    /// and outside the limiter and timeout on purpose: the first runs are
+    /// expected to be slow. `feature_len` is used where the input axis is dynamic.
+    pub fn warmup(&self, category: AssetCategory, feature_len: usize) -> Result<std::time::Duration> {
+        let start = std::time::Instant::now();
+        
+        let models = match category {
+            AssetCategory::CryptoFutures => self.crypto.read(),
+            AssetCategory::Equity => self.equity.read(),
+        };
+        let model_set = models.as_ref().ok_or_else(|| {
+            Error::Model(format!("Models NOT loaded for {:?}. Cannot warm up.", category))
+        })?;
+        
+        for model_type in [ModelType::IDEC, ModelType::Transformer, ModelType::GBDT, ModelType::Edge] {
+            let len = model_set.input_lens.get(&model_type).copied().flatten().unwrap_or(feature_len);
+            let input = Array2::<f32>::zeros((1, len));
+            let layout = model_set.layouts[&model_type];
             
-            Ok::<_, Error>((edge_bps, confidence))
-        }).await
-        .map_err(|e| Error::Model(format!("Inference task failed: {}", e)))??;
+            for _ in 0..WARMUP_RUNS {
+                let outputs = run_session(model_set.session(model_type), &input)?;
+                layout.read(&outputs).map_err(|e| {
+                    Error::Model(format!("{:?} warmup failed: {}", model_type, e))
+                })?;
+            }
+        }
         
-        Ok(Prediction {
-            timestamp_ns: chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0),
-            symbol: String::new(),
-            edge_bps: result.0,
-            confidence: result.1,
-            horizon_ms: 5000,
-            model_version: "v1.0".to_string(),
-        })
+        let elapsed = start.elapsed();
+        metrics::histogram!("inference_warmup_ms", elapsed.as_secs_f64() * 1000.0,
+            "category" => format!("{:?}", category)
+        );
+        tracing::info!("🔥 {:?} models warmed up in {:?}", category, elapsed);
+        Ok(elapsed)
     }
     
-    /// Ensemble prediction - MANDATORY (no single model fallback)
+    /// Ensemble prediction - MANDATORY (fails below `min_models_required`)
     pub async fn predict_ensemble(
         &self,
         category: AssetCategory,
         features: &Array1<f32>,
     ) -> Result<Prediction> {
-        let models = vec![ModelType::IDEC, ModelType::Transformer, ModelType::GBDT];
+        let models = [ModelType::IDEC, ModelType::Transformer, ModelType::GBDT]
+            .into_iter()
+            .filter(|m| self.ensemble.weight(*m) > 0.0);
         
         let mut predictions = Vec::new();
         let mut errors = Vec::new();
         
         for model_type in models {
             match self.predict(category, features, model_type).await {
-                Ok(pred) => predictions.push(pred),
+                Ok(pred) => predictions.push((model_type, pred)),
                 Err(e) => {
                     tracing::error!("❌ Model {:?} failed: {}", model_type, e);
                     errors.push(format!("{:?}: {}", model_type, e));
@@ -239,27 +825,44 @@ impl InferencePool {
             }
         }
         
-        if predictions.is_empty() {
-            return Err(Error::Model(format!(
-                "ALL ensemble models failed. Errors: {:?}. Cannot continue.",
-                errors
-            )));
-        }
+        self.ensemble.combine(&predictions).map_err(|e| {
+            Error::Model(format!("{}. Errors: {:?}. Cannot continue.", e, errors))
+        })
+    }
+    
+    /// `predict_ensemble` over a batch: one `predict_batch` per model, then
+    /// each row combined on its own. A model whose batch fails is missing
+    /// from every row.
+    pub async fn predict_ensemble_batch(
+        &self,
+        category: AssetCategory,
+        features: &[Array1<f32>],
+    ) -> Result<Vec<Prediction>> {
+        let models = [ModelType::IDEC, ModelType::Transformer, ModelType::GBDT]
+            .into_iter()
+            .filter(|m| self.ensemble.weight(*m) > 0.0);
         
-        // Weighted average
-        let total_confidence: f64 = predictions.iter().map(|p| p.confidence).sum();
-        let weighted_edge: f64 = predictions.iter()
-            .map(|p| p.edge_bps * p.confidence / total_confidence)
-            .sum();
+        let mut rows: Vec<Vec<(ModelType, Prediction)>> = vec![Vec::new(); features.len()];
+        let mut errors = Vec::new();
         
-        Ok(Prediction {
-            timestamp_ns: chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0),
-            symbol: String::new(),
-            edge_bps: weighted_edge,
-            confidence: total_confidence / predictions.len() as f64,
-            horizon_ms: 5000,
-            model_version: "ensemble-v1.0".to_string(),
-        })
+        for model_type in models {
+            match self.predict_batch(category, features, model_type).await {
+                Ok(batch) => {
+                    for (row, pred) in rows.iter_mut().zip(batch) {
+                        row.push((model_type, pred));
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("❌ Model {:?} batch failed: {}", model_type, e);
+                    errors.push(format!("{:?}: {}", model_type, e));
+                }
+            }
+        }
+        
+        rows.iter()
+            .map(|predictions| self.ensemble.combine(predictions))
+            .collect::<Result<Vec<_>>>()
+            .map_err(|e| Error::Model(format!("{}. Errors: {:?}. Cannot continue.", e, errors)))
     }
 }
 
@@ -269,6 +872,7 @@ impl InferencePool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::contract;
     
     #[test]
     fn test_inference_pool_creation() {
@@ -277,6 +881,215 @@ mod tests {
         assert!(!pool.has_equity_models());
     }
     
+    #[test]
+    fn test_ensemble_weighting() {
+        let pred = |edge_bps: f64, confidence: f64| Prediction {
+            timestamp_ns: 0,
+            symbol: String::new(),
+            edge_bps,
+            confidence,
+            horizon_ms: 5000,
+            model_version: "test".to_string(),
+        };
+        let config = EnsembleConfig {
+            idec_weight: 3.0,
+            transformer_weight: 1.0,
+            gbdt_weight: 0.0,
+            min_models_required: 2,
+            max_edge_std_bps: 0.0,
+        };
+        
+        // Edge weights 3 * 0.5 = 1.5 and 1 * 1.0 = 1.0: (10 * 1.5 + 20 * 1.0) / 2.5 = 14
+        let combined = config.combine(&[
+            (ModelType::IDEC, pred(10.0, 0.5)),
+            (ModelType::Transformer, pred(20.0, 1.0)),
+        ]).unwrap();
+        assert!((combined.edge_bps - 14.0).abs() < 1e-9);
+        assert!((combined.confidence - 0.625).abs() < 1e-9);
+        
+        // A zero-weight model contributes nothing
+        let with_gbdt = config.combine(&[
+            (ModelType::IDEC, pred(10.0, 0.5)),
+            (ModelType::Transformer, pred(20.0, 1.0)),
+            (ModelType::GBDT, pred(-100.0, 1.0)),
+        ]).unwrap();
+        assert!((with_gbdt.edge_bps - 14.0).abs() < 1e-9);
+        
+        let err = config.combine(&[(ModelType::IDEC, pred(10.0, 0.5))]).unwrap_err();
+        assert!(err.to_string().contains("2 required"));
+    }
+    
+    #[test]
+    fn test_ensemble_disagreement_cuts_confidence() {
+        let pred = |edge_bps: f64| Prediction {
+            timestamp_ns: 0,
+            symbol: String::new(),
+            edge_bps,
+            confidence: 0.9,
+            horizon_ms: 5000,
+            model_version: "test".to_string(),
+        };
+        let config = EnsembleConfig { max_edge_std_bps: 5.0, ..EnsembleConfig::default() };
+        let min_confidence = crate::router::GateParams::default().min_confidence;
+        
+        // Agreeing models keep their confidence
+        let agree = config.combine(&[
+            (ModelType::IDEC, pred(12.0)),
+            (ModelType::Transformer, pred(14.0)),
+            (ModelType::GBDT, pred(16.0)),
+        ]).unwrap();
+        assert!((agree.confidence - 0.9).abs() < 1e-9);
+        
+        // Edges of +30/-10/-20: std ~21.6 bps, confidence 0.9 * 5 / 21.6 ~ 0.21
+        let split = [
+            (ModelType::IDEC, pred(30.0)),
+            (ModelType::Transformer, pred(-10.0)),
+            (ModelType::GBDT, pred(-20.0)),
+        ];
+        assert!((config.edge_std_bps(&split) - 21.602).abs() < 1e-3);
+        let disagree = config.combine(&split).unwrap();
+        assert!(disagree.confidence < min_confidence);
+    }
+    
+    #[test]
+    fn test_output_layout_validation() {
+        let out = |name: &str, dims: &[Option<u32>]| (name.to_string(), dims.to_vec());
+        
+        // Positional [batch, 2], batch axis dynamic
+        let packed = OutputLayout::resolve(ModelType::Edge, &[out("output", &[None, Some(2)])]).unwrap();
+        assert_eq!(packed, OutputLayout::Packed { output: 0 });
+        let row = ArrayD::from_shape_vec(vec![1, 2], vec![12.5, 0.7]).unwrap();
+        let (edge, confidence) = packed.read(&[row]).unwrap();
+        assert!((edge - 12.5).abs() < 1e-6 && (confidence - 0.7).abs() < 1e-6);
+        
+        // Named outputs in any order, e.g. a GBDT regressor plus a calibrator
+        let named = OutputLayout::resolve(
+            ModelType::GBDT,
+            &[out(CONFIDENCE_OUTPUT, &[None, Some(1)]), out(EDGE_OUTPUT, &[None])],
+        ).unwrap();
+        assert_eq!(named, OutputLayout::Named { edge: 1, confidence: 0 });
+        let arrays = [
+            ArrayD::from_shape_vec(vec![1, 1], vec![0.9]).unwrap(),
+            ArrayD::from_shape_vec(vec![1], vec![-3.0]).unwrap(),
+        ];
+        let (edge, confidence) = named.read(&arrays).unwrap();
+        assert!((edge + 3.0).abs() < 1e-6 && (confidence - 0.9).abs() < 1e-6);
+        
+        // Single-column output can't carry confidence
+        let err = OutputLayout::resolve(ModelType::GBDT, &[out("variable", &[None, Some(1)])]).unwrap_err();
+        assert!(err.to_string().contains("[?, 1]"));
+        
+        // A runtime shape smaller than declared errors instead of panicking
+        let short = ArrayD::from_shape_vec(vec![1, 1], vec![1.0]).unwrap();
+        assert!(matches!(packed.read(&[short]), Err(Error::Model(_))));
+        assert!(matches!(packed.read(&[]), Err(Error::Model(_))));
+    }
+    
+    #[test]
+    fn test_input_len_validation() {
+        let expected = expected_input_len(ModelType::Edge, &[None, Some(128)]).unwrap();
+        assert_eq!(expected, Some(128));
+        assert!(check_input_len(ModelType::Edge, expected, 128).is_ok());
+        
+        let err = check_input_len(ModelType::Edge, expected, 100).unwrap_err();
+        assert!(matches!(err, Error::Feature(_)));
+        assert!(err.to_string().contains("expects 128 features, got 100"));
+        
+        // Dynamic feature axis accepts any length; non-matrix inputs are refused at load
+        assert!(check_input_len(ModelType::Edge, None, 100).is_ok());
+        assert!(expected_input_len(ModelType::Edge, &[Some(1), Some(10), Some(128)]).is_err());
+    }
+    
+    #[test]
+    fn test_provider_falls_back_to_cpu() {
+        // No GPU: every device lands on CPU
+        for device in [DeviceType::CPU, DeviceType::CUDA(0), DeviceType::ROCm(1), DeviceType::TensorRT] {
+            assert_eq!(select_provider(device, |p| p == Provider::CPU), Provider::CPU);
+        }
+        assert!(Provider::CPU.is_available());
+        
+        // TensorRT missing but CUDA present: CUDA before CPU
+        let cuda_only = |p: Provider| matches!(p, Provider::CUDA(_) | Provider::CPU);
+        assert_eq!(select_provider(DeviceType::TensorRT, cuda_only), Provider::CUDA(0));
+        assert_eq!(select_provider(DeviceType::CUDA(2), cuda_only), Provider::CUDA(2));
+    }
+    
+    #[cfg(feature = "cuda")]
+    #[test]
+    fn test_cuda_provider_active() {
+        assert_eq!(select_provider(DeviceType::CUDA(0), Provider::is_available), Provider::CUDA(0));
+    }
+    
+    #[test]
+    fn test_warmup_requires_loaded_models() {
+        let pool = InferencePool::new(100).unwrap();
+        
+        let err = pool.warmup(AssetCategory::CryptoFutures, features::layout::NUM_FEATURES).unwrap_err();
+        assert!(err.to_string().contains("NOT loaded"));
+    }
+    
+    /// Model bundle whose four models are all `fixtures/linear.onnx`:
+    /// `output = features[N, 3] x [[1, 0], [0, 0], [0, 0.1]]`
+    fn linear_bundle(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("linear-models-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(contract::CONTRACT_FILE), contract::FEATURE_CONTRACT_VERSION.to_string()).unwrap();
+        for model in ["idec", "transformer", "gbdt", "edge"] {
+            std::fs::write(dir.join(format!("{}.onnx", model)), include_bytes!("../fixtures/linear.onnx")).unwrap();
+        }
+        dir
+    }
+    
+    #[tokio::test]
+    async fn test_batch_rows_match_single_reads() {
+        let rows = vec![
+            Array1::from(vec![1.0f32, 2.0, 3.0]),
+            Array1::from(vec![4.0f32, 5.0, 6.0]),
+            Array1::from(vec![7.0f32, 8.0, 9.0]),
+        ];
+        let input = stack_features(&rows).unwrap();
+        assert_eq!(input.dim(), (3, 3));
+        for (i, row) in rows.iter().enumerate() {
+            assert_eq!(input.row(i), row.view());
+        }
+        
+        // Fake model: edge = first feature, confidence = last / 10, per row
+        let single = |f: &Array1<f32>| ArrayD::from_shape_vec(vec![1, 2], vec![f[0], f[2] / 10.0]).unwrap();
+        let batched = ArrayD::from_shape_vec(
+            vec![3, 2],
+            rows.iter().flat_map(|f| [f[0], f[2] / 10.0]).collect(),
+        ).unwrap();
+        
+        let layout = OutputLayout::Packed { output: 0 };
+        for (i, row) in rows.iter().enumerate() {
+            let one = layout.read(&[single(row)]).unwrap();
+            assert_eq!(layout.read_row(&[batched.clone()], i).unwrap(), one);
+        }
+        assert!(layout.read_row(&[batched], 3).is_err());
+        
+        // Ragged batches are refused before they reach ORT
+        let ragged = vec![rows[0].clone(), Array1::from(vec![1.0f32])];
+        assert!(matches!(stack_features(&ragged), Err(Error::Feature(_))));
+        
+        // Through a real session: each batched row is what `predict` gives alone
+        let pool = InferencePool::new(1_000).unwrap();
+        pool.load_crypto(&linear_bundle("batch")).unwrap();
+        let category = AssetCategory::CryptoFutures;
+        
+        let batch = pool.predict_batch(category, &rows, ModelType::Edge).await.unwrap();
+        let ensemble = pool.predict_ensemble_batch(category, &rows).await.unwrap();
+        assert_eq!((batch.len(), ensemble.len()), (3, 3));
+        for (i, row) in rows.iter().enumerate() {
+            let single = pool.predict(category, row, ModelType::Edge).await.unwrap();
+            assert_eq!((batch[i].edge_bps, batch[i].confidence), (single.edge_bps, single.confidence));
+            assert!((single.edge_bps - row[0] as f64).abs() < 1e-6);
+            
+            let single = pool.predict_ensemble(category, row).await.unwrap();
+            assert_eq!((ensemble[i].edge_bps, ensemble[i].confidence), (single.edge_bps, single.confidence));
+        }
+        assert!(pool.predict_batch(category, &[], ModelType::Edge).await.unwrap().is_empty());
+    }
+    
     #[test]
     fn test_missing_models_fail() {
         let pool = InferencePool::new(100).unwrap();
@@ -291,4 +1104,55 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("NOT loaded"));
     }
+    
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_limiter_caps_concurrency() {
+        let limiter = InferenceLimiter::new("test", InferenceLimits { max_concurrency: 2, max_queue: 16 });
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        
+        let tasks: Vec<_> = (0..8)
+            .map(|i| {
+                let limiter = limiter.clone();
+                let (running, peak) = (running.clone(), peak.clone());
+                tokio::spawn(async move {
+                    limiter.run(move || {
+                        let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                        peak.fetch_max(now, Ordering::SeqCst);
+                        std::thread::sleep(std::time::Duration::from_millis(20));
+                        running.fetch_sub(1, Ordering::SeqCst);
+                        i
+                    }).await
+                })
+            })
+            .collect();
+        
+        let mut done = Vec::new();
+        for task in tasks {
+            done.push(task.await.unwrap().unwrap());
+        }
+        
+        assert_eq!(done, (0..8).collect::<Vec<_>>());
+        assert!(peak.load(Ordering::SeqCst) <= 2, "peak {}", peak.load(Ordering::SeqCst));
+        assert_eq!(limiter.queue_depth(), 0);
+    }
+    
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_limiter_rejects_when_queue_full() {
+        let limiter = InferenceLimiter::new("test", InferenceLimits { max_concurrency: 1, max_queue: 1 });
+        let slow = || std::thread::sleep(std::time::Duration::from_millis(200));
+        
+        let running = tokio::spawn({ let l = limiter.clone(); async move { l.run(slow).await } });
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        let queued = tokio::spawn({ let l = limiter.clone(); async move { l.run(slow).await } });
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert_eq!(limiter.queue_depth(), 1);
+        
+        assert!(matches!(limiter.run(|| ()).await, Err(Error::RateLimit(_))));
+        assert_eq!(limiter.queue_depth(), 1);
+        
+        running.await.unwrap().unwrap();
+        queued.await.unwrap().unwrap();
+        assert_eq!(limiter.queue_depth(), 0);
+    }
 }
\ No newline at end of file
@@ -6,17 +6,56 @@ pub mod router;
 pub mod ws_server;
 pub mod s3_writer;
 pub mod rl_agent;
+pub mod diagnostics;
+pub mod observation;
+pub mod registry;
+pub mod supervisor;
+pub mod control;
+pub mod execution;
+pub mod advanced_features;
+pub mod marking;
+pub mod rl_stats;
+pub mod cadence;
+pub mod feed;
+pub mod replay;
+pub mod slippage;
+pub mod holds;
+pub mod shutdown;
 
+use cadence::{CadenceConfig, DecisionScheduler};
 use common::*;
+use diagnostics::{DecisionStats, DiagnosticsBundle, HealthReport};
+use execution::{ParentOrders, SlicingConfig};
+use holds::HoldTracker;
 use features::{FeatureComputer, DeviceType};
-use inference::{InferencePool, ModelType};
-use router::{OrderRouter, GateParams, CostModel};
+use inference::{EnsembleConfig, InferenceLimits, InferencePool, ModelType};
+use marking::{BookMarks, MarkConfig};
+use observation::{Admission, ObservationTracker};
+use registry::SymbolRegistry;
+use replay::{DecisionLog, DecisionRecord};
+use shutdown::ShutdownConfig;
+use slippage::{SlippageConfig, SlippageTracker};
+use router::{OrderRouter, GateParams, CostModel, FeeSchedule};
 use rl_agent::{RLAgent, MarketState};
+use rl_stats::ValueTracker;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::{mpsc, watch};
+use tokio::sync::{broadcast, mpsc, watch};
 use parking_lot::RwLock;
 
+/// Dead-man's switch window requested from venues that support it
+const CANCEL_ON_DISCONNECT_DEADLINE_MS: u64 = 60_000;
+
+/// How often account fee tiers are re-fetched from the venues
+const FEE_REFRESH_INTERVAL_S: u64 = 3_600;
+
+/// Decisions buffered per `/decisions` subscriber before it starts lagging
+const DECISION_FEED_CAPACITY: usize = 1_024;
+
+const RL_MODEL_DIR: &str = "models/rl";
+const RL_ACTOR_PATH: &str = "models/rl/actor.onnx";
+const RL_CRITIC_PATH: &str = "models/rl/critic.onnx";
+
 /// Trading engine with MANDATORY RL agent and ML models
 pub struct TradingEngine {
     config: Arc<RwLock<EngineConfig>>,
@@ -39,21 +78,119 @@ pub struct TradingEngine {
     // Channels
     snapshot_tx: mpsc::UnboundedSender<MarketSnapshot>,
     metrics_tx: watch::Sender<PerformanceMetrics>,
+    risk_tx: watch::Sender<RiskSnapshot>,
+    alerts: Arc<RwLock<Option<Arc<ws_server::AlertPublisher>>>>,
+    training_export: Arc<RwLock<Option<Arc<advanced_features::AdvancedFeaturesManager>>>>,
+    
+    // Diagnostics
+    last_update: Arc<RwLock<HashMap<String, i64>>>,
+    decision_stats: Arc<RwLock<DecisionStats>>,
+    
+    // RL critic values paired with realized rewards
+    rl_values: Arc<RwLock<ValueTracker>>,
+    
+    // Observe-only grace period for newly added symbols
+    observation: Arc<RwLock<ObservationTracker>>,
+    
+    // Tracked symbol → venue / asset category
+    symbols: Arc<SymbolRegistry>,
+    
+    // Worked (sliced) parent orders and their children
+    parent_orders: Arc<RwLock<ParentOrders>>,
+    
+    // Latest book reference prices for mark-to-market
+    marks: Arc<RwLock<HashMap<String, BookMarks>>>,
+    
+    // ML decisions with their inputs, for replay
+    decision_log: Option<Arc<DecisionLog>>,
+    
+    // Live feed of the same records, for the blotter
+    decision_tx: broadcast::Sender<DecisionRecord>,
+    
+    // Realized slippage per symbol, feeding the cost model
+    slippage: Arc<RwLock<SlippageTracker>>,
+    
+    // Opened positions and when their intended hold runs out
+    holds: Arc<RwLock<HoldTracker>>,
+    
+    // Account fee tiers per venue, feeding the cost model
+    fees: Arc<RwLock<FeeSchedule>>,
 }
 
+/// Engine settings; see `Default` for the value each falls back to
 #[derive(Debug, Clone)]
 pub struct EngineConfig {
     pub mode: TradingMode,
+    /// Snapshots per feature batch; a full batch flushes immediately
     pub batch_size: usize,
+    /// Flush a partial batch after this long
     pub batch_timeout_ms: u64,
+    /// Rolling window per symbol, in snapshots
+    pub feature_window_size: usize,
     pub inference_timeout_ms: u64,
+    /// Per-model cap on concurrent ONNX runs
+    pub inference_limits: InferenceLimits,
+    /// Model weights for `DecisionMode::MLEnsemble`
+    pub ensemble: EnsembleConfig,
     pub gate_params: GateParams,
     pub gpu_device: DeviceType,
+    /// Per-batch GPU deadline in ms; past it `GPUFirst` computes on CPU
+    /// and `GPUOnly` skips the batch (unset waits indefinitely)
+    pub gpu_timeout_ms: Option<u64>,
     pub decision_mode: DecisionMode,
+    /// Seconds a symbol is observed after `add_symbol` before it may trade
+    /// (0 = off); this includes the symbols added at startup
+    pub observe_grace_s: u64,
+    /// When large orders are sliced into child orders
+    pub slicing: SlicingConfig,
+    /// Which price positions are marked at
+    pub marking: MarkConfig,
+    /// Fixed decision intervals; symbols without one decide on every flush
+    pub cadence: CadenceConfig,
+    /// JSON-lines file of ML decisions and their inputs (off when `None`)
+    pub decision_log: Option<String>,
+    /// How realized slippage adapts the cost model's buffer
+    pub slippage: SlippageConfig,
+    /// Seconds to wait for the first market snapshot before failing the run
+    pub feed_grace_s: u64,
+    /// Snapshots buffered ahead of the batching loop; past this, superseded
+    /// books are dropped
+    pub snapshot_queue_capacity: usize,
+    /// Cancels (and optional closes) on the way down
+    pub shutdown: ShutdownConfig,
+}
+
+impl Default for EngineConfig {
+    /// Paper trading on CPU with ML routing: the mode that needs the least
+    /// hardware, and no models beyond the ML ones
+    fn default() -> Self {
+        Self {
+            mode: TradingMode::Paper,
+            batch_size: 32,
+            batch_timeout_ms: 10,
+            feature_window_size: features::cpu::DEFAULT_WINDOW_SIZE,
+            inference_timeout_ms: 3,
+            inference_limits: InferenceLimits::default(),
+            ensemble: EnsembleConfig::default(),
+            gate_params: GateParams::default(),
+            gpu_device: DeviceType::CPU,
+            gpu_timeout_ms: None,
+            decision_mode: DecisionMode::MLTraditional,
+            observe_grace_s: 0,
+            slicing: SlicingConfig::default(),
+            marking: MarkConfig::default(),
+            cadence: CadenceConfig::default(),
+            decision_log: None,
+            slippage: SlippageConfig::default(),
+            feed_grace_s: feed::DEFAULT_GRACE_S,
+            snapshot_queue_capacity: feed::DEFAULT_QUEUE_CAPACITY,
+            shutdown: ShutdownConfig::default(),
+        }
+    }
 }
 
 /// Decision mode - BOTH are mandatory, choose which to use
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
 pub enum DecisionMode {
     /// Use RL agent for decisions (REQUIRES: actor.onnx, critic.onnx)
     RLAgent,
@@ -63,6 +200,9 @@ pub enum DecisionMode {
     
     /// Use both: RL for decision, ML for validation (REQUIRES: All models)
     Hybrid,
+    
+    /// ML routing on the weighted IDEC/Transformer/GBDT ensemble instead of the edge model
+    MLEnsemble,
 }
 
 impl TradingEngine {
@@ -70,22 +210,12 @@ impl TradingEngine {
     pub fn new(config: EngineConfig, risk_limits: RiskLimits) -> Result<Self> {
         tracing::info!("🚀 Initializing HFT Engine (MANDATORY models mode)");
         
-        // 1. Initialize GPU feature computer (mandatory)
-        let feature_computer = Arc::new(
-            FeatureComputer::new(config.gpu_device, config.batch_size)
-                .map_err(|e| Error::Internal(format!("GPU init FAILED: {}. This is REQUIRED.", e)))?
-        );
-        tracing::info!("✅ GPU feature computer initialized");
-        
-        // 2. Initialize ML inference pool (MANDATORY)
-        let inference_pool = Arc::new(InferencePool::new(config.inference_timeout_ms)?);
-        tracing::info!("✅ ML inference pool initialized");
-        
-        // 3. Initialize RL agent (MANDATORY)
+        // Initialize RL agent (MANDATORY)
+        common::contract::verify_contract(std::path::Path::new(RL_MODEL_DIR))?;
         let rl_agent = Arc::new(
             RLAgent::new(
-                "models/rl/actor.onnx",
-                Some("models/rl/critic.onnx"),
+                RL_ACTOR_PATH,
+                Some(RL_CRITIC_PATH),
                 rl_agent::RLAgentConfig {
                     action_type: rl_agent::ActionType::MultiDiscrete,
                     sequence_length: 10,
@@ -100,11 +230,44 @@ impl TradingEngine {
         );
         tracing::info!("✅ RL Agent initialized");
         
-        // 4. Initialize router (for risk checks only)
+        Self::with_rl_agent(config, risk_limits, rl_agent)
+    }
+    
+    /// `new` around an already loaded RL agent instead of the one under `models/rl`
+    pub fn with_rl_agent(config: EngineConfig, risk_limits: RiskLimits, rl_agent: Arc<RLAgent>) -> Result<Self> {
+        risk_limits.validate()?;
+        
+        // 1. Initialize GPU feature computer (mandatory)
+        let mut feature_computer = FeatureComputer::new(config.gpu_device, config.batch_size)
+            .map_err(|e| Error::Internal(format!("GPU init FAILED: {}. This is REQUIRED.", e)))?;
+        if let Some(ms) = config.gpu_timeout_ms {
+            feature_computer = feature_computer.with_gpu_timeout(std::time::Duration::from_millis(ms));
+        }
+        let feature_computer = Arc::new(feature_computer);
+        tracing::info!("✅ GPU feature computer initialized");
+        
+        // 2. Initialize ML inference pool (MANDATORY)
+        let inference_pool = Arc::new(
+            InferencePool::new(config.inference_timeout_ms)?
+                .with_limits(config.inference_limits)
+                .with_ensemble(config.ensemble)
+                .with_device(config.gpu_device),
+        );
+        tracing::info!("✅ ML inference pool initialized");
+        
+        // 3. Initialize router (for risk checks only)
         let router = Arc::new(OrderRouter::new(config.gate_params.clone(), risk_limits));
         
+        let observation = ObservationTracker::new(config.observe_grace_s);
+        let decision_log = config.decision_log.as_deref().map(DecisionLog::open).transpose()?.map(Arc::new);
+        let slippage = SlippageTracker::new(config.slippage.clone());
+        
         let (snapshot_tx, _) = mpsc::unbounded_channel();
+        // Receivers come from `subscribe_*`; publishing uses `send_replace`
+        // so values land even while nobody is subscribed
         let (metrics_tx, _) = watch::channel(PerformanceMetrics::default());
+        let (risk_tx, _) = watch::channel(RiskSnapshot::default());
+        let (decision_tx, _) = broadcast::channel(DECISION_FEED_CAPACITY);
         
         tracing::info!("✅ Trading engine initialized successfully");
         tracing::info!("⚠️  Decision mode: {:?}", config.decision_mode);
@@ -118,6 +281,21 @@ impl TradingEngine {
             adapters: Arc::new(RwLock::new(HashMap::new())),
             snapshot_tx,
             metrics_tx,
+            risk_tx,
+            alerts: Arc::new(RwLock::new(None)),
+            training_export: Arc::new(RwLock::new(None)),
+            last_update: Arc::new(RwLock::new(HashMap::new())),
+            decision_stats: Arc::new(RwLock::new(DecisionStats::default())),
+            rl_values: Arc::new(RwLock::new(ValueTracker::default())),
+            observation: Arc::new(RwLock::new(observation)),
+            symbols: Arc::new(SymbolRegistry::new()),
+            parent_orders: Arc::new(RwLock::new(ParentOrders::default())),
+            marks: Arc::new(RwLock::new(HashMap::new())),
+            decision_log,
+            decision_tx,
+            slippage: Arc::new(RwLock::new(slippage)),
+            holds: Arc::new(RwLock::new(HoldTracker::default())),
+            fees: Arc::new(RwLock::new(FeeSchedule::default())),
         })
     }
     
@@ -159,6 +337,11 @@ impl TradingEngine {
         // Verify models are actually loaded
         self.verify_models_loaded()?;
         
+        // Pay the first-run cost now rather than on the first live signal
+        for category in [AssetCategory::CryptoFutures, AssetCategory::Equity] {
+            self.inference_pool.warmup(category, features::layout::NUM_FEATURES)?;
+        }
+        
         Ok(())
     }
     
@@ -172,17 +355,17 @@ impl TradingEngine {
                 tracing::info!("✅ RL Agent models verified");
             }
             
-            DecisionMode::MLTraditional => {
+            DecisionMode::MLTraditional | DecisionMode::MLEnsemble => {
                 // Verify ML models loaded
                 if !self.inference_pool.has_crypto_models() {
-                    return Err(Error::Internal(
-                        "Crypto ML models NOT loaded. Required for MLTraditional mode.".to_string()
-                    ));
+                    return Err(Error::Internal(format!(
+                        "Crypto ML models NOT loaded. Required for {:?} mode.", config.decision_mode
+                    )));
                 }
                 if !self.inference_pool.has_equity_models() {
-                    return Err(Error::Internal(
-                        "Equity ML models NOT loaded. Required for MLTraditional mode.".to_string()
-                    ));
+                    return Err(Error::Internal(format!(
+                        "Equity ML models NOT loaded. Required for {:?} mode.", config.decision_mode
+                    )));
                 }
                 tracing::info!("✅ ML models verified");
             }
@@ -203,27 +386,136 @@ impl TradingEngine {
     
     /// Add exchange adapter
     pub fn add_adapter(&self, label: String, adapter: Arc<dyn adapters::ExchangeAdapter>) {
+        // Resting orders must not outlive the market-data connection
+        let hook = adapters::CancelOnDisconnect::new(
+            Arc::downgrade(&adapter),
+            CANCEL_ON_DISCONNECT_DEADLINE_MS,
+        );
+        adapter.set_disconnect_hook(Arc::new(hook));
+        
         self.adapters.write().insert(label, adapter);
     }
     
-    /// Add symbol to track
-    pub fn add_symbol(&self, symbol: String, window_size: usize) {
+    /// Autocomplete `prefix` against a connected adapter for `venue`
+    pub async fn search_symbols(&self, venue: Venue, prefix: &str) -> Result<Vec<String>> {
+        let adapter = self.adapters
+            .read()
+            .values()
+            .find(|a| a.venue() == venue && a.is_connected())
+            .cloned()
+            .ok_or_else(|| Error::NotFound(format!("{:?} is not connected", venue)))?;
+        
+        adapter.search_symbols(prefix).await
+    }
+    
+    /// Fetch a tracked symbol's tick/lot spec from its venue and register it
+    pub async fn load_symbol_spec(&self, symbol: &str) -> Result<()> {
+        let venue = self.symbols.venue(symbol)?;
+        let adapter = self.adapters
+            .read()
+            .values()
+            .find(|a| a.venue() == venue)
+            .cloned()
+            .ok_or_else(|| Error::NotFound(format!("No {:?} adapter for {}", venue, symbol)))?;
+        
+        let spec = adapter.symbol_spec(symbol).await?;
+        
+        tracing::info!("{} spec: tick {} lot {}", symbol, spec.tick_size, spec.lot_size);
+        self.set_symbol_spec(spec);
+        Ok(())
+    }
+    
+    /// Add symbol to track on `venue`, with a rolling window of
+    /// `EngineConfig::feature_window_size` snapshots
+    pub fn add_symbol(&self, symbol: String, venue: Venue) {
+        let window_size = self.config.read().feature_window_size;
+        let info = self.symbols.register(symbol.clone(), venue);
+        self.feature_computer.set_symbol_category(symbol.clone(), info.category);
+        
+        let now_ns = chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0);
+        self.observation.write().on_join(&symbol, now_ns);
         self.feature_computer.add_symbol(symbol, window_size);
     }
     
+    /// Set tick/lot spec used for tick-normalized features
+    pub fn set_symbol_spec(&self, spec: SymbolSpec) {
+        self.feature_computer.set_symbol_spec(spec);
+    }
+    
+    /// Assign a symbol's asset category for feature-compute dispatch
+    pub fn set_symbol_category(&self, symbol: String, category: AssetCategory) {
+        self.feature_computer.set_symbol_category(symbol, category);
+    }
+    
+    /// Select the feature-compute mode for an asset category
+    pub fn set_category_compute_mode(
+        &self,
+        category: AssetCategory,
+        mode: features::ComputeMode,
+    ) -> Result<()> {
+        self.feature_computer.set_category_mode(category, mode)
+    }
+    
+    /// Default feature-compute mode, e.g. force CPU during a GPU fault
+    pub fn set_compute_mode(&self, mode: features::ComputeMode) -> Result<()> {
+        self.feature_computer.set_mode(mode)
+    }
+    
+    /// Default feature-compute mode in effect (after any GPU demotion)
+    pub fn compute_mode(&self) -> features::ComputeMode {
+        self.feature_computer.current_mode()
+    }
+    
     /// Main trading loop with batching
     pub async fn run(&self, mut shutdown: watch::Receiver<bool>) -> Result<()> {
         tracing::info!("🎯 Trading loop starting (MANDATORY models mode)");
         
-        let (market_tx, mut market_rx) = mpsc::unbounded_channel::<MarketSnapshot>();
+        let (market_tx, market_rx) = feed::snapshot_queue(self.config.read().snapshot_queue_capacity);
+        
+        // Every adapter's snapshot stream feeds the batching loop
+        let feeds: Vec<_> = self.adapters.read()
+            .values()
+            .filter_map(|adapter| match adapter.snapshot_receiver() {
+                Ok(rx) => Some((adapter.venue(), rx)),
+                Err(e) => {
+                    tracing::warn!("{:?}: no snapshot feed: {}", adapter.venue(), e);
+                    None
+                }
+            })
+            .collect();
+        let feed_grace = std::time::Duration::from_secs(self.config.read().feed_grace_s);
+        let mut feed_handle = tokio::spawn(feed::forward_snapshots(
+            feeds,
+            self.feature_computer.clone(),
+            market_tx,
+            feed_grace,
+        ));
         
         let engine_clone = self.clone_for_processing();
         let config = self.config.read().clone();
         
-        let processing_handle = tokio::spawn(async move {
+        let mut processing_handle = tokio::spawn(async move {
             engine_clone.process_with_batching(market_rx, config).await
         });
         
+        let hold_engine = self.clone_for_processing();
+        let hold_handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_millis(holds::SWEEP_INTERVAL_MS));
+            loop {
+                interval.tick().await;
+                hold_engine.sweep_holds().await;
+            }
+        });
+        
+        let fee_engine = self.clone_for_processing();
+        let fee_handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(FEE_REFRESH_INTERVAL_S));
+            loop {
+                interval.tick().await;
+                fee_engine.refresh_fees().await;
+            }
+        });
+
         loop {
             tokio::select! {
                 _ = shutdown.changed() => {
@@ -232,13 +524,295 @@ impl TradingEngine {
                         break;
                     }
                 }
+                // Surface a dead processing loop to the supervisor instead of idling
+                res = &mut processing_handle => {
+                    return Err(Error::Internal(match res {
+                        Ok(()) => "processing loop exited unexpectedly".to_string(),
+                        Err(e) => format!("processing loop died: {}", e),
+                    }));
+                }
+                res = &mut feed_handle => {
+                    processing_handle.abort();
+                    hold_handle.abort();
+                    fee_handle.abort();
+                    return match res {
+                        Ok(Ok(())) => Err(Error::Internal("market data feed exited unexpectedly".to_string())),
+                        Ok(Err(e)) => Err(e),
+                        Err(e) => Err(Error::Internal(format!("market data feed died: {}", e))),
+                    };
+                }
             }
         }
         
+        feed_handle.abort();
         processing_handle.abort();
+        hold_handle.abort();
+        fee_handle.abort();
         Ok(())
     }
     
+    /// Fetch every adapter's account fee tier. Venues that fail keep their
+    /// last tier, or the fallback if none was ever fetched. Returns the
+    /// number of venues refreshed.
+    pub async fn refresh_fees(&self) -> usize {
+        let adapters: Vec<_> = self.adapters.read().values().cloned().collect();
+        let mut refreshed = 0;
+        
+        for adapter in adapters {
+            let venue = adapter.venue();
+            match adapter.fee_tier().await {
+                Ok(tier) => {
+                    tracing::debug!(
+                        "{:?} fees: maker {:.2} bps, taker {:.2} bps",
+                        venue, tier.maker_fee_bps, tier.taker_fee_bps
+                    );
+                    self.fees.write().update(venue, tier);
+                    refreshed += 1;
+                }
+                Err(e) if self.fees.read().is_known(venue) => {
+                    tracing::warn!("{:?}: fee tier fetch failed: {}; keeping last known fees", venue, e);
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "{:?}: fee tier fetch failed: {}; using default fees {:?}",
+                        venue, e, router::FALLBACK_FEES
+                    );
+                }
+            }
+        }
+        
+        refreshed
+    }
+    
+    /// Send reduce-only closes for every hold past its intended duration.
+    /// Returns the number of closes sent.
+    pub async fn sweep_holds(&self) -> usize {
+        if self.config.read().mode != TradingMode::Live {
+            return 0;
+        }
+        
+        let now_ns = chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0);
+        let exits = self.holds.write().expired(now_ns);
+        let mut sent = 0;
+        
+        for (symbol, decision) in exits {
+            let Some(mid) = self.marks.read().get(&symbol).map(|m| m.mid) else {
+                tracing::warn!("No mark for {}; hold exit deferred", symbol);
+                self.holds.write().on_close_failed(&symbol);
+                continue;
+            };
+            
+            // `execute_trade` closes on the adapter for the hold's venue
+            match self.execute_trade(&symbol, &decision, mid, &[]).await {
+                Ok(()) => sent += 1,
+                Err(e) => {
+                    tracing::error!("❌ Hold exit FAILED for {}: {}", symbol, e);
+                    self.holds.write().on_close_failed(&symbol);
+                }
+            }
+        }
+        
+        sent
+    }
+    
+    /// Stop trading and clear the venues: cancel resting orders on every
+    /// tracked symbol and, when configured, reduce-only close the holds the
+    /// engine opened. Each venue ack is waited for up to the configured
+    /// timeout. The decision log is flushed last. Call once `run` has returned.
+    pub async fn shutdown(&self) {
+        let (was_live, config) = {
+            let mut config = self.config.write();
+            let was_live = config.mode == TradingMode::Live;
+            config.mode = TradingMode::Paused;
+            (was_live, config.shutdown.clone())
+        };
+        let timeout = std::time::Duration::from_millis(config.ack_timeout_ms);
+        let adapters: Vec<_> = self.adapters.read().values().cloned().collect();
+        
+        for adapter in &adapters {
+            let venue = adapter.venue();
+            let symbols = self.symbols.symbols_on(venue);
+            let unconfirmed = shutdown::cancel_open_orders(adapter.as_ref(), venue, &symbols, timeout).await;
+            
+            if unconfirmed.is_empty() {
+                tracing::info!("{:?}: cancelled resting orders on {} symbols", venue, symbols.len());
+            } else {
+                tracing::error!("{:?}: orders may still rest on {:?}", venue, unconfirmed);
+                metrics::increment_counter!("shutdown_cancel_unconfirmed", "venue" => format!("{:?}", venue));
+            }
+        }
+        
+        if config.close_positions && was_live {
+            self.close_holds(timeout).await;
+        }
+        
+        // Everything decided so far reaches the disk
+        if let Some(log) = &self.decision_log {
+            log.shutdown().await;
+        }
+    }
+    
+    /// Exit every tracked hold with one reduce-only IOC market order on the
+    /// hold's venue, never sliced, each bounded by `timeout`
+    async fn close_holds(&self, timeout: std::time::Duration) {
+        let exits = self.holds.write().close_all("Shutdown");
+        let adapters: Vec<_> = self.adapters.read().values().cloned().collect();
+        
+        for (symbol, decision) in exits {
+            let Some(mid) = self.marks.read().get(&symbol).map(|m| m.mid) else {
+                tracing::error!("No mark for {}; position left open", symbol);
+                continue;
+            };
+            let Some(adapter) = self.symbols.venue(&symbol).ok()
+                .and_then(|venue| adapters.iter().find(|a| a.venue() == venue))
+            else {
+                tracing::error!("No adapter for {}'s venue; position left open", symbol);
+                continue;
+            };
+            
+            let order = adapter.venue()
+                .nearest_tif(TimeInForce::IOC)
+                .and_then(|tif| order_request(&symbol, &decision, mid, tif));
+            let mut order = match order {
+                Ok(order) => order,
+                Err(e) => {
+                    tracing::error!("❌ Shutdown close FAILED for {}: {}", symbol, e);
+                    continue;
+                }
+            };
+            self.router.get_risk_manager().read().finalize_order(&mut order);
+            if order.quantity <= 0.0 {
+                tracing::info!("Nothing left to close for {}", symbol);
+                continue;
+            }
+            
+            match tokio::time::timeout(timeout, adapter.send_order(order)).await {
+                Ok(Ok(ack)) => {
+                    tracing::info!("Closed {} on shutdown: {:?}", symbol, ack.status);
+                    let now_ns = chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0);
+                    self.holds.write().on_closed(&symbol, now_ns);
+                }
+                Ok(Err(e)) => tracing::error!("❌ Shutdown close FAILED for {}: {}", symbol, e),
+                Err(_) => tracing::error!("❌ Shutdown close for {} not acked within {:?}", symbol, timeout),
+            }
+        }
+    }
+    
+    /// Mark to market every `marking.interval_ms` until shutdown
+    pub async fn run_marking(&self, mut shutdown: watch::Receiver<bool>) -> Result<()> {
+        let period = std::time::Duration::from_millis(self.config.read().marking.interval_ms.max(100));
+        let mut interval = tokio::time::interval(period);
+        
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    self.mark_to_market().await;
+                }
+                _ = shutdown.changed() => {
+                    if *shutdown.borrow() {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+    
+    /// Re-fetch positions from every adapter, mark them at the configured
+    /// source, size their maintenance margin from the venue's leverage tiers
+    /// and hand them to the risk manager along with account equity. Venue
+    /// initial margin and liquidation prices are kept. The PnL change since
+    /// the last cycle goes to the daily loss limit and kill switch. Returns
+    /// the number of positions marked.
+    pub async fn mark_to_market(&self) -> usize {
+        let marking = self.config.read().marking.clone();
+        let adapters: Vec<_> = self.adapters.read().values().cloned().collect();
+        let risk = self.router.get_risk_manager();
+        let mut marked = 0;
+        let mut equity = None;
+        let mut pnl_change = 0.0;
+        
+        for adapter in adapters {
+            let venue = adapter.venue();
+            match adapter.balances().await {
+                Ok(balances) => {
+                    let risk = risk.read();
+                    let total: f64 = balances.values()
+                        .filter_map(|b| match risk.asset_to_base(&b.asset, b.total) {
+                            Ok(value) => Some(value),
+                            Err(e) => {
+                                tracing::warn!("{:?}: {} balance left out of equity: {}", venue, b.asset, e);
+                                None
+                            }
+                        })
+                        .sum();
+                    *equity.get_or_insert(0.0) += total;
+                }
+                Err(e) => tracing::warn!("{:?}: balance fetch for equity failed: {}", venue, e),
+            }
+
+            let positions: Vec<Position> = match adapter.positions().await {
+                Ok(positions) => positions.into_iter().filter(|p| p.size != 0.0).collect(),
+                Err(e) => {
+                    tracing::warn!("{:?}: position fetch for MTM failed: {}", venue, e);
+                    continue;
+                }
+            };
+            
+            // A flat or no longer reported position on this venue is closed
+            {
+                let mut risk = risk.write();
+                for symbol in risk.position_symbols() {
+                    let held_here = self.symbols.venue(&symbol).is_ok_and(|v| v == venue);
+                    if held_here && !positions.iter().any(|p| p.symbol == symbol) {
+                        risk.remove_position(&symbol);
+                    }
+                }
+            }
+            
+            for mut position in positions {
+                let category = self.symbols.category(&position.symbol).unwrap_or(match venue {
+                    Venue::IBKR => AssetCategory::Equity,
+                    _ => AssetCategory::CryptoFutures,
+                });
+                let source = marking.source(venue, category);
+                let book = self.marks.read().get(&position.symbol).copied();
+                
+                match marking::mark_price(source, book.as_ref(), Some(position.mark_price)) {
+                    Some(mark) => {
+                        marking::apply_mark(&mut position, mark);
+                        marked += 1;
+                    }
+                    None => tracing::debug!("No {:?} mark for {}, keeping venue values", source, position.symbol),
+                }
+                
+                match adapter.leverage_tiers(&position.symbol).await {
+                    Ok(tiers) => marking::apply_margin(&mut position, &tiers),
+                    Err(e) => tracing::debug!("No leverage tiers for {}: {}, maintenance margin unknown", position.symbol, e),
+                }
+                pnl_change += risk.read().pnl_change(&position);
+                risk.write().update_position(position);
+            }
+        }
+        
+        // Keep the last known equity if no venue reported balances
+        if let Some(equity) = equity {
+            risk.write().update_equity(equity);
+        }
+        
+        // Book the cycle's PnL change; a zero change still rolls the day over
+        let tripped = risk.write().update_pnl(pnl_change);
+        if tripped {
+            let message = format!(
+                "Daily loss {:.2} {} breached the limit; kill switch activated, manual reset required",
+                risk.read().daily_pnl(), risk.read().base_currency()
+            );
+            self.publish_alert(AlertLevel::Critical, "risk", message).await;
+        }
+        self.risk_tx.send_replace(risk.read().snapshot());
+        
+        marked
+    }
+    
     fn clone_for_processing(&self) -> Self {
         Self {
             config: self.config.clone(),
@@ -249,21 +823,77 @@ impl TradingEngine {
             adapters: self.adapters.clone(),
             snapshot_tx: self.snapshot_tx.clone(),
             metrics_tx: self.metrics_tx.clone(),
+            risk_tx: self.risk_tx.clone(),
+            alerts: self.alerts.clone(),
+            training_export: self.training_export.clone(),
+            last_update: self.last_update.clone(),
+            decision_stats: self.decision_stats.clone(),
+            rl_values: self.rl_values.clone(),
+            observation: self.observation.clone(),
+            symbols: self.symbols.clone(),
+            parent_orders: self.parent_orders.clone(),
+            marks: self.marks.clone(),
+            decision_log: self.decision_log.clone(),
+            decision_tx: self.decision_tx.clone(),
+            slippage: self.slippage.clone(),
+            holds: self.holds.clone(),
+            fees: self.fees.clone(),
         }
     }
     
     /// Process market data with batching for GPU efficiency
     async fn process_with_batching(
         &self,
-        mut market_rx: mpsc::UnboundedReceiver<MarketSnapshot>,
+        mut market_rx: feed::SnapshotReceiver,
         config: EngineConfig,
     ) {
-        let mut batch = Vec::with_capacity(config.batch_size);
+        let mut batch = feed::SnapshotBatch::with_capacity(config.batch_size);
         let mut last_flush = std::time::Instant::now();
         let mut perf = PerformanceMetrics::default();
         
-        while let Some(snapshot) = market_rx.recv().await {
-            batch.push(snapshot);
+        let mut scheduler = DecisionScheduler::default();
+        let mut cadence_tick = config.cadence.tick_ms()
+            .map(|ms| tokio::time::interval(std::time::Duration::from_millis(ms)));
+        
+        loop {
+            let snapshot = tokio::select! {
+                snapshot = market_rx.recv() => match snapshot {
+                    Some(snapshot) => snapshot,
+                    None => break,
+                },
+                _ = async {
+                    match cadence_tick.as_mut() {
+                        Some(tick) => tick.tick().await,
+                        None => std::future::pending().await,
+                    }
+                } => {
+                    let now_ns = chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0);
+                    let due = scheduler.due(now_ns);
+                    if !due.is_empty() {
+                        self.process_signals(due, &mut perf).await;
+                    }
+                    continue;
+                }
+            };
+            
+            let now_ns = chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0);
+            self.last_update.write().insert(snapshot.symbol.clone(), now_ns);
+            if let Some(marks) = BookMarks::from_book(&snapshot.orderbook) {
+                self.marks.write().insert(snapshot.symbol.clone(), marks);
+            }
+            
+            // Explicit pairs double as reference rates for base-currency risk
+            if common::currency::split_pair(&snapshot.symbol).is_some() {
+                if let Some(mid) = snapshot.orderbook.mid_price() {
+                    self.router.get_risk_manager().write().observe_price(&snapshot.symbol, mid);
+                }
+            }
+            
+            // Only the newest book per symbol is worth computing features for
+            let symbol = snapshot.symbol.clone();
+            if batch.push(snapshot) {
+                metrics::increment_counter!("market_snapshots_coalesced", "symbol" => symbol);
+            }
             
             let should_flush = batch.len() >= config.batch_size
                 || last_flush.elapsed().as_millis() >= config.batch_timeout_ms as u128;
@@ -273,7 +903,11 @@ impl TradingEngine {
                 
                 // STEP 1: GPU Feature Computation (MANDATORY - no fallback)
                 let feature_start = std::time::Instant::now();
-                let features = match self.feature_computer.compute_batch(&batch) {
+                let features = self.feature_computer.compute_batch(batch.snapshots());
+                if let Some(message) = self.feature_computer.take_health_alert() {
+                    self.publish_alert(AlertLevel::Warning, "features", message).await;
+                }
+                let features = match features {
                     Ok(f) => f,
                     Err(e) => {
                         tracing::error!("❌ GPU feature computation FAILED: {}", e);
@@ -283,23 +917,41 @@ impl TradingEngine {
                         continue; // Skip this batch
                     }
                 };
+                
+                // Never pair a symbol with another symbol's features
+                if let Err(e) = features::verify_alignment(batch.snapshots(), &features) {
+                    tracing::error!("❌ Dropping feature batch: {}", e);
+                    metrics::increment_counter!("engine_feature_batch_misaligned");
+                    batch.clear();
+                    continue;
+                }
                 perf.feature_p99_us = feature_start.elapsed().as_micros() as f64;
                 
-                // STEP 2: Process each signal with MANDATORY models
+                // STEP 2: Process each signal with MANDATORY models; timed
+                // symbols only refresh their latest features here
                 let inference_start = std::time::Instant::now();
-                for computed in features {
-                    if let Err(e) = self.process_signal_mandatory(&computed, &mut perf).await {
-                        tracing::error!("❌ Signal processing FAILED for {}: {}", computed.symbol, e);
-                        metrics::increment_counter!("signal_processing_error", 
-                            "symbol" => computed.symbol.clone()
-                        );
-                    }
-                }
+                let now_ns = chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0);
+                let immediate: Vec<_> = features
+                    .into_iter()
+                    .filter_map(|computed| {
+                        let category = self.symbols.category(&computed.symbol).ok();
+                        match config.cadence.interval_ms(&computed.symbol, category) {
+                            Some(interval_ms) => {
+                                scheduler.update(computed, interval_ms, now_ns);
+                                None
+                            }
+                            None => Some(computed),
+                        }
+                    })
+                    .collect();
+                self.process_signals(immediate, &mut perf).await;
                 perf.model_p99_us = inference_start.elapsed().as_micros() as f64;
                 
                 // Update metrics
                 perf.snapshots_per_sec = batch.len() as f64 / cycle_start.elapsed().as_secs_f64();
-                let _ = self.metrics_tx.send(perf.clone());
+                perf.dropped_frames = market_rx.dropped();
+                self.metrics_tx.send_replace(perf.clone());
+                self.risk_tx.send_replace(self.router.get_risk_manager().read().snapshot());
                 
                 batch.clear();
                 last_flush = std::time::Instant::now();
@@ -314,50 +966,155 @@ impl TradingEngine {
         }
     }
     
-    /// Process signal with MANDATORY models (no fallbacks)
+    async fn process_signals(&self, features: Vec<features::ComputedFeatures>, perf: &mut PerformanceMetrics) {
+        let predictions = self.predict_signals(&features, perf).await;
+        for (computed, prediction) in features.into_iter().zip(predictions) {
+            if let Err(e) = self.process_signal_mandatory(&computed, prediction, perf).await {
+                tracing::error!("❌ Signal processing FAILED for {}: {}", computed.symbol, e);
+                metrics::increment_counter!("signal_processing_error", 
+                    "symbol" => computed.symbol.clone()
+                );
+            }
+        }
+    }
+    
+    /// ML predictions for a batch of signals, one batched run per asset
+    /// category. Signals left `None` (RL mode, or a failed batch) predict on
+    /// their own in `decide_with_ml_mandatory`.
+    async fn predict_signals(
+        &self,
+        signals: &[features::ComputedFeatures],
+        perf: &mut PerformanceMetrics,
+    ) -> Vec<Option<Prediction>> {
+        let mut predictions = vec![None; signals.len()];
+        let ensemble = {
+            let config = self.config.read();
+            if config.mode == TradingMode::Paused {
+                return predictions;
+            }
+            match config.decision_mode {
+                DecisionMode::RLAgent => return predictions,
+                DecisionMode::MLEnsemble => true,
+                DecisionMode::MLTraditional | DecisionMode::Hybrid => false,
+            }
+        };
+        
+        for category in [AssetCategory::CryptoFutures, AssetCategory::Equity] {
+            let rows: Vec<usize> = (0..signals.len())
+                .filter(|&i| self.symbols.category(&signals[i].symbol).ok() == Some(category))
+                .collect();
+            if rows.is_empty() {
+                continue;
+            }
+            
+            let features: Vec<_> = rows.iter().map(|&i| signals[i].features.clone()).collect();
+            let model_start = std::time::Instant::now();
+            let batch = if ensemble {
+                self.inference_pool.predict_ensemble_batch(category, &features).await
+            } else {
+                self.inference_pool.predict_batch(category, &features, ModelType::Edge).await
+            };
+            
+            match batch {
+                Ok(batch) => {
+                    perf.model_p50_us = model_start.elapsed().as_micros() as f64;
+                    for (i, prediction) in rows.into_iter().zip(batch) {
+                        predictions[i] = Some(prediction);
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("⚠️  Batched {:?} inference failed, predicting per symbol: {}", category, e);
+                    metrics::increment_counter!("inference_batch_failed", "category" => format!("{:?}", category));
+                }
+            }
+        }
+        
+        predictions
+    }
+    
+    /// Process signal with MANDATORY models (no fallbacks). `prediction` is
+    /// the signal's share of a batched run, when there was one.
     async fn process_signal_mandatory(
         &self,
         computed: &features::ComputedFeatures,
+        prediction: Option<Prediction>,
         perf: &mut PerformanceMetrics,
     ) -> Result<()> {
-        let config = self.config.read();
+        // Copied out so no config guard is held across the awaits below
+        let (mode, decision_mode) = {
+            let config = self.config.read();
+            (config.mode, config.decision_mode)
+        };
         
-        if config.mode == TradingMode::Paused {
+        if mode == TradingMode::Paused {
             return Ok(());
         }
         
-        let features = self.features_to_vec(computed);
+        let features = self.features_to_vec(computed)?;
         
-        // Get decision based on mode - ALL MANDATORY
-        let decision = match config.decision_mode {
+        let now_ns = chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0);
+        self.rl_values.write().observe_mark(&computed.symbol, features.mid_price, now_ns);
+        self.router.get_risk_manager().write().observe_features(&features);
+        
+        // Get decision based on mode - ALL MANDATORY. ML paths also keep
+        // the record of what the router saw.
+        let (decision, record) = match decision_mode {
             DecisionMode::RLAgent => {
-                self.decide_with_rl_mandatory(computed, &features).await?
+                (self.decide_with_rl_mandatory(computed, &features).await?, None)
             }
             
             DecisionMode::MLTraditional => {
-                self.decide_with_ml_mandatory(computed, &features, perf).await?
+                let record = self.decide_with_ml_mandatory(computed, &features, prediction, perf, false).await?;
+                (record.decision.clone(), Some(record))
+            }
+            
+            DecisionMode::MLEnsemble => {
+                let record = self.decide_with_ml_mandatory(computed, &features, prediction, perf, true).await?;
+                (record.decision.clone(), Some(record))
             }
             
             DecisionMode::Hybrid => {
-                self.decide_hybrid_mandatory(computed, &features, perf).await?
+                self.decide_hybrid_mandatory(computed, &features, prediction, perf).await?
             }
         };
         
-        if !decision.should_trade {
-            return Ok(());
-        }
+        self.decision_stats.write().record(&decision);
         
-        // Execute trade
-        if config.mode == TradingMode::Live {
-            self.execute_trade(&computed.symbol, &decision, &features).await?;
-        } else {
-            tracing::debug!(
-                "Paper trade: {} {:?} size={:.4}",
-                computed.symbol, decision.style, decision.size_fraction
-            );
+        // Bound first so the observation lock is released before any trade
+        let admission = self.observation.write().admit(&computed.symbol, &decision, now_ns);
+        let executed = match admission {
+            Admission::Skip => Ok(false),
+            Admission::Shadow => {
+                tracing::info!(
+                    "Shadow decision (observing): {} {:?} size={:.4} reason={}",
+                    computed.symbol, decision.style, decision.size_fraction, decision.reason
+                );
+                metrics::increment_counter!("shadow_decisions", "symbol" => computed.symbol.clone());
+                Ok(false)
+            }
+            // Execute trade
+            Admission::Trade if mode == TradingMode::Live => {
+                let ladder = computed.features.as_slice().unwrap_or(&[]);
+                self.execute_trade(&computed.symbol, &decision, features.mid_price, ladder)
+                    .await
+                    .map(|()| true)
+            }
+            Admission::Trade => {
+                tracing::debug!(
+                    "Paper trade: {} {:?} size={:.4}",
+                    computed.symbol, decision.style, decision.size_fraction
+                );
+                Ok(false)
+            }
+        };
+        
+        if let Some(mut record) = record {
+            record.decision = decision;
+            record.executed = matches!(executed, Ok(true));
+            self.publish_decision(record);
         }
         
-        Ok(())
+        executed.map(|_| ())
     }
     
     /// RL-based decision (MANDATORY - fails if error)
@@ -377,6 +1134,26 @@ impl TradingEngine {
         
         let mut decision = self.rl_agent.to_route_decision(&rl_action, features);
         
+        // Going flat closes whatever the symbol still holds
+        if rl_action.action.is_flat() {
+            if let Some(exit) = self.holds.read().close(&computed.symbol, "RL flat".to_string()) {
+                decision = exit;
+            }
+        }
+        
+        tracing::debug!(
+            "RL decision: {} {:?} side={:?} size={:.4} value={:.4} confidence={:.3}",
+            computed.symbol, decision.style, decision.side, decision.size_fraction,
+            rl_action.value, rl_action.confidence
+        );
+        let now_ns = chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0);
+        self.rl_values.write().record(&computed.symbol, rl_action.value, &decision, features.mid_price, now_ns);
+
+        // Exits only reduce risk
+        if decision.reduce_only {
+            return Ok(decision);
+        }
+        
         // Apply risk checks
         let risk_manager = self.router.get_risk_manager();
         let notional = features.mid_price * decision.size_fraction;
@@ -389,100 +1166,161 @@ impl TradingEngine {
         Ok(decision)
     }
     
-    /// ML-based decision (MANDATORY - fails if error)
+    /// Edge model prediction, or the weighted ensemble when `ensemble` is set
+    async fn predict_ml(
+        &self,
+        category: AssetCategory,
+        features: &ndarray::Array1<f32>,
+        ensemble: bool,
+    ) -> Result<Prediction> {
+        if ensemble {
+            self.inference_pool.predict_ensemble(category, features).await
+        } else {
+            self.inference_pool.predict(category, features, ModelType::Edge).await
+        }
+    }
+    
+    /// ML-based decision (MANDATORY - fails if error). Runs inference
+    /// itself unless a batched `prediction` is passed in.
     async fn decide_with_ml_mandatory(
         &self,
         computed: &features::ComputedFeatures,
         features: &FeatureVec,
+        prediction: Option<Prediction>,
         perf: &mut PerformanceMetrics,
-    ) -> Result<RouteDecision> {
-        let category = AssetCategory::CryptoFutures; // TODO: determine from symbol
+        ensemble: bool,
+    ) -> Result<DecisionRecord> {
+        let category = self.symbols.category(&computed.symbol)?;
         
         // Run ML inference - NO fallback, must succeed
-        let model_start = std::time::Instant::now();
-        let prediction = self.inference_pool
-            .predict(category, &computed.features, ModelType::Edge)
-            .await
-            .map_err(|e| {
-                tracing::error!("❌ ML inference FAILED: {}", e);
-                Error::Internal(format!("ML inference failed: {}. No fallback available.", e))
-            })?;
+        let prediction = match prediction {
+            Some(prediction) => prediction,
+            None => {
+                let model_start = std::time::Instant::now();
+                let prediction = self.predict_ml(category, &computed.features, ensemble)
+                    .await
+                    .map_err(|e| {
+                        tracing::error!("❌ ML inference FAILED: {}", e);
+                        Error::Internal(format!("ML inference failed: {}. No fallback available.", e))
+                    })?;
+                perf.model_p50_us = model_start.elapsed().as_micros() as f64;
+                prediction
+            }
+        };
         
-        perf.model_p50_us = model_start.elapsed().as_micros() as f64;
+        // Every ML decision's inputs and output are a training sample
+        if let Some(export) = self.training_export.read().as_ref() {
+            export.record_sample(features.clone(), prediction.clone());
+        }
         
-        // Cost model
-        let costs = CostModel {
-            taker_fee_bps: 5.0,
-            maker_fee_bps: 2.0,
-            maker_rebate_bps: 1.0,
-            impact_bps: features.impact_bps_1pct,
-            slippage_buffer_bps: 1.0,
+        // Cost model at the venue's fee tier
+        let fees = self.symbols.venue(&computed.symbol)
+            .map_or(router::FALLBACK_FEES, |venue| self.fees.read().get(venue));
+        let (taker_slippage, passive_slippage) = {
+            let slippage = self.slippage.read();
+            (slippage.buffer_bps(&computed.symbol, OrderStyle::TakerNow), slippage.passive_buffer_bps(&computed.symbol))
         };
+        let costs = CostModel::from_fees(&fees, features.impact_bps_1pct, taker_slippage, passive_slippage);
         
-        // Route decision
-        let decision = self.router.decide(&prediction, features, &costs);
+        // Route decision, published once it's been acted on
+        Ok(DecisionRecord::route(&self.router, computed, category, features, prediction, costs))
+    }
+    
+    /// Log and broadcast a decision once it's been acted on
+    fn publish_decision(&self, record: DecisionRecord) {
+        if let Some(log) = &self.decision_log {
+            if let Err(e) = log.append(&record) {
+                tracing::warn!("Decision log write failed: {}", e);
+            }
+        }
         
-        Ok(decision)
+        // Nobody listening is fine
+        let _ = self.decision_tx.send(record);
     }
     
-    /// Hybrid decision: RL primary, ML validation (BOTH mandatory)
+    /// Re-run a logged ML decision through the current model and router.
+    /// Read-only: nothing is executed or recorded.
+    pub async fn replay_decision(&self, record: &DecisionRecord) -> Result<RouteDecision> {
+        let computed = record.computed_features();
+        let ensemble = self.config.read().decision_mode == DecisionMode::MLEnsemble;
+        let prediction = self.predict_ml(record.category, &computed.features, ensemble).await?;
+        
+        Ok(replay::replay_decision(&self.router, record, &prediction))
+    }
+    
+    /// Hybrid decision: RL primary, ML validation (BOTH mandatory). The
+    /// ML record comes back with it when validation ran.
     async fn decide_hybrid_mandatory(
         &self,
         computed: &features::ComputedFeatures,
         features: &FeatureVec,
+        prediction: Option<Prediction>,
         perf: &mut PerformanceMetrics,
-    ) -> Result<RouteDecision> {
+    ) -> Result<(RouteDecision, Option<DecisionRecord>)> {
         // Get RL decision (MANDATORY)
         let rl_decision = self.decide_with_rl_mandatory(computed, features).await?;
         
+        // Exits don't need ML validation
+        if rl_decision.reduce_only {
+            return Ok((rl_decision, None));
+        }
+        
         // Get ML decision for validation (MANDATORY)
-        let ml_decision = self.decide_with_ml_mandatory(computed, features, perf).await?;
+        let record = self.decide_with_ml_mandatory(computed, features, prediction, perf, false).await?;
+        let ml_decision = &record.decision;
+        
+        // Directionless RL actions take the side of the ML edge
+        let side = rl_decision.side.or(ml_decision.side);
+        let opposed = matches!((rl_decision.side, ml_decision.side), (Some(rl), Some(ml)) if rl != ml);
         
-        // Validate: both must agree to trade
-        if rl_decision.should_trade && ml_decision.should_trade {
+        // Validate: both must agree to trade, and on the direction
+        let decision = if rl_decision.should_trade && ml_decision.should_trade && !opposed {
             // Use RL decision with ML confidence as validation
-            Ok(rl_decision)
+            RouteDecision { side, ..rl_decision }
         } else {
             // Disagreement - don't trade
-            Ok(RouteDecision {
+            RouteDecision {
                 should_trade: false,
                 reason: format!(
-                    "RL/ML disagreement: RL={}, ML={}",
+                    "RL/ML disagreement: RL={} {:?}, ML={} {:?}",
                     rl_decision.should_trade,
-                    ml_decision.should_trade
+                    rl_decision.side,
+                    ml_decision.should_trade,
+                    ml_decision.side
                 ),
                 ..rl_decision
-            })
-        }
+            }
+        };
+        
+        Ok((decision, Some(record)))
     }
     
-    fn features_to_vec(&self, computed: &features::ComputedFeatures) -> FeatureVec {
-        let f = &computed.features;
-        FeatureVec {
-            timestamp_ns: computed.timestamp_ns,
-            symbol: computed.symbol.clone(),
-            mid_price: f[0] as f64,
-            spread_bps: f[1] as f64,
-            ofi_1s: f.get(4).copied().unwrap_or(0.0) as f64,
-            obi_1s: f.get(3).copied().unwrap_or(0.0) as f64,
-            depth_imbalance: f.get(3).copied().unwrap_or(0.0) as f64,
-            depth_a: 0.001,
-            depth_beta: 0.5,
-            realized_vol_5s: 0.02,
-            atr_30s: 10.0,
-            funding_bps_8h: f[2] as f64,
-            impact_bps_1pct: 0.5,
-            microprice: f[0] as f64,
-            vwap_ratio: f.get(5).copied().unwrap_or(1.0) as f64,
+    fn features_to_vec(&self, computed: &features::ComputedFeatures) -> Result<FeatureVec> {
+        let mut vec = computed.to_feature_vec();
+        
+        // Equities have no funding leg
+        if self.symbols.category(&computed.symbol)? == AssetCategory::Equity {
+            vec.funding_bps_8h = 0.0;
         }
+        
+        Ok(vec)
     }
     
     fn get_market_state(&self, symbol: &str) -> Result<MarketState> {
-        // TODO: Get from risk manager
+        // TODO: PnL and inventory risk from the risk manager
+        let now_ns = chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0);
+        let (position_size, holding_duration_s) = match self.holds.read().get(symbol) {
+            Some(hold) => (
+                if hold.side == Side::Buy { hold.quantity } else { -hold.quantity },
+                (now_ns - hold.entry_ns) as f64 / 1e9,
+            ),
+            None => (0.0, 0.0),
+        };
+        
         Ok(MarketState {
-            position_size: 0.0,
+            position_size,
             unrealized_pnl: 0.0,
-            holding_duration_s: 0.0,
+            holding_duration_s,
             inventory_risk: 0.0,
         })
     }
@@ -491,46 +1329,130 @@ impl TradingEngine {
         &self,
         symbol: &str,
         decision: &RouteDecision,
-        features: &FeatureVec,
+        mid_price: f64,
+        ladder: &[f32],
     ) -> Result<()> {
-        let adapters = self.adapters.read();
-        let adapter = adapters.values().next()
-            .ok_or_else(|| Error::Internal("No adapter".to_string()))?;
+        let venue = self.symbols.venue(symbol)?;
+        let adapter = self.adapters
+            .read()
+            .values()
+            .find(|a| a.venue() == venue)
+            .cloned()
+            .ok_or_else(|| Error::NotFound(format!("No {:?} adapter for {}", venue, symbol)))?;
         
-        let side = if features.ofi_1s > 0.0 { Side::Buy } else { Side::Sell };
+        let time_in_force = adapter.venue().nearest_tif(TimeInForce::for_style(decision.style))?;
+        let mut order = order_request(symbol, decision, mid_price, time_in_force)?;
+        let side = order.side;
+        let quantity = order.quantity;
+        let now_ns = chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0);
         
-        let order = OrderRequest {
-            client_id: format!("{}_{}", symbol, chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0)),
-            symbol: symbol.to_string(),
-            side,
-            order_type: match decision.style {
-                OrderStyle::TakerNow => OrderType::Market,
-                OrderStyle::MakerPassive => OrderType::PostOnly,
-                OrderStyle::Sniper => OrderType::Limit,
-            },
-            quantity: decision.size_fraction,
-            price: if decision.style == OrderStyle::Sniper {
-                Some(features.mid_price)
-            } else {
-                None
-            },
-            reduce_only: false,
-            time_in_force: TimeInForce::GTC,
-        };
+        self.router.get_risk_manager().read().finalize_order(&mut order);
+        if order.quantity <= 0.0 {
+            tracing::debug!("Nothing to reduce for {}; order dropped", symbol);
+            self.holds.write().on_closed(symbol, now_ns);
+            return Ok(());
+        }
+        
+        let slicing = self.config.read().slicing.clone();
+        let depth = execution::opposite_depth(ladder, side);
+        let plan = execution::plan(order, decision, mid_price, depth, &slicing);
+        self.parent_orders.write().register(&plan);
+        
+        // Fills are measured against the touch the decision saw
+        let fill_ref = self.marks.read().get(symbol).map(|m| FillReference {
+            style: decision.style,
+            bid: m.bid,
+            ask: m.ask,
+        });
+        
+        // First child now; its failure surfaces to the caller
+        let start = tokio::time::Instant::now();
+        let mut children = plan.children.into_iter();
+        if let Some(first) = children.next() {
+            if let Err(e) = Self::send_child(adapter.as_ref(), &self.parent_orders, &self.slippage, fill_ref, first.order).await {
+                self.parent_orders.write().cancel(&plan.parent_id);
+                if decision.reduce_only {
+                    self.holds.write().on_close_failed(symbol);
+                }
+                return Err(e);
+            }
+        }
+        
+        if decision.reduce_only {
+            self.holds.write().on_closed(symbol, now_ns);
+        } else {
+            self.holds.write().on_entry(symbol, side, quantity, decision.hold_duration_s, now_ns);
+        }
+        
+        let rest: Vec<execution::ChildOrder> = children.collect();
+        if rest.is_empty() {
+            self.parent_orders.write().prune();
+            return Ok(());
+        }
+        
+        tracing::info!("Working {} via {:?}: {} more child orders", plan.parent_id, plan.algo, rest.len());
+        
+        let parents = self.parent_orders.clone();
+        let slippage = self.slippage.clone();
+        let risk = self.router.get_risk_manager();
+        let config = self.config.clone();
+        let parent_id = plan.parent_id;
+        
+        tokio::spawn(async move {
+            for child in rest {
+                tokio::time::sleep_until(start + child.delay).await;
+                
+                // Each child rechecks what the first was sent under
+                let cancelled = parents.read().get(&parent_id).is_none_or(|p| p.cancelled);
+                let live = config.read().mode == TradingMode::Live;
+                if cancelled || !live || risk.read().get_state().kill_switch_active {
+                    tracing::warn!("Stopped working {}: cancelled, not live or kill switch active", parent_id);
+                    parents.write().cancel(&parent_id);
+                    break;
+                }
+                
+                if Self::send_child(adapter.as_ref(), &parents, &slippage, fill_ref, child.order).await.is_err() {
+                    parents.write().cancel(&parent_id);
+                    break;
+                }
+            }
+            
+            parents.write().prune();
+        });
+        
+        Ok(())
+    }
+    
+    /// Send one child order and record it against its parent, and its fill
+    /// price (when the venue reports one) against the decision's touch
+    async fn send_child(
+        adapter: &dyn adapters::ExchangeAdapter,
+        parents: &RwLock<ParentOrders>,
+        slippage: &RwLock<SlippageTracker>,
+        fill_ref: Option<FillReference>,
+        order: OrderRequest,
+    ) -> Result<()> {
+        let symbol = order.symbol.clone();
+        let child_id = order.client_id.clone();
+        let quantity = order.quantity;
+        let side = order.side;
         
         match adapter.send_order(order).await {
             Ok(ack) => {
-                tracing::info!("✅ Order sent: {} - {:?}", symbol, ack.status);
-                metrics::increment_counter!("orders_sent", "symbol" => symbol.to_string());
+                tracing::info!("✅ Order sent: {} {} - {:?}", symbol, child_id, ack.status);
+                if let (Some(fill_price), Some(r)) = (ack.avg_fill_price, fill_ref) {
+                    slippage.write().record_fill(&symbol, r.style, side, r.bid, r.ask, fill_price);
+                }
+                metrics::increment_counter!("orders_sent", "symbol" => symbol);
+                parents.write().record_sent(&child_id, quantity);
+                Ok(())
             }
             Err(e) => {
-                tracing::error!("❌ Order FAILED: {}", e);
-                metrics::increment_counter!("order_rejects", "symbol" => symbol.to_string());
-                return Err(e);
+                tracing::error!("❌ Order FAILED: {} {}: {}", symbol, child_id, e);
+                metrics::increment_counter!("order_rejects", "symbol" => symbol);
+                Err(e)
             }
         }
-        
-        Ok(())
     }
     
     pub fn set_mode(&self, mode: TradingMode) {
@@ -542,7 +1464,561 @@ impl TradingEngine {
         self.config.read().mode
     }
     
+    /// Halt new risk (used when a critical subsystem dies)
+    pub fn activate_kill_switch(&self) {
+        self.router.get_risk_manager().write().activate_kill_switch();
+    }
+    
+    /// Resume taking new risk (operator action)
+    pub fn deactivate_kill_switch(&self) {
+        self.router.get_risk_manager().write().deactivate_kill_switch();
+    }
+    
     pub fn get_metrics(&self) -> PerformanceMetrics {
         self.metrics_tx.borrow().clone()
     }
-}
\ No newline at end of file
+    
+    /// Route engine-raised alerts (e.g. an automatic kill switch) through `publisher`
+    pub fn set_alert_publisher(&self, publisher: Arc<ws_server::AlertPublisher>) {
+        *self.alerts.write() = Some(publisher);
+    }
+    
+    /// Queue each ML decision's features and prediction for training export
+    pub fn set_training_export(&self, manager: Arc<advanced_features::AdvancedFeaturesManager>) {
+        *self.training_export.write() = Some(manager);
+    }
+    
+    async fn publish_alert(&self, level: AlertLevel, source: &str, message: String) {
+        let publisher = self.alerts.read().clone();
+        match publisher {
+            Some(publisher) => publisher.publish(level, source.to_string(), message).await,
+            None => tracing::error!("[{}] {}", source, message),
+        }
+    }
+    
+    /// Per-cycle performance metrics, updated as each batch is processed
+    pub fn subscribe_metrics(&self) -> watch::Receiver<PerformanceMetrics> {
+        self.metrics_tx.subscribe()
+    }
+    
+    /// Latest risk snapshot
+    pub fn subscribe_risk(&self) -> watch::Receiver<RiskSnapshot> {
+        self.risk_tx.subscribe()
+    }
+    
+    /// Sender side of the decision feed; subscribe per client
+    pub fn decision_sender(&self) -> broadcast::Sender<DecisionRecord> {
+        self.decision_tx.clone()
+    }
+    
+    /// Milliseconds since each symbol's last market update
+    pub fn symbol_update_ages_ms(&self) -> std::collections::BTreeMap<String, i64> {
+        let now_ns = chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0);
+        
+        self.last_update.read()
+            .iter()
+            .map(|(symbol, ts)| (symbol.clone(), (now_ns - ts) / 1_000_000))
+            .collect()
+    }
+    
+    pub fn health_report(&self) -> HealthReport {
+        let config = self.config.read();
+        let risk = self.router.get_risk_manager().read().get_state();
+        
+        let stale_symbols: Vec<String> = self.symbol_update_ages_ms()
+            .into_iter()
+            .filter(|(_, age)| *age > diagnostics::STALE_AFTER_MS)
+            .map(|(symbol, _)| symbol)
+            .collect();
+        
+        let adapters = self.adapters.read()
+            .iter()
+            .map(|(label, adapter)| diagnostics::AdapterInfo {
+                label: label.clone(),
+                venue: adapter.venue(),
+            })
+            .collect();
+        
+        let healthy = !risk.kill_switch_active && !risk.daily_loss_exceeded && stale_symbols.is_empty();
+        
+        HealthReport {
+            status: if healthy { "healthy" } else { "degraded" }.to_string(),
+            mode: config.mode,
+            decision_mode: format!("{:?}", config.decision_mode),
+            kill_switch_active: risk.kill_switch_active,
+            daily_loss_exceeded: risk.daily_loss_exceeded,
+            adapters,
+            stale_symbols,
+        }
+    }
+    
+    /// RL critic values and their realized rewards, for `/models/rl/stats`
+    pub fn rl_values(&self) -> Arc<RwLock<ValueTracker>> {
+        self.rl_values.clone()
+    }
+    
+    /// Capture a diagnostics bundle; `config` is redacted on write
+    pub fn diagnostics(&self, config: serde_json::Value, recent_alerts: Vec<Alert>) -> DiagnosticsBundle {
+        let mut models = self.inference_pool.model_info();
+        let version = inference::read_model_version(std::path::Path::new(RL_MODEL_DIR));
+        models.push(diagnostics::ModelInfo {
+            name: "rl_actor".to_string(),
+            path: RL_ACTOR_PATH.to_string(),
+            version: version.clone(),
+            loaded: self.rl_agent.is_healthy(),
+        });
+        models.push(diagnostics::ModelInfo {
+            name: "rl_critic".to_string(),
+            path: RL_CRITIC_PATH.to_string(),
+            version,
+            loaded: self.rl_agent.has_critic(),
+        });
+        
+        DiagnosticsBundle {
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            config,
+            health: self.health_report(),
+            recent_alerts,
+            symbol_update_age_ms: self.symbol_update_ages_ms(),
+            models,
+            performance: self.get_metrics(),
+            decisions: self.decision_stats.read().clone(),
+        }
+    }
+}
+
+/// The order a decision asks for. The side comes from the decision alone;
+/// one without a direction is refused rather than guessed from the book.
+/// Passive orders on venues without post-only rest as a limit at mid.
+fn order_request(
+    symbol: &str,
+    decision: &RouteDecision,
+    mid_price: f64,
+    time_in_force: TimeInForce,
+) -> Result<OrderRequest> {
+    let side = decision.side.ok_or_else(|| {
+        Error::InvalidData(format!("Decision for {} carries no side: {}", symbol, decision.reason))
+    })?;
+    
+    let order_type = match decision.style {
+        OrderStyle::TakerNow => OrderType::Market,
+        OrderStyle::MakerPassive if time_in_force == TimeInForce::GTX => OrderType::PostOnly,
+        OrderStyle::MakerPassive | OrderStyle::Sniper => OrderType::Limit,
+    };
+    
+    Ok(OrderRequest {
+        client_id: format!("{}_{}", symbol, chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0)),
+        symbol: symbol.to_string(),
+        side,
+        order_type,
+        quantity: decision.size_fraction,
+        price: if order_type == OrderType::Limit {
+            Some(mid_price)
+        } else {
+            None
+        },
+        reduce_only: decision.reduce_only,
+        time_in_force,
+    })
+}
+
+/// What a child's fill is measured against: the touch when the decision was
+/// made, and the style that picks which side of it
+#[derive(Debug, Clone, Copy)]
+struct FillReference {
+    style: OrderStyle,
+    bid: f64,
+    ask: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_order_side_follows_decision() {
+        let decision = RouteDecision {
+            style: OrderStyle::TakerNow,
+            size_fraction: 0.02,
+            hold_duration_s: 30.0,
+            urgency: 0.9,
+            should_trade: true,
+            side: Some(Side::Sell),
+            reduce_only: false,
+            reason: "Edge: -12.00 bps".to_string(),
+        };
+        
+        // The order book plays no part in the side
+        let order = order_request("BTC", &decision, 50_000.0, TimeInForce::IOC).unwrap();
+        assert_eq!(order.side, Side::Sell);
+        assert!(!order.reduce_only);
+        
+        let exit = RouteDecision { side: Some(Side::Buy), reduce_only: true, ..decision.clone() };
+        let order = order_request("BTC", &exit, 50_000.0, TimeInForce::IOC).unwrap();
+        assert_eq!(order.side, Side::Buy);
+        assert!(order.reduce_only);
+        
+        let directionless = RouteDecision { side: None, ..decision.clone() };
+        assert!(order_request("BTC", &directionless, 50_000.0, TimeInForce::IOC).is_err());
+        
+        // Without post-only a maker order becomes a day limit at mid
+        let maker = RouteDecision { style: OrderStyle::MakerPassive, ..decision };
+        let tif = Venue::IBKR.nearest_tif(TimeInForce::for_style(maker.style)).unwrap();
+        let order = order_request("AAPL", &maker, 190.0, tif).unwrap();
+        assert_eq!((order.order_type, order.price, order.time_in_force), (OrderType::Limit, Some(190.0), TimeInForce::DAY));
+        
+        let order = order_request("BTC", &maker, 50_000.0, TimeInForce::GTX).unwrap();
+        assert_eq!((order.order_type, order.price), (OrderType::PostOnly, None));
+    }
+    
+    /// Engine on CPU features with `fixtures/linear.onnx` as the RL actor,
+    /// so no model bundle is needed on disk
+    fn test_engine(config: EngineConfig) -> TradingEngine {
+        let actor = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/linear.onnx");
+        let rl_agent = RLAgent::new(actor, None, rl_agent::RLAgentConfig {
+            action_type: rl_agent::ActionType::Discrete,
+            sequence_length: 1,
+            use_recurrent: false,
+            epsilon: 0.0,
+            temperature: 1.0,
+        }).unwrap();
+        TradingEngine::with_rl_agent(config, RiskLimits::default(), Arc::new(rl_agent)).unwrap()
+    }
+    
+    fn book(symbol: &str, bid: f64, ask: f64) -> MarketSnapshot {
+        let level = |price: f64| Level { price: price.into(), quantity: 1.0 };
+        MarketSnapshot {
+            timestamp_ns: 1,
+            symbol: symbol.to_string(),
+            orderbook: OrderBook {
+                symbol: symbol.to_string(),
+                timestamp_ns: 1,
+                bids: vec![level(bid)],
+                asks: vec![level(ask)],
+                sequence: 1,
+            },
+            recent_trades: vec![],
+            funding_rate_bps: None,
+            open_interest: None,
+            volume_24h: 0.0,
+            quality: DataQuality::Live,
+        }
+    }
+    
+    /// Venue stand-in reporting set positions and filling every order
+    struct FakeVenue {
+        venue: Venue,
+        positions: parking_lot::Mutex<Vec<Position>>,
+        orders: parking_lot::Mutex<Vec<OrderRequest>>,
+    }
+    
+    impl FakeVenue {
+        fn new(venue: Venue) -> Self {
+            Self {
+                venue,
+                positions: parking_lot::Mutex::new(Vec::new()),
+                orders: parking_lot::Mutex::new(Vec::new()),
+            }
+        }
+    }
+    
+    #[async_trait::async_trait]
+    impl adapters::MarketDataStream for FakeVenue {
+        async fn subscribe_orderbook(&mut self, _symbols: &[String]) -> Result<()> {
+            Ok(())
+        }
+        
+        async fn subscribe_trades(&mut self, _symbols: &[String]) -> Result<()> {
+            Ok(())
+        }
+        
+        fn snapshot_receiver(&self) -> Result<mpsc::UnboundedReceiver<MarketSnapshot>> {
+            Err(Error::NotFound("no feed".to_string()))
+        }
+    }
+    
+    #[async_trait::async_trait]
+    impl adapters::AccountData for FakeVenue {
+        async fn balances(&self) -> Result<HashMap<String, Balance>> {
+            Ok(HashMap::new())
+        }
+        
+        async fn positions(&self) -> Result<Vec<Position>> {
+            Ok(self.positions.lock().clone())
+        }
+        
+        async fn fee_tier(&self) -> Result<FeeTier> {
+            Ok(router::FALLBACK_FEES)
+        }
+        
+        async fn leverage(&self) -> Result<f64> {
+            Ok(1.0)
+        }
+    }
+    
+    #[async_trait::async_trait]
+    impl adapters::OrderRouter for FakeVenue {
+        async fn send_order(&self, order: OrderRequest) -> Result<OrderAck> {
+            let ack = OrderAck {
+                venue_order_id: format!("fake-{}", self.orders.lock().len()),
+                client_id: order.client_id.clone(),
+                status: OrderStatus::Filled,
+                timestamp_ns: 0,
+                avg_fill_price: order.price,
+            };
+            self.orders.lock().push(order);
+            Ok(ack)
+        }
+        
+        async fn cancel_order(&self, _order_id: &str) -> Result<()> {
+            Ok(())
+        }
+        
+        async fn cancel_all(&self, _symbol: &str) -> Result<()> {
+            Ok(())
+        }
+        
+        async fn get_order(&self, order_id: &str) -> Result<OrderAck> {
+            Err(Error::NotFound(order_id.to_string()))
+        }
+    }
+    
+    #[async_trait::async_trait]
+    impl adapters::MarketInfo for FakeVenue {
+        async fn list_symbols(&self) -> Result<Vec<String>> {
+            Ok(vec![])
+        }
+        
+        async fn search_symbols(&self, _prefix: &str) -> Result<Vec<String>> {
+            Ok(vec![])
+        }
+        
+        async fn funding_rate(&self, _symbol: &str) -> Result<f64> {
+            Ok(0.0)
+        }
+        
+        async fn open_interest(&self, _symbol: &str) -> Result<f64> {
+            Ok(0.0)
+        }
+        
+        async fn volume_24h(&self, _symbol: &str) -> Result<f64> {
+            Ok(0.0)
+        }
+        
+        async fn leverage_tiers(&self, symbol: &str) -> Result<Vec<margin::LeverageTier>> {
+            Err(Error::NotFound(symbol.to_string()))
+        }
+        
+        async fn symbol_spec(&self, symbol: &str) -> Result<SymbolSpec> {
+            Err(Error::NotFound(symbol.to_string()))
+        }
+    }
+    
+    #[async_trait::async_trait]
+    impl adapters::ExchangeAdapter for FakeVenue {
+        fn venue(&self) -> Venue {
+            self.venue
+        }
+        
+        fn is_connected(&self) -> bool {
+            true
+        }
+        
+        async fn connect(&mut self) -> Result<()> {
+            Ok(())
+        }
+        
+        async fn disconnect(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_metrics_reach_late_subscribers() {
+        // Paused: the flush computes features and publishes, but decides nothing
+        let config = EngineConfig { mode: TradingMode::Paused, batch_size: 1, ..EngineConfig::default() };
+        let engine = test_engine(config.clone());
+        engine.add_symbol("BTC".to_string(), Venue::Hyperliquid);
+        
+        // One flush runs before any client subscribes
+        let (market_tx, market_rx) = feed::snapshot_queue(4);
+        market_tx.send(book("BTC", 49_999.0, 50_001.0)).unwrap();
+        drop(market_tx);
+        engine.process_with_batching(market_rx, config).await;
+        
+        let rx = engine.subscribe_metrics();
+        assert!(rx.borrow().snapshots_per_sec > 0.0, "{:?}", *rx.borrow());
+        assert_eq!(rx.borrow().dropped_frames, 0);
+        assert_eq!(engine.marks.read()["BTC"].mid, 50_000.0);
+    }
+    
+    #[tokio::test]
+    async fn test_search_skips_a_hyperliquid_adapter_until_connected() {
+        let engine = test_engine(EngineConfig::default());
+        let credentials = common::security::ApiCredentials::new("key".to_string(), "secret".to_string(), true);
+        let adapter = adapters::HyperliquidAdapter::new(credentials);
+        engine.add_adapter("hyperliquid".to_string(), Arc::new(adapter));
+        
+        // Checking the connection from inside the runtime must not block
+        match engine.search_symbols(Venue::Hyperliquid, "BT").await {
+            Err(Error::NotFound(msg)) => assert!(msg.contains("Hyperliquid"), "{}", msg),
+            other => panic!("expected NotFound, got {:?}", other),
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_marked_losses_trip_the_kill_switch() {
+        let engine = test_engine(EngineConfig::default());
+        engine.add_symbol("BTC".to_string(), Venue::Hyperliquid);
+        let venue = Arc::new(FakeVenue::new(Venue::Hyperliquid));
+        venue.positions.lock().push(Position {
+            symbol: "BTC".to_string(),
+            size: 1.0,
+            entry_price: 50_000.0,
+            mark_price: 50_000.0,
+            unrealized_pnl: 0.0,
+            realized_pnl: 0.0,
+            leverage: 1.0,
+            margin_used: 50_000.0,
+            liquidation_price: None,
+            maintenance_margin: None,
+        });
+        engine.add_adapter("hl".to_string(), venue.clone());
+        let mark = |bid: f64, ask: f64| {
+            let marks = BookMarks::from_book(&book("BTC", bid, ask).orderbook).unwrap();
+            engine.marks.write().insert("BTC".to_string(), marks);
+        };
+        let risk = engine.router.get_risk_manager();
+        
+        // First sighting is the baseline; later marks book their change
+        mark(51_999.0, 52_001.0);
+        assert_eq!(engine.mark_to_market().await, 1);
+        assert_eq!(risk.read().daily_pnl(), 0.0);
+        
+        mark(45_999.0, 46_001.0);
+        engine.mark_to_market().await;
+        assert!((risk.read().daily_pnl() + 6_000.0).abs() < 1e-6);
+        assert!(!engine.subscribe_risk().borrow().kill_switch_active);
+        
+        // -12k on the day breaches the 10k limit
+        mark(39_999.0, 40_001.0);
+        engine.mark_to_market().await;
+        assert!((risk.read().daily_pnl() + 12_000.0).abs() < 1e-6);
+        assert!(engine.subscribe_risk().borrow().kill_switch_active);
+        assert!(risk.read().check_limits("BTC", 1_000.0).is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_trades_go_to_the_symbol_venue() {
+        let engine = test_engine(EngineConfig { mode: TradingMode::Live, ..EngineConfig::default() });
+        engine.add_symbol("BTC".to_string(), Venue::Hyperliquid);
+        engine.add_symbol("ETH".to_string(), Venue::BinanceFutures);
+        let hyperliquid = Arc::new(FakeVenue::new(Venue::Hyperliquid));
+        let binance = Arc::new(FakeVenue::new(Venue::BinanceFutures));
+        engine.add_adapter("hyperliquid".to_string(), hyperliquid.clone());
+        engine.add_adapter("binance".to_string(), binance.clone());
+        
+        let decision = RouteDecision {
+            style: OrderStyle::TakerNow,
+            size_fraction: 0.01,
+            hold_duration_s: 30.0,
+            urgency: 0.9,
+            should_trade: true,
+            side: Some(Side::Buy),
+            reduce_only: false,
+            reason: "Edge: 12.00 bps".to_string(),
+        };
+        engine.execute_trade("ETH", &decision, 3_000.0, &[]).await.unwrap();
+        engine.execute_trade("BTC", &decision, 50_000.0, &[]).await.unwrap();
+        
+        let symbols = |venue: &FakeVenue| -> Vec<String> {
+            venue.orders.lock().iter().map(|o| o.symbol.clone()).collect()
+        };
+        assert_eq!(symbols(&binance), vec!["ETH".to_string()]);
+        assert_eq!(symbols(&hyperliquid), vec!["BTC".to_string()]);
+        
+        // Untracked symbols have no venue to trade on
+        assert!(engine.execute_trade("SOL", &decision, 150.0, &[]).await.is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_hold_exit_closes_on_its_venue() {
+        let engine = test_engine(EngineConfig { mode: TradingMode::Live, ..EngineConfig::default() });
+        engine.add_symbol("ETH".to_string(), Venue::BinanceFutures);
+        let hyperliquid = Arc::new(FakeVenue::new(Venue::Hyperliquid));
+        engine.add_adapter("hyperliquid".to_string(), hyperliquid.clone());
+        
+        let marks = BookMarks::from_book(&book("ETH", 2_999.0, 3_001.0).orderbook).unwrap();
+        engine.marks.write().insert("ETH".to_string(), marks);
+        engine.holds.write().on_entry("ETH", Side::Buy, 2.0, 0.0, 0);
+        engine.router.get_risk_manager().write().update_position(Position {
+            symbol: "ETH".to_string(),
+            size: 2.0,
+            entry_price: 3_000.0,
+            mark_price: 3_000.0,
+            unrealized_pnl: 0.0,
+            realized_pnl: 0.0,
+            leverage: 1.0,
+            margin_used: 6_000.0,
+            liquidation_price: None,
+            maintenance_margin: None,
+        });
+        
+        // No Binance adapter yet: the exit waits for the next sweep
+        assert_eq!(engine.sweep_holds().await, 0);
+        assert!(hyperliquid.orders.lock().is_empty());
+        
+        let binance = Arc::new(FakeVenue::new(Venue::BinanceFutures));
+        engine.add_adapter("binance".to_string(), binance.clone());
+        assert_eq!(engine.sweep_holds().await, 1);
+        
+        let orders = binance.orders.lock().clone();
+        assert_eq!(orders.len(), 1);
+        assert_eq!((orders[0].side, orders[0].quantity), (Side::Sell, 2.0));
+        assert!(orders[0].reduce_only);
+        assert!(hyperliquid.orders.lock().is_empty());
+        assert!(engine.holds.read().get("ETH").is_none());
+    }
+    
+    #[tokio::test]
+    async fn test_shutdown_closes_each_hold_in_one_order_on_its_venue() {
+        let mut config = EngineConfig { mode: TradingMode::Live, ..EngineConfig::default() };
+        config.shutdown.close_positions = true;
+        // Would work a 150k close as TWAP children if it went through `execute_trade`
+        config.slicing.min_slice_notional = 1_000.0;
+        config.slicing.aggressive_urgency = 1.1;
+        let engine = test_engine(config);
+        
+        engine.add_symbol("BTC".to_string(), Venue::Hyperliquid);
+        let binance = Arc::new(FakeVenue::new(Venue::BinanceFutures));
+        let hyperliquid = Arc::new(FakeVenue::new(Venue::Hyperliquid));
+        engine.add_adapter("binance".to_string(), binance.clone());
+        engine.add_adapter("hyperliquid".to_string(), hyperliquid.clone());
+        
+        let marks = BookMarks::from_book(&book("BTC", 49_999.0, 50_001.0).orderbook).unwrap();
+        engine.marks.write().insert("BTC".to_string(), marks);
+        engine.holds.write().on_entry("BTC", Side::Buy, 3.0, 600.0, 0);
+        engine.router.get_risk_manager().write().update_position(Position {
+            symbol: "BTC".to_string(),
+            size: 3.0,
+            entry_price: 50_000.0,
+            mark_price: 50_000.0,
+            unrealized_pnl: 0.0,
+            realized_pnl: 0.0,
+            leverage: 1.0,
+            margin_used: 150_000.0,
+            liquidation_price: None,
+            maintenance_margin: None,
+        });
+        
+        engine.shutdown().await;
+        
+        let orders = hyperliquid.orders.lock().clone();
+        assert_eq!(orders.len(), 1);
+        assert_eq!((orders[0].side, orders[0].quantity), (Side::Sell, 3.0));
+        assert_eq!((orders[0].order_type, orders[0].time_in_force), (OrderType::Market, TimeInForce::IOC));
+        assert!(orders[0].reduce_only);
+        assert!(binance.orders.lock().is_empty());
+        assert!(engine.holds.read().get("BTC").is_none());
+    }
+}
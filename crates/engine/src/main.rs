@@ -1,15 +1,16 @@
 // crates/engine/src/main.rs (Fully Integrated with Advanced Features)
 use engine::*;
 use common::*;
-use adapters::{HyperliquidAdapter};
-use common::security::{CredentialStore, ApiCredentials};
+use adapters::{AccountData, BinanceAdapter, ExchangeAdapter, HyperliquidAdapter, IbkrAdapter, RestFallbackConfig};
+use common::security::{CredentialStore, ApiCredentials, DataSourceKeys};
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::{watch, broadcast};
 use tracing_subscriber::EnvFilter;
 
 // Import advanced features
-use crate::advanced_features::{AdvancedConfig, AdvancedFeaturesManager};
-use features::gpu_compute::DeviceType;
+use engine::advanced_features::{AdvancedConfig, AdvancedFeaturesManager};
+use features::DeviceType;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -90,19 +91,14 @@ async fn main() -> Result<()> {
         
         // GPU acceleration
         enable_gpu: config.advanced.gpu.enabled,
-        gpu_device: match config.advanced.gpu.device.as_str() {
-            "CUDA" => DeviceType::CUDA(config.advanced.gpu.device_id),
-            "ROCm" => DeviceType::ROCm(config.advanced.gpu.device_id),
-            "TensorRT" => DeviceType::TensorRT,
-            _ => DeviceType::CPU,
-        },
+        gpu_device: device_type(&config.advanced.gpu.device, config.advanced.gpu.device_id),
         gpu_batch_size: config.advanced.gpu.batch_size,
         gpu_model_path: config.advanced.gpu.model_path.clone(),
     };
     
     let advanced_manager = if config.advanced.enabled {
         tracing::info!("Initializing advanced features...");
-        match AdvancedFeaturesManager::new(advanced_config).await {
+        match AdvancedFeaturesManager::new(advanced_config, s3_client.clone()).await {
             Ok(manager) => {
                 let stats = manager.stats();
                 tracing::info!("Advanced features initialized: {:?}", stats);
@@ -122,24 +118,7 @@ async fn main() -> Result<()> {
     // STANDARD ENGINE INITIALIZATION
     // ============================================
     
-    let engine_config = EngineConfig {
-        mode: config.engine.mode,
-        feature_window_size: config.engine.feature_window_size,
-        inference_timeout_ms: config.engine.inference_timeout_ms,
-        gate_params: router::GateParams {
-            enabled: config.gate.enabled,
-            min_edge_bps: config.gate.min_edge_bps,
-            min_confidence: config.gate.min_confidence,
-            max_hold_s: config.gate.max_hold_s,
-            max_spread_bps: config.gate.max_spread_bps,
-        },
-        enable_s3_writer: config.s3.enabled,
-        s3_bucket: if config.s3.bucket.is_empty() {
-            None
-        } else {
-            Some(config.s3.bucket.clone())
-        },
-    };
+    let engine_config = engine_config(&config.engine, &config.gate);
     
     let risk_limits = RiskLimits {
         max_notional_per_symbol: config.risk.max_notional_per_symbol,
@@ -147,7 +126,14 @@ async fn main() -> Result<()> {
         max_leverage: config.risk.max_leverage,
         max_loss_per_day: config.risk.max_loss_per_day,
         max_position_concentration: config.risk.max_position_concentration,
+        max_open_positions: config.risk.max_open_positions,
+        base_currency: config.risk.base_currency,
+        reference_rates: config.risk.reference_rates,
+        reset_hour_utc: config.risk.reset_hour_utc,
+        var_correlation: config.risk.var_correlation,
+        kill_switch_loss_multiple: config.risk.kill_switch_loss_multiple,
     };
+    risk_limits.validate()?;
     
     let trading_engine = Arc::new(TradingEngine::new(engine_config, risk_limits)?);
     
@@ -162,8 +148,16 @@ async fn main() -> Result<()> {
     if config.venues.hyperliquid.enabled {
         match load_hyperliquid_adapter(&cred_store) {
             Ok(adapter) => {
-                trading_engine.add_adapter("hyperliquid".to_string(), Arc::new(adapter));
-                tracing::info!("Hyperliquid adapter added");
+                let mut adapter = adapter.with_dry_run(config.venues.hyperliquid.dry_run);
+                if let Some(fallback) = &config.venues.hyperliquid.rest_fallback {
+                    adapter = adapter.with_rest_fallback(rest_fallback_config(fallback));
+                }
+                if let Err(e) = adapter.connect().await {
+                    tracing::warn!("Failed to connect Hyperliquid adapter: {}", e);
+                } else {
+                    trading_engine.add_adapter("hyperliquid".to_string(), Arc::new(adapter));
+                    tracing::info!("Hyperliquid adapter added");
+                }
             }
             Err(e) => {
                 tracing::warn!("Failed to load Hyperliquid adapter: {}", e);
@@ -171,10 +165,48 @@ async fn main() -> Result<()> {
         }
     }
     
+    if config.venues.binance.enabled {
+        match load_binance_adapter(&cred_store) {
+            Ok(adapter) => {
+                let mut adapter = adapter.with_dry_run(config.venues.binance.dry_run);
+                if let Err(e) = adapter.connect().await {
+                    tracing::warn!("Failed to connect Binance adapter: {}", e);
+                } else {
+                    trading_engine.add_adapter("binance".to_string(), Arc::new(adapter));
+                    tracing::info!("Binance adapter added");
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Failed to load Binance adapter: {}", e);
+            }
+        }
+    }
+    
+    if config.venues.ibkr.enabled {
+        // IB needs the gateway session checked before any request is made
+        let mut adapter = IbkrAdapter::new(&config.venues.ibkr.gateway_host, config.venues.ibkr.gateway_port)
+            .with_dry_run(config.venues.ibkr.dry_run)
+            .with_confirmed_warnings(config.venues.ibkr.confirm_warnings.clone());
+        match adapter.connect().await {
+            Ok(()) => {
+                trading_engine.add_adapter("ibkr".to_string(), Arc::new(adapter));
+                tracing::info!("IBKR adapter added");
+            }
+            Err(e) => {
+                tracing::warn!("Failed to connect IBKR adapter: {}", e);
+            }
+        }
+    }
+    
     // Add symbols to track
     let symbols = vec!["BTC-USD", "ETH-USD", "SOL-USD"];
     for symbol in symbols {
-        trading_engine.add_symbol(symbol.to_string(), config.engine.feature_window_size);
+        trading_engine.add_symbol(symbol.to_string(), Venue::Hyperliquid);
+        
+        // Tick-normalized features stay zero until the spec is known
+        if let Err(e) = trading_engine.load_symbol_spec(symbol).await {
+            tracing::warn!("No symbol spec for {}: {}", symbol, e);
+        }
         
         // Initialize multi-threaded order book for this symbol
         if let Some(ref manager) = advanced_manager {
@@ -184,15 +216,76 @@ async fn main() -> Result<()> {
         }
     }
     
+    // ML decisions feed the training export
+    if let Some(ref manager) = advanced_manager {
+        trading_engine.set_training_export(manager.clone());
+    }
+    
     // Create WebSocket server
-    let (perf_tx, perf_rx) = watch::channel(PerformanceMetrics::default());
-    let (risk_tx, risk_rx) = watch::channel(RiskSnapshot::default());
     let (alert_tx, _alert_rx) = broadcast::channel(1000);
+    let alert_publisher = Arc::new(ws_server::AlertPublisher::new(
+        alert_tx.clone(),
+        sns_client,
+        config.sns.topic_arn.clone(),
+    ));
+    trading_engine.set_alert_publisher(alert_publisher);
+    
+    // Keep recent alerts for diagnostics dumps
+    let alert_log = Arc::new(diagnostics::AlertLog::new(200));
+    {
+        let mut alert_rx = alert_tx.subscribe();
+        let alert_log = alert_log.clone();
+        
+        tokio::spawn(async move {
+            loop {
+                match alert_rx.recv().await {
+                    Ok(alert) => alert_log.record(alert),
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+    
+    let diagnostics_handle = diagnostics::DiagnosticsHandle {
+        engine: trading_engine.clone(),
+        alerts: alert_log,
+        config: load_config_json(),
+        output_dir: std::path::PathBuf::from("diagnostics"),
+    };
+    
+    // Operator control socket; disabled unless a shared token is configured
+    let control = match std::env::var("ENGINE_CONTROL_TOKEN") {
+        Ok(token) if !token.is_empty() => {
+            let (handle, control_rx) = control::control_channel(token)?;
+            tokio::spawn(control::run_control_loop(control_rx, trading_engine.clone()));
+            tracing::info!("Control channel enabled on /control");
+            Some(handle)
+        }
+        _ => {
+            tracing::info!("ENGINE_CONTROL_TOKEN not set; /control disabled");
+            None
+        }
+    };
+    
+    // Ranked universe for /universe; stays empty when the universe is disabled
+    let (universe_tx, universe_rx) = watch::channel(Vec::new());
     
     let metrics_state = ws_server::MetricsState {
-        performance_rx: perf_rx,
-        risk_rx,
+        performance_rx: trading_engine.subscribe_metrics(),
+        risk_rx: trading_engine.subscribe_risk(),
         alert_tx: alert_tx.clone(),
+        decision_tx: trading_engine.decision_sender(),
+        diagnostics: Some(diagnostics_handle),
+        control,
+        universe_rx,
+        symbols: Some(trading_engine.clone()),
+        balances: Some(Arc::new(KeychainBalances {
+            store: Arc::new(CredentialStore::new_simple()),
+            ibkr_gateway: (config.venues.ibkr.gateway_host.clone(), config.venues.ibkr.gateway_port),
+        })),
+        rl_values: Some(trading_engine.rl_values()),
+        send_buffer: config.websocket.send_buffer,
     };
     
     let ws_app = ws_server::create_metrics_server(metrics_state);
@@ -200,6 +293,17 @@ async fn main() -> Result<()> {
     // Setup shutdown signal
     let (shutdown_tx, shutdown_rx) = watch::channel(false);
     
+    // Restart crashed subsystems; a dead trading loop halts trading instead
+    let supervisor = {
+        let engine = trading_engine.clone();
+        supervisor::Supervisor::new(shutdown_rx.clone())
+            .with_alerts(alert_tx.clone())
+            .on_escalate(move |task| {
+                tracing::error!("Critical task {} died; activating kill switch", task);
+                engine.activate_kill_switch();
+            })
+    };
+    
     // Spawn WebSocket server (Axum 0.8)
     let ws_handle = tokio::spawn(async move {
         let addr = format!("{}:{}", config.websocket.host, config.websocket.port);
@@ -219,14 +323,35 @@ async fn main() -> Result<()> {
             refresh_interval_mins: config.universe.refresh_interval_mins,
             min_volume_usd: config.universe.min_volume_usd,
             min_liquidity_usd: config.universe.min_liquidity_usd,
+            min_score: config.universe.min_score,
+            rotation_hysteresis_pct: config.universe.rotation_hysteresis_pct,
+            fetch: config.universe.fetch.clone(),
         };
-        let data_sources = universe::data_sources::DataSources::new();
-        let universe_manager = Arc::new(universe::UniverseManager::new(universe_config, data_sources));
+        let data_sources = match DataSourceKeys::load(&cred_store) {
+            Ok(keys) => universe::data_sources::DataSources::with_keys(keys),
+            Err(e) => {
+                tracing::warn!("No data source keys ({}), keyed sources will be skipped", e);
+                universe::data_sources::DataSources::new()
+            }
+        };
+        let universe_manager = Arc::new(
+            universe::UniverseManager::new(universe_config, data_sources).with_publisher(universe_tx),
+        );
         
         let shutdown_rx_clone = shutdown_rx.clone();
-        Some(tokio::spawn(async move {
-            universe_manager.run(shutdown_rx_clone).await
-        }))
+        let task = supervisor.spawn(
+            "universe_manager",
+            supervisor::RestartPolicy::backoff(
+                std::time::Duration::from_secs(1),
+                std::time::Duration::from_secs(60),
+            ),
+            move || {
+                let universe_manager = universe_manager.clone();
+                let shutdown = shutdown_rx_clone.clone();
+                async move { universe_manager.run(shutdown).await }
+            },
+        );
+        Some(task.handle)
     } else {
         None
     };
@@ -237,14 +362,37 @@ async fn main() -> Result<()> {
         let shutdown_rx_clone = shutdown_rx.clone();
         let advanced_clone = advanced_manager.clone();
         
-        tokio::spawn(async move {
-            run_trading_loop(engine_clone, advanced_clone, shutdown_rx_clone).await
-        })
+        supervisor
+            .spawn("trading_loop", supervisor::RestartPolicy::Escalate, move || {
+                run_trading_loop(engine_clone.clone(), advanced_clone.clone(), shutdown_rx_clone.clone())
+            })
+            .handle
     };
     
-    // Spawn metrics updater
-    let metrics_handle = {
+    // Positions, equity and the daily loss limit follow the venues
+    let marking_handle = {
         let engine_clone = trading_engine.clone();
+        let shutdown_rx_clone = shutdown_rx.clone();
+        
+        supervisor
+            .spawn(
+                "mark_to_market",
+                supervisor::RestartPolicy::backoff(
+                    std::time::Duration::from_secs(1),
+                    std::time::Duration::from_secs(30),
+                ),
+                move || {
+                    let engine = engine_clone.clone();
+                    let shutdown = shutdown_rx_clone.clone();
+                    async move { engine.run_marking(shutdown).await }
+                },
+            )
+            .handle
+    };
+    
+    // Engine metrics reach clients through its own watch channels; only
+    // advanced feature stats are polled
+    let metrics_handle = {
         let advanced_clone = advanced_manager.clone();
         
         tokio::spawn(async move {
@@ -252,10 +400,6 @@ async fn main() -> Result<()> {
             loop {
                 interval.tick().await;
                 
-                // Standard metrics
-                let metrics = engine_clone.get_metrics();
-                let _ = perf_tx.send(metrics);
-                
                 // Advanced features stats
                 if let Some(ref manager) = advanced_clone {
                     let stats = manager.stats();
@@ -265,13 +409,6 @@ async fn main() -> Result<()> {
         })
     };
     
-    // Spawn alert publisher
-    let alert_publisher = Arc::new(ws_server::AlertPublisher::new(
-        alert_tx, 
-        sns_client,
-        config.sns.topic_arn
-    ));
-    
     tracing::info!("All systems initialized successfully");
     
     // Wait for shutdown signal
@@ -289,14 +426,16 @@ async fn main() -> Result<()> {
         let _ = handle.await;
     }
     let _ = engine_handle.await;
+    let _ = marking_handle.await;
+    
+    // Nothing may be left resting on the venues
+    trading_engine.shutdown().await;
     ws_handle.abort();
     metrics_handle.abort();
     
-    // Shutdown advanced features
-    if let Some(manager) = advanced_manager {
-        Arc::try_unwrap(manager)
-            .ok()
-            .map(|m| m.shutdown());
+    // Flush advanced features exports; other clones may still be alive
+    if let Some(manager) = &advanced_manager {
+        manager.shutdown().await;
     }
     
     tracing::info!("Engine shutdown complete");
@@ -329,7 +468,6 @@ async fn run_trading_loop(
                     
                     // GPU feature computation would happen here
                     // RL agent decisions would happen here
-                    // Training sample collection would happen here
                 }
             }
             _ = shutdown.changed() => {
@@ -350,7 +488,6 @@ struct Config {
     gate: GateSection,
     risk: RiskSection,
     universe: UniverseSection,
-    s3: S3Section,
     sns: SnsSection,
     websocket: WebSocketSection,
     models: ModelsSection,
@@ -359,11 +496,124 @@ struct Config {
     enable_aws: bool,
 }
 
+/// The engine's settings from the `[engine]` and `[gate]` sections
+fn engine_config(engine: &EngineSection, gate: &GateSection) -> EngineConfig {
+    EngineConfig {
+        mode: engine.mode,
+        batch_size: engine.batch_size,
+        batch_timeout_ms: engine.batch_timeout_ms,
+        feature_window_size: engine.feature_window_size,
+        inference_timeout_ms: engine.inference_timeout_ms,
+        inference_limits: engine.inference_limits,
+        ensemble: engine.ensemble,
+        gate_params: router::GateParams {
+            enabled: gate.enabled,
+            min_edge_bps: gate.min_edge_bps,
+            min_confidence: gate.min_confidence,
+            max_hold_s: gate.max_hold_s,
+            max_spread_bps: gate.max_spread_bps,
+            book_pressure_weight: gate.book_pressure_weight,
+            maker_margin_bps: gate.maker_margin_bps,
+            maker_max_urgency: gate.maker_max_urgency,
+            spread_percentile: gate.spread_percentile,
+            spread_history: gate.spread_history,
+            spread_min_samples: gate.spread_min_samples,
+        },
+        gpu_device: if engine.gpu_enabled {
+            device_type(&engine.gpu_device, engine.gpu_device_id)
+        } else {
+            DeviceType::CPU
+        },
+        gpu_timeout_ms: engine.gpu_timeout_ms,
+        decision_mode: engine.decision_mode,
+        observe_grace_s: engine.observe_grace_s,
+        slicing: engine.slicing.clone(),
+        marking: engine.marking.clone(),
+        cadence: engine.cadence.clone(),
+        decision_log: engine.decision_log.clone(),
+        slippage: engine.slippage.clone(),
+        feed_grace_s: engine.feed_grace_s,
+        snapshot_queue_capacity: engine.snapshot_queue_capacity,
+        shutdown: engine.shutdown.clone(),
+    }
+}
+
+/// `CUDA`, `ROCm` or `TensorRT`; anything else computes on CPU
+fn device_type(name: &str, device_id: usize) -> DeviceType {
+    match name {
+        "CUDA" => DeviceType::CUDA(device_id),
+        "ROCm" => DeviceType::ROCm(device_id),
+        "TensorRT" => DeviceType::TensorRT,
+        _ => DeviceType::CPU,
+    }
+}
+
 #[derive(serde::Deserialize)]
 struct EngineSection {
     mode: TradingMode,
+    #[serde(default = "default_batch_size")]
+    batch_size: usize,
+    #[serde(default = "default_batch_timeout_ms")]
+    batch_timeout_ms: u64,
+    #[serde(default = "default_feature_window_size")]
     feature_window_size: usize,
+    #[serde(default)]
+    gpu_enabled: bool,
+    #[serde(default)]
+    gpu_device: String,
+    #[serde(default)]
+    gpu_device_id: usize,
+    #[serde(default = "default_decision_mode")]
+    decision_mode: DecisionMode,
     inference_timeout_ms: u64,
+    #[serde(default)]
+    inference_limits: inference::InferenceLimits,
+    #[serde(default)]
+    ensemble: inference::EnsembleConfig,
+    #[serde(default)]
+    observe_grace_s: u64,
+    #[serde(default)]
+    marking: marking::MarkConfig,
+    #[serde(default)]
+    cadence: cadence::CadenceConfig,
+    #[serde(default)]
+    decision_log: Option<String>,
+    #[serde(default)]
+    slippage: slippage::SlippageConfig,
+    #[serde(default = "default_feed_grace_s")]
+    feed_grace_s: u64,
+    #[serde(default)]
+    gpu_timeout_ms: Option<u64>,
+    #[serde(default = "default_snapshot_queue_capacity")]
+    snapshot_queue_capacity: usize,
+    #[serde(default)]
+    shutdown: shutdown::ShutdownConfig,
+    #[serde(default)]
+    slicing: execution::SlicingConfig,
+}
+
+fn default_batch_size() -> usize {
+    EngineConfig::default().batch_size
+}
+
+fn default_batch_timeout_ms() -> u64 {
+    EngineConfig::default().batch_timeout_ms
+}
+
+fn default_feature_window_size() -> usize {
+    EngineConfig::default().feature_window_size
+}
+
+fn default_decision_mode() -> DecisionMode {
+    EngineConfig::default().decision_mode
+}
+
+fn default_feed_grace_s() -> u64 {
+    feed::DEFAULT_GRACE_S
+}
+
+fn default_snapshot_queue_capacity() -> usize {
+    feed::DEFAULT_QUEUE_CAPACITY
 }
 
 #[derive(serde::Deserialize)]
@@ -373,6 +623,38 @@ struct GateSection {
     min_confidence: f64,
     max_hold_s: f64,
     max_spread_bps: f64,
+    #[serde(default = "default_book_pressure_weight")]
+    book_pressure_weight: f64,
+    #[serde(default = "default_maker_margin_bps")]
+    maker_margin_bps: f64,
+    #[serde(default = "default_maker_max_urgency")]
+    maker_max_urgency: f64,
+    #[serde(default)]
+    spread_percentile: f64,
+    #[serde(default = "default_spread_history")]
+    spread_history: usize,
+    #[serde(default = "default_spread_min_samples")]
+    spread_min_samples: usize,
+}
+
+fn default_book_pressure_weight() -> f64 {
+    router::GateParams::default().book_pressure_weight
+}
+
+fn default_maker_margin_bps() -> f64 {
+    router::GateParams::default().maker_margin_bps
+}
+
+fn default_maker_max_urgency() -> f64 {
+    router::GateParams::default().maker_max_urgency
+}
+
+fn default_spread_history() -> usize {
+    router::GateParams::default().spread_history
+}
+
+fn default_spread_min_samples() -> usize {
+    router::GateParams::default().spread_min_samples
 }
 
 #[derive(serde::Deserialize)]
@@ -382,6 +664,34 @@ struct RiskSection {
     max_leverage: f64,
     max_loss_per_day: f64,
     max_position_concentration: f64,
+    #[serde(default = "default_max_open_positions")]
+    max_open_positions: usize,
+    #[serde(default = "default_base_currency")]
+    base_currency: String,
+    #[serde(default = "default_reference_rates")]
+    reference_rates: HashMap<String, f64>,
+    #[serde(default)]
+    reset_hour_utc: u32,
+    #[serde(default)]
+    var_correlation: f64,
+    #[serde(default = "default_kill_switch_loss_multiple")]
+    kill_switch_loss_multiple: f64,
+}
+
+fn default_max_open_positions() -> usize {
+    RiskLimits::default().max_open_positions
+}
+
+fn default_base_currency() -> String {
+    RiskLimits::default().base_currency
+}
+
+fn default_kill_switch_loss_multiple() -> f64 {
+    RiskLimits::default().kill_switch_loss_multiple
+}
+
+fn default_reference_rates() -> HashMap<String, f64> {
+    RiskLimits::default().reference_rates
 }
 
 #[derive(serde::Deserialize)]
@@ -395,13 +705,16 @@ struct UniverseSection {
     refresh_interval_mins: u64,
     min_volume_usd: f64,
     min_liquidity_usd: f64,
+    #[serde(default)]
+    min_score: f64,
+    #[serde(default = "default_rotation_hysteresis_pct")]
+    rotation_hysteresis_pct: f64,
+    #[serde(default)]
+    fetch: universe::FetchPolicy,
 }
 
-#[derive(serde::Deserialize)]
-struct S3Section {
-    enabled: bool,
-    bucket: String,
-    region: String,
+fn default_rotation_hysteresis_pct() -> f64 {
+    universe::UniverseConfig::default().rotation_hysteresis_pct
 }
 
 #[derive(serde::Deserialize)]
@@ -414,6 +727,8 @@ struct SnsSection {
 struct WebSocketSection {
     host: String,
     port: u16,
+    #[serde(default)]
+    send_buffer: ws_server::SendBuffer,
 }
 
 #[derive(serde::Deserialize)]
@@ -433,6 +748,36 @@ struct VenuesSection {
 struct VenueConfig {
     enabled: bool,
     rate_limit_per_sec: u64,
+    /// Live data and decisions, but orders are only logged (not paper mode)
+    #[serde(default)]
+    dry_run: bool,
+    /// REST book polling while the WS is down; only Hyperliquid supports it
+    rest_fallback: Option<RestFallbackSection>,
+}
+
+#[derive(serde::Deserialize)]
+struct RestFallbackSection {
+    enabled: bool,
+    #[serde(default = "default_fallback_activate_after_ms")]
+    activate_after_ms: u64,
+    #[serde(default = "default_fallback_poll_interval_ms")]
+    poll_interval_ms: u64,
+}
+
+fn default_fallback_activate_after_ms() -> u64 {
+    RestFallbackConfig::default().activate_after.as_millis() as u64
+}
+
+fn default_fallback_poll_interval_ms() -> u64 {
+    RestFallbackConfig::default().poll_interval.as_millis() as u64
+}
+
+fn rest_fallback_config(section: &RestFallbackSection) -> RestFallbackConfig {
+    RestFallbackConfig {
+        enabled: section.enabled,
+        activate_after: std::time::Duration::from_millis(section.activate_after_ms),
+        poll_interval: std::time::Duration::from_millis(section.poll_interval_ms),
+    }
 }
 
 #[derive(serde::Deserialize)]
@@ -440,6 +785,12 @@ struct IbkrConfig {
     enabled: bool,
     gateway_host: String,
     gateway_port: u16,
+    /// Same as `VenueConfig::dry_run`
+    #[serde(default)]
+    dry_run: bool,
+    /// IB warning message ids confirmed automatically; others reject the order
+    #[serde(default)]
+    confirm_warnings: Vec<String>,
 }
 
 #[derive(serde::Deserialize)]
@@ -504,6 +855,48 @@ fn load_config() -> Result<Config> {
     Ok(config)
 }
 
+/// Raw config as JSON for diagnostics (redacted when dumped)
+fn load_config_json() -> serde_json::Value {
+    std::fs::read_to_string("config/engine.toml")
+        .ok()
+        .and_then(|s| toml::from_str::<toml::Value>(&s).ok())
+        .and_then(|v| serde_json::to_value(v).ok())
+        .unwrap_or(serde_json::Value::Null)
+}
+
+/// Balances for `/balances`, read with the account's own stored credentials
+struct KeychainBalances {
+    store: Arc<CredentialStore>,
+    ibkr_gateway: (String, u16),
+}
+
+#[async_trait::async_trait]
+impl ws_server::BalanceSource for KeychainBalances {
+    async fn balances(&self, venue: Venue, label: &str, live: bool) -> Result<HashMap<String, Balance>> {
+        match venue {
+            Venue::Hyperliquid => HyperliquidAdapter::new(self.credentials(venue, label, live).await?).balances().await,
+            Venue::BinanceFutures => BinanceAdapter::new(self.credentials(venue, label, live).await?).balances().await,
+            Venue::IBKR => {
+                // IB authenticates through the gateway session, not stored keys
+                let mut adapter = IbkrAdapter::new(&self.ibkr_gateway.0, self.ibkr_gateway.1);
+                adapter.connect().await?;
+                adapter.balances().await
+            }
+        }
+    }
+}
+
+impl KeychainBalances {
+    /// Keychain reads block, so they run off the runtime's workers
+    async fn credentials(&self, venue: Venue, label: &str, live: bool) -> Result<ApiCredentials> {
+        let store = self.store.clone();
+        let label = label.to_string();
+        tokio::task::spawn_blocking(move || store.load(venue, &label, live))
+            .await
+            .map_err(|e| Error::Internal(format!("credential lookup panicked: {}", e)))?
+    }
+}
+
 fn load_hyperliquid_adapter(store: &CredentialStore) -> Result<HyperliquidAdapter> {
     match store.load(Venue::Hyperliquid, "default", false) {
         Ok(creds) => Ok(HyperliquidAdapter::new(creds)),
@@ -518,3 +911,81 @@ fn load_hyperliquid_adapter(store: &CredentialStore) -> Result<HyperliquidAdapte
         }
     }
 }
+
+fn load_binance_adapter(store: &CredentialStore) -> Result<BinanceAdapter> {
+    match store.load(Venue::BinanceFutures, "default", false) {
+        Ok(creds) => Ok(BinanceAdapter::new(creds)),
+        Err(_) => {
+            let api_key = std::env::var("BINANCE_API_KEY")
+                .map_err(|_| Error::Config("BINANCE_API_KEY not set".to_string()))?;
+            let api_secret = std::env::var("BINANCE_SECRET")
+                .map_err(|_| Error::Config("BINANCE_SECRET not set".to_string()))?;
+            
+            let creds = ApiCredentials::new(api_key, api_secret, false);
+            Ok(BinanceAdapter::new(creds))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_engine_config_from_toml() {
+        let engine: EngineSection = toml::from_str(r#"
+            mode = "Paper"
+            gpu_enabled = true
+            gpu_device = "CUDA"
+            gpu_device_id = 1
+            batch_size = 64
+            batch_timeout_ms = 5
+            inference_timeout_ms = 3
+            decision_mode = "MLEnsemble"
+            
+            [shutdown]
+            close_positions = true
+        "#).unwrap();
+        let gate: GateSection = toml::from_str(r#"
+            enabled = true
+            min_edge_bps = 5.0
+            min_confidence = 0.6
+            max_hold_s = 300.0
+            max_spread_bps = 20.0
+        "#).unwrap();
+        let config = engine_config(&engine, &gate);
+        assert_eq!(config.mode, TradingMode::Paper);
+        assert_eq!((config.batch_size, config.batch_timeout_ms), (64, 5));
+        assert!(matches!(config.gpu_device, DeviceType::CUDA(1)));
+        assert_eq!(config.decision_mode, DecisionMode::MLEnsemble);
+        assert!(config.shutdown.close_positions);
+        
+        // Keys left out fall back to EngineConfig's defaults
+        let defaults = EngineConfig::default();
+        assert_eq!(config.feature_window_size, defaults.feature_window_size);
+        assert_eq!(config.feed_grace_s, defaults.feed_grace_s);
+        assert_eq!(config.snapshot_queue_capacity, defaults.snapshot_queue_capacity);
+        assert!(config.gpu_timeout_ms.is_none());
+        assert_eq!(config.gate_params.spread_history, defaults.gate_params.spread_history);
+    }
+    
+    #[test]
+    fn test_rest_fallback_from_toml() {
+        let venue: VenueConfig = toml::from_str(r#"
+            enabled = true
+            rate_limit_per_sec = 10
+            
+            [rest_fallback]
+            enabled = true
+            poll_interval_ms = 250
+        "#).unwrap();
+        let fallback = rest_fallback_config(venue.rest_fallback.as_ref().unwrap());
+        assert!(fallback.enabled);
+        assert_eq!(fallback.poll_interval, std::time::Duration::from_millis(250));
+        assert_eq!(fallback.activate_after, RestFallbackConfig::default().activate_after);
+        
+        // No section leaves the adapter's default (off)
+        let venue: VenueConfig = toml::from_str("enabled = true\nrate_limit_per_sec = 10").unwrap();
+        assert!(venue.rest_fallback.is_none());
+    }
+}
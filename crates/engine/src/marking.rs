@@ -0,0 +1,196 @@
+// crates/engine/src/marking.rs
+use common::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Price positions are marked at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MarkSource {
+    /// Top-of-book mid
+    #[default]
+    Mid,
+    /// Size-weighted top-of-book microprice
+    Microprice,
+    /// Mark price reported by the venue with the position, mid if it has none
+    VenueMark,
+}
+
+/// Mark source selection: a venue override wins over a category override,
+/// which wins over `default`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct MarkConfig {
+    pub default: MarkSource,
+    pub by_category: HashMap<AssetCategory, MarkSource>,
+    pub by_venue: HashMap<Venue, MarkSource>,
+    /// How often positions are re-fetched and marked
+    pub interval_ms: u64,
+}
+
+impl Default for MarkConfig {
+    fn default() -> Self {
+        Self {
+            default: MarkSource::Mid,
+            by_category: HashMap::new(),
+            by_venue: HashMap::new(),
+            interval_ms: 1000,
+        }
+    }
+}
+
+impl MarkConfig {
+    pub fn source(&self, venue: Venue, category: AssetCategory) -> MarkSource {
+        self.by_venue
+            .get(&venue)
+            .or_else(|| self.by_category.get(&category))
+            .copied()
+            .unwrap_or(self.default)
+    }
+}
+
+/// Book-derived reference prices, kept per symbol from the latest snapshot
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BookMarks {
+    pub mid: f64,
+    pub microprice: f64,
+    pub bid: f64,
+    pub ask: f64,
+    pub timestamp_ns: i64,
+}
+
+impl BookMarks {
+    pub fn from_book(book: &OrderBook) -> Option<Self> {
+        let mid = book.mid_price()?;
+        Some(Self {
+            mid,
+            microprice: book.microprice().unwrap_or(mid),
+            bid: book.best_bid()?.price.0,
+            ask: book.best_ask()?.price.0,
+            timestamp_ns: book.timestamp_ns,
+        })
+    }
+}
+
+/// Mark price for `source`; `None` if neither the book nor the venue has one
+pub fn mark_price(source: MarkSource, book: Option<&BookMarks>, venue_mark: Option<f64>) -> Option<f64> {
+    let venue_mark = venue_mark.filter(|m| m.is_finite() && *m > 0.0);
+    
+    match source {
+        MarkSource::Mid => book.map(|b| b.mid),
+        MarkSource::Microprice => book.map(|b| b.microprice),
+        MarkSource::VenueMark => venue_mark.or(book.map(|b| b.mid)),
+    }
+}
+
+/// Set the mark and recompute unrealized PnL from the entry price
+pub fn apply_mark(position: &mut Position, mark: f64) {
+    position.mark_price = mark;
+    position.unrealized_pnl = position.size * (mark - position.entry_price);
+}
+
+/// Set the tiered maintenance margin at the mark, and derive a liquidation
+/// price where the venue didn't report one. `margin_used` stays the venue's
+/// initial margin.
+pub fn apply_margin(position: &mut Position, tiers: &[margin::LeverageTier]) {
+    let Some(maintenance) = margin::maintenance_margin(tiers, position.size * position.mark_price) else {
+        return;
+    };
+    position.maintenance_margin = Some(maintenance);
+    
+    if position.liquidation_price.is_none() {
+        position.liquidation_price = margin::liquidation_price(position, tiers);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_mark_sources_upnl() {
+        // Bid-heavy book: mid 102, microprice (101 * 1 + 103 * 3) / 4 = 102.5
+        let level = |price: f64, quantity: f64| Level { price: price.into(), quantity };
+        let book = OrderBook {
+            symbol: "BTC".to_string(),
+            timestamp_ns: 1,
+            bids: vec![level(101.0, 3.0)],
+            asks: vec![level(103.0, 1.0)],
+            sequence: 1,
+        };
+        let marks = BookMarks::from_book(&book).unwrap();
+        
+        let upnl = |source: MarkSource, venue_mark: Option<f64>| {
+            let mut position = Position {
+                symbol: "BTC".to_string(),
+                size: 2.0,
+                entry_price: 100.0,
+                mark_price: venue_mark.unwrap_or(0.0),
+                unrealized_pnl: 0.0,
+                realized_pnl: 0.0,
+                leverage: 1.0,
+                margin_used: 0.0,
+                liquidation_price: None,
+                maintenance_margin: None,
+            };
+            apply_mark(&mut position, mark_price(source, Some(&marks), venue_mark).unwrap());
+            position.unrealized_pnl
+        };
+        
+        assert!((upnl(MarkSource::Mid, None) - 4.0).abs() < 1e-9);
+        assert!((upnl(MarkSource::Microprice, None) - 5.0).abs() < 1e-9);
+        assert!((upnl(MarkSource::VenueMark, Some(101.5)) - 3.0).abs() < 1e-9);
+        // No venue mark: falls back to mid
+        assert!((upnl(MarkSource::VenueMark, None) - 4.0).abs() < 1e-9);
+        
+        let config = MarkConfig {
+            by_category: HashMap::from([(AssetCategory::CryptoFutures, MarkSource::VenueMark)]),
+            by_venue: HashMap::from([(Venue::BinanceFutures, MarkSource::Microprice)]),
+            ..MarkConfig::default()
+        };
+        assert_eq!(config.source(Venue::Hyperliquid, AssetCategory::CryptoFutures), MarkSource::VenueMark);
+        assert_eq!(config.source(Venue::BinanceFutures, AssetCategory::CryptoFutures), MarkSource::Microprice);
+        assert_eq!(config.source(Venue::IBKR, AssetCategory::Equity), MarkSource::Mid);
+    }
+    
+    #[test]
+    fn test_large_position_uses_higher_tier_maintenance() {
+        let mut tiers = vec![
+            margin::LeverageTier { notional_floor: 0.0, max_leverage: 50.0, maintenance_margin_rate: 0.01, maintenance_amount: 0.0 },
+            margin::LeverageTier { notional_floor: 250_000.0, max_leverage: 20.0, maintenance_margin_rate: 0.025, maintenance_amount: 0.0 },
+        ];
+        margin::fill_maintenance_amounts(&mut tiers);
+        
+        let position = |size: f64, liquidation_price: Option<f64>| Position {
+            symbol: "BTC".to_string(),
+            size,
+            entry_price: 50_000.0,
+            mark_price: 50_000.0,
+            unrealized_pnl: 0.0,
+            realized_pnl: 0.0,
+            leverage: 10.0,
+            margin_used: size.abs() * 5_000.0,
+            liquidation_price,
+            maintenance_margin: None,
+        };
+        
+        let mut small = position(1.0, None);
+        apply_margin(&mut small, &tiers);
+        assert!((small.maintenance_margin.unwrap() - 500.0).abs() < 1e-6);
+        assert_eq!(small.margin_used, 5_000.0);
+        
+        // 1M notional: 2.5% tier less the 3,750 continuity deduction
+        let mut large = position(-20.0, None);
+        apply_margin(&mut large, &tiers);
+        let maintenance = large.maintenance_margin.unwrap();
+        assert!((maintenance - 21_250.0).abs() < 1e-6);
+        assert!(maintenance > 1_000_000.0 * 0.01);
+        assert_eq!(large.margin_used, 100_000.0);
+        assert!(large.liquidation_price.unwrap() > 50_000.0);
+        
+        // Venue-reported liquidation prices are kept
+        let mut reported = position(1.0, Some(45_000.0));
+        apply_margin(&mut reported, &tiers);
+        assert_eq!(reported.liquidation_price, Some(45_000.0));
+    }
+}
@@ -0,0 +1,122 @@
+// crates/engine/src/observation.rs - Observe-only grace period for new symbols
+use common::*;
+use std::collections::HashMap;
+
+/// What to do with a routing decision for a symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Admission {
+    /// Eligible: send the order
+    Trade,
+    /// Still observing: log the would-be decision, place nothing
+    Shadow,
+    /// Decision didn't want to trade anyway
+    Skip,
+}
+
+/// Tracks when each symbol joined the active selection and holds it in
+/// observe-only mode until the grace period has elapsed
+#[derive(Debug)]
+pub struct ObservationTracker {
+    grace_ns: i64,
+    joined_ns: HashMap<String, i64>,
+    shadow_decisions: HashMap<String, u64>,
+}
+
+impl ObservationTracker {
+    /// `grace_s == 0` disables observation
+    pub fn new(grace_s: u64) -> Self {
+        Self {
+            grace_ns: grace_s as i64 * 1_000_000_000,
+            joined_ns: HashMap::new(),
+            shadow_decisions: HashMap::new(),
+        }
+    }
+    
+    pub fn set_grace_s(&mut self, grace_s: u64) {
+        self.grace_ns = grace_s as i64 * 1_000_000_000;
+    }
+    
+    /// Start (or restart) the grace period for a symbol joining the selection
+    pub fn on_join(&mut self, symbol: &str, now_ns: i64) {
+        self.joined_ns.insert(symbol.to_string(), now_ns);
+        self.shadow_decisions.remove(symbol);
+    }
+    
+    pub fn on_leave(&mut self, symbol: &str) {
+        self.joined_ns.remove(symbol);
+        self.shadow_decisions.remove(symbol);
+    }
+    
+    /// Symbols never registered via `on_join` are not held back
+    pub fn is_observing(&self, symbol: &str, now_ns: i64) -> bool {
+        self.joined_ns
+            .get(symbol)
+            .is_some_and(|joined| now_ns - joined < self.grace_ns)
+    }
+    
+    /// Gate a decision, counting shadow decisions made while observing
+    pub fn admit(&mut self, symbol: &str, decision: &RouteDecision, now_ns: i64) -> Admission {
+        if !decision.should_trade {
+            return Admission::Skip;
+        }
+        
+        if self.is_observing(symbol, now_ns) {
+            *self.shadow_decisions.entry(symbol.to_string()).or_default() += 1;
+            return Admission::Shadow;
+        }
+        
+        Admission::Trade
+    }
+    
+    pub fn shadow_decisions(&self, symbol: &str) -> u64 {
+        self.shadow_decisions.get(symbol).copied().unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const SEC: i64 = 1_000_000_000;
+    
+    fn decision(should_trade: bool) -> RouteDecision {
+        RouteDecision {
+            style: OrderStyle::MakerPassive,
+            size_fraction: 0.02,
+            hold_duration_s: 30.0,
+            urgency: 0.5,
+            should_trade,
+            side: None,
+            reduce_only: false,
+            reason: "test".to_string(),
+        }
+    }
+    
+    #[test]
+    fn test_new_symbol_shadows_until_grace_elapses() {
+        let mut tracker = ObservationTracker::new(60);
+        tracker.on_join("SOL", 0);
+        
+        assert_eq!(tracker.admit("SOL", &decision(true), 10 * SEC), Admission::Shadow);
+        assert_eq!(tracker.admit("SOL", &decision(true), 59 * SEC), Admission::Shadow);
+        assert_eq!(tracker.admit("SOL", &decision(false), 30 * SEC), Admission::Skip);
+        assert_eq!(tracker.shadow_decisions("SOL"), 2);
+        
+        assert_eq!(tracker.admit("SOL", &decision(true), 60 * SEC), Admission::Trade);
+        
+        // Established symbols are unaffected
+        assert_eq!(tracker.admit("BTC", &decision(true), 10 * SEC), Admission::Trade);
+        
+        // Rejoining restarts the grace period
+        tracker.on_join("SOL", 100 * SEC);
+        assert_eq!(tracker.admit("SOL", &decision(true), 101 * SEC), Admission::Shadow);
+    }
+    
+    #[test]
+    fn test_zero_grace_disables_observation() {
+        let mut tracker = ObservationTracker::new(0);
+        tracker.on_join("SOL", 0);
+        
+        assert_eq!(tracker.admit("SOL", &decision(true), 0), Admission::Trade);
+    }
+}
@@ -0,0 +1,100 @@
+// crates/engine/src/registry.rs - Symbol → venue / asset category
+use common::*;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+
+/// Where a tracked symbol trades
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SymbolInfo {
+    pub venue: Venue,
+    pub category: AssetCategory,
+}
+
+/// Tracked symbols, filled in by `TradingEngine::add_symbol`
+#[derive(Default)]
+pub struct SymbolRegistry {
+    symbols: RwLock<HashMap<String, SymbolInfo>>,
+}
+
+impl SymbolRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    pub fn register(&self, symbol: impl Into<String>, venue: Venue) -> SymbolInfo {
+        let info = SymbolInfo { venue, category: venue.category() };
+        self.symbols.write().insert(symbol.into(), info);
+        info
+    }
+    
+    pub fn remove(&self, symbol: &str) {
+        self.symbols.write().remove(symbol);
+    }
+    
+    pub fn get(&self, symbol: &str) -> Result<SymbolInfo> {
+        self.symbols
+            .read()
+            .get(symbol)
+            .copied()
+            .ok_or_else(|| Error::NotFound(format!("Symbol {} was never registered (call add_symbol first)", symbol)))
+    }
+    
+    pub fn category(&self, symbol: &str) -> Result<AssetCategory> {
+        self.get(symbol).map(|info| info.category)
+    }
+    
+    pub fn venue(&self, symbol: &str) -> Result<Venue> {
+        self.get(symbol).map(|info| info.venue)
+    }
+    
+    /// Tracked symbols on `venue`, sorted
+    pub fn symbols_on(&self, venue: Venue) -> Vec<String> {
+        let mut symbols: Vec<String> = self.symbols
+            .read()
+            .iter()
+            .filter(|(_, info)| info.venue == venue)
+            .map(|(symbol, _)| symbol.clone())
+            .collect();
+        symbols.sort();
+        symbols
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inference::{InferencePool, ModelType};
+    use ndarray::Array1;
+    
+    #[test]
+    fn test_symbol_categories() {
+        let registry = SymbolRegistry::new();
+        registry.register("AAPL", Venue::IBKR);
+        registry.register("BTC", Venue::Hyperliquid);
+        
+        assert_eq!(registry.category("AAPL").unwrap(), AssetCategory::Equity);
+        assert_eq!(registry.category("BTC").unwrap(), AssetCategory::CryptoFutures);
+        assert_eq!(registry.venue("AAPL").unwrap(), Venue::IBKR);
+        assert_eq!(registry.symbols_on(Venue::Hyperliquid), vec!["BTC".to_string()]);
+        assert!(matches!(registry.get("ETH"), Err(Error::NotFound(_))));
+    }
+    
+    #[tokio::test]
+    async fn test_predictions_use_symbol_model_set() {
+        let registry = SymbolRegistry::new();
+        registry.register("AAPL", Venue::IBKR);
+        registry.register("BTC", Venue::Hyperliquid);
+        
+        // No models loaded: the error names the model set that was consulted
+        let pool = InferencePool::new(100).unwrap();
+        for (symbol, expected) in [("AAPL", "Equity"), ("BTC", "CryptoFutures")] {
+            let category = registry.category(symbol).unwrap();
+            let err = pool.predict(category, &Array1::zeros(100), ModelType::Edge)
+                .await
+                .unwrap_err()
+                .to_string();
+            
+            assert!(err.contains(expected), "{} routed to wrong model set: {}", symbol, err);
+        }
+    }
+}
@@ -0,0 +1,308 @@
+// crates/engine/src/replay.rs - Decision log and single-decision replay
+use crate::router::{CostModel, OrderRouter, RiskState};
+use common::*;
+use features::{ComputedFeatures, Device};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Everything the ML decision path saw for one signal, enough to re-run it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecisionRecord {
+    pub timestamp_ns: i64,
+    pub symbol: String,
+    pub category: AssetCategory,
+    /// Raw feature vector in `features::layout` order
+    pub features: Vec<f32>,
+    pub prediction: Prediction,
+    pub costs: CostModel,
+    /// Risk state the gate checked against
+    pub risk: RiskState,
+    pub decision: RouteDecision,
+    /// An order was sent for the decision
+    #[serde(default)]
+    pub executed: bool,
+}
+
+impl DecisionRecord {
+    /// Route `prediction` against the live risk state, keeping every input.
+    /// The record starts out not executed.
+    pub fn route(
+        router: &OrderRouter,
+        computed: &ComputedFeatures,
+        category: AssetCategory,
+        features: &FeatureVec,
+        prediction: Prediction,
+        costs: CostModel,
+    ) -> Self {
+        let risk = router.get_risk_manager().read().get_state();
+        let decision = router.decide_with_state(&prediction, features, &costs, &risk);
+        
+        Self {
+            timestamp_ns: computed.timestamp_ns,
+            symbol: computed.symbol.clone(),
+            category,
+            features: computed.features.to_vec(),
+            prediction,
+            costs,
+            risk,
+            decision,
+            executed: false,
+        }
+    }
+    
+    /// The logged features as the pipeline computed them
+    pub fn computed_features(&self) -> ComputedFeatures {
+        ComputedFeatures {
+            symbol: self.symbol.clone(),
+            timestamp_ns: self.timestamp_ns,
+            features: ndarray::Array1::from_vec(self.features.clone()),
+            computed_on: Device::CPU,
+        }
+    }
+    
+    /// Model-facing features, with the same equity adjustment as live
+    pub fn feature_vec(&self) -> FeatureVec {
+        let mut vec = self.computed_features().to_feature_vec();
+        if self.category == AssetCategory::Equity {
+            vec.funding_bps_8h = 0.0;
+        }
+        vec
+    }
+}
+
+/// Re-run the read-only routing path on a record's logged inputs.
+/// `prediction` is the current model's output on `record.features`; passing
+/// `record.prediction` replays the router alone.
+pub fn replay_decision(router: &OrderRouter, record: &DecisionRecord, prediction: &Prediction) -> RouteDecision {
+    router.decide_with_state(prediction, &record.feature_vec(), &record.costs, &record.risk)
+}
+
+/// Append-only JSON-lines decision log. Records are queued to a writer task
+/// that buffers them and flushes whenever the queue runs dry, so the decision
+/// path never waits on the disk.
+pub struct DecisionLog {
+    tx: Mutex<Option<mpsc::UnboundedSender<DecisionRecord>>>,
+    writer: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl DecisionLog {
+    /// Open (or create) the log and start its writer; needs a Tokio runtime
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let (tx, rx) = mpsc::unbounded_channel();
+        let writer = tokio::spawn(run_writer(tokio::fs::File::from_std(file), rx));
+        
+        Ok(Self {
+            tx: Mutex::new(Some(tx)),
+            writer: Mutex::new(Some(writer)),
+        })
+    }
+    
+    pub fn append(&self, record: &DecisionRecord) -> Result<()> {
+        let sent = self.tx
+            .lock()
+            .as_ref()
+            .is_some_and(|tx| tx.send(record.clone()).is_ok());
+        
+        if sent {
+            Ok(())
+        } else {
+            Err(Error::Internal("Decision log is closed".to_string()))
+        }
+    }
+    
+    /// Stop taking records and wait until every queued one is on disk.
+    /// Idempotent.
+    pub async fn shutdown(&self) {
+        self.tx.lock().take();
+        
+        let writer = self.writer.lock().take();
+        if let Some(writer) = writer {
+            if let Err(e) = writer.await {
+                tracing::error!("Decision log writer failed: {}", e);
+            }
+        }
+    }
+}
+
+async fn run_writer(file: tokio::fs::File, mut rx: mpsc::UnboundedReceiver<DecisionRecord>) {
+    let mut out = tokio::io::BufWriter::new(file);
+    
+    while let Some(record) = rx.recv().await {
+        write_record(&mut out, &record).await;
+        while let Ok(record) = rx.try_recv() {
+            write_record(&mut out, &record).await;
+        }
+        
+        if let Err(e) = out.flush().await {
+            tracing::warn!("Decision log flush failed: {}", e);
+        }
+    }
+}
+
+async fn write_record(out: &mut tokio::io::BufWriter<tokio::fs::File>, record: &DecisionRecord) {
+    let mut line = match serde_json::to_vec(record) {
+        Ok(line) => line,
+        Err(e) => {
+            tracing::warn!("Decision for {} not logged: {}", record.symbol, e);
+            return;
+        }
+    };
+    line.push(b'\n');
+    
+    if let Err(e) = out.write_all(&line).await {
+        tracing::warn!("Decision log write failed: {}", e);
+    }
+}
+
+/// Read every record of a decision log
+pub fn read_decision_log(path: impl AsRef<Path>) -> Result<Vec<DecisionRecord>> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut records = Vec::new();
+    
+    for line in reader.lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            records.push(serde_json::from_str(&line)?);
+        }
+    }
+    
+    Ok(records)
+}
+
+/// The record for `symbol` at `timestamp_ns`
+pub fn find_record(records: &[DecisionRecord], symbol: &str, timestamp_ns: i64) -> Result<DecisionRecord> {
+    records
+        .iter()
+        .find(|r| r.symbol == symbol && r.timestamp_ns == timestamp_ns)
+        .cloned()
+        .ok_or_else(|| Error::NotFound(format!("No logged decision for {} at {}", symbol, timestamp_ns)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::router::GateParams;
+    use features::layout;
+    
+    /// A decision log as the engine wrote it: one traded signal at 7, one
+    /// gated out for low confidence at 9
+    const DECISION_LOG_FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/decision_log.jsonl");
+    
+    fn assert_same_decision(replayed: &RouteDecision, logged: &RouteDecision) {
+        assert_eq!(replayed.style, logged.style);
+        assert_eq!(replayed.side, logged.side);
+        assert_eq!(replayed.should_trade, logged.should_trade);
+        assert_eq!(replayed.reduce_only, logged.reduce_only);
+        assert_eq!(replayed.reason, logged.reason);
+        for (a, b) in [
+            (replayed.size_fraction, logged.size_fraction),
+            (replayed.hold_duration_s, logged.hold_duration_s),
+            (replayed.urgency, logged.urgency),
+        ] {
+            assert!((a - b).abs() < 1e-9, "{} vs {}", a, b);
+        }
+    }
+    
+    #[test]
+    fn test_logged_decision_replays_to_same_result() {
+        let router = OrderRouter::new(GateParams::default(), RiskLimits::default());
+        let records = read_decision_log(DECISION_LOG_FIXTURE).unwrap();
+        assert_eq!(records.len(), 2);
+        
+        let logged = find_record(&records, "BTC", 7).unwrap();
+        assert!(logged.executed);
+        assert_eq!(logged.features[layout::MID_PRICE], 50_000.0);
+        
+        let replayed = replay_decision(&router, &logged, &logged.prediction);
+        assert!(replayed.should_trade);
+        assert_same_decision(&replayed, &logged.decision);
+        
+        let gated = find_record(&records, "BTC", 9).unwrap();
+        assert_same_decision(&replay_decision(&router, &gated, &gated.prediction), &gated.decision);
+        
+        // A stronger model output on the same inputs routes differently
+        let mut prediction = logged.prediction.clone();
+        prediction.confidence = 1.0;
+        assert!(replay_decision(&router, &logged, &prediction).size_fraction > logged.decision.size_fraction);
+        
+        assert!(find_record(&records, "BTC", 8).is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_log_written_off_the_decision_path() {
+        let records = read_decision_log(DECISION_LOG_FIXTURE).unwrap();
+        
+        let path = std::env::temp_dir().join(format!("decision-log-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let log = DecisionLog::open(&path).unwrap();
+        for record in &records {
+            log.append(record).unwrap();
+        }
+        
+        // Shutdown drains the queue to disk, then refuses new records
+        log.shutdown().await;
+        assert!(log.append(&records[0]).is_err());
+        log.shutdown().await;
+        
+        let written = read_decision_log(&path).unwrap();
+        assert_eq!(
+            serde_json::to_value(&written).unwrap(),
+            serde_json::to_value(&records).unwrap(),
+        );
+        let _ = std::fs::remove_file(&path);
+    }
+    
+    #[test]
+    fn test_gated_out_signal_is_recorded() {
+        let router = OrderRouter::new(GateParams::default(), RiskLimits::default());
+        
+        let mut raw = vec![0.0f32; layout::NUM_FEATURES];
+        raw[layout::MID_PRICE] = 50_000.0;
+        raw[layout::SPREAD_BPS] = 2.0;
+        let computed = ComputedFeatures {
+            symbol: "BTC".to_string(),
+            timestamp_ns: 9,
+            features: ndarray::Array1::from_vec(raw),
+            computed_on: Device::CPU,
+        };
+        
+        let prediction = Prediction {
+            timestamp_ns: 9,
+            symbol: "BTC".to_string(),
+            edge_bps: 15.0,
+            confidence: 0.2,
+            horizon_ms: 5000,
+            model_version: "test".to_string(),
+        };
+        let costs = CostModel::from_fees(&crate::router::FALLBACK_FEES, 0.5, 1.0, 1.0);
+        
+        let record = DecisionRecord::route(
+            &router,
+            &computed,
+            AssetCategory::CryptoFutures,
+            &computed.to_feature_vec(),
+            prediction,
+            costs,
+        );
+        
+        assert_eq!(record.symbol, "BTC");
+        assert_eq!(record.timestamp_ns, 9);
+        assert!(!record.decision.should_trade);
+        assert!(record.decision.reason.starts_with("Low confidence"), "{}", record.decision.reason);
+        assert!(!record.executed);
+        
+        // Subscribers see it as published
+        let (tx, mut rx) = tokio::sync::broadcast::channel(8);
+        tx.send(record).unwrap();
+        let received = rx.try_recv().unwrap();
+        assert_eq!(received.prediction.confidence, 0.2);
+        assert!(!received.decision.should_trade);
+    }
+}
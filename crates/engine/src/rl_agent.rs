@@ -3,6 +3,7 @@ use common::*;
 use ndarray::{Array1, Array2};
 use ort::{Session, Value};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use parking_lot::RwLock;
 
 /// RL Agent for trading decisions
@@ -11,6 +12,8 @@ pub struct RLAgent {
     critic: Option<Arc<Session>>,
     config: RLAgentConfig,
     state_buffer: Arc<RwLock<StateBuffer>>,
+    /// Whether the last `get_action` produced an action
+    healthy: AtomicBool,
 }
 
 #[derive(Debug, Clone)]
@@ -60,15 +63,31 @@ impl RLAgent {
                 states: Vec::new(),
                 max_length: config.sequence_length,
             })),
+            healthy: AtomicBool::new(true),
         })
     }
     
+    /// False once an action fails, until one succeeds again
+    pub fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::Relaxed)
+    }
+    
+    pub fn has_critic(&self) -> bool {
+        self.critic.is_some()
+    }
+    
     /// Get action from current state
     pub fn get_action(
         &self,
         features: &Array1<f32>,
         market_state: &MarketState,
     ) -> Result<RLAction> {
+        let action = self.infer(features, market_state);
+        self.healthy.store(action.is_ok(), Ordering::Relaxed);
+        action
+    }
+    
+    fn infer(&self, features: &Array1<f32>, market_state: &MarketState) -> Result<RLAction> {
         // Build state vector
         let state = self.build_state(features, market_state);
         
@@ -94,9 +113,9 @@ impl RLAgent {
         
         // Sample action
         let action = match self.config.action_type {
-            ActionType::Discrete => self.sample_discrete(action_logits),
-            ActionType::Continuous => self.sample_continuous(action_logits),
-            ActionType::MultiDiscrete => self.sample_multi_discrete(action_logits),
+            ActionType::Discrete => sample_discrete(action_logits, &self.config),
+            ActionType::Continuous => sample_continuous(action_logits, &self.config),
+            ActionType::MultiDiscrete => sample_multi_discrete(action_logits),
         }?;
         
         // Get value estimate if critic available
@@ -145,51 +164,6 @@ impl RLAgent {
         Ok(Value::from_array(array)?)
     }
     
-    fn sample_discrete(&self, logits: &[f32]) -> Result<Action> {
-        let probs = softmax(logits, self.config.temperature);
-        
-        let action_idx = if self.config.epsilon > 0.0 && rand::random::<f64>() < self.config.epsilon {
-            rand::random::<usize>() % probs.len()
-        } else {
-            probs.iter()
-                .enumerate()
-                .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
-                .map(|(i, _)| i)
-                .unwrap()
-        };
-        
-        Ok(Action::Discrete(action_idx))
-    }
-    
-    fn sample_continuous(&self, logits: &[f32]) -> Result<Action> {
-        // Assume logits = [mean, log_std]
-        let mean = logits[0];
-        let std = logits[1].exp().clamp(0.01, 1.0);
-        
-        let value = if self.config.epsilon > 0.0 {
-            use rand_distr::{Normal, Distribution};
-            let normal = Normal::new(mean as f64, std as f64).unwrap();
-            normal.sample(&mut rand::thread_rng()) as f32
-        } else {
-            mean
-        };
-        
-        Ok(Action::Continuous(value.clamp(-1.0, 1.0)))
-    }
-    
-    fn sample_multi_discrete(&self, logits: &[f32]) -> Result<Action> {
-        // Assume logits split into: [style:3, size:5, duration:4]
-        let style_logits = &logits[0..3];
-        let size_logits = &logits[3..8];
-        let duration_logits = &logits[8..12];
-        
-        let style = argmax(&softmax(style_logits, 1.0));
-        let size = argmax(&softmax(size_logits, 1.0));
-        let duration = argmax(&softmax(duration_logits, 1.0));
-        
-        Ok(Action::MultiDiscrete { style, size, duration })
-    }
-    
     fn compute_confidence(&self, logits: &[f32]) -> f64 {
         let probs = softmax(logits, 1.0);
         let max_prob = probs.iter().fold(0.0f32, |a, &b| a.max(b));
@@ -200,61 +174,9 @@ impl RLAgent {
     pub fn to_route_decision(
         &self,
         action: &RLAction,
-        features: &FeatureVec,
+        _features: &FeatureVec,
     ) -> RouteDecision {
-        match &action.action {
-            Action::Discrete(idx) => {
-                // 0=Hold, 1=Buy, 2=Sell
-                let should_trade = *idx != 0;
-                let side = if *idx == 1 { Side::Buy } else { Side::Sell };
-                
-                RouteDecision {
-                    style: OrderStyle::MakerPassive,
-                    size_fraction: if should_trade { 0.02 } else { 0.0 },
-                    hold_duration_s: 30.0,
-                    urgency: action.confidence,
-                    should_trade,
-                    reason: format!("RL action: {}", idx),
-                }
-            }
-            
-            Action::Continuous(size) => {
-                let should_trade = size.abs() > 0.01;
-                
-                RouteDecision {
-                    style: if size.abs() > 0.5 {
-                        OrderStyle::TakerNow
-                    } else {
-                        OrderStyle::MakerPassive
-                    },
-                    size_fraction: size.abs() as f64 * 0.1,
-                    hold_duration_s: 30.0,
-                    urgency: action.confidence,
-                    should_trade,
-                    reason: format!("RL size: {:.3}", size),
-                }
-            }
-            
-            Action::MultiDiscrete { style, size, duration } => {
-                let order_style = match style {
-                    0 => OrderStyle::MakerPassive,
-                    1 => OrderStyle::TakerNow,
-                    _ => OrderStyle::Sniper,
-                };
-                
-                let size_fraction = (*size as f64 + 1.0) * 0.01; // 1-5 -> 0.02-0.06
-                let hold_duration = (*duration as f64 + 1.0) * 10.0; // 10-40s
-                
-                RouteDecision {
-                    style: order_style,
-                    size_fraction,
-                    hold_duration_s: hold_duration,
-                    urgency: action.confidence,
-                    should_trade: *size > 0,
-                    reason: format!("RL multi: s{} sz{} d{}", style, size, duration),
-                }
-            }
-        }
+        route_decision(action)
     }
 }
 
@@ -272,6 +194,17 @@ pub enum Action {
     MultiDiscrete { style: usize, size: usize, duration: usize },
 }
 
+impl Action {
+    /// Hold, or a size too small to trade (same thresholds as `route_decision`)
+    pub fn is_flat(&self) -> bool {
+        match self {
+            Action::Discrete(idx) => *idx == 0,
+            Action::Continuous(size) => size.abs() <= 0.01,
+            Action::MultiDiscrete { size, .. } => *size == 0,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct MarketState {
     pub position_size: f64,
@@ -280,21 +213,168 @@ pub struct MarketState {
     pub inventory_risk: f64,
 }
 
+/// Map a sampled action to a decision. Direction comes from the action itself
+/// (discrete buy/sell, or the sign of a continuous size); multi-discrete
+/// actions carry no direction.
+fn route_decision(action: &RLAction) -> RouteDecision {
+    match &action.action {
+        Action::Discrete(idx) => {
+            // 0=Hold, 1=Buy, 2=Sell
+            let should_trade = *idx != 0;
+            let side = if *idx == 1 { Side::Buy } else { Side::Sell };
+            
+            RouteDecision {
+                style: OrderStyle::MakerPassive,
+                size_fraction: if should_trade { 0.02 } else { 0.0 },
+                hold_duration_s: 30.0,
+                urgency: action.confidence,
+                should_trade,
+                side: should_trade.then_some(side),
+                reduce_only: false,
+                reason: format!("RL action: {}", idx),
+            }
+        }
+        
+        Action::Continuous(size) => {
+            let should_trade = size.abs() > 0.01;
+            let side = if *size < 0.0 { Side::Sell } else { Side::Buy };
+            
+            RouteDecision {
+                style: if size.abs() > 0.5 {
+                    OrderStyle::TakerNow
+                } else {
+                    OrderStyle::MakerPassive
+                },
+                size_fraction: size.abs() as f64 * 0.1,
+                hold_duration_s: 30.0,
+                urgency: action.confidence,
+                should_trade,
+                side: should_trade.then_some(side),
+                reduce_only: false,
+                reason: format!("RL size: {:.3}", size),
+            }
+        }
+        
+        Action::MultiDiscrete { style, size, duration } => {
+            let order_style = match style {
+                0 => OrderStyle::MakerPassive,
+                1 => OrderStyle::TakerNow,
+                _ => OrderStyle::Sniper,
+            };
+            
+            let size_fraction = (*size as f64 + 1.0) * 0.01; // 1-5 -> 0.02-0.06
+            let hold_duration = (*duration as f64 + 1.0) * 10.0; // 10-40s
+            
+            RouteDecision {
+                style: order_style,
+                size_fraction,
+                hold_duration_s: hold_duration,
+                urgency: action.confidence,
+                should_trade: *size > 0,
+                side: None,
+                reduce_only: false,
+                reason: format!("RL multi: s{} sz{} d{}", style, size, duration),
+            }
+        }
+    }
+}
+
+fn sample_discrete(logits: &[f32], config: &RLAgentConfig) -> Result<Action> {
+    if logits.is_empty() {
+        return Err(Error::Model("Actor returned no logits".to_string()));
+    }
+    
+    let probs = softmax(logits, config.temperature);
+    
+    let action_idx = if config.epsilon > 0.0 && rand::random::<f64>() < config.epsilon {
+        rand::random::<usize>() % probs.len()
+    } else {
+        argmax(&probs)
+    };
+    
+    Ok(Action::Discrete(action_idx))
+}
+
+fn sample_continuous(logits: &[f32], config: &RLAgentConfig) -> Result<Action> {
+    // Assume logits = [mean, log_std]
+    if logits.len() < 2 {
+        return Err(Error::Model(format!("Continuous actor expects 2 outputs, got {}", logits.len())));
+    }
+    
+    // Non-finite outputs collapse to "no position" rather than panicking
+    let mean = if logits[0].is_finite() { logits[0] } else { 0.0 };
+    let std = if logits[1].is_nan() { 0.01 } else { logits[1].exp().clamp(0.01, 1.0) };
+    
+    let value = if config.epsilon > 0.0 {
+        use rand_distr::{Normal, Distribution};
+        match Normal::new(mean as f64, std as f64) {
+            Ok(normal) => normal.sample(&mut rand::thread_rng()) as f32,
+            Err(_) => mean,
+        }
+    } else {
+        mean
+    };
+    
+    Ok(Action::Continuous(value.clamp(-1.0, 1.0)))
+}
+
+fn sample_multi_discrete(logits: &[f32]) -> Result<Action> {
+    // Assume logits split into: [style:3, size:5, duration:4]
+    if logits.len() < 12 {
+        return Err(Error::Model(format!("MultiDiscrete actor expects 12 outputs, got {}", logits.len())));
+    }
+    
+    let style_logits = &logits[0..3];
+    let size_logits = &logits[3..8];
+    let duration_logits = &logits[8..12];
+    
+    let style = argmax(&softmax(style_logits, 1.0));
+    let size = argmax(&softmax(size_logits, 1.0));
+    let duration = argmax(&softmax(duration_logits, 1.0));
+    
+    Ok(Action::MultiDiscrete { style, size, duration })
+}
+
+/// Softmax that treats NaN logits as impossible actions; falls back to a
+/// uniform distribution when no logit is usable
 fn softmax(logits: &[f32], temperature: f64) -> Vec<f32> {
-    let max = logits.iter().fold(f32::NEG_INFINITY, |a, &b| a.max(b));
+    if logits.is_empty() {
+        return vec![];
+    }
+    
+    let uniform = || vec![1.0 / logits.len() as f32; logits.len()];
+    
+    let max = logits.iter()
+        .copied()
+        .filter(|x| !x.is_nan())
+        .fold(f32::NEG_INFINITY, f32::max);
+    if !max.is_finite() {
+        return uniform();
+    }
+    
     let exp: Vec<f32> = logits.iter()
-        .map(|&x| ((x - max) as f64 / temperature).exp() as f32)
+        .map(|&x| if x.is_nan() { 0.0 } else { ((x - max) as f64 / temperature).exp() as f32 })
         .collect();
     let sum: f32 = exp.iter().sum();
+    
+    if !(sum.is_finite() && sum > 0.0) {
+        return uniform();
+    }
+    
     exp.iter().map(|&x| x / sum).collect()
 }
 
+/// Index of the largest value, ignoring NaN (first index wins ties; 0 if empty)
 fn argmax(probs: &[f32]) -> usize {
     probs.iter()
+        .map(|p| if p.is_nan() { f32::NEG_INFINITY } else { *p })
         .enumerate()
-        .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+        .fold(None, |best: Option<(usize, f32)>, (i, p)| match best {
+            Some((_, b)) if p.total_cmp(&b).is_le() => best,
+            _ => Some((i, p)),
+        })
         .map(|(i, _)| i)
-        .unwrap()
+        .unwrap_or(0)
 }
 
 #[cfg(test)]
@@ -308,4 +388,84 @@ mod tests {
         let sum: f32 = probs.iter().sum();
         assert!((sum - 1.0).abs() < 1e-6);
     }
+    
+    fn config(action_type: ActionType) -> RLAgentConfig {
+        RLAgentConfig {
+            action_type,
+            sequence_length: 1,
+            use_recurrent: false,
+            epsilon: 0.0,
+            temperature: 1.0,
+        }
+    }
+    
+    #[test]
+    fn test_softmax_nan_logits() {
+        let probs = softmax(&[f32::NAN, 1.0, 2.0], 1.0);
+        assert_eq!(probs[0], 0.0);
+        assert!(probs.iter().all(|p| p.is_finite()));
+        assert!((probs.iter().sum::<f32>() - 1.0).abs() < 1e-6);
+        
+        // Nothing usable: uniform
+        let probs = softmax(&[f32::NAN, f32::NAN], 1.0);
+        assert_eq!(probs, vec![0.5, 0.5]);
+    }
+    
+    #[test]
+    fn test_argmax_nan() {
+        assert_eq!(argmax(&[0.1, f32::NAN, 0.7, 0.2]), 2);
+        assert_eq!(argmax(&[f32::NAN, f32::NAN]), 0);
+        assert_eq!(argmax(&[]), 0);
+        assert_eq!(argmax(&[0.5, 0.5]), 0);
+    }
+    
+    #[test]
+    fn test_sample_nan_logits() {
+        let logits = [f32::NAN, 0.5, 3.0];
+        match sample_discrete(&logits, &config(ActionType::Discrete)).unwrap() {
+            Action::Discrete(idx) => assert_eq!(idx, 2),
+            other => panic!("unexpected action {:?}", other),
+        }
+        
+        match sample_continuous(&[f32::NAN, f32::NAN], &config(ActionType::Continuous)).unwrap() {
+            Action::Continuous(v) => assert_eq!(v, 0.0),
+            other => panic!("unexpected action {:?}", other),
+        }
+        
+        let mut multi = [f32::NAN; 12];
+        multi[1] = 1.0;
+        match sample_multi_discrete(&multi).unwrap() {
+            Action::MultiDiscrete { style, size, duration } => {
+                assert_eq!(style, 1);
+                assert_eq!(size, 0);
+                assert_eq!(duration, 0);
+            }
+            other => panic!("unexpected action {:?}", other),
+        }
+        
+        assert!(sample_discrete(&[], &config(ActionType::Discrete)).is_err());
+        assert!(sample_multi_discrete(&[0.0; 4]).is_err());
+    }
+    
+    #[test]
+    fn test_continuous_sign_sets_side() {
+        let action = |value: f32| RLAction {
+            action: Action::Continuous(value),
+            value: 0.0,
+            confidence: 0.7,
+        };
+        
+        let short = route_decision(&action(-0.3));
+        assert!(short.should_trade);
+        assert_eq!(short.side, Some(Side::Sell));
+        assert!((short.size_fraction - 0.03).abs() < 1e-6);
+        
+        let long = route_decision(&action(0.3));
+        assert_eq!(long.side, Some(Side::Buy));
+        assert!((long.size_fraction - 0.03).abs() < 1e-6);
+        
+        let flat = route_decision(&action(0.005));
+        assert!(!flat.should_trade);
+        assert_eq!(flat.side, None);
+    }
 }
\ No newline at end of file
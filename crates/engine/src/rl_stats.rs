@@ -0,0 +1,234 @@
+// crates/engine/src/rl_stats.rs - Critic value estimates vs realized rewards
+use common::*;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+
+/// Resolved (value, reward) pairs the rolling correlation is computed over
+pub const DEFAULT_WINDOW: usize = 500;
+
+/// Resolved records kept for `/models/rl/stats`
+const RECENT_RECORDS: usize = 20;
+
+/// A critic estimate captured with the decision it was made for
+#[derive(Debug, Clone, Serialize)]
+pub struct ValueRecord {
+    pub symbol: String,
+    pub timestamp_ns: i64,
+    pub value: f64,
+    pub side: Option<Side>,
+    pub size_fraction: f64,
+    pub reason: String,
+    pub entry_mid: f64,
+    /// Realized reward in bps, once the hold horizon has passed
+    pub reward_bps: Option<f64>,
+    #[serde(skip)]
+    due_ns: i64,
+}
+
+/// Summary served by `/models/rl/stats`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RlValueStats {
+    pub recorded: u64,
+    pub resolved: u64,
+    pub pending: usize,
+    pub window: usize,
+    pub mean_value: Option<f64>,
+    pub mean_reward_bps: Option<f64>,
+    /// Pearson correlation of value vs reward over the window
+    pub correlation: Option<f64>,
+    pub recent: Vec<ValueRecord>,
+}
+
+/// Pairs RL critic values with the reward of the decision they were made for.
+/// The reward is the mid-price return in the decided direction over the
+/// decision's hold horizon; decisions without a side score zero.
+pub struct ValueTracker {
+    pending: HashMap<String, VecDeque<ValueRecord>>,
+    pairs: VecDeque<(f64, f64)>,
+    recent: VecDeque<ValueRecord>,
+    window: usize,
+    recorded: u64,
+    resolved: u64,
+}
+
+impl Default for ValueTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_WINDOW)
+    }
+}
+
+impl ValueTracker {
+    pub fn new(window: usize) -> Self {
+        Self {
+            pending: HashMap::new(),
+            pairs: VecDeque::with_capacity(window),
+            recent: VecDeque::with_capacity(RECENT_RECORDS),
+            window: window.max(2),
+            recorded: 0,
+            resolved: 0,
+        }
+    }
+    
+    /// Capture the critic value behind `decision`, made at `mid` and `now_ns`
+    pub fn record(&mut self, symbol: &str, value: f32, decision: &RouteDecision, mid: f64, now_ns: i64) {
+        let hold_ns = (decision.hold_duration_s.max(0.0) * 1e9) as i64;
+        let record = ValueRecord {
+            symbol: symbol.to_string(),
+            timestamp_ns: now_ns,
+            value: value as f64,
+            side: decision.side.filter(|_| decision.should_trade),
+            size_fraction: decision.size_fraction,
+            reason: decision.reason.clone(),
+            entry_mid: mid,
+            reward_bps: None,
+            due_ns: now_ns + hold_ns,
+        };
+        
+        self.pending.entry(symbol.to_string()).or_default().push_back(record);
+        self.recorded += 1;
+    }
+    
+    /// Resolve every record for `symbol` whose hold horizon has passed at `mid`
+    pub fn observe_mark(&mut self, symbol: &str, mid: f64, now_ns: i64) {
+        if !mid.is_finite() || mid <= 0.0 {
+            return;
+        }
+        let Some(queue) = self.pending.get_mut(symbol) else {
+            return;
+        };
+        
+        let mut matured = Vec::new();
+        while queue.front().is_some_and(|r| r.due_ns <= now_ns) {
+            matured.extend(queue.pop_front());
+        }
+        if queue.is_empty() {
+            self.pending.remove(symbol);
+        }
+        
+        for mut record in matured {
+            let direction = match record.side {
+                Some(Side::Buy) => 1.0,
+                Some(Side::Sell) => -1.0,
+                None => 0.0,
+            };
+            let reward = if record.entry_mid > 0.0 {
+                direction * (mid / record.entry_mid - 1.0) * 1e4
+            } else {
+                0.0
+            };
+            record.reward_bps = Some(reward);
+            self.resolve(record);
+        }
+    }
+    
+    fn resolve(&mut self, record: ValueRecord) {
+        let reward = record.reward_bps.unwrap_or(0.0);
+        if self.pairs.len() == self.window {
+            self.pairs.pop_front();
+        }
+        self.pairs.push_back((record.value, reward));
+        
+        if self.recent.len() == RECENT_RECORDS {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(record);
+        self.resolved += 1;
+        
+        if let Some(correlation) = self.correlation() {
+            metrics::gauge!("rl_value_reward_correlation", correlation);
+        }
+    }
+    
+    /// Pearson correlation over the window; `None` until two distinct points exist
+    pub fn correlation(&self) -> Option<f64> {
+        let n = self.pairs.len() as f64;
+        if self.pairs.len() < 2 {
+            return None;
+        }
+        
+        let (mean_v, mean_r) = self.means()?;
+        let (mut cov, mut var_v, mut var_r) = (0.0, 0.0, 0.0);
+        for (v, r) in &self.pairs {
+            cov += (v - mean_v) * (r - mean_r);
+            var_v += (v - mean_v).powi(2);
+            var_r += (r - mean_r).powi(2);
+        }
+        
+        let denom = (var_v * var_r).sqrt();
+        (denom > f64::EPSILON * n).then(|| cov / denom)
+    }
+    
+    fn means(&self) -> Option<(f64, f64)> {
+        if self.pairs.is_empty() {
+            return None;
+        }
+        let n = self.pairs.len() as f64;
+        let (sum_v, sum_r) = self.pairs.iter().fold((0.0, 0.0), |(sv, sr), (v, r)| (sv + v, sr + r));
+        Some((sum_v / n, sum_r / n))
+    }
+    
+    pub fn stats(&self) -> RlValueStats {
+        let means = self.means();
+        RlValueStats {
+            recorded: self.recorded,
+            resolved: self.resolved,
+            pending: self.pending.values().map(VecDeque::len).sum(),
+            window: self.pairs.len(),
+            mean_value: means.map(|(v, _)| v),
+            mean_reward_bps: means.map(|(_, r)| r),
+            correlation: self.correlation(),
+            recent: self.recent.iter().cloned().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn decision(side: Side) -> RouteDecision {
+        RouteDecision {
+            style: OrderStyle::MakerPassive,
+            size_fraction: 0.02,
+            hold_duration_s: 1.0,
+            urgency: 0.5,
+            should_trade: true,
+            side: Some(side),
+            reduce_only: false,
+            reason: "RL: Buy".to_string(),
+        }
+    }
+    
+    #[test]
+    fn test_values_recorded_and_correlation_updates() {
+        let mut tracker = ValueTracker::new(10);
+        let s = 1_000_000_000;
+        
+        tracker.record("BTC", 0.9, &decision(Side::Buy), 100.0, 0);
+        tracker.record("BTC", 0.1, &decision(Side::Sell), 100.0, 0);
+        
+        let stats = tracker.stats();
+        assert_eq!((stats.recorded, stats.resolved, stats.pending), (2, 0, 2));
+        assert_eq!(stats.correlation, None);
+        
+        // Not due yet
+        tracker.observe_mark("BTC", 101.0, s / 2);
+        assert_eq!(tracker.stats().resolved, 0);
+        
+        // Price rose: the confident long earns, the short loses
+        tracker.observe_mark("BTC", 101.0, s);
+        let stats = tracker.stats();
+        assert_eq!((stats.resolved, stats.pending), (2, 0));
+        assert_eq!(stats.recent[0].value, 0.9f32 as f64);
+        assert_eq!(stats.recent[0].side, Some(Side::Buy));
+        assert!((stats.recent[0].reward_bps.unwrap() - 100.0).abs() < 1e-6);
+        assert!((stats.recent[1].reward_bps.unwrap() + 100.0).abs() < 1e-6);
+        assert!((stats.correlation.unwrap() - 1.0).abs() < 1e-9);
+        
+        // An outcome against the critic pulls the correlation down
+        tracker.record("BTC", 0.8, &decision(Side::Buy), 100.0, s);
+        tracker.observe_mark("BTC", 98.0, 2 * s);
+        let correlation = tracker.stats().correlation.unwrap();
+        assert!(correlation < 1.0 && correlation > -1.0, "{}", correlation);
+    }
+}
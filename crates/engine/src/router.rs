@@ -1,8 +1,10 @@
 // crates/engine/src/router.rs
 use common::*;
-use std::collections::HashMap;
+use common::currency::{self, CurrencyConverter};
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
 
 /// Gate parameters
 #[derive(Debug, Clone)]
@@ -12,6 +14,20 @@ pub struct GateParams {
     pub max_hold_s: f64,
     pub max_spread_bps: f64,
     pub enabled: bool,
+    /// Urgency added (or removed) by book pressure that agrees (or disagrees)
+    /// with the signal direction; 0 ignores the book
+    pub book_pressure_weight: f64,
+    /// Rest passively when its expected net edge beats taking's by more than this
+    pub maker_margin_bps: f64,
+    /// Urgency above which orders always take
+    pub maker_max_urgency: f64,
+    /// Reject spreads above this percentile (0-1) of the symbol's own recent
+    /// spreads instead of `max_spread_bps`; 0 keeps the static threshold
+    pub spread_percentile: f64,
+    /// Spreads kept per symbol for the percentile
+    pub spread_history: usize,
+    /// Samples needed before the percentile replaces `max_spread_bps`
+    pub spread_min_samples: usize,
 }
 
 impl Default for GateParams {
@@ -22,18 +38,28 @@ impl Default for GateParams {
             max_hold_s: 30.0,
             max_spread_bps: 10.0,
             enabled: true,
+            book_pressure_weight: 0.2,
+            maker_margin_bps: 2.0,
+            maker_max_urgency: 0.9,
+            spread_percentile: 0.0,
+            spread_history: 1000,
+            spread_min_samples: 100,
         }
     }
 }
 
 /// Cost model for trading
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CostModel {
     pub taker_fee_bps: f64,
     pub maker_fee_bps: f64,
     pub maker_rebate_bps: f64,
     pub impact_bps: f64,
+    /// Realized slippage of taker fills beyond the touch they crossed
     pub slippage_buffer_bps: f64,
+    /// Realized slippage of resting fills beyond the price they rested at
+    #[serde(default)]
+    pub maker_slippage_buffer_bps: f64,
 }
 
 impl CostModel {
@@ -42,7 +68,7 @@ impl CostModel {
     }
     
     pub fn total_cost_maker(&self) -> f64 {
-        self.maker_fee_bps + self.impact_bps + self.slippage_buffer_bps - self.maker_rebate_bps
+        self.maker_fee_bps + self.impact_bps + self.maker_slippage_buffer_bps - self.maker_rebate_bps
     }
     
     pub fn net_edge_taker(&self, pred_edge_bps: f64) -> f64 {
@@ -52,20 +78,115 @@ impl CostModel {
     pub fn net_edge_maker(&self, pred_edge_bps: f64) -> f64 {
         pred_edge_bps - self.total_cost_maker()
     }
+    
+    /// Costs at an account fee tier; a negative maker fee is a rebate
+    pub fn from_fees(fees: &FeeTier, impact_bps: f64, slippage_buffer_bps: f64, maker_slippage_buffer_bps: f64) -> Self {
+        Self {
+            taker_fee_bps: fees.taker_fee_bps,
+            maker_fee_bps: fees.maker_fee_bps.max(0.0),
+            maker_rebate_bps: (-fees.maker_fee_bps).max(0.0),
+            impact_bps,
+            slippage_buffer_bps,
+            maker_slippage_buffer_bps,
+        }
+    }
+}
+
+/// Fees assumed for a venue whose tier hasn't been fetched: 5 bps taker,
+/// 1 bps net maker
+pub const FALLBACK_FEES: FeeTier = FeeTier {
+    maker_fee_bps: 1.0,
+    taker_fee_bps: 5.0,
+    volume_30d: 0.0,
+};
+
+/// Account fee tier per venue, as last fetched from its adapter
+#[derive(Debug, Default)]
+pub struct FeeSchedule {
+    tiers: HashMap<Venue, FeeTier>,
+}
+
+impl FeeSchedule {
+    pub fn update(&mut self, venue: Venue, tier: FeeTier) {
+        self.tiers.insert(venue, tier);
+    }
+    
+    pub fn is_known(&self, venue: Venue) -> bool {
+        self.tiers.contains_key(&venue)
+    }
+    
+    /// Last fetched tier for `venue`, `FALLBACK_FEES` until one arrives
+    pub fn get(&self, venue: Venue) -> FeeTier {
+        self.tiers.get(&venue).cloned().unwrap_or(FALLBACK_FEES)
+    }
+}
+
+/// Rolling window of one symbol's observed spreads
+#[derive(Debug, Default)]
+pub struct SpreadHistory {
+    spreads: VecDeque<f64>,
+}
+
+impl SpreadHistory {
+    pub fn record(&mut self, spread_bps: f64, capacity: usize) {
+        if !spread_bps.is_finite() {
+            return;
+        }
+        self.spreads.push_back(spread_bps);
+        while self.spreads.len() > capacity.max(1) {
+            self.spreads.pop_front();
+        }
+    }
+    
+    pub fn len(&self) -> usize {
+        self.spreads.len()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.spreads.is_empty()
+    }
+    
+    /// Nearest-rank percentile (`q` in 0-1), `None` when empty
+    pub fn percentile(&self, q: f64) -> Option<f64> {
+        if self.spreads.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<f64> = self.spreads.iter().copied().collect();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let rank = (q.clamp(0.0, 1.0) * sorted.len() as f64).ceil() as usize;
+        Some(sorted[rank.saturating_sub(1)])
+    }
 }
 
 /// Trade gate - decides if signal is strong enough
 pub struct TradeGate {
     params: Arc<RwLock<GateParams>>,
+    spreads: RwLock<HashMap<String, SpreadHistory>>,
 }
 
 impl TradeGate {
     pub fn new(params: GateParams) -> Self {
         Self {
             params: Arc::new(RwLock::new(params)),
+            spreads: RwLock::new(HashMap::new()),
         }
     }
     
+    /// Spread threshold for `symbol`: its configured percentile once enough
+    /// history exists, `max_spread_bps` otherwise. `spread_bps` joins the
+    /// history after the threshold is taken so it isn't judged against itself.
+    fn spread_threshold(&self, params: &GateParams, symbol: &str, spread_bps: f64) -> f64 {
+        let mut spreads = self.spreads.write();
+        let history = spreads.entry(symbol.to_string()).or_default();
+        
+        let threshold = match history.percentile(params.spread_percentile) {
+            Some(p) if params.spread_percentile > 0.0 && history.len() >= params.spread_min_samples => p,
+            _ => params.max_spread_bps,
+        };
+        history.record(spread_bps, params.spread_history);
+        threshold
+    }
+    
     pub fn update_params(&self, params: GateParams) {
         *self.params.write() = params;
     }
@@ -84,6 +205,14 @@ impl TradeGate {
             return GateResult::Reject("Gate disabled".to_string());
         }
         
+        // NaN compares false against every threshold below, so reject it up front
+        if !prediction.confidence.is_finite() || !prediction.edge_bps.is_finite() {
+            return GateResult::Reject(format!(
+                "Non-finite prediction: edge={} confidence={}",
+                prediction.edge_bps, prediction.confidence
+            ));
+        }
+
         // Check confidence
         if prediction.confidence < params.min_confidence {
             return GateResult::Reject(format!(
@@ -93,15 +222,16 @@ impl TradeGate {
         }
         
         // Check spread
-        if features.spread_bps > params.max_spread_bps {
+        let max_spread_bps = self.spread_threshold(&params, &features.symbol, features.spread_bps);
+        if features.spread_bps > max_spread_bps {
             return GateResult::Reject(format!(
                 "Wide spread: {:.2} > {:.2} bps",
-                features.spread_bps, params.max_spread_bps
+                features.spread_bps, max_spread_bps
             ));
         }
         
-        // Check net edge after costs
-        let net_edge = costs.net_edge_taker(prediction.edge_bps);
+        // Check net edge after costs; the sign only picks the side
+        let net_edge = costs.net_edge_taker(prediction.edge_bps.abs());
         if net_edge < params.min_edge_bps {
             return GateResult::Reject(format!(
                 "Insufficient edge: {:.2} < {:.2} bps",
@@ -120,24 +250,70 @@ impl TradeGate {
         
         GateResult::Pass {
             net_edge_bps: net_edge,
-            urgency: self.compute_urgency(prediction, features),
+            urgency: self.compute_urgency(prediction, features, params.book_pressure_weight),
         }
     }
     
-    fn compute_urgency(&self, prediction: &Prediction, features: &FeatureVec) -> f64 {
+    fn compute_urgency(&self, prediction: &Prediction, features: &FeatureVec, book_pressure_weight: f64) -> f64 {
         // Higher urgency for:
         // - Higher confidence
         // - Tighter spread
         // - Stronger signal
+        // - Book pressure in the signal's direction (lower against it)
         
         let confidence_factor = prediction.confidence;
         let spread_factor = (10.0 - features.spread_bps).max(0.0) / 10.0;
         let signal_factor = (prediction.edge_bps.abs() / 20.0).min(1.0);
+        let pressure_factor = prediction.edge_bps.signum() * book_pressure(features);
         
-        (confidence_factor * 0.4 + spread_factor * 0.3 + signal_factor * 0.3).clamp(0.0, 1.0)
+        let urgency = confidence_factor * 0.4 + spread_factor * 0.3 + signal_factor * 0.3
+            + pressure_factor * book_pressure_weight;
+
+        // NaN model output must not leak into style/size selection
+        if urgency.is_nan() { 0.0 } else { urgency.clamp(0.0, 1.0) }
     }
 }
 
+/// Buy-side pressure in [-1, 1]: the mean of OBI and the microprice offset
+/// from mid in half-spreads (capped at one)
+fn book_pressure(features: &FeatureVec) -> f64 {
+    let half_spread = features.mid_price * features.spread_bps / 2e4;
+    let micro_offset = if half_spread > 0.0 {
+        ((features.microprice - features.mid_price) / half_spread).clamp(-1.0, 1.0)
+    } else {
+        0.0
+    };
+    
+    let pressure = (features.obi_1s.clamp(-1.0, 1.0) + micro_offset) / 2.0;
+    if pressure.is_nan() { 0.0 } else { pressure }
+}
+
+/// Distance (bps) the market must travel to a resting order for its fill
+/// chance to halve
+const FILL_DISTANCE_SCALE_BPS: f64 = 10.0;
+
+/// Chance a resting order `distance_bps` from the opposite touch fills
+/// before the signal plays out. Urgent signals leave no time to wait.
+pub fn fill_probability(distance_bps: f64, urgency: f64) -> f64 {
+    let p = (1.0 - urgency) / (1.0 + distance_bps.max(0.0) / FILL_DISTANCE_SCALE_BPS);
+    if p.is_nan() { 0.0 } else { p.clamp(0.0, 1.0) }
+}
+
+/// Expected net edge (bps from mid) of each style for an `edge_bps` signal.
+/// Taking fills for sure but pays its costs and half the spread; resting at
+/// the touch earns the half spread but fills a full spread away; the mid
+/// limit of `Sniper` earns nothing on the spread and fills half a spread away.
+pub fn style_edges(edge_bps: f64, costs: &CostModel, spread_bps: f64, urgency: f64) -> [(OrderStyle, f64); 3] {
+    let half_spread = spread_bps.max(0.0) / 2.0;
+    let maker_edge = costs.net_edge_maker(edge_bps);
+    
+    [
+        (OrderStyle::TakerNow, costs.net_edge_taker(edge_bps) - half_spread),
+        (OrderStyle::MakerPassive, fill_probability(spread_bps, urgency) * (maker_edge + half_spread)),
+        (OrderStyle::Sniper, fill_probability(half_spread, urgency) * maker_edge),
+    ]
+}
+
 #[derive(Debug, Clone)]
 pub enum GateResult {
     Pass { net_edge_bps: f64, urgency: f64 },
@@ -145,7 +321,7 @@ pub enum GateResult {
 }
 
 /// Risk state
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RiskState {
     pub current_notional: f64,
     pub max_notional: f64,
@@ -185,9 +361,19 @@ impl OrderRouter {
         costs: &CostModel,
     ) -> RouteDecision {
         let risk_state = self.risk_manager.read().get_state();
-        
+        self.decide_with_state(prediction, features, costs, &risk_state)
+    }
+    
+    /// Routing decision against a given risk state rather than the live one
+    pub fn decide_with_state(
+        &self,
+        prediction: &Prediction,
+        features: &FeatureVec,
+        costs: &CostModel,
+        risk_state: &RiskState,
+    ) -> RouteDecision {
         // Check gate
-        let gate_result = self.gate.check(prediction, features, costs, &risk_state);
+        let gate_result = self.gate.check(prediction, features, costs, risk_state);
         
         let (should_trade, reason, urgency) = match gate_result {
             GateResult::Pass { net_edge_bps, urgency } => {
@@ -203,12 +389,14 @@ impl OrderRouter {
                 hold_duration_s: 0.0,
                 urgency: 0.0,
                 should_trade: false,
+                side: None,
+                reduce_only: false,
                 reason,
             };
         }
         
-        // Determine order style based on urgency and spread
-        let style = self.select_style(urgency, features.spread_bps);
+        // Determine order style from each style's expected net edge
+        let style = self.select_style(prediction.edge_bps.abs(), costs, features.spread_bps, urgency);
         
         // Size based on conviction and risk
         let size_fraction = self.compute_size(prediction.confidence, urgency);
@@ -226,17 +414,26 @@ impl OrderRouter {
             hold_duration_s,
             urgency,
             should_trade: true,
+            side: Some(if prediction.edge_bps > 0.0 { Side::Buy } else { Side::Sell }),
+            reduce_only: false,
             reason,
         }
     }
     
-    fn select_style(&self, urgency: f64, spread_bps: f64) -> OrderStyle {
-        if urgency > 0.8 {
-            OrderStyle::TakerNow
-        } else if urgency > 0.5 && spread_bps < 3.0 {
-            OrderStyle::Sniper // Join best bid/ask
+    fn select_style(&self, edge_bps: f64, costs: &CostModel, spread_bps: f64, urgency: f64) -> OrderStyle {
+        let params = self.gate.params.read();
+        if urgency > params.maker_max_urgency {
+            return OrderStyle::TakerNow;
+        }
+        
+        // Rest only when the fill-discounted edge clearly beats crossing now
+        let [(_, taker), maker, sniper] = style_edges(edge_bps, costs, spread_bps, urgency);
+        let (style, passive) = if maker.1 >= sniper.1 { maker } else { sniper };
+        
+        if passive > taker + params.maker_margin_bps {
+            style
         } else {
-            OrderStyle::MakerPassive
+            OrderStyle::TakerNow
         }
     }
     
@@ -275,26 +472,212 @@ pub struct RiskManager {
     limits: RiskLimits,
     positions: HashMap<String, Position>,
     daily_pnl: f64,
-    daily_start: i64,
+    /// Unix seconds of the next daily rollover
+    next_reset: i64,
     kill_switch: bool,
+    fx: CurrencyConverter,
+    /// Quote asset per symbol where the symbol name doesn't carry one
+    quote_assets: HashMap<String, String>,
+    /// Account equity in base currency, `None` until the first account sync
+    equity: Option<f64>,
+    /// Daily return volatility (fraction) per symbol, for VaR
+    volatilities: HashMap<String, f64>,
+}
+
+/// One-sided 95% standard normal quantile
+const Z_95: f64 = 1.645;
+/// Weight of each feature update in the per-symbol vol estimate
+const VOL_EWMA_ALPHA: f64 = 0.01;
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// Daily return vol implied by a feature update: the 5s realized vol scaled
+/// by sqrt(time), or the 1s-bar ATR relative to mid when no trades printed
+pub fn daily_vol(features: &FeatureVec) -> Option<f64> {
+    let vol = if features.realized_vol_5s > 0.0 {
+        features.realized_vol_5s * (SECONDS_PER_DAY / 5.0).sqrt()
+    } else if features.atr_30s > 0.0 && features.mid_price > 0.0 {
+        features.atr_30s / features.mid_price * SECONDS_PER_DAY.sqrt()
+    } else {
+        return None;
+    };
+    vol.is_finite().then_some(vol)
+}
+
+/// First `reset_hour_utc`:00 UTC strictly after `now` (unix seconds)
+fn next_rollover(now: i64, reset_hour_utc: u32) -> i64 {
+    let rollover = now - now.rem_euclid(86_400) + reset_hour_utc as i64 * 3_600;
+    if rollover > now { rollover } else { rollover + 86_400 }
 }
 
 impl RiskManager {
     pub fn new(limits: RiskLimits) -> Self {
+        Self::new_at(limits, chrono::Utc::now().timestamp())
+    }
+    
+    /// Manager whose trading day is the one containing `now` (unix seconds)
+    pub fn new_at(limits: RiskLimits, now: i64) -> Self {
+        let fx = CurrencyConverter::new(limits.base_currency.clone(), &limits.reference_rates);
+        let next_reset = next_rollover(now, limits.reset_hour_utc);
         Self {
             limits,
             positions: HashMap::new(),
             daily_pnl: 0.0,
-            daily_start: chrono::Utc::now().timestamp(),
+            next_reset,
             kill_switch: false,
+            fx,
+            quote_assets: HashMap::new(),
+            equity: None,
+            volatilities: HashMap::new(),
+        }
+    }
+    
+    pub fn base_currency(&self) -> &str {
+        self.fx.base()
+    }
+    
+    /// Record the quote asset of a bare symbol (e.g. `BTC` on a USDC venue)
+    pub fn set_quote_asset(&mut self, symbol: &str, asset: &str) {
+        self.quote_assets.insert(symbol.to_string(), asset.to_string());
+    }
+    
+    /// Quote asset of `symbol`: registered, parsed from the pair, or the base
+    /// currency for bare symbols
+    pub fn quote_asset<'a>(&'a self, symbol: &'a str) -> &'a str {
+        if let Some(asset) = self.quote_assets.get(symbol) {
+            return asset;
+        }
+        currency::split_pair(symbol).map_or(self.fx.base(), |(_, quote)| quote)
+    }
+    
+    /// Feed a pair's mid price into the reference rates
+    pub fn observe_price(&mut self, symbol: &str, mid: f64) {
+        self.fx.observe_pair(symbol, mid);
+    }
+    
+    pub fn set_reference_rate(&mut self, asset: &str, rate: f64) {
+        self.fx.set_rate(asset, rate);
+    }
+    
+    /// Convert an amount quoted in `symbol`'s quote asset into base currency
+    pub fn to_base(&self, symbol: &str, amount: f64) -> Result<f64> {
+        self.fx.to_base(amount, self.quote_asset(symbol))
+    }
+    
+    /// Convert an amount of `asset` into base currency
+    pub fn asset_to_base(&self, asset: &str, amount: f64) -> Result<f64> {
+        self.fx.to_base(amount, asset)
+    }
+    
+    /// Account equity (margin available to back positions), in base currency
+    pub fn update_equity(&mut self, equity: f64) {
+        self.equity = Some(equity);
+    }
+    
+    pub fn equity(&self) -> Option<f64> {
+        self.equity
+    }
+    
+    fn position_notional(&self, position: &Position) -> Result<f64> {
+        self.to_base(&position.symbol, position.size.abs() * position.mark_price)
+    }
+    
+    /// Gross notional in base currency, failing if any position lacks a rate
+    fn gross_notional(&self) -> Result<f64> {
+        self.positions.values().map(|p| self.position_notional(p)).sum()
+    }
+    
+    /// Record `symbol`'s daily return volatility as a fraction of price
+    pub fn set_volatility(&mut self, symbol: &str, daily_vol: f64) {
+        if daily_vol.is_finite() && daily_vol >= 0.0 {
+            self.volatilities.insert(symbol.to_string(), daily_vol);
+        }
+    }
+    
+    /// Blend the daily vol implied by a feature update into the symbol's estimate
+    pub fn observe_features(&mut self, features: &FeatureVec) {
+        let Some(vol) = daily_vol(features) else {
+            return;
+        };
+        let estimate = match self.volatilities.get(&features.symbol) {
+            Some(prev) => prev + VOL_EWMA_ALPHA * (vol - prev),
+            None => vol,
+        };
+        self.set_volatility(&features.symbol, estimate);
+    }
+    
+    pub fn volatility(&self, symbol: &str) -> Option<f64> {
+        self.volatilities.get(symbol).copied()
+    }
+    
+    /// Amount in base currency, falling back to the raw quote value
+    fn base_or_raw(&self, symbol: &str, amount: f64) -> f64 {
+        self.to_base(symbol, amount).unwrap_or_else(|e| {
+            tracing::warn!("{}", e);
+            amount
+        })
+    }
+    
+    /// Portfolio view for the risk stream, in base currency; VaR as in `var_95`
+    pub fn snapshot(&self) -> RiskSnapshot {
+        let mut snapshot = RiskSnapshot {
+            timestamp_ns: chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0),
+            num_positions: self.open_positions(),
+            daily_pnl: self.daily_pnl,
+            max_leverage: self.limits.max_leverage,
+            kill_switch_active: self.kill_switch,
+            ..RiskSnapshot::default()
+        };
+        for p in self.positions.values() {
+            let notional = self.base_or_raw(&p.symbol, p.size * p.mark_price);
+            snapshot.gross_notional += notional.abs();
+            snapshot.net_notional += notional;
+            snapshot.total_margin_used += self.base_or_raw(&p.symbol, p.margin_used);
+            snapshot.total_maintenance_margin += self.base_or_raw(&p.symbol, p.maintenance_margin.unwrap_or(0.0));
+            snapshot.unrealized_pnl += self.base_or_raw(&p.symbol, p.unrealized_pnl);
+            snapshot.realized_pnl += self.base_or_raw(&p.symbol, p.realized_pnl);
         }
+        
+        snapshot.total_pnl = snapshot.unrealized_pnl + snapshot.realized_pnl;
+        snapshot.available_margin = self.equity
+            .map_or(0.0, |equity| (equity - snapshot.total_margin_used).max(0.0));
+        snapshot.var_95 = self.var_95();
+        snapshot
+    }
+    
+    /// Parametric one-day 95% VaR in base currency. Assumes normal daily
+    /// returns with each symbol's tracked vol and one pairwise correlation
+    /// (`var_correlation`) for every pair, so the portfolio variance is
+    /// (1 - rho) * sum(x_i^2) + rho * (sum x_i)^2 for signed dollar vols x_i:
+    /// rho = 0 is independence, rho = 1 adds exposures linearly with shorts
+    /// offsetting longs. Symbols without a vol estimate contribute nothing.
+    pub fn var_95(&self) -> f64 {
+        let exposures: Vec<f64> = self.positions.values()
+            .filter_map(|p| {
+                let vol = self.volatilities.get(&p.symbol)?;
+                Some(self.base_or_raw(&p.symbol, p.size * p.mark_price) * vol)
+            })
+            .collect();
+        
+        let rho = self.limits.var_correlation;
+        let sum_sq: f64 = exposures.iter().map(|x| x * x).sum();
+        let sum: f64 = exposures.iter().sum();
+        let variance = (1.0 - rho) * sum_sq + rho * sum * sum;
+        
+        Z_95 * variance.max(0.0).sqrt()
     }
     
     pub fn get_state(&self) -> RiskState {
+        // Unconvertible positions are counted at their raw quote value;
+        // check_limits refuses new risk until a rate is known
         let current_notional: f64 = self.positions.values()
-            .map(|p| p.size.abs() * p.mark_price)
+            .map(|p| {
+                self.position_notional(p).unwrap_or_else(|e| {
+                    tracing::warn!("{}", e);
+                    p.size.abs() * p.mark_price
+                })
+            })
             .sum();
-        
+
         let daily_loss_exceeded = self.daily_pnl < -self.limits.max_loss_per_day;
         
         RiskState {
@@ -311,15 +694,72 @@ impl RiskManager {
         self.positions.insert(position.symbol.clone(), position);
     }
     
-    pub fn update_pnl(&mut self, pnl_delta: f64) {
+    /// Forget a closed position
+    pub fn remove_position(&mut self, symbol: &str) -> Option<Position> {
+        self.positions.remove(symbol)
+    }
+    
+    /// Symbols with a stored position, flat ones included
+    pub fn position_symbols(&self) -> Vec<String> {
+        self.positions.keys().cloned().collect()
+    }
+    
+    /// Change in realized plus unrealized PnL of `position` since the stored
+    /// one, in base currency. A symbol not held before only sets the
+    /// baseline, so PnL from before the engine saw it isn't booked today.
+    pub fn pnl_change(&self, position: &Position) -> f64 {
+        let Some(prev) = self.positions.get(&position.symbol) else {
+            return 0.0;
+        };
+        let change = (position.realized_pnl + position.unrealized_pnl) - (prev.realized_pnl + prev.unrealized_pnl);
+        self.base_or_raw(&position.symbol, change)
+    }
+    
+    /// Apply a PnL change, in base currency. Returns true if it tripped the kill switch.
+    pub fn update_pnl(&mut self, pnl_delta: f64) -> bool {
+        self.update_pnl_at(pnl_delta, chrono::Utc::now().timestamp())
+    }
+    
+    /// Apply a PnL change realized at `now` (unix seconds), after rolling the
+    /// day over if `now` is past the reset hour. Returns true if the change
+    /// tripped the kill switch.
+    pub fn update_pnl_at(&mut self, pnl_delta: f64, now: i64) -> bool {
+        self.roll_day_at(now);
         self.daily_pnl += pnl_delta;
+        self.check_daily_loss()
+    }
+    
+    /// Activate the kill switch once daily loss reaches
+    /// `kill_switch_loss_multiple * max_loss_per_day`. Only a manual
+    /// deactivation clears it; day rollovers don't. Returns true on the trip.
+    pub fn check_daily_loss(&mut self) -> bool {
+        let trip_at = self.limits.max_loss_per_day * self.limits.kill_switch_loss_multiple;
+        if self.kill_switch || self.daily_pnl > -trip_at {
+            return false;
+        }
         
-        // Reset daily PnL at midnight UTC
-        let now = chrono::Utc::now().timestamp();
-        if now - self.daily_start > 86400 {
-            self.daily_pnl = 0.0;
-            self.daily_start = now;
+        self.kill_switch = true;
+        tracing::error!(
+            "🛑 Kill switch auto-activated: daily PnL {:.2} {} breached -{:.2}",
+            self.daily_pnl, self.base_currency(), trip_at
+        );
+        metrics::increment_counter!("kill_switch_auto_trips");
+        true
+    }
+    
+    pub fn daily_pnl(&self) -> f64 {
+        self.daily_pnl
+    }
+    
+    /// Start a new trading day once `now` crosses `reset_hour_utc`
+    pub fn roll_day_at(&mut self, now: i64) {
+        if now < self.next_reset {
+            return;
         }
+        
+        tracing::info!("Daily PnL rollover: {:.2} {} reset", self.daily_pnl, self.base_currency());
+        self.daily_pnl = 0.0;
+        self.next_reset = next_rollover(now, self.limits.reset_hour_utc);
     }
     
     pub fn activate_kill_switch(&mut self) {
@@ -332,6 +772,56 @@ impl RiskManager {
         tracing::info!("Kill switch deactivated");
     }
     
+    /// Number of symbols with a non-zero position
+    pub fn open_positions(&self) -> usize {
+        self.positions.values().filter(|p| p.size != 0.0).count()
+    }
+    
+    /// Breadth check: new entries are capped at `max_open_positions`,
+    /// adding to an existing position is always allowed
+    pub fn can_open(&self, symbol: &str) -> Result<()> {
+        let is_open = self.positions.get(symbol).is_some_and(|p| p.size != 0.0);
+        if is_open {
+            return Ok(());
+        }
+        
+        let open = self.open_positions();
+        if open >= self.limits.max_open_positions {
+            metrics::increment_counter!("risk_rejections_total", "reason" => "max_open_positions");
+            return Err(Error::RiskCheck(format!(
+                "Max open positions reached: {} >= {}, cannot open {}",
+                open, self.limits.max_open_positions, symbol
+            )));
+        }
+        
+        Ok(())
+    }
+    
+    /// Final sizing pass before submission: reduce-only orders are clamped to
+    /// the open position so an exit can flatten but never flip it
+    pub fn finalize_order(&self, order: &mut OrderRequest) {
+        if !order.reduce_only {
+            return;
+        }
+        
+        let position = self.positions.get(&order.symbol).map_or(0.0, |p| p.size);
+        let reducible = match order.side {
+            Side::Buy if position < 0.0 => -position,
+            Side::Sell if position > 0.0 => position,
+            _ => 0.0,
+        };
+        
+        if order.quantity > reducible {
+            tracing::debug!(
+                "Clamping reduce-only {} {:?} from {} to {}",
+                order.symbol, order.side, order.quantity, reducible
+            );
+            order.quantity = reducible;
+        }
+    }
+    
+    /// `additional_notional` is in the symbol's quote asset; all comparisons
+    /// are made in base currency
     pub fn check_limits(&self, symbol: &str, additional_notional: f64) -> Result<()> {
         let state = self.get_state();
         
@@ -343,20 +833,63 @@ impl RiskManager {
             return Err(Error::RiskCheck("Daily loss limit exceeded".to_string()));
         }
         
-        if state.current_notional + additional_notional > state.max_notional {
+        self.can_open(symbol)?;
+        
+        let to_risk = |e: Error| Error::RiskCheck(e.to_string());
+        let current_notional = self.gross_notional().map_err(to_risk)?;
+        let additional_notional = self.to_base(symbol, additional_notional).map_err(to_risk)?;
+        
+        if current_notional + additional_notional > state.max_notional {
             return Err(Error::RiskCheck(format!(
-                "Would exceed max notional: {:.0} + {:.0} > {:.0}",
-                state.current_notional, additional_notional, state.max_notional
+                "Would exceed max notional: {:.0} + {:.0} > {:.0} {}",
+                current_notional, additional_notional, state.max_notional, self.base_currency()
             )));
         }
         
         // Check per-symbol limit
-        if let Some(pos) = self.positions.get(symbol) {
-            let pos_notional = pos.size.abs() * pos.mark_price;
-            if pos_notional + additional_notional > self.limits.max_notional_per_symbol {
+        let pos_notional = match self.positions.get(symbol) {
+            Some(pos) => self.position_notional(pos).map_err(to_risk)?,
+            None => 0.0,
+        };
+        if self.positions.contains_key(symbol) && pos_notional + additional_notional > self.limits.max_notional_per_symbol {
+            return Err(Error::RiskCheck(format!(
+                "Would exceed per-symbol limit for {}: {:.0} + {:.0} > {:.0}",
+                symbol, pos_notional, additional_notional, self.limits.max_notional_per_symbol
+            )));
+        }
+        
+        // Check leverage against synced equity; before the first sync there's
+        // nothing to measure against and the notional limits stand alone
+        let gross_after = current_notional + additional_notional;
+        if let Some(equity) = self.equity {
+            if equity <= 0.0 {
+                return Err(Error::RiskCheck(format!(
+                    "No equity to back new risk: {:.0} {}", equity, self.base_currency()
+                )));
+            }
+            
+            let leverage = gross_after / equity;
+            if leverage > self.limits.max_leverage {
+                metrics::increment_counter!("risk_rejections_total", "reason" => "max_leverage");
                 return Err(Error::RiskCheck(format!(
-                    "Would exceed per-symbol limit for {}: {:.0} + {:.0} > {:.0}",
-                    symbol, pos_notional, additional_notional, self.limits.max_notional_per_symbol
+                    "Would exceed max leverage: {:.0} gross / {:.0} equity {} = {:.2}x > {:.2}x",
+                    gross_after, equity, self.base_currency(), leverage, self.limits.max_leverage
+                )));
+            }
+        }
+        
+        // Check concentration: the symbol's share of the gross book afterwards.
+        // A book no larger than one symbol's allowance (the first position
+        // included) is concentrated by construction, so the per-symbol limit
+        // governs until it grows past that.
+        if gross_after > self.limits.max_notional_per_symbol {
+            let concentration = (pos_notional + additional_notional) / gross_after;
+            if concentration > self.limits.max_position_concentration {
+                metrics::increment_counter!("risk_rejections_total", "reason" => "max_position_concentration");
+                return Err(Error::RiskCheck(format!(
+                    "Would exceed concentration limit for {}: {:.1}% of {:.0} {} > {:.1}%",
+                    symbol, concentration * 100.0, gross_after, self.base_currency(),
+                    self.limits.max_position_concentration * 100.0
                 )));
             }
         }
@@ -406,6 +939,7 @@ mod tests {
             maker_rebate_bps: 1.0,
             impact_bps: 2.0,
             slippage_buffer_bps: 1.0,
+            maker_slippage_buffer_bps: 1.0,
         };
         
         let risk = RiskState {
@@ -419,6 +953,124 @@ mod tests {
         
         let result = gate.check(&prediction, &features, &costs, &risk);
         assert!(matches!(result, GateResult::Pass { .. }));
+        
+        // NaN model output is rejected rather than slipping past the thresholds
+        let nan_prediction = Prediction { edge_bps: f64::NAN, ..prediction.clone() };
+        let result = gate.check(&nan_prediction, &features, &costs, &risk);
+        assert!(matches!(result, GateResult::Reject(_)));
+        
+        let nan_confidence = Prediction { confidence: f64::NAN, ..prediction };
+        assert_eq!(gate.compute_urgency(&nan_confidence, &features, 0.2), 0.0);
+    }
+    
+    #[test]
+    fn test_spread_percentile_gate() {
+        let gate = TradeGate::new(GateParams {
+            spread_percentile: 0.9,
+            spread_min_samples: 20,
+            ..GateParams::default()
+        });
+        
+        let prediction = Prediction {
+            timestamp_ns: 0,
+            symbol: "ALT".to_string(),
+            edge_bps: 60.0,
+            confidence: 0.8,
+            horizon_ms: 5000,
+            model_version: "test".to_string(),
+        };
+        let quote = |spread_bps: f64| FeatureVec {
+            timestamp_ns: 0,
+            symbol: "ALT".to_string(),
+            mid_price: 2.0,
+            spread_bps,
+            ofi_1s: 0.0,
+            obi_1s: 0.0,
+            depth_imbalance: 0.0,
+            depth_a: 0.0,
+            depth_beta: 0.0,
+            realized_vol_5s: 0.0,
+            atr_30s: 0.0,
+            funding_bps_8h: 0.0,
+            impact_bps_1pct: 0.0,
+            microprice: 2.0,
+            vwap_ratio: 1.0,
+        };
+        let costs = CostModel {
+            taker_fee_bps: 5.0,
+            maker_fee_bps: 2.0,
+            maker_rebate_bps: 0.0,
+            impact_bps: 1.0,
+            slippage_buffer_bps: 1.0,
+            maker_slippage_buffer_bps: 1.0,
+        };
+        let risk = RiskState {
+            current_notional: 0.0,
+            max_notional: 100000.0,
+            daily_pnl: 0.0,
+            daily_loss_limit: 10000.0,
+            kill_switch_active: false,
+            daily_loss_exceeded: false,
+        };
+        
+        // Normally 20-24 bps wide: the static 10 bps gate rejects it outright
+        // until enough history builds up
+        assert!(matches!(gate.check(&prediction, &quote(20.0), &costs, &risk), GateResult::Reject(_)));
+        for i in 0..30 {
+            gate.check(&prediction, &quote(20.0 + (i % 5) as f64), &costs, &risk);
+        }
+        
+        assert!(matches!(gate.check(&prediction, &quote(22.0), &costs, &risk), GateResult::Pass { .. }));
+        match gate.check(&prediction, &quote(40.0), &costs, &risk) {
+            GateResult::Reject(reason) => assert!(reason.contains("Wide spread"), "{}", reason),
+            GateResult::Pass { .. } => panic!("temporarily wide spread passed"),
+        }
+    }
+    
+    #[test]
+    fn test_book_pressure_raises_aligned_urgency() {
+        let gate = TradeGate::new(GateParams::default());
+        let weight = GateParams::default().book_pressure_weight;
+        
+        let prediction = Prediction {
+            timestamp_ns: 0,
+            symbol: "BTC".to_string(),
+            edge_bps: 12.0,
+            confidence: 0.7,
+            horizon_ms: 5000,
+            model_version: "test".to_string(),
+        };
+        let book = |obi_1s: f64, microprice: f64| FeatureVec {
+            timestamp_ns: 0,
+            symbol: "BTC".to_string(),
+            mid_price: 50000.0,
+            spread_bps: 2.0,
+            ofi_1s: 0.0,
+            obi_1s,
+            depth_imbalance: obi_1s,
+            depth_a: 0.0,
+            depth_beta: 0.0,
+            realized_vol_5s: 0.0,
+            atr_30s: 0.0,
+            funding_bps_8h: 0.0,
+            impact_bps_1pct: 0.0,
+            microprice,
+            vwap_ratio: 1.0,
+        };
+        
+        let balanced = gate.compute_urgency(&prediction, &book(0.0, 50000.0), weight);
+        let bid_heavy = gate.compute_urgency(&prediction, &book(0.8, 50004.0), weight);
+        let ask_heavy = gate.compute_urgency(&prediction, &book(-0.8, 49996.0), weight);
+        
+        assert!(bid_heavy > balanced, "{} <= {}", bid_heavy, balanced);
+        assert!(ask_heavy < balanced, "{} >= {}", ask_heavy, balanced);
+        
+        // A sell signal is helped by the ask-heavy book instead
+        let sell = Prediction { edge_bps: -12.0, ..prediction };
+        assert!(gate.compute_urgency(&sell, &book(-0.8, 49996.0), weight) > balanced);
+        
+        // Zero weight ignores the book
+        assert_eq!(gate.compute_urgency(&sell, &book(0.8, 50004.0), 0.0), balanced);
     }
     
     #[test]
@@ -458,11 +1110,153 @@ mod tests {
             maker_rebate_bps: 1.0,
             impact_bps: 2.0,
             slippage_buffer_bps: 1.0,
+            maker_slippage_buffer_bps: 1.0,
         };
         
         let decision = router.decide(&prediction, &features, &costs);
         assert!(decision.should_trade);
         assert!(decision.size_fraction > 0.0);
+        assert_eq!(decision.side, Some(Side::Buy));
+        
+        // A short signal trades short despite buy-side order flow
+        let sell = Prediction { edge_bps: -15.0, ..prediction };
+        let decision = router.decide(&sell, &features, &costs);
+        assert!(decision.should_trade);
+        assert_eq!(decision.side, Some(Side::Sell));
+    }
+    
+    /// Signal with a fixed horizon
+    fn signal(edge_bps: f64, confidence: f64) -> Prediction {
+        Prediction {
+            timestamp_ns: 0,
+            symbol: "BTC".to_string(),
+            edge_bps,
+            confidence,
+            horizon_ms: 5000,
+            model_version: "test".to_string(),
+        }
+    }
+    
+    /// Book without pressure either way, so urgency comes from the signal and spread
+    fn neutral_book(spread_bps: f64) -> FeatureVec {
+        FeatureVec {
+            timestamp_ns: 0,
+            symbol: "BTC".to_string(),
+            mid_price: 50000.0,
+            spread_bps,
+            ofi_1s: 0.0,
+            obi_1s: 0.0,
+            depth_imbalance: 0.0,
+            depth_a: 0.001,
+            depth_beta: 0.5,
+            realized_vol_5s: 0.02,
+            atr_30s: 10.0,
+            funding_bps_8h: 0.0,
+            impact_bps_1pct: 0.5,
+            microprice: 50000.0,
+            vwap_ratio: 1.0,
+        }
+    }
+    
+    #[test]
+    fn test_maker_rebate_prefers_passive() {
+        let router = OrderRouter::new(GateParams::default(), RiskLimits::default());
+        
+        // Urgency 0.2 + 0.06 + 0.18 = 0.44 on an 8 bps spread
+        let prediction = signal(12.0, 0.5);
+        let features = neutral_book(8.0);
+        
+        let flat_fees = CostModel {
+            taker_fee_bps: 5.0,
+            maker_fee_bps: 5.0,
+            maker_rebate_bps: 0.0,
+            impact_bps: 1.0,
+            slippage_buffer_bps: 1.0,
+            maker_slippage_buffer_bps: 1.0,
+        };
+        let decision = router.decide(&prediction, &features, &flat_fees);
+        assert!(decision.should_trade);
+        assert_eq!(decision.style, OrderStyle::TakerNow);
+        
+        // Same signal on a venue paying makers: resting nets 7 bps more
+        let rebate = CostModel { maker_fee_bps: 0.0, maker_rebate_bps: 2.0, ..flat_fees.clone() };
+        let decision = router.decide(&prediction, &features, &rebate);
+        assert!(decision.should_trade);
+        assert_eq!(decision.style, OrderStyle::MakerPassive);
+        
+        // Urgency past the cap (0.97) still takes despite the rebate
+        let urgent = signal(20.0, 1.0);
+        assert_eq!(router.decide(&urgent, &neutral_book(1.0), &rebate).style, OrderStyle::TakerNow);
+    }
+    
+    #[test]
+    fn test_fee_tier_drives_routing() {
+        let router = OrderRouter::new(GateParams::default(), RiskLimits::default());
+        let prediction = signal(12.0, 0.5);
+        let features = neutral_book(8.0);
+        
+        let standard = FeeTier { maker_fee_bps: 4.0, taker_fee_bps: 5.0, volume_30d: 0.0 };
+        let vip = FeeTier { maker_fee_bps: -1.0, ..standard.clone() };
+        let standard_costs = CostModel::from_fees(&standard, features.impact_bps_1pct, 1.0, 1.0);
+        let vip_costs = CostModel::from_fees(&vip, features.impact_bps_1pct, 1.0, 1.0);
+        
+        assert_eq!(vip_costs.maker_rebate_bps, 1.0);
+        assert_eq!(vip_costs.net_edge_maker(20.0) - standard_costs.net_edge_maker(20.0), 5.0);
+        assert_eq!(vip_costs.net_edge_taker(20.0), standard_costs.net_edge_taker(20.0));
+        
+        // The lower maker fee alone turns a take into a resting order
+        let on_standard = router.decide(&prediction, &features, &standard_costs);
+        let on_vip = router.decide(&prediction, &features, &vip_costs);
+        assert!(on_standard.should_trade && on_vip.should_trade);
+        assert_eq!(on_standard.style, OrderStyle::TakerNow);
+        assert_eq!(on_vip.style, OrderStyle::MakerPassive);
+        
+        // The gate clears edge net of the taker fee: 11 bps passes 5 bps net
+        // of a 4.5 bps taker cost but not the standard tier's 6.5
+        let cheap_taker = FeeTier { taker_fee_bps: 3.0, ..vip.clone() };
+        let cheap_taker_costs = CostModel::from_fees(&cheap_taker, features.impact_bps_1pct, 1.0, 1.0);
+        let thin = signal(11.0, 0.5);
+        let tight = neutral_book(1.0);
+        assert!(!router.decide(&thin, &tight, &standard_costs).should_trade);
+        assert!(router.decide(&thin, &tight, &cheap_taker_costs).should_trade);
+        
+        // Unfetched venues fall back to the default tier
+        let mut schedule = FeeSchedule::default();
+        assert_eq!(schedule.get(Venue::Hyperliquid).taker_fee_bps, FALLBACK_FEES.taker_fee_bps);
+        schedule.update(Venue::Hyperliquid, vip);
+        assert_eq!(schedule.get(Venue::Hyperliquid).maker_fee_bps, -1.0);
+        assert!(!schedule.is_known(Venue::BinanceFutures));
+    }
+    
+    #[test]
+    fn test_style_from_net_edge() {
+        let router = OrderRouter::new(GateParams::default(), RiskLimits::default());
+        let costs = CostModel {
+            taker_fee_bps: 2.0,
+            maker_fee_bps: 0.0,
+            maker_rebate_bps: 1.0,
+            impact_bps: 0.5,
+            slippage_buffer_bps: 0.5,
+            maker_slippage_buffer_bps: 0.5,
+        };
+        
+        // Thin edge, patient signal (urgency 0.395): crossing nets 2 bps,
+        // resting ~4.4 after its fill odds
+        let thin = signal(9.0, 0.5);
+        let [(_, taker), (_, maker), _] = style_edges(9.0, &costs, 8.0, 0.395);
+        assert!(maker > taker + 2.0, "maker {} taker {}", maker, taker);
+        assert_eq!(router.decide(&thin, &neutral_book(8.0), &costs).style, OrderStyle::MakerPassive);
+        
+        // Fat edge, urgency 0.9: crossing now keeps 36 bps, waiting risks it
+        let fat = signal(40.0, 0.9);
+        assert_eq!(router.decide(&fat, &neutral_book(2.0), &costs).style, OrderStyle::TakerNow);
+        
+        // Fill odds fall with distance and urgency
+        assert!(fill_probability(2.0, 0.3) > fill_probability(8.0, 0.3));
+        assert!(fill_probability(2.0, 0.3) > fill_probability(2.0, 0.7));
+        assert_eq!(fill_probability(2.0, 1.0), 0.0);
+        assert_eq!(fill_probability(0.0, 0.0), 1.0);
+        assert_eq!(fill_probability(2.0, f64::NAN), 0.0);
     }
     
     #[test]
@@ -473,6 +1267,8 @@ mod tests {
             max_leverage: 3.0,
             max_loss_per_day: 5000.0,
             max_position_concentration: 0.5,
+            max_open_positions: 10,
+            ..RiskLimits::default()
         };
         
         let mut manager = RiskManager::new(limits);
@@ -491,6 +1287,7 @@ mod tests {
             leverage: 1.0,
             margin_used: 50000.0,
             liquidation_price: None,
+            maintenance_margin: None,
         };
         
         manager.update_position(position);
@@ -502,4 +1299,355 @@ mod tests {
         manager.activate_kill_switch();
         assert!(manager.check_limits("ETH", 10000.0).is_err());
     }
+    
+    #[test]
+    fn test_risk_snapshot() {
+        let mut manager = RiskManager::new(RiskLimits::default());
+        let position = |symbol: &str, size: f64, mark_price: f64, margin_used: f64| Position {
+            symbol: symbol.to_string(),
+            size,
+            entry_price: mark_price,
+            mark_price,
+            unrealized_pnl: 100.0,
+            realized_pnl: -20.0,
+            leverage: 2.0,
+            margin_used,
+            liquidation_price: None,
+            maintenance_margin: None,
+        };
+        let mut btc = position("BTC", 1.0, 50_000.0, 25_000.0);
+        btc.maintenance_margin = Some(250.0);
+        manager.update_position(btc);
+        manager.update_position(position("ETH", -10.0, 3_000.0, 15_000.0));
+        manager.update_position(position("SOL", 0.0, 150.0, 0.0));
+        manager.update_equity(100_000.0);
+        manager.set_volatility("BTC", 0.03);
+        manager.activate_kill_switch();
+        
+        let snapshot = manager.snapshot();
+        assert_eq!(snapshot.num_positions, 2);
+        assert!((snapshot.gross_notional - 80_000.0).abs() < 1e-6);
+        assert!((snapshot.net_notional - 20_000.0).abs() < 1e-6);
+        assert!((snapshot.total_margin_used - 40_000.0).abs() < 1e-6);
+        assert!((snapshot.total_maintenance_margin - 250.0).abs() < 1e-6);
+        // Available margin is what initial margin leaves, not maintenance
+        assert!((snapshot.available_margin - 60_000.0).abs() < 1e-6);
+        assert!((snapshot.total_pnl - 240.0).abs() < 1e-6);
+        assert!(snapshot.kill_switch_active);
+        
+        // Only BTC has a known vol: 1.645 * 50k * 3%
+        assert!((snapshot.var_95 - 2_467.5).abs() < 1e-6);
+    }
+    
+    #[test]
+    fn test_var_aggregation() {
+        let position = |symbol: &str, size: f64, mark_price: f64| Position {
+            symbol: symbol.to_string(),
+            size,
+            entry_price: mark_price,
+            mark_price,
+            unrealized_pnl: 0.0,
+            realized_pnl: 0.0,
+            leverage: 1.0,
+            margin_used: 0.0,
+            liquidation_price: None,
+            maintenance_margin: None,
+        };
+        let book = |limits: RiskLimits| {
+            let mut manager = RiskManager::new(limits);
+            // $1,000 and -$1,200 of daily vol
+            manager.update_position(position("BTC", 1.0, 50_000.0));
+            manager.update_position(position("ETH", -10.0, 3_000.0));
+            manager.set_volatility("BTC", 0.02);
+            manager.set_volatility("ETH", 0.04);
+            manager
+        };
+        
+        // Independent: 1.645 * sqrt(1000^2 + 1200^2)
+        let independent = book(RiskLimits::default()).var_95();
+        assert!((independent - 1.645 * 2_440_000f64.sqrt()).abs() < 1e-6);
+        
+        // rho = 0.5: 0.5 * 2.44M + 0.5 * (-200)^2, the short hedging the long
+        let correlated = book(RiskLimits { var_correlation: 0.5, ..RiskLimits::default() }).var_95();
+        assert!((correlated - 1.645 * 1_240_000f64.sqrt()).abs() < 1e-6);
+        assert_eq!(book(RiskLimits::default()).snapshot().var_95, independent);
+        
+        // Feature-derived vol: 0.1% over 5s is ~13.1% a day
+        let mut manager = RiskManager::new(RiskLimits::default());
+        let features = FeatureVec {
+            timestamp_ns: 0,
+            symbol: "SOL".to_string(),
+            mid_price: 150.0,
+            spread_bps: 1.0,
+            ofi_1s: 0.0,
+            obi_1s: 0.0,
+            depth_imbalance: 0.0,
+            depth_a: 0.0,
+            depth_beta: 0.0,
+            realized_vol_5s: 0.001,
+            atr_30s: 0.0,
+            funding_bps_8h: 0.0,
+            impact_bps_1pct: 0.0,
+            microprice: 150.0,
+            vwap_ratio: 1.0,
+        };
+        manager.observe_features(&features);
+        assert!((manager.volatility("SOL").unwrap() - 0.001 * 17_280f64.sqrt()).abs() < 1e-12);
+    }
+    
+    #[test]
+    fn test_cross_asset_notional_in_base_currency() {
+        let limits = RiskLimits {
+            max_notional_per_symbol: 200_000.0,
+            max_total_notional: 200_000.0,
+            // Only the currency conversion is under test here
+            max_position_concentration: 1.0,
+            ..RiskLimits::default()
+        };
+        let mut manager = RiskManager::new(limits);
+        manager.set_reference_rate("EUR", 1.25);
+        manager.observe_price("BTC-USD", 40_000.0);
+        
+        let position = |symbol: &str, size: f64, mark_price: f64| Position {
+            symbol: symbol.to_string(),
+            size,
+            entry_price: mark_price,
+            mark_price,
+            unrealized_pnl: 0.0,
+            realized_pnl: 0.0,
+            leverage: 1.0,
+            margin_used: 0.0,
+            liquidation_price: None,
+            maintenance_margin: None,
+        };
+        
+        // 10_000 USDT at par, 2 * 0.05 BTC at 40k, 80 * 100 EUR at 1.25, -50 AAPL at 200 USD
+        manager.update_position(position("SOLUSDT", 100.0, 100.0));
+        manager.update_position(position("ETH-BTC", 2.0, 0.05));
+        manager.update_position(position("SAP-EUR", 80.0, 100.0));
+        manager.update_position(position("AAPL", -50.0, 200.0));
+        
+        let state = manager.get_state();
+        assert!((state.current_notional - 34_000.0).abs() < 1e-6);
+        
+        // 100k EUR of extra notional is 125k USD, over the 200k total with the book
+        assert!(manager.check_limits("SAP-EUR", 100_000.0).is_ok());
+        assert!(manager.check_limits("SAP-EUR", 140_000.0).is_err());
+        
+        // No JPY rate: the aggregate can't be trusted, so new risk is refused
+        manager.update_position(position("7203-JPY", 100.0, 3_000.0));
+        assert!(manager.check_limits("BTC-USD", 1.0).is_err());
+        manager.set_reference_rate("JPY", 0.0067);
+        assert!(manager.check_limits("BTC-USD", 1.0).is_ok());
+    }
+    
+    #[test]
+    fn test_max_open_positions() {
+        let limits = RiskLimits {
+            max_open_positions: 2,
+            ..RiskLimits::default()
+        };
+        
+        let mut manager = RiskManager::new(limits);
+        
+        for symbol in ["BTC", "ETH"] {
+            manager.update_position(Position {
+                symbol: symbol.to_string(),
+                size: 1.0,
+                entry_price: 1000.0,
+                mark_price: 1000.0,
+                unrealized_pnl: 0.0,
+                realized_pnl: 0.0,
+                leverage: 1.0,
+                margin_used: 1000.0,
+                liquidation_price: None,
+                maintenance_margin: None,
+            });
+        }
+        
+        assert_eq!(manager.open_positions(), 2);
+        
+        // New symbol at the cap is rejected
+        assert!(manager.can_open("SOL").is_err());
+        assert!(manager.check_limits("SOL", 1000.0).is_err());
+        
+        // Adding to an existing position is still allowed
+        assert!(manager.can_open("BTC").is_ok());
+        assert!(manager.check_limits("BTC", 1000.0).is_ok());
+    }
+    
+    #[test]
+    fn test_concentration_limit() {
+        let limits = RiskLimits {
+            max_notional_per_symbol: 100_000.0,
+            max_total_notional: 500_000.0,
+            max_position_concentration: 0.25,
+            ..RiskLimits::default()
+        };
+        let mut manager = RiskManager::new(limits);
+        
+        // First position: the whole book, but within the per-symbol limit
+        assert!(manager.check_limits("BTC", 90_000.0).is_ok());
+        
+        let position = |symbol: &str, notional: f64| Position {
+            symbol: symbol.to_string(),
+            size: 1.0,
+            entry_price: notional,
+            mark_price: notional,
+            unrealized_pnl: 0.0,
+            realized_pnl: 0.0,
+            leverage: 1.0,
+            margin_used: 0.0,
+            liquidation_price: None,
+            maintenance_margin: None,
+        };
+        manager.update_position(position("BTC", 90_000.0));
+        manager.update_position(position("ETH", 50_000.0));
+        manager.update_position(position("SOL", 50_000.0));
+        
+        // BTC to 95k of a 195k book: per-symbol and total pass, 48.7% > 25% doesn't
+        let err = manager.check_limits("BTC", 5_000.0).unwrap_err();
+        assert!(matches!(&err, Error::RiskCheck(m) if m.contains("concentration")), "{}", err);
+        
+        // A new small position keeps its share under the limit
+        assert!(manager.check_limits("AVAX", 20_000.0).is_ok());
+    }
+    
+    #[test]
+    fn test_leverage_limit() {
+        let limits = RiskLimits { max_leverage: 3.0, ..RiskLimits::default() };
+        let mut manager = RiskManager::new(limits);
+        manager.update_position(Position {
+            symbol: "BTC".to_string(),
+            size: 1.0,
+            entry_price: 50_000.0,
+            mark_price: 50_000.0,
+            unrealized_pnl: 0.0,
+            realized_pnl: 0.0,
+            leverage: 1.0,
+            margin_used: 0.0,
+            liquidation_price: None,
+            maintenance_margin: None,
+        });
+        
+        // Unsynced: only the notional limits apply
+        assert!(manager.check_limits("BTC", 40_000.0).is_ok());
+        
+        // 90k gross on 30k equity is exactly 3x
+        manager.update_equity(30_000.0);
+        assert!(manager.check_limits("BTC", 40_000.0).is_ok());
+        
+        let err = manager.check_limits("BTC", 45_000.0).unwrap_err();
+        assert!(matches!(&err, Error::RiskCheck(m) if m.contains("3.17x > 3.00x")), "{}", err);
+        
+        manager.update_equity(0.0);
+        assert!(manager.check_limits("BTC", 1.0).is_err());
+    }
+    
+    #[test]
+    fn test_daily_pnl_resets_at_utc_rollover() {
+        // 2024-01-01 22:00:00 UTC
+        let t0 = 1_704_146_400;
+        let midnight = t0 + 2 * 3_600;
+        let mut manager = RiskManager::new_at(RiskLimits::default(), t0);
+        
+        manager.update_pnl_at(-100.0, t0);
+        manager.update_pnl_at(-50.0, midnight - 1);
+        assert_eq!(manager.get_state().daily_pnl, -150.0);
+        
+        // Crossing midnight resets once; later updates the same day accumulate
+        manager.update_pnl_at(10.0, midnight);
+        manager.update_pnl_at(5.0, midnight + 1);
+        manager.update_pnl_at(5.0, midnight + 12 * 3_600);
+        assert_eq!(manager.get_state().daily_pnl, 20.0);
+        
+        // The next day rolls over even when the first update comes hours late
+        manager.update_pnl_at(-1.0, midnight + 86_400 + 5 * 3_600);
+        assert_eq!(manager.get_state().daily_pnl, -1.0);
+        
+        // A configured reset hour moves the boundary
+        let limits = RiskLimits { reset_hour_utc: 8, ..RiskLimits::default() };
+        let mut manager = RiskManager::new_at(limits, midnight);
+        manager.update_pnl_at(-10.0, midnight + 7 * 3_600);
+        manager.update_pnl_at(-10.0, midnight + 8 * 3_600 - 1);
+        assert_eq!(manager.get_state().daily_pnl, -20.0);
+        manager.update_pnl_at(-10.0, midnight + 8 * 3_600);
+        assert_eq!(manager.get_state().daily_pnl, -10.0);
+    }
+    
+    #[test]
+    fn test_daily_loss_trips_kill_switch() {
+        let t0 = 1_704_146_400;
+        let limits = RiskLimits { max_loss_per_day: 1_000.0, ..RiskLimits::default() };
+        let mut manager = RiskManager::new_at(limits, t0);
+        
+        assert!(!manager.update_pnl_at(-600.0, t0));
+        assert!(!manager.update_pnl_at(-399.0, t0 + 60));
+        assert!(manager.check_limits("BTC", 1_000.0).is_ok());
+        
+        // Cumulative -1,000 reaches the limit: trips once
+        assert!(manager.update_pnl_at(-1.0, t0 + 120));
+        assert!(manager.get_state().kill_switch_active);
+        assert!(!manager.update_pnl_at(-50.0, t0 + 180));
+        
+        // Recovering PnL and a new trading day leave it on
+        manager.update_pnl_at(5_000.0, t0 + 240);
+        manager.roll_day_at(t0 + 2 * 86_400);
+        assert!(manager.get_state().kill_switch_active);
+        assert!(manager.check_limits("BTC", 1_000.0).is_err());
+        
+        manager.deactivate_kill_switch();
+        assert!(manager.check_limits("BTC", 1_000.0).is_ok());
+        
+        // A lower multiple trips earlier
+        let limits = RiskLimits {
+            max_loss_per_day: 1_000.0,
+            kill_switch_loss_multiple: 0.5,
+            ..RiskLimits::default()
+        };
+        let mut manager = RiskManager::new_at(limits, t0);
+        assert!(manager.update_pnl_at(-500.0, t0));
+    }
+    
+    #[test]
+    fn test_reduce_only_clamped_to_position() {
+        let mut manager = RiskManager::new(RiskLimits::default());
+        manager.update_position(Position {
+            symbol: "BTC".to_string(),
+            size: 0.75,
+            entry_price: 50000.0,
+            mark_price: 50000.0,
+            unrealized_pnl: 0.0,
+            realized_pnl: 0.0,
+            leverage: 1.0,
+            margin_used: 37500.0,
+            liquidation_price: None,
+            maintenance_margin: None,
+        });
+        
+        let mut order = OrderRequest {
+            client_id: "exit".to_string(),
+            symbol: "BTC".to_string(),
+            side: Side::Sell,
+            order_type: OrderType::Market,
+            quantity: 2.0,
+            price: None,
+            reduce_only: true,
+            time_in_force: TimeInForce::IOC,
+        };
+        
+        // Oversized exit flattens exactly
+        manager.finalize_order(&mut order);
+        assert_eq!(order.quantity, 0.75);
+        
+        // Reduce-only in the direction of the position can't reduce anything
+        let mut wrong_side = OrderRequest { side: Side::Buy, quantity: 1.0, ..order.clone() };
+        manager.finalize_order(&mut wrong_side);
+        assert_eq!(wrong_side.quantity, 0.0);
+        
+        // Non-reduce-only orders are untouched
+        let mut entry = OrderRequest { reduce_only: false, quantity: 2.0, ..order };
+        manager.finalize_order(&mut entry);
+        assert_eq!(entry.quantity, 2.0);
+    }
 }
@@ -1,15 +1,310 @@
 use common::*;
+use arrow_array::{ArrayRef, Float64Array, Int64Array, RecordBatch, StringArray, UInt64Array};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use async_trait::async_trait;
 use aws_sdk_s3::Client;
+use aws_sdk_s3::primitives::ByteStream;
+use parquet::arrow::ArrowWriter;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
 
-pub struct S3Writer {
+/// Destination for encoded shards
+#[async_trait]
+pub trait ObjectSink: Send + Sync {
+    async fn put(&self, key: &str, body: Vec<u8>) -> Result<()>;
+}
+
+/// Uploads shards to an S3 bucket
+pub struct S3Sink {
     client: Client,
     bucket: String,
 }
 
+#[async_trait]
+impl ObjectSink for S3Sink {
+    async fn put(&self, key: &str, body: Vec<u8>) -> Result<()> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .body(ByteStream::from(body))
+            .send()
+            .await
+            .map_err(|e| Error::Internal(format!("S3 upload of s3://{}/{} failed: {}", self.bucket, key, e)))?;
+        Ok(())
+    }
+}
+
+/// Writes shards under a local directory, keys become relative paths
+pub struct LocalSink {
+    root: PathBuf,
+}
+
+impl LocalSink {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+#[async_trait]
+impl ObjectSink for LocalSink {
+    async fn put(&self, key: &str, body: Vec<u8>) -> Result<()> {
+        let path = self.root.join(key);
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir).await?;
+        }
+        tokio::fs::write(&path, body).await?;
+        Ok(())
+    }
+}
+
+/// Feature columns, in schema order after `timestamp_ns` and `symbol`
+const FEATURE_COLUMNS: &[(&str, fn(&FeatureVec) -> f64)] = &[
+    ("mid_price", |f| f.mid_price),
+    ("spread_bps", |f| f.spread_bps),
+    ("ofi_1s", |f| f.ofi_1s),
+    ("obi_1s", |f| f.obi_1s),
+    ("depth_imbalance", |f| f.depth_imbalance),
+    ("depth_a", |f| f.depth_a),
+    ("depth_beta", |f| f.depth_beta),
+    ("realized_vol_5s", |f| f.realized_vol_5s),
+    ("atr_30s", |f| f.atr_30s),
+    ("funding_bps_8h", |f| f.funding_bps_8h),
+    ("impact_bps_1pct", |f| f.impact_bps_1pct),
+    ("microprice", |f| f.microprice),
+    ("vwap_ratio", |f| f.vwap_ratio),
+];
+
+/// Arrow schema of a training shard: one row per (sample, prediction) pair
+pub fn training_schema() -> SchemaRef {
+    let mut fields = vec![
+        Field::new("timestamp_ns", DataType::Int64, false),
+        Field::new("symbol", DataType::Utf8, false),
+    ];
+    fields.extend(FEATURE_COLUMNS.iter().map(|(name, _)| Field::new(*name, DataType::Float64, false)));
+    fields.extend([
+        Field::new("edge_bps", DataType::Float64, false),
+        Field::new("confidence", DataType::Float64, false),
+        Field::new("horizon_ms", DataType::UInt64, false),
+        Field::new("model_version", DataType::Utf8, false),
+    ]);
+    Arc::new(Schema::new(fields))
+}
+
+#[derive(Default)]
+struct Shard {
+    samples: Vec<FeatureVec>,
+    predictions: Vec<Prediction>,
+}
+
+/// Buffers training samples per symbol and uploads them as parquet shards
+pub struct S3Writer {
+    sink: Arc<dyn ObjectSink>,
+    prefix: String,
+    samples_per_shard: usize,
+    pending: parking_lot::Mutex<HashMap<String, Shard>>,
+}
+
 impl S3Writer {
-    pub fn new(client: Client, bucket: String) -> Self {
-        Self { client, bucket }
+    pub fn new(client: Client, bucket: String, prefix: String, samples_per_shard: usize) -> Self {
+        Self::with_sink(Arc::new(S3Sink { client, bucket }), prefix, samples_per_shard)
+    }
+    
+    pub fn with_sink(sink: Arc<dyn ObjectSink>, prefix: String, samples_per_shard: usize) -> Self {
+        Self {
+            sink,
+            prefix: prefix.trim_end_matches('/').to_string(),
+            samples_per_shard: samples_per_shard.max(1),
+            pending: parking_lot::Mutex::new(HashMap::new()),
+        }
     }
     
-    // TODO: Implement parquet writing
+    /// Buffer `samples[i]` with `predictions[i]`, uploading every shard that fills up
+    pub async fn write_batch(&self, samples: &[FeatureVec], predictions: &[Prediction]) -> Result<()> {
+        if samples.len() != predictions.len() {
+            return Err(Error::Internal(format!(
+                "Training batch has {} samples but {} predictions",
+                samples.len(), predictions.len()
+            )));
+        }
+        
+        let full = {
+            let mut pending = self.pending.lock();
+            let mut full = Vec::new();
+            for (sample, prediction) in samples.iter().zip(predictions) {
+                let shard = pending.entry(sample.symbol.clone()).or_default();
+                shard.samples.push(sample.clone());
+                shard.predictions.push(prediction.clone());
+                
+                if shard.samples.len() >= self.samples_per_shard {
+                    full.push((sample.symbol.clone(), std::mem::take(shard)));
+                }
+            }
+            full
+        };
+        
+        self.upload_all(full).await
+    }
+    
+    /// Upload all partially filled shards (e.g. on shutdown)
+    pub async fn flush(&self) -> Result<()> {
+        let partial: Vec<_> = self.pending
+            .lock()
+            .drain()
+            .filter(|(_, shard)| !shard.samples.is_empty())
+            .collect();
+        
+        self.upload_all(partial).await
+    }
+    
+    /// Upload shards in order; on failure the failed shard and everything after
+    /// it go back into the buffer so nothing is lost before the error surfaces
+    async fn upload_all(&self, shards: Vec<(String, Shard)>) -> Result<()> {
+        let mut shards = shards.into_iter();
+        while let Some((symbol, shard)) = shards.next() {
+            if let Err(e) = self.upload(&symbol, &shard).await {
+                let mut pending = self.pending.lock();
+                for (symbol, mut shard) in std::iter::once((symbol, shard)).chain(shards) {
+                    let slot = pending.entry(symbol).or_default();
+                    shard.samples.append(&mut slot.samples);
+                    shard.predictions.append(&mut slot.predictions);
+                    *slot = shard;
+                }
+                metrics::increment_counter!("s3_writer_upload_failures_total");
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+    
+    async fn upload(&self, symbol: &str, shard: &Shard) -> Result<()> {
+        let body = encode_parquet(&shard.samples, &shard.predictions)?;
+        let key = self.shard_key(symbol, shard.samples[0].timestamp_ns);
+        let rows = shard.samples.len();
+        
+        self.sink.put(&key, body).await?;
+        
+        metrics::counter!("s3_writer_rows_total", rows as u64);
+        tracing::debug!("Uploaded {} training rows to {}", rows, key);
+        Ok(())
+    }
+    
+    /// `{prefix}/{symbol}/{yyyy}/{mm}/{dd}/{uuid}.parquet`, dated by the first row
+    fn shard_key(&self, symbol: &str, timestamp_ns: i64) -> String {
+        let date = chrono::DateTime::from_timestamp_nanos(timestamp_ns).format("%Y/%m/%d");
+        format!("{}/{}/{}/{}.parquet", self.prefix, symbol, date, uuid::Uuid::new_v4())
+    }
+}
+
+/// Encode rows into an in-memory parquet file with `training_schema`
+pub fn encode_parquet(samples: &[FeatureVec], predictions: &[Prediction]) -> Result<Vec<u8>> {
+    let mut columns: Vec<ArrayRef> = vec![
+        Arc::new(Int64Array::from_iter_values(samples.iter().map(|s| s.timestamp_ns))),
+        Arc::new(StringArray::from_iter_values(samples.iter().map(|s| s.symbol.as_str()))),
+    ];
+    columns.extend(FEATURE_COLUMNS.iter().map(|(_, get)| {
+        Arc::new(Float64Array::from_iter_values(samples.iter().map(get))) as ArrayRef
+    }));
+    columns.extend([
+        Arc::new(Float64Array::from_iter_values(predictions.iter().map(|p| p.edge_bps))) as ArrayRef,
+        Arc::new(Float64Array::from_iter_values(predictions.iter().map(|p| p.confidence))),
+        Arc::new(UInt64Array::from_iter_values(predictions.iter().map(|p| p.horizon_ms))),
+        Arc::new(StringArray::from_iter_values(predictions.iter().map(|p| p.model_version.as_str()))),
+    ]);
+    
+    let schema = training_schema();
+    let batch = RecordBatch::try_new(schema.clone(), columns).map_err(parquet_err)?;
+    
+    let mut buffer = Vec::new();
+    let mut writer = ArrowWriter::try_new(&mut buffer, schema, None).map_err(parquet_err)?;
+    writer.write(&batch).map_err(parquet_err)?;
+    writer.close().map_err(parquet_err)?;
+    
+    Ok(buffer)
+}
+
+fn parquet_err(e: impl std::fmt::Display) -> Error {
+    Error::Internal(format!("Parquet encoding failed: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    
+    fn row(symbol: &str, i: usize) -> (FeatureVec, Prediction) {
+        let timestamp_ns = 1_760_400_000_000_000_000 + i as i64;
+        let sample = FeatureVec {
+            timestamp_ns,
+            symbol: symbol.to_string(),
+            mid_price: 100.0 + i as f64,
+            spread_bps: 1.0,
+            ofi_1s: 0.0,
+            obi_1s: 0.0,
+            depth_imbalance: 0.0,
+            depth_a: 0.0,
+            depth_beta: 0.0,
+            realized_vol_5s: 0.0,
+            atr_30s: 0.0,
+            funding_bps_8h: 0.0,
+            impact_bps_1pct: 0.0,
+            microprice: 100.0,
+            vwap_ratio: 1.0,
+        };
+        let prediction = Prediction {
+            timestamp_ns,
+            symbol: symbol.to_string(),
+            edge_bps: 2.5,
+            confidence: 0.8,
+            horizon_ms: 1000,
+            model_version: "v1".to_string(),
+        };
+        (sample, prediction)
+    }
+    
+    fn parquet_files(dir: &std::path::Path, out: &mut Vec<PathBuf>) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                parquet_files(&path, out);
+            } else {
+                out.push(path);
+            }
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_shards_written_with_schema_and_rows() {
+        let root = std::env::temp_dir().join(format!("s3_writer_test_{}", uuid::Uuid::new_v4()));
+        let writer = S3Writer::with_sink(Arc::new(LocalSink::new(&root)), "training/".to_string(), 3);
+        
+        let (samples, predictions): (Vec<_>, Vec<_>) = (0..4).map(|i| row("BTC", i))
+            .chain([row("ETH", 0)])
+            .unzip();
+        writer.write_batch(&samples, &predictions).await.unwrap();
+        
+        // Only the full BTC shard is uploaded before flush
+        let mut files = Vec::new();
+        parquet_files(&root, &mut files);
+        assert_eq!(files.len(), 1);
+        
+        let rel = files[0].strip_prefix(&root).unwrap().to_string_lossy().into_owned();
+        assert!(rel.starts_with("training/BTC/2025/10/14/"), "{}", rel);
+        assert!(rel.ends_with(".parquet"));
+        
+        let reader = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&files[0]).unwrap()).unwrap();
+        assert_eq!(reader.schema().fields(), training_schema().fields());
+        let rows: usize = reader.build().unwrap().map(|b| b.unwrap().num_rows()).sum();
+        assert_eq!(rows, 3);
+        
+        writer.flush().await.unwrap();
+        let mut files = Vec::new();
+        parquet_files(&root, &mut files);
+        assert_eq!(files.len(), 3);
+        
+        assert!(writer.write_batch(&samples[..1], &[]).await.is_err());
+        
+        std::fs::remove_dir_all(&root).ok();
+    }
 }
\ No newline at end of file
@@ -0,0 +1,99 @@
+// crates/engine/src/shutdown.rs - Leave nothing resting on the venues at exit
+use common::*;
+use serde::Deserialize;
+use std::time::Duration;
+
+/// What the engine does with its orders and positions on the way down
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ShutdownConfig {
+    /// Also reduce-only close every position the engine opened
+    pub close_positions: bool,
+    /// How long each venue gets to confirm a cancel or close
+    pub ack_timeout_ms: u64,
+}
+
+impl Default for ShutdownConfig {
+    fn default() -> Self {
+        Self {
+            close_positions: false,
+            ack_timeout_ms: 5_000,
+        }
+    }
+}
+
+/// Cancel everything resting on `symbols`, all at once, giving each symbol
+/// `timeout` to confirm. Returns the symbols that aren't confirmed clear.
+pub async fn cancel_open_orders<R: adapters::OrderRouter + ?Sized>(
+    router: &R,
+    venue: Venue,
+    symbols: &[String],
+    timeout: Duration,
+) -> Vec<String> {
+    let cancels = symbols.iter().map(|symbol| async move {
+        match tokio::time::timeout(timeout, router.cancel_all(symbol)).await {
+            Ok(Ok(())) => None,
+            Ok(Err(e)) => {
+                tracing::error!("{:?}: shutdown cancel failed for {}: {}", venue, symbol, e);
+                Some(symbol.clone())
+            }
+            Err(_) => {
+                tracing::error!("{:?}: shutdown cancel for {} not confirmed within {:?}", venue, symbol, timeout);
+                Some(symbol.clone())
+            }
+        }
+    });
+    
+    futures::future::join_all(cancels).await.into_iter().flatten().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use parking_lot::Mutex;
+    
+    /// Acks every cancel except on `stuck`, which never answers
+    struct FakeRouter {
+        cancelled: Mutex<Vec<String>>,
+        stuck: &'static str,
+    }
+    
+    #[async_trait]
+    impl adapters::OrderRouter for FakeRouter {
+        async fn send_order(&self, _order: OrderRequest) -> Result<OrderAck> {
+            Err(Error::Internal("not used".to_string()))
+        }
+        
+        async fn cancel_order(&self, _order_id: &str) -> Result<()> {
+            Ok(())
+        }
+        
+        async fn cancel_all(&self, symbol: &str) -> Result<()> {
+            self.cancelled.lock().push(symbol.to_string());
+            if symbol == self.stuck {
+                std::future::pending::<()>().await;
+            }
+            Ok(())
+        }
+        
+        async fn get_order(&self, _order_id: &str) -> Result<OrderAck> {
+            Err(Error::NotFound("not used".to_string()))
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_shutdown_cancels_every_tracked_symbol() {
+        let router = FakeRouter { cancelled: Mutex::new(vec![]), stuck: "SOL" };
+        let symbols: Vec<String> = ["BTC", "ETH", "SOL"].iter().map(|s| s.to_string()).collect();
+        
+        let unconfirmed = cancel_open_orders(&router, Venue::Hyperliquid, &symbols, Duration::from_millis(50)).await;
+        
+        let mut cancelled = router.cancelled.lock().clone();
+        cancelled.sort();
+        assert_eq!(cancelled, symbols);
+        
+        // A venue that never acks doesn't hold up shutdown past the timeout
+        assert_eq!(unconfirmed, vec!["SOL".to_string()]);
+    }
+}
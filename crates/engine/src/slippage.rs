@@ -0,0 +1,146 @@
+// crates/engine/src/slippage.rs - Realized slippage feeding the cost model
+use common::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// How realized slippage adapts the cost model's slippage buffer
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SlippageConfig {
+    /// Buffer for symbols without fills yet, and the estimate's starting point
+    pub prior_bps: f64,
+    /// Weight of each new fill in the moving average, in (0, 1]
+    pub alpha: f64,
+    pub min_bps: f64,
+    pub max_bps: f64,
+}
+
+impl Default for SlippageConfig {
+    fn default() -> Self {
+        Self {
+            prior_bps: 1.0,
+            alpha: 0.1,
+            min_bps: 0.0,
+            max_bps: 25.0,
+        }
+    }
+}
+
+/// Price a style's expected edge already assumes it fills at, so the buffer
+/// only carries what comes on top: takers cross to the far touch, passive
+/// orders rest at their own touch and `Sniper` limits at mid
+pub fn reference_price(style: OrderStyle, side: Side, bid: f64, ask: f64) -> f64 {
+    match (style, side) {
+        (OrderStyle::TakerNow, Side::Buy) | (OrderStyle::MakerPassive, Side::Sell) => ask,
+        (OrderStyle::TakerNow, Side::Sell) | (OrderStyle::MakerPassive, Side::Buy) => bid,
+        (OrderStyle::Sniper, _) => (bid + ask) / 2.0,
+    }
+}
+
+/// Slippage of a fill against its `reference_price`, in bps. Positive means
+/// the fill was worse than the reference.
+pub fn realized_slippage_bps(side: Side, reference: f64, fill_price: f64) -> Option<f64> {
+    if !(reference > 0.0 && fill_price.is_finite()) {
+        return None;
+    }
+    
+    let sign = match side {
+        Side::Buy => 1.0,
+        Side::Sell => -1.0,
+    };
+    Some(sign * (fill_price - reference) / reference * 1e4)
+}
+
+/// Exponentially weighted realized slippage per symbol and order style
+#[derive(Debug, Default)]
+pub struct SlippageTracker {
+    config: SlippageConfig,
+    estimates: HashMap<(String, OrderStyle), f64>,
+}
+
+impl SlippageTracker {
+    pub fn new(config: SlippageConfig) -> Self {
+        Self { config, estimates: HashMap::new() }
+    }
+    
+    /// Fold in a fill of a `style` order, against the touch at decision time
+    pub fn record_fill(&mut self, symbol: &str, style: OrderStyle, side: Side, bid: f64, ask: f64, fill_price: f64) {
+        let Some(observed) = realized_slippage_bps(side, reference_price(style, side, bid, ask), fill_price) else {
+            return;
+        };
+        
+        let alpha = self.config.alpha.clamp(f64::EPSILON, 1.0);
+        let estimate = self.estimates.entry((symbol.to_string(), style)).or_insert(self.config.prior_bps);
+        *estimate += alpha * (observed - *estimate);
+        metrics::gauge!("slippage_estimate_bps", *estimate,
+            "symbol" => symbol.to_string(),
+            "style" => format!("{:?}", style)
+        );
+    }
+    
+    /// Slippage buffer for `style`; price improvement never takes it below `min_bps`
+    pub fn buffer_bps(&self, symbol: &str, style: OrderStyle) -> f64 {
+        let estimate = self.estimates
+            .get(&(symbol.to_string(), style))
+            .copied()
+            .unwrap_or(self.config.prior_bps);
+        estimate.clamp(self.config.min_bps, self.config.max_bps)
+    }
+    
+    /// Buffer for resting orders: the worse of the two passive styles
+    pub fn passive_buffer_bps(&self, symbol: &str) -> f64 {
+        self.buffer_bps(symbol, OrderStyle::MakerPassive).max(self.buffer_bps(symbol, OrderStyle::Sniper))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_buffer_converges_to_realized_slippage() {
+        let mut tracker = SlippageTracker::new(SlippageConfig::default());
+        let buffer = |tracker: &SlippageTracker| tracker.buffer_bps("BTC", OrderStyle::TakerNow);
+        assert_eq!(buffer(&tracker), 1.0);
+        
+        // Book 99.99 / 100.01: takers filled 4 bps through the touch they crossed
+        for i in 0..60 {
+            let (side, fill) = if i % 2 == 0 { (Side::Buy, 100.01 * 1.0004) } else { (Side::Sell, 99.99 * 0.9996) };
+            tracker.record_fill("BTC", OrderStyle::TakerNow, side, 99.99, 100.01, fill);
+        }
+        
+        assert!((buffer(&tracker) - 4.0).abs() < 0.01, "{}", buffer(&tracker));
+        assert_eq!(tracker.buffer_bps("ETH", OrderStyle::TakerNow), 1.0);
+        
+        // Sustained price improvement floors at min_bps
+        for _ in 0..100 {
+            tracker.record_fill("BTC", OrderStyle::TakerNow, Side::Buy, 99.99, 100.01, 99.99);
+        }
+        assert_eq!(buffer(&tracker), 0.0);
+        
+        assert_eq!(realized_slippage_bps(Side::Sell, 0.0, 1.0), None);
+    }
+    
+    #[test]
+    fn test_half_spread_and_maker_fills_stay_out_of_the_taker_buffer() {
+        let mut tracker = SlippageTracker::new(SlippageConfig::default());
+        
+        // Takers filling exactly at the touch: the half spread is not slippage
+        for i in 0..100 {
+            let (side, fill) = if i % 2 == 0 { (Side::Buy, 100.05) } else { (Side::Sell, 99.95) };
+            tracker.record_fill("BTC", OrderStyle::TakerNow, side, 99.95, 100.05, fill);
+        }
+        let taker = tracker.buffer_bps("BTC", OrderStyle::TakerNow);
+        assert!(taker < 0.01, "{}", taker);
+        
+        // Makers filled at their own touch; a 2 bps adverse tick lands in their buffer only
+        for _ in 0..100 {
+            tracker.record_fill("BTC", OrderStyle::MakerPassive, Side::Buy, 99.95, 100.05, 99.95 * 1.0002);
+        }
+        assert_eq!(tracker.buffer_bps("BTC", OrderStyle::TakerNow), taker);
+        assert!((tracker.buffer_bps("BTC", OrderStyle::MakerPassive) - 2.0).abs() < 0.01);
+        assert!((tracker.passive_buffer_bps("BTC") - 2.0).abs() < 0.01);
+        
+        assert_eq!(reference_price(OrderStyle::Sniper, Side::Sell, 99.95, 100.05), 100.0);
+    }
+}
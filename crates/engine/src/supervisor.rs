@@ -0,0 +1,224 @@
+// crates/engine/src/supervisor.rs - Restart or escalate when background tasks die
+use common::*;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, watch};
+use tokio::task::JoinHandle;
+
+/// A run at least this long resets the backoff
+const STABLE_AFTER: Duration = Duration::from_secs(60);
+
+/// What to do when a supervised task exits before shutdown
+#[derive(Debug, Clone, Copy)]
+pub enum RestartPolicy {
+    /// Respawn after `base * 2^n` (capped at `max`) for the n-th consecutive failure
+    Restart { base: Duration, max: Duration },
+    /// Run the escalation hook (e.g. kill switch) and stop supervising
+    Escalate,
+}
+
+impl RestartPolicy {
+    pub fn backoff(base: Duration, max: Duration) -> Self {
+        RestartPolicy::Restart { base, max }
+    }
+}
+
+type EscalateFn = Arc<dyn Fn(&str) + Send + Sync>;
+
+/// Watches critical tasks through their `JoinHandle`s
+#[derive(Clone)]
+pub struct Supervisor {
+    shutdown: watch::Receiver<bool>,
+    alert_tx: Option<broadcast::Sender<Alert>>,
+    on_escalate: Option<EscalateFn>,
+}
+
+/// Handle to a supervised task
+pub struct Supervised {
+    pub handle: JoinHandle<()>,
+    restarts: Arc<AtomicUsize>,
+}
+
+impl Supervised {
+    pub fn restarts(&self) -> usize {
+        self.restarts.load(Ordering::Relaxed)
+    }
+}
+
+impl Supervisor {
+    pub fn new(shutdown: watch::Receiver<bool>) -> Self {
+        Self {
+            shutdown,
+            alert_tx: None,
+            on_escalate: None,
+        }
+    }
+    
+    pub fn with_alerts(mut self, alert_tx: broadcast::Sender<Alert>) -> Self {
+        self.alert_tx = Some(alert_tx);
+        self
+    }
+    
+    pub fn on_escalate(mut self, hook: impl Fn(&str) + Send + Sync + 'static) -> Self {
+        self.on_escalate = Some(Arc::new(hook));
+        self
+    }
+    
+    /// Spawn `factory()` and keep it alive according to `policy` until shutdown
+    pub fn spawn<F, Fut>(&self, name: &str, policy: RestartPolicy, factory: F) -> Supervised
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        let restarts = Arc::new(AtomicUsize::new(0));
+        let name = name.to_string();
+        let this = self.clone();
+        let counter = restarts.clone();
+        
+        let handle = tokio::spawn(async move {
+            this.supervise(name, policy, factory, counter).await;
+        });
+        
+        Supervised { handle, restarts }
+    }
+    
+    async fn supervise<F, Fut>(mut self, name: String, policy: RestartPolicy, factory: F, restarts: Arc<AtomicUsize>)
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        let mut failures: u32 = 0;
+        
+        loop {
+            let started = Instant::now();
+            let mut task = tokio::spawn(factory());
+            
+            let outcome = tokio::select! {
+                res = &mut task => res,
+                _ = wait_for_shutdown(&mut self.shutdown) => {
+                    // The task owns its own shutdown handling; give it a chance to finish
+                    let _ = task.await;
+                    return;
+                }
+            };
+            
+            if *self.shutdown.borrow() {
+                return;
+            }
+            
+            let reason = match outcome {
+                Ok(Ok(())) => "returned early".to_string(),
+                Ok(Err(e)) => format!("failed: {}", e),
+                Err(e) if e.is_panic() => "panicked".to_string(),
+                Err(e) => format!("was cancelled: {}", e),
+            };
+            
+            match policy {
+                RestartPolicy::Restart { base, max } => {
+                    if started.elapsed() >= STABLE_AFTER {
+                        failures = 0;
+                    }
+                    let delay = base.saturating_mul(2u32.saturating_pow(failures)).min(max);
+                    failures = failures.saturating_add(1);
+                    restarts.fetch_add(1, Ordering::Relaxed);
+                    
+                    self.alert(AlertLevel::Warning, &name, format!("Task {} {}; restarting in {:?}", name, reason, delay));
+                    metrics::increment_counter!("task_restarts", "task" => name.clone());
+                    
+                    tokio::select! {
+                        _ = tokio::time::sleep(delay) => {}
+                        _ = wait_for_shutdown(&mut self.shutdown) => return,
+                    }
+                }
+                RestartPolicy::Escalate => {
+                    self.alert(AlertLevel::Critical, &name, format!("Task {} {}; escalating", name, reason));
+                    if let Some(hook) = &self.on_escalate {
+                        hook(&name);
+                    }
+                    return;
+                }
+            }
+        }
+    }
+    
+    fn alert(&self, level: AlertLevel, source: &str, message: String) {
+        match level {
+            AlertLevel::Critical => tracing::error!("[supervisor] {}", message),
+            _ => tracing::warn!("[supervisor] {}", message),
+        }
+        
+        if let Some(tx) = &self.alert_tx {
+            let _ = tx.send(Alert {
+                timestamp_ns: chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0),
+                level,
+                source: source.to_string(),
+                message,
+                metadata: serde_json::json!({}),
+            });
+        }
+    }
+}
+
+async fn wait_for_shutdown(shutdown: &mut watch::Receiver<bool>) {
+    while !*shutdown.borrow() {
+        if shutdown.changed().await.is_err() {
+            // Sender gone: nothing will ever signal shutdown
+            std::future::pending::<()>().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicBool;
+    
+    #[tokio::test]
+    async fn test_early_return_is_restarted() {
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let supervisor = Supervisor::new(shutdown_rx);
+        let runs = Arc::new(AtomicUsize::new(0));
+        
+        let counter = runs.clone();
+        let task = supervisor.spawn(
+            "flaky",
+            RestartPolicy::backoff(Duration::from_millis(1), Duration::from_millis(5)),
+            move || {
+                let counter = counter.clone();
+                async move {
+                    // First run exits immediately; the restart stays up
+                    if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                        return Ok(());
+                    }
+                    std::future::pending::<Result<()>>().await
+                }
+            },
+        );
+        
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+        assert_eq!(task.restarts(), 1);
+        
+        let _ = shutdown_tx.send(true);
+    }
+    
+    #[tokio::test]
+    async fn test_escalate_runs_hook_without_restart() {
+        let (_shutdown_tx, shutdown_rx) = watch::channel(false);
+        let escalated = Arc::new(AtomicBool::new(false));
+        
+        let flag = escalated.clone();
+        let supervisor = Supervisor::new(shutdown_rx)
+            .on_escalate(move |_| flag.store(true, Ordering::SeqCst));
+        
+        let mut task = supervisor.spawn("critical", RestartPolicy::Escalate, || async {
+            Err(Error::Internal("boom".to_string()))
+        });
+        
+        (&mut task.handle).await.unwrap();
+        assert!(escalated.load(Ordering::SeqCst));
+        assert_eq!(task.restarts(), 0);
+    }
+}
@@ -1,13 +1,21 @@
 // crates/engine/src/ws_server.rs
 use axum::{
-    extract::{ws::WebSocket, State, WebSocketUpgrade},
-    response::IntoResponse,
-    routing::get,
+    extract::{ws::{Message, WebSocket}, State, WebSocketUpgrade},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
     Router,
 };
 use common::*;
-use futures::{SinkExt, StreamExt};
+use crate::control::{ControlCommand, ControlHandle, CONTROL_TOKEN_HEADER};
+use crate::replay::DecisionRecord;
+use crate::rl_stats::ValueTracker;
+use async_trait::async_trait;
+use futures::{Sink, SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::{broadcast, watch};
 use tower_http::cors::CorsLayer;
 
@@ -17,6 +25,101 @@ pub struct MetricsState {
     pub performance_rx: watch::Receiver<PerformanceMetrics>,
     pub risk_rx: watch::Receiver<RiskSnapshot>,
     pub alert_tx: broadcast::Sender<Alert>,
+    /// Routing decisions as they're acted on, for `/decisions`
+    pub decision_tx: broadcast::Sender<DecisionRecord>,
+    pub diagnostics: Option<crate::diagnostics::DiagnosticsHandle>,
+    /// Operator commands; `/control` is refused when unset
+    pub control: Option<ControlHandle>,
+    /// Ranked universe, republished after each rebuild/refresh
+    pub universe_rx: watch::Receiver<Vec<UniverseAsset>>,
+    /// Venue symbol autocomplete; `/symbols` is refused when unset
+    pub symbols: Option<Arc<dyn SymbolSearch>>,
+    /// Live balances of saved accounts; `/balances` is refused when unset
+    /// or when no control channel is configured to authenticate it
+    pub balances: Option<Arc<dyn BalanceSource>>,
+    /// RL critic values vs realized rewards; `/models/rl/stats` is refused when unset
+    pub rl_values: Option<Arc<parking_lot::RwLock<ValueTracker>>>,
+    /// Per-client send limits for the watch-backed streams
+    pub send_buffer: SendBuffer,
+}
+
+/// Per-client send limits for `/metrics` and `/risk`.
+/// Each client has at most one message in flight; updates published while it
+/// is being written coalesce into the latest value, so a slow client sees
+/// fewer, newer states instead of a growing queue.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct SendBuffer {
+    /// A client that hasn't taken one message in this long is disconnected
+    pub send_timeout_ms: u64,
+}
+
+impl Default for SendBuffer {
+    fn default() -> Self {
+        Self { send_timeout_ms: 5_000 }
+    }
+}
+
+/// Stream every change of `rx` to `sink` as JSON, wrapped by `frame` and
+/// coalescing to the latest value while the client lags. Returns when the
+/// channel or the client is gone, or the client stalls past `send_timeout_ms`.
+async fn forward_latest<T, M, S>(
+    mut rx: watch::Receiver<T>,
+    mut sink: S,
+    buffer: SendBuffer,
+    stream: &'static str,
+    frame: impl Fn(&T) -> M,
+)
+where
+    M: Serialize,
+    S: Sink<Message> + Unpin,
+{
+    let send_timeout = Duration::from_millis(buffer.send_timeout_ms);
+    let mut sent: u64 = 0;
+    
+    while rx.changed().await.is_ok() {
+        // Mark seen before the send: anything published while it's in flight
+        // shows up as one change carrying only the newest value
+        let json = match serde_json::to_string(&frame(&rx.borrow_and_update())) {
+            Ok(j) => j,
+            Err(e) => {
+                tracing::warn!("Failed to serialize {}: {}", stream, e);
+                continue;
+            }
+        };
+        
+        match tokio::time::timeout(send_timeout, sink.send(Message::Text(json.into()))).await {
+            Ok(Ok(())) => sent += 1,
+            Ok(Err(_)) => break,
+            Err(_) => {
+                tracing::warn!("Dropping stalled {} client after {} messages", stream, sent);
+                metrics::increment_counter!("ws_slow_client_disconnects", "stream" => stream);
+                break;
+            }
+        }
+    }
+}
+
+/// Suggestions returned per `/symbols` query
+const MAX_SYMBOL_SUGGESTIONS: usize = 20;
+
+/// What `/symbols` searches; `TradingEngine` in production
+#[async_trait]
+pub trait SymbolSearch: Send + Sync {
+    async fn search_symbols(&self, venue: Venue, prefix: &str) -> Result<Vec<String>>;
+}
+
+#[async_trait]
+impl SymbolSearch for crate::TradingEngine {
+    async fn search_symbols(&self, venue: Venue, prefix: &str) -> Result<Vec<String>> {
+        crate::TradingEngine::search_symbols(self, venue, prefix).await
+    }
+}
+
+/// What `/balances` reads; the account's stored credentials in production
+#[async_trait]
+pub trait BalanceSource: Send + Sync {
+    async fn balances(&self, venue: Venue, label: &str, live: bool) -> Result<HashMap<String, Balance>>;
 }
 
 /// Create metrics server
@@ -25,7 +128,14 @@ pub fn create_metrics_server(state: MetricsState) -> Router {
         .route("/metrics", get(metrics_handler))
         .route("/risk", get(risk_handler))
         .route("/alerts", get(alerts_handler))
+        .route("/decisions", get(decisions_handler))
         .route("/health", get(health_handler))
+        .route("/diagnostics", post(diagnostics_handler))
+        .route("/control", get(control_handler))
+        .route("/universe", get(universe_handler))
+        .route("/symbols", get(symbols_handler))
+        .route("/balances", get(balances_handler))
+        .route("/models/rl/stats", get(rl_stats_handler))
         .with_state(state)
         .layer(CorsLayer::permissive())
 }
@@ -38,32 +148,8 @@ async fn metrics_handler(
     ws.on_upgrade(move |socket| handle_metrics_socket(socket, state))
 }
 
-async fn handle_metrics_socket(mut socket: WebSocket, state: MetricsState) {
-    let mut perf_rx = state.performance_rx.clone();
-    
-    loop {
-        tokio::select! {
-            changed = perf_rx.changed() => {
-                if changed.is_err() {
-                    break;
-                }
-                
-                let metrics = perf_rx.borrow().clone();
-                let json = match serde_json::to_string(&metrics) {
-                    Ok(j) => j,
-                    Err(e) => {
-                        tracing::warn!("Failed to serialize metrics: {}", e);
-                        continue;
-                    }
-                };
-                
-                if socket.send(axum::extract::ws::Message::Text(json)).await.is_err() {
-                    break;
-                }
-            }
-        }
-    }
-    
+async fn handle_metrics_socket(socket: WebSocket, state: MetricsState) {
+    forward_latest(state.performance_rx.clone(), socket, state.send_buffer, "metrics", |m| WsMsg::Metrics(m.clone())).await;
     tracing::debug!("Metrics WebSocket closed");
 }
 
@@ -75,33 +161,149 @@ async fn risk_handler(
     ws.on_upgrade(move |socket| handle_risk_socket(socket, state))
 }
 
-async fn handle_risk_socket(mut socket: WebSocket, state: MetricsState) {
-    let mut risk_rx = state.risk_rx.clone();
+async fn handle_risk_socket(socket: WebSocket, state: MetricsState) {
+    forward_latest(state.risk_rx.clone(), socket, state.send_buffer, "risk", |r| WsMsg::Risk(r.clone())).await;
+    tracing::debug!("Risk WebSocket closed");
+}
+
+/// WebSocket handler for universe snapshots
+async fn universe_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<MetricsState>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_universe_socket(socket, state))
+}
+
+async fn handle_universe_socket(mut socket: WebSocket, state: MetricsState) {
+    let mut universe_rx = state.universe_rx.clone();
     
-    loop {
-        tokio::select! {
-            changed = risk_rx.changed() => {
-                if changed.is_err() {
-                    break;
-                }
-                
-                let risk = risk_rx.borrow().clone();
-                let json = match serde_json::to_string(&risk) {
-                    Ok(j) => j,
-                    Err(e) => {
-                        tracing::warn!("Failed to serialize risk: {}", e);
-                        continue;
-                    }
-                };
-                
-                if socket.send(axum::extract::ws::Message::Text(json)).await.is_err() {
-                    break;
-                }
+    // The universe changes every few minutes at most, so send the current one
+    // on connect rather than waiting for the next rebuild
+    universe_rx.mark_changed();
+    
+    while universe_rx.changed().await.is_ok() {
+        let json = match serde_json::to_string(&WsMsg::Universe(universe_rx.borrow_and_update().clone())) {
+            Ok(j) => j,
+            Err(e) => {
+                tracing::warn!("Failed to serialize universe: {}", e);
+                continue;
             }
+        };
+        
+        if socket.send(Message::Text(json.into())).await.is_err() {
+            break;
         }
     }
     
-    tracing::debug!("Risk WebSocket closed");
+    tracing::debug!("Universe WebSocket closed");
+}
+
+/// WebSocket for symbol autocomplete: one `SymbolReply` per `SymbolQuery`
+async fn symbols_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<MetricsState>,
+) -> Response {
+    let Some(search) = state.symbols else {
+        return (StatusCode::SERVICE_UNAVAILABLE, "symbol search not configured").into_response();
+    };
+    
+    ws.on_upgrade(move |socket| handle_symbols_socket(socket, search))
+}
+
+async fn handle_symbols_socket(mut socket: WebSocket, search: Arc<dyn SymbolSearch>) {
+    while let Some(Ok(msg)) = socket.recv().await {
+        let text = match msg {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+        
+        let query = match serde_json::from_str::<SymbolQuery>(text.as_str()) {
+            Ok(query) => query,
+            Err(e) => {
+                tracing::warn!("Invalid symbol query: {}", e);
+                continue;
+            }
+        };
+        
+        let reply = match search.search_symbols(query.venue, &query.prefix).await {
+            Ok(mut symbols) => {
+                symbols.truncate(MAX_SYMBOL_SUGGESTIONS);
+                SymbolReply { venue: query.venue, prefix: query.prefix, symbols, error: None }
+            }
+            Err(e) => SymbolReply {
+                venue: query.venue,
+                prefix: query.prefix,
+                symbols: Vec::new(),
+                error: Some(e.to_string()),
+            },
+        };
+        
+        let json = serde_json::to_string(&reply).unwrap_or_default();
+        if socket.send(Message::Text(json.into())).await.is_err() {
+            break;
+        }
+    }
+    
+    tracing::debug!("Symbols WebSocket closed");
+}
+
+/// WebSocket for account balances: one `BalanceReply` per `BalanceQuery`,
+/// behind the control token
+async fn balances_handler(
+    ws: WebSocketUpgrade,
+    headers: HeaderMap,
+    State(state): State<MetricsState>,
+) -> Response {
+    if let Err(refused) = authorize_control(&state, &headers, "/balances") {
+        return refused;
+    }
+    
+    let Some(source) = state.balances else {
+        return (StatusCode::SERVICE_UNAVAILABLE, "balance queries not configured").into_response();
+    };
+    
+    ws.on_upgrade(move |socket| handle_balances_socket(socket, source))
+}
+
+async fn handle_balances_socket(mut socket: WebSocket, source: Arc<dyn BalanceSource>) {
+    while let Some(Ok(msg)) = socket.recv().await {
+        let text = match msg {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+        
+        let query = match serde_json::from_str::<BalanceQuery>(text.as_str()) {
+            Ok(query) => query,
+            Err(e) => {
+                tracing::warn!("Invalid balance query: {}", e);
+                continue;
+            }
+        };
+        
+        let reply = match source.balances(query.venue, &query.label, query.live).await {
+            Ok(balances) => BalanceReply {
+                venue: query.venue,
+                label: query.label,
+                balances: balances.into_iter().map(|(asset, b)| (asset, b.total)).collect(),
+                error: None,
+            },
+            Err(e) => BalanceReply {
+                venue: query.venue,
+                label: query.label,
+                balances: HashMap::new(),
+                error: Some(e.to_string()),
+            },
+        };
+        
+        let json = serde_json::to_string(&reply).unwrap_or_default();
+        if socket.send(Message::Text(json.into())).await.is_err() {
+            break;
+        }
+    }
+    
+    tracing::debug!("Balances WebSocket closed");
 }
 
 /// WebSocket handler for alerts
@@ -120,7 +322,7 @@ async fn handle_alerts_socket(mut socket: WebSocket, state: MetricsState) {
             alert = alert_rx.recv() => {
                 match alert {
                     Ok(a) => {
-                        let json = match serde_json::to_string(&a) {
+                        let json = match serde_json::to_string(&WsMsg::Alert(a)) {
                             Ok(j) => j,
                             Err(e) => {
                                 tracing::warn!("Failed to serialize alert: {}", e);
@@ -141,6 +343,45 @@ async fn handle_alerts_socket(mut socket: WebSocket, state: MetricsState) {
     tracing::debug!("Alerts WebSocket closed");
 }
 
+/// WebSocket handler for routing decisions
+async fn decisions_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<MetricsState>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_decisions_socket(socket, state))
+}
+
+async fn handle_decisions_socket(mut socket: WebSocket, state: MetricsState) {
+    let mut decision_rx = state.decision_tx.subscribe();
+    
+    loop {
+        let record = match decision_rx.recv().await {
+            Ok(record) => record,
+            // A slow client skips what it missed rather than disconnecting
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                tracing::warn!("Decisions client lagged, skipped {} records", skipped);
+                metrics::counter!("ws_decisions_lagged", skipped);
+                continue;
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        };
+        
+        let json = match serde_json::to_string(&record) {
+            Ok(j) => j,
+            Err(e) => {
+                tracing::warn!("Failed to serialize decision: {}", e);
+                continue;
+            }
+        };
+        
+        if socket.send(axum::extract::ws::Message::Text(json)).await.is_err() {
+            break;
+        }
+    }
+    
+    tracing::debug!("Decisions WebSocket closed");
+}
+
 /// Health check endpoint
 async fn health_handler() -> impl IntoResponse {
     axum::Json(serde_json::json!({
@@ -149,6 +390,104 @@ async fn health_handler() -> impl IntoResponse {
     }))
 }
 
+/// The control channel, once `headers` carry its `x-control-token`. Routes
+/// behind it are refused outright when no control channel is configured.
+fn authorize_control(
+    state: &MetricsState,
+    headers: &HeaderMap,
+    route: &str,
+) -> std::result::Result<ControlHandle, Response> {
+    let Some(control) = state.control.clone() else {
+        return Err((StatusCode::SERVICE_UNAVAILABLE, "control channel not configured").into_response());
+    };
+    
+    let presented = headers
+        .get(CONTROL_TOKEN_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+    if !control.authorize(presented) {
+        tracing::warn!("Rejected {} request: bad or missing {}", route, CONTROL_TOKEN_HEADER);
+        return Err(StatusCode::UNAUTHORIZED.into_response());
+    }
+    
+    Ok(control)
+}
+
+/// WebSocket for operator commands, authenticated by `x-control-token`
+async fn control_handler(
+    ws: WebSocketUpgrade,
+    headers: HeaderMap,
+    State(state): State<MetricsState>,
+) -> Response {
+    match authorize_control(&state, &headers, "/control") {
+        Ok(control) => ws.on_upgrade(move |socket| handle_control_socket(socket, control)),
+        Err(refused) => refused,
+    }
+}
+
+async fn handle_control_socket(mut socket: WebSocket, control: ControlHandle) {
+    while let Some(Ok(msg)) = socket.recv().await {
+        let text = match msg {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+        
+        let reply = match serde_json::from_str::<ControlCommand>(text.as_str()) {
+            Ok(command) => match control.send(command).await {
+                Ok(reply) => serde_json::to_value(&reply).unwrap_or_default(),
+                Err(e) => serde_json::json!({ "ok": false, "error": e.to_string() }),
+            },
+            Err(e) => serde_json::json!({ "ok": false, "error": format!("invalid command: {}", e) }),
+        };
+        
+        if socket.send(Message::Text(reply.to_string().into())).await.is_err() {
+            break;
+        }
+    }
+    
+    tracing::debug!("Control WebSocket closed");
+}
+
+/// RL critic value estimates and their rolling correlation with realized rewards
+async fn rl_stats_handler(State(state): State<MetricsState>) -> Response {
+    let Some(values) = state.rl_values else {
+        return (StatusCode::SERVICE_UNAVAILABLE, "RL stats not configured").into_response();
+    };
+    
+    let stats = values.read().stats();
+    axum::Json(stats).into_response()
+}
+
+/// Dump a redacted diagnostics bundle to disk (`dump-diagnostics`), behind
+/// the control token
+async fn diagnostics_handler(headers: HeaderMap, State(state): State<MetricsState>) -> Response {
+    if let Err(refused) = authorize_control(&state, &headers, "/diagnostics") {
+        return refused;
+    }
+    
+    let Some(handle) = state.diagnostics else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            axum::Json(serde_json::json!({ "error": "diagnostics not configured" })),
+        ).into_response();
+    };
+    
+    match handle.dump() {
+        Ok(path) => (
+            StatusCode::OK,
+            axum::Json(serde_json::json!({ "path": path.display().to_string() })),
+        ).into_response(),
+        Err(e) => {
+            tracing::error!("Diagnostics dump failed: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                axum::Json(serde_json::json!({ "error": e.to_string() })),
+            ).into_response()
+        }
+    }
+}
+
 /// Alert publisher for critical events
 pub struct AlertPublisher {
     tx: broadcast::Sender<Alert>,
@@ -230,11 +569,20 @@ mod tests {
         let (perf_tx, perf_rx) = watch::channel(PerformanceMetrics::default());
         let (risk_tx, risk_rx) = watch::channel(RiskSnapshot::default());
         let (alert_tx, _) = broadcast::channel(100);
+        let (_universe_tx, universe_rx) = watch::channel(Vec::new());
         
         let state = MetricsState {
             performance_rx: perf_rx,
             risk_rx,
             alert_tx,
+            decision_tx: broadcast::channel(10).0,
+            diagnostics: None,
+            control: None,
+            universe_rx,
+            symbols: None,
+            balances: None,
+            rl_values: None,
+            send_buffer: SendBuffer::default(),
         };
         
         let app = create_metrics_server(state);
@@ -242,4 +590,336 @@ mod tests {
         // Server is ready to accept connections
         assert!(true);
     }
+    
+    struct FakeEngine {
+        mode: parking_lot::Mutex<TradingMode>,
+        kill_switch: parking_lot::Mutex<bool>,
+    }
+    
+    impl crate::control::ControlTarget for FakeEngine {
+        fn set_mode(&self, mode: TradingMode) {
+            *self.mode.lock() = mode;
+        }
+        
+        fn set_kill_switch(&self, active: bool) {
+            *self.kill_switch.lock() = active;
+        }
+        
+        fn control_state(&self) -> crate::control::ControlReply {
+            crate::control::ControlReply {
+                ok: true,
+                mode: *self.mode.lock(),
+                kill_switch_active: *self.kill_switch.lock(),
+            }
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_control_socket_sets_mode() {
+        use tokio_tungstenite::tungstenite::{client::IntoClientRequest, Message as WsMessage};
+        
+        let engine = Arc::new(FakeEngine {
+            mode: parking_lot::Mutex::new(TradingMode::Live),
+            kill_switch: parking_lot::Mutex::new(false),
+        });
+        let (control, control_rx) = crate::control::control_channel("tok").unwrap();
+        tokio::spawn(crate::control::run_control_loop(control_rx, engine.clone()));
+        
+        let (_perf_tx, performance_rx) = watch::channel(PerformanceMetrics::default());
+        let (_risk_tx, risk_rx) = watch::channel(RiskSnapshot::default());
+        let (alert_tx, _) = broadcast::channel(10);
+        let (_universe_tx, universe_rx) = watch::channel(Vec::new());
+        let app = create_metrics_server(MetricsState {
+            performance_rx,
+            risk_rx,
+            alert_tx,
+            decision_tx: broadcast::channel(10).0,
+            diagnostics: None,
+            control: Some(control),
+            universe_rx,
+            symbols: None,
+            balances: None,
+            rl_values: None,
+            send_buffer: SendBuffer::default(),
+        });
+        
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/control", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        
+        // No token: refused before the upgrade
+        assert!(tokio_tungstenite::connect_async(url.as_str()).await.is_err());
+        
+        let mut request = url.as_str().into_client_request().unwrap();
+        request.headers_mut().insert(CONTROL_TOKEN_HEADER, "tok".parse().unwrap());
+        let (mut ws, _) = tokio_tungstenite::connect_async(request).await.unwrap();
+        
+        let mut roundtrip = async |cmd: &str| -> serde_json::Value {
+            ws.send(WsMessage::Text(cmd.to_string().into())).await.unwrap();
+            let reply = ws.next().await.unwrap().unwrap();
+            serde_json::from_str(reply.to_text().unwrap()).unwrap()
+        };
+        
+        let reply = roundtrip(r#"{"cmd":"set_mode","mode":"Paused"}"#).await;
+        assert_eq!(reply["ok"], true);
+        assert_eq!(reply["mode"], "Paused");
+        assert_eq!(*engine.mode.lock(), TradingMode::Paused);
+        
+        let reply = roundtrip(r#"{"cmd":"kill_switch","active":true}"#).await;
+        assert_eq!(reply["kill_switch_active"], true);
+        assert!(*engine.kill_switch.lock());
+        
+        let reply = roundtrip(r#"{"cmd":"flatten_all"}"#).await;
+        assert_eq!(reply["ok"], false);
+        assert!(reply["error"].as_str().unwrap().contains("invalid command"));
+        assert_eq!(*engine.mode.lock(), TradingMode::Paused);
+    }
+    
+    #[tokio::test]
+    async fn test_diagnostics_requires_control_token() {
+        use tower::ServiceExt;
+        
+        let state = |control: Option<ControlHandle>| MetricsState {
+            performance_rx: watch::channel(PerformanceMetrics::default()).1,
+            risk_rx: watch::channel(RiskSnapshot::default()).1,
+            alert_tx: broadcast::channel(10).0,
+            decision_tx: broadcast::channel(10).0,
+            diagnostics: None,
+            control,
+            universe_rx: watch::channel(Vec::new()).1,
+            symbols: None,
+            balances: None,
+            rl_values: None,
+            send_buffer: SendBuffer::default(),
+        };
+        let post = |token: Option<&str>| {
+            let mut request = axum::http::Request::post("/diagnostics");
+            if let Some(token) = token {
+                request = request.header(CONTROL_TOKEN_HEADER, token);
+            }
+            request.body(axum::body::Body::empty()).unwrap()
+        };
+        let (control, _control_rx) = crate::control::control_channel("tok").unwrap();
+        
+        // Without a control channel nothing can authenticate
+        let app = create_metrics_server(state(None));
+        assert_eq!(app.oneshot(post(Some("tok"))).await.unwrap().status(), StatusCode::SERVICE_UNAVAILABLE);
+        
+        let app = create_metrics_server(state(Some(control)));
+        assert_eq!(app.clone().oneshot(post(None)).await.unwrap().status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(app.clone().oneshot(post(Some("nope"))).await.unwrap().status(), StatusCode::UNAUTHORIZED);
+        
+        // Authorized, then refused only because no dump target is configured
+        let response = app.oneshot(post(Some("tok"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+    
+    #[tokio::test]
+    async fn test_universe_socket_streams_updates() {
+        let (_perf_tx, performance_rx) = watch::channel(PerformanceMetrics::default());
+        let (_risk_tx, risk_rx) = watch::channel(RiskSnapshot::default());
+        let (alert_tx, _) = broadcast::channel(10);
+        let (universe_tx, universe_rx) = watch::channel(Vec::new());
+        let app = create_metrics_server(MetricsState {
+            performance_rx,
+            risk_rx,
+            alert_tx,
+            decision_tx: broadcast::channel(10).0,
+            diagnostics: None,
+            control: None,
+            universe_rx,
+            symbols: None,
+            balances: None,
+            rl_values: None,
+            send_buffer: SendBuffer::default(),
+        });
+        
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/universe", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        
+        let (mut ws, _) = tokio_tungstenite::connect_async(url.as_str()).await.unwrap();
+        let mut next_universe = async || -> Vec<UniverseAsset> {
+            let frame = ws.next().await.unwrap().unwrap();
+            match serde_json::from_str(frame.to_text().unwrap()).unwrap() {
+                WsMsg::Universe(universe) => universe,
+                other => panic!("expected a universe frame, got {:?}", other),
+            }
+        };
+        
+        // Current (empty) universe on connect
+        assert!(next_universe().await.is_empty());
+        
+        universe_tx.send_replace(vec![UniverseAsset {
+            symbol: "SOL".to_string(),
+            venue: Venue::Hyperliquid,
+            category: AssetCategory::CryptoFutures,
+            score: 0.82,
+            rank: 1,
+            metrics: AssetMetrics::default(),
+        }]);
+        
+        let universe = next_universe().await;
+        assert_eq!(universe.len(), 1);
+        assert_eq!(universe[0].symbol, "SOL");
+        assert_eq!(universe[0].rank, 1);
+        assert_eq!(universe[0].score, 0.82);
+    }
+    
+    struct FakeVenues;
+    
+    #[async_trait]
+    impl SymbolSearch for FakeVenues {
+        async fn search_symbols(&self, venue: Venue, prefix: &str) -> Result<Vec<String>> {
+            match venue {
+                Venue::Hyperliquid => Ok(["BTC", "BLUR", "ETH"]
+                    .into_iter()
+                    .filter(|s| s.starts_with(prefix))
+                    .map(String::from)
+                    .collect()),
+                other => Err(Error::NotFound(format!("{:?} is not connected", other))),
+            }
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_symbols_socket_answers_queries() {
+        use tokio_tungstenite::tungstenite::Message as WsMessage;
+        
+        let (_perf_tx, performance_rx) = watch::channel(PerformanceMetrics::default());
+        let (_risk_tx, risk_rx) = watch::channel(RiskSnapshot::default());
+        let (alert_tx, _) = broadcast::channel(10);
+        let (_universe_tx, universe_rx) = watch::channel(Vec::new());
+        let app = create_metrics_server(MetricsState {
+            performance_rx,
+            risk_rx,
+            alert_tx,
+            decision_tx: broadcast::channel(10).0,
+            diagnostics: None,
+            control: None,
+            universe_rx,
+            symbols: Some(Arc::new(FakeVenues)),
+            balances: None,
+            rl_values: None,
+            send_buffer: SendBuffer::default(),
+        });
+        
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/symbols", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        
+        let (mut ws, _) = tokio_tungstenite::connect_async(url.as_str()).await.unwrap();
+        let mut query = async |venue: Venue, prefix: &str| -> SymbolReply {
+            let query = SymbolQuery { venue, prefix: prefix.to_string() };
+            ws.send(WsMessage::Text(serde_json::to_string(&query).unwrap().into())).await.unwrap();
+            let reply = ws.next().await.unwrap().unwrap();
+            serde_json::from_str(reply.to_text().unwrap()).unwrap()
+        };
+        
+        let reply = query(Venue::Hyperliquid, "B").await;
+        assert_eq!(reply.symbols, vec!["BTC", "BLUR"]);
+        assert_eq!(reply.prefix, "B");
+        assert!(reply.error.is_none());
+        
+        let reply = query(Venue::IBKR, "AA").await;
+        assert!(reply.symbols.is_empty());
+        assert!(reply.error.unwrap().contains("not connected"));
+    }
+    
+    /// One funded Hyperliquid account; every other lookup fails
+    struct FakeAccounts;
+    
+    #[async_trait]
+    impl BalanceSource for FakeAccounts {
+        async fn balances(&self, venue: Venue, label: &str, _live: bool) -> Result<HashMap<String, Balance>> {
+            if venue != Venue::Hyperliquid || label != "main" {
+                return Err(Error::NotFound(format!("no stored credentials for {}", label)));
+            }
+            let usdc = Balance { asset: "USDC".to_string(), free: 900.0, locked: 100.0, total: 1_000.0 };
+            Ok(HashMap::from([("USDC".to_string(), usdc)]))
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_balances_socket_answers_queries() {
+        use tokio_tungstenite::tungstenite::{client::IntoClientRequest, Message as WsMessage};
+        
+        let (control, _control_rx) = crate::control::control_channel("tok").unwrap();
+        let (_perf_tx, performance_rx) = watch::channel(PerformanceMetrics::default());
+        let (_risk_tx, risk_rx) = watch::channel(RiskSnapshot::default());
+        let (alert_tx, _) = broadcast::channel(10);
+        let (_universe_tx, universe_rx) = watch::channel(Vec::new());
+        let app = create_metrics_server(MetricsState {
+            performance_rx,
+            risk_rx,
+            alert_tx,
+            decision_tx: broadcast::channel(10).0,
+            diagnostics: None,
+            control: Some(control),
+            universe_rx,
+            symbols: None,
+            balances: Some(Arc::new(FakeAccounts)),
+            rl_values: None,
+            send_buffer: SendBuffer::default(),
+        });
+        
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/balances", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        
+        // Balances are account data: no token, no upgrade
+        assert!(tokio_tungstenite::connect_async(url.as_str()).await.is_err());
+        
+        let mut request = url.as_str().into_client_request().unwrap();
+        request.headers_mut().insert(CONTROL_TOKEN_HEADER, "tok".parse().unwrap());
+        let (mut ws, _) = tokio_tungstenite::connect_async(request).await.unwrap();
+        let mut query = async |venue: Venue, label: &str| -> BalanceReply {
+            let query = BalanceQuery { venue, label: label.to_string(), live: true };
+            ws.send(WsMessage::Text(serde_json::to_string(&query).unwrap().into())).await.unwrap();
+            let reply = ws.next().await.unwrap().unwrap();
+            serde_json::from_str(reply.to_text().unwrap()).unwrap()
+        };
+        
+        let reply = query(Venue::Hyperliquid, "main").await;
+        assert_eq!(reply.label, "main");
+        assert_eq!(reply.balances.get("USDC"), Some(&1_000.0));
+        assert!(reply.error.is_none());
+        
+        let reply = query(Venue::BinanceFutures, "main").await;
+        assert!(reply.balances.is_empty());
+        assert!(reply.error.unwrap().contains("no stored credentials"));
+    }
+    
+    #[tokio::test]
+    async fn test_slow_client_gets_latest_state() {
+        let (tx, rx) = watch::channel(0u64);
+        // Zero-capacity client: the server can hold one message, nothing more
+        let (sink, mut client) = futures::channel::mpsc::channel::<Message>(0);
+        let buffer = SendBuffer { send_timeout_ms: 200 };
+        let server = tokio::spawn(forward_latest(rx, sink, buffer, "test", |v: &u64| *v));
+        
+        // Publish far faster than the client reads
+        for i in 1..=1_000u64 {
+            tx.send(i).unwrap();
+            if i % 100 == 0 {
+                tokio::task::yield_now().await;
+            }
+        }
+        
+        let mut received = Vec::new();
+        while let Ok(Some(Message::Text(text))) = tokio::time::timeout(Duration::from_millis(50), client.next()).await {
+            received.push(text.as_str().parse::<u64>().unwrap());
+        }
+        
+        assert_eq!(received.last(), Some(&1_000));
+        assert!(received.len() < 20, "intermediate states should coalesce, got {}", received.len());
+        assert!(received.windows(2).all(|w| w[0] < w[1]));
+        
+        // A client that stops reading is dropped once a send times out
+        tx.send(1_001).unwrap();
+        tx.send(1_002).unwrap();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        tx.send(1_003).unwrap();
+        tokio::time::timeout(Duration::from_secs(2), server).await.unwrap().unwrap();
+    }
 }
\ No newline at end of file
@@ -0,0 +1,302 @@
+// crates/features/src/cpu.rs - Stateful CPU feature builder
+use common::*;
+use crate::{indicators, layout, ComputedFeatures, Device};
+use ndarray::Array1;
+use std::collections::{HashMap, VecDeque};
+
+/// Rolling window for symbols that reach `compute_batch` without `add_symbol`
+pub const DEFAULT_WINDOW_SIZE: usize = 100;
+
+/// Trades folded into the per-snapshot flow features, as on the GPU path
+const FLOW_TRADES: usize = 100;
+
+/// Rolling per-symbol book state, one entry per distinct book update
+#[derive(Debug)]
+struct SymbolState {
+    window_size: usize,
+    mids: VecDeque<f64>,
+    /// Order flow imbalance between consecutive books over OFI_LEVELS
+    ofi: VecDeque<f64>,
+    obi: VecDeque<f64>,
+    /// Top OFI_LEVELS of the last update
+    last_book: Option<OrderBook>,
+    last_update: Option<(i64, u64)>,
+}
+
+impl SymbolState {
+    fn new(window_size: usize) -> Self {
+        let window_size = window_size.max(2);
+        Self {
+            window_size,
+            mids: VecDeque::with_capacity(window_size),
+            ofi: VecDeque::with_capacity(window_size),
+            obi: VecDeque::with_capacity(window_size),
+            last_book: None,
+            last_update: None,
+        }
+    }
+    
+    /// Fold a book into the window; the same update seen twice counts once
+    fn observe(&mut self, book: &OrderBook) {
+        let (Some(bid), Some(ask)) = (book.best_bid(), book.best_ask()) else {
+            return;
+        };
+        let update = (book.timestamp_ns, book.sequence);
+        if self.last_update == Some(update) {
+            return;
+        }
+        self.last_update = Some(update);
+        
+        let ofi = self.last_book
+            .as_ref()
+            .map_or(0.0, |prev| indicators::order_flow_imbalance(prev, book, layout::OFI_LEVELS));
+        self.last_book = Some(OrderBook {
+            symbol: book.symbol.clone(),
+            timestamp_ns: book.timestamp_ns,
+            bids: book.bids.iter().take(layout::OFI_LEVELS).cloned().collect(),
+            asks: book.asks.iter().take(layout::OFI_LEVELS).cloned().collect(),
+            sequence: book.sequence,
+        });
+        
+        push_bounded(&mut self.mids, (bid.price.0 + ask.price.0) / 2.0, self.window_size);
+        push_bounded(&mut self.ofi, ofi, self.window_size);
+        push_bounded(&mut self.obi, book.imbalance(layout::OBI_LEVELS), self.window_size);
+    }
+    
+    /// sqrt of summed squared mid log returns over the window
+    fn realized_vol(&self) -> f64 {
+        self.mids
+            .iter()
+            .zip(self.mids.iter().skip(1))
+            .filter(|(a, b)| **a > 0.0 && **b > 0.0)
+            .map(|(a, b)| (b / a).ln().powi(2))
+            .sum::<f64>()
+            .sqrt()
+    }
+    
+    /// Mean absolute mid move between updates: the true range of one-update bars
+    fn atr(&self) -> f64 {
+        let moves = self.mids.len().saturating_sub(1);
+        if moves == 0 {
+            return 0.0;
+        }
+        
+        let total: f64 = self.mids
+            .iter()
+            .zip(self.mids.iter().skip(1))
+            .map(|(a, b)| (b - a).abs())
+            .sum();
+        total / moves as f64
+    }
+    
+    fn mean_obi(&self) -> f64 {
+        if self.obi.is_empty() {
+            0.0
+        } else {
+            self.obi.iter().sum::<f64>() / self.obi.len() as f64
+        }
+    }
+    
+    fn write_windows(&self, out: &mut [f32]) {
+        out[layout::WINDOW_VOL] = self.realized_vol() as f32;
+        out[layout::WINDOW_ATR] = self.atr() as f32;
+        out[layout::WINDOW_OFI] = self.ofi.iter().sum::<f64>() as f32;
+        out[layout::WINDOW_OBI] = self.mean_obi() as f32;
+    }
+}
+
+fn push_bounded(ring: &mut VecDeque<f64>, value: f64, capacity: usize) {
+    if ring.len() == capacity {
+        ring.pop_front();
+    }
+    ring.push_back(value);
+}
+
+/// CPU feature computation with per-symbol rolling windows. Fills the same
+/// per-snapshot slots as the GPU kernel, plus the raw depth ladder and the
+/// rolling `WINDOW_*` slots, which GPU batches get from `fill_windows`.
+/// Rolling state stays here whichever device computes a batch.
+#[derive(Debug, Default)]
+pub struct CpuFeatureBuilder {
+    symbols: HashMap<String, SymbolState>,
+}
+
+impl CpuFeatureBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Track `symbol` with a rolling window of `window_size` book updates
+    pub fn add_symbol(&mut self, symbol: String, window_size: usize) {
+        self.symbols.insert(symbol, SymbolState::new(window_size));
+    }
+    
+    /// Fold a book update into its symbol's rolling state
+    pub fn update_book(&mut self, orderbook: &OrderBook) {
+        self.state(&orderbook.symbol).observe(orderbook);
+    }
+    
+    fn state(&mut self, symbol: &str) -> &mut SymbolState {
+        self.symbols
+            .entry(symbol.to_string())
+            .or_insert_with(|| SymbolState::new(DEFAULT_WINDOW_SIZE))
+    }
+    
+    /// Update each snapshot's rolling state and write its `WINDOW_*` slots
+    /// into features computed elsewhere (the GPU kernel has no rolling state)
+    pub fn fill_windows(&mut self, snapshots: &[MarketSnapshot], features: &mut [ComputedFeatures]) {
+        for (snap, computed) in snapshots.iter().zip(features.iter_mut()) {
+            let state = self.state(&snap.symbol);
+            state.observe(&snap.orderbook);
+            if let Some(out) = computed.features.as_slice_mut() {
+                state.write_windows(out);
+            }
+        }
+    }
+    
+    /// Update each snapshot's rolling state, then compute its features
+    pub fn compute_batch(&mut self, snapshots: &[MarketSnapshot]) -> Result<Vec<ComputedFeatures>> {
+        snapshots.iter().map(|snap| self.compute(snap)).collect()
+    }
+    
+    fn compute(&mut self, snap: &MarketSnapshot) -> Result<ComputedFeatures> {
+        let book = &snap.orderbook;
+        let mid = book.mid_price().ok_or_else(|| {
+            Error::Internal(format!("{}: cannot compute features on a one-sided book", snap.symbol))
+        })?;
+        
+        let state = self.state(&snap.symbol);
+        state.observe(book);
+        
+        let mut out = vec![0.0f32; layout::NUM_FEATURES];
+        out[layout::MID_PRICE] = mid as f32;
+        out[layout::SPREAD_BPS] = book.spread_bps().unwrap_or(0.0) as f32;
+        out[layout::FUNDING_BPS] = snap.funding_rate_bps.unwrap_or(0.0) as f32;
+        out[layout::OBI] = book.imbalance(layout::OBI_LEVELS) as f32;
+        out[layout::MICROPRICE] = indicators::depth_weighted_microprice(book, indicators::DEFAULT_MICROPRICE_LEVELS)
+            .unwrap_or(mid) as f32;
+        
+        // Trade flow over the most recent trades
+        let (mut buy, mut sell, mut notional) = (0.0, 0.0, 0.0);
+        for trade in snap.recent_trades.iter().rev().take(FLOW_TRADES) {
+            match trade.side {
+                Side::Buy => buy += trade.quantity,
+                Side::Sell => sell += trade.quantity,
+            }
+            notional += trade.price * trade.quantity;
+        }
+        let volume = buy + sell;
+        out[layout::OFI] = (buy - sell) as f32;
+        let vwap_ratio = if volume > 0.0 { mid / (notional / volume) } else { 1.0 };
+        out[layout::VWAP_RATIO] = vwap_ratio as f32;
+        
+        state.write_windows(&mut out);
+        
+        let ask_start = layout::DEPTH_LADDER_START + 2 * layout::DEPTH_LADDER_LEVELS;
+        for (start, side) in [(layout::DEPTH_LADDER_START, &book.bids), (ask_start, &book.asks)] {
+            for (i, level) in side.iter().take(layout::DEPTH_LADDER_LEVELS).enumerate() {
+                out[start + 2 * i] = level.price.0 as f32;
+                out[start + 2 * i + 1] = level.quantity as f32;
+            }
+        }
+        
+        Ok(ComputedFeatures {
+            symbol: snap.symbol.clone(),
+            timestamp_ns: snap.timestamp_ns,
+            features: Array1::from_vec(out),
+            computed_on: Device::CPU,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ordered_float::OrderedFloat;
+    
+    fn snapshot(mid: f64, sequence: u64) -> MarketSnapshot {
+        let level = |price: f64, quantity: f64| Level { price: OrderedFloat(price), quantity };
+        MarketSnapshot {
+            timestamp_ns: sequence as i64,
+            symbol: "BTC".to_string(),
+            orderbook: OrderBook {
+                symbol: "BTC".to_string(),
+                timestamp_ns: sequence as i64,
+                bids: vec![level(mid - 0.5, 2.0)],
+                asks: vec![level(mid + 0.5, 1.0)],
+                sequence,
+            },
+            recent_trades: vec![],
+            funding_rate_bps: None,
+            open_interest: None,
+            volume_24h: 0.0,
+            quality: DataQuality::Live,
+        }
+    }
+    
+    #[test]
+    fn test_rolling_vol_over_price_series() {
+        let mut builder = CpuFeatureBuilder::new();
+        builder.add_symbol("BTC".to_string(), 4);
+        
+        // Alternating +1% / -1% moves
+        let prices = [100.0, 101.0, 100.0, 101.0, 100.0, 101.0];
+        let snapshots: Vec<_> = prices.iter().enumerate().map(|(i, p)| snapshot(*p, i as u64 + 1)).collect();
+        let features = builder.compute_batch(&snapshots).unwrap();
+        
+        let up = (101.0f64 / 100.0).ln().powi(2);
+        let down = (100.0f64 / 101.0).ln().powi(2);
+        let vol = |i: usize| features[i].features[layout::WINDOW_VOL] as f64;
+        
+        assert_eq!(vol(0), 0.0);
+        assert!((vol(1) - up.sqrt()).abs() < 1e-6);
+        assert!((vol(2) - (up + down).sqrt()).abs() < 1e-6);
+        // The window holds 4 mids, so 3 returns from here on
+        assert!((vol(3) - (2.0 * up + down).sqrt()).abs() < 1e-6);
+        assert!((vol(5) - (2.0 * up + down).sqrt()).abs() < 1e-6);
+        assert!((features[5].features[layout::WINDOW_ATR] - 1.0).abs() < 1e-6);
+        
+        // Re-computing the same update doesn't advance the window
+        let again = builder.compute_batch(&snapshots[5..]).unwrap();
+        assert_eq!(again[0].features[layout::WINDOW_VOL], features[5].features[layout::WINDOW_VOL]);
+        assert!(matches!(again[0].computed_on, Device::CPU));
+    }
+    
+    #[test]
+    fn test_book_features_and_flow() {
+        let mut builder = CpuFeatureBuilder::new();
+        let mut snap = snapshot(100.5, 1);
+        snap.recent_trades = vec![
+            Trade { symbol: "BTC".to_string(), timestamp_ns: 1, price: 100.0, quantity: 3.0, side: Side::Buy, trade_id: "1".to_string() },
+            Trade { symbol: "BTC".to_string(), timestamp_ns: 1, price: 101.0, quantity: 1.0, side: Side::Sell, trade_id: "2".to_string() },
+        ];
+        
+        let first = builder.compute_batch(std::slice::from_ref(&snap)).unwrap().remove(0);
+        let f = &first.features;
+        assert_eq!(f.len(), layout::NUM_FEATURES);
+        assert!((f[layout::OBI] - 1.0 / 3.0).abs() < 1e-6);
+        assert_eq!(f[layout::OFI], 2.0);
+        assert!((f[layout::VWAP_RATIO] as f64 - 100.5 / 100.25).abs() < 1e-6);
+        // Bid-heavy touch: microprice above mid
+        assert!(f[layout::MICROPRICE] > f[layout::MID_PRICE]);
+        assert_eq!(f[layout::DEPTH_LADDER_START], 100.0);
+        assert_eq!(f[layout::DEPTH_LADDER_START + 2 * layout::DEPTH_LADDER_LEVELS + 1], 1.0);
+        
+        // Bid size grows at the same price: positive order flow
+        let mut next = snapshot(100.5, 2);
+        next.orderbook.bids[0].quantity = 5.0;
+        let second = builder.compute_batch(std::slice::from_ref(&next)).unwrap().remove(0);
+        assert_eq!(second.features[layout::WINDOW_OFI], 3.0);
+        
+        // A second ask level appearing behind the touch adds selling pressure
+        let mut third = snapshot(100.5, 3);
+        third.orderbook.bids[0].quantity = 5.0;
+        third.orderbook.asks.push(Level { price: OrderedFloat(101.5), quantity: 2.0 });
+        let third = builder.compute_batch(&[third]).unwrap().remove(0);
+        assert_eq!(third.features[layout::WINDOW_OFI], 1.0);
+        
+        // One-sided book
+        snap.orderbook.asks.clear();
+        assert!(builder.compute_batch(&[snap]).is_err());
+    }
+}
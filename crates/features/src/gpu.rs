@@ -1,5 +1,6 @@
 // crates/features/src/gpu.rs
 use common::*;
+use crate::{indicators, layout};
 use ndarray::Array1;
 use std::sync::Arc;
 use parking_lot::Mutex;
@@ -12,9 +13,22 @@ pub enum DeviceType {
     TensorRT,
 }
 
+/// Batch compute on a device. `FeatureComputer` only reaches the GPU through
+/// this, so a stand-in device can replace it.
+pub trait GpuBackend: Send + Sync {
+    fn compute_batch(&self, snapshots: &[MarketSnapshot]) -> Result<Vec<crate::ComputedFeatures>>;
+}
+
+impl GpuBackend for GpuFeatureComputer {
+    fn compute_batch(&self, snapshots: &[MarketSnapshot]) -> Result<Vec<crate::ComputedFeatures>> {
+        GpuFeatureComputer::compute_batch(self, snapshots)
+    }
+}
+
 pub struct GpuFeatureComputer {
     device: DeviceType,
     batch_size: usize,
+    microprice_levels: usize,
     #[cfg(feature = "cuda")]
     cuda: Option<CudaBackend>,
     #[cfg(feature = "wgpu")]
@@ -22,59 +36,77 @@ pub struct GpuFeatureComputer {
 }
 
 #[cfg(feature = "cuda")]
-struct CudaBackend {
-    device: Arc<cudarc::driver::CudaDevice>,
-    kernel: cudarc::driver::CudaFunction,
+pub(crate) struct CudaBackend {
+    pub(crate) device: Arc<cudarc::driver::CudaDevice>,
+    pub(crate) kernel: cudarc::driver::CudaFunction,
 }
 
-#[cfg(feature = "wgpu")]
-struct WgpuBackend {
-    device: wgpu::Device,
-    queue: wgpu::Queue,
-    pipeline: wgpu::ComputePipeline,
-}
-
-impl GpuFeatureComputer {
-    pub fn new(device: DeviceType, batch_size: usize) -> Result<Self> {
-        match device {
-            #[cfg(feature = "cuda")]
-            DeviceType::CUDA(id) => Self::new_cuda(id, batch_size),
-            
-            #[cfg(feature = "wgpu")]
-            DeviceType::ROCm(id) => Self::new_wgpu(batch_size),
-            
-            _ => Err(Error::Internal("GPU backend not compiled".to_string())),
-        }
-    }
-    
-    #[cfg(feature = "cuda")]
-    fn new_cuda(device_id: usize, batch_size: usize) -> Result<Self> {
-        use cudarc::driver::*;
-        
-        let device = CudaDevice::new(device_id)
+#[cfg(feature = "cuda")]
+impl CudaBackend {
+    /// Open `device_id` and load the `compute_features` kernel
+    pub(crate) fn load(device_id: usize) -> Result<Self> {
+        let device = cudarc::driver::CudaDevice::new(device_id)
             .map_err(|e| Error::Internal(format!("CUDA init: {:?}", e)))?;
         
-        // Compile CUDA kernel
-        let ptx = compile_cuda_kernel();
-        let kernel = device.load_ptx(ptx, "features", &["compute_features"])
+        device.load_ptx(compile_cuda_kernel()?, "features", &["compute_features"])
             .map_err(|e| Error::Internal(format!("Kernel load: {:?}", e)))?;
+        let kernel = device.get_func("features", "compute_features")
+            .ok_or_else(|| Error::Internal("Kernel load: compute_features missing".to_string()))?;
         
         tracing::info!("CUDA device {} ready: {}", device_id, device.name());
         
-        Ok(Self {
-            device: DeviceType::CUDA(device_id),
-            batch_size,
-            cuda: Some(CudaBackend {
-                device: Arc::new(device),
-                kernel,
-            }),
-            #[cfg(feature = "wgpu")]
-            wgpu: None,
-        })
+        Ok(Self { device, kernel })
     }
     
-    #[cfg(feature = "wgpu")]
-    fn new_wgpu(batch_size: usize) -> Result<Self> {
+    /// Run the kernel over `input` (`n` records of `INPUT_STRIDE`), returning
+    /// `n * NUM_FEATURES` outputs; any CUDA failure is an error, never zeros
+    pub(crate) fn launch(&self, input: &[f32], n: usize, microprice_levels: usize) -> Result<Vec<f32>> {
+        use cudarc::driver::{LaunchAsync, LaunchConfig};
+        
+        if n == 0 {
+            return Ok(Vec::new());
+        }
+        debug_assert_eq!(input.len(), n * INPUT_STRIDE);
+        
+        let d_input = self.device.htod_sync_copy(input)
+            .map_err(|e| Error::Internal(format!("Upload: {:?}", e)))?;
+        let mut d_output = self.device.alloc_zeros::<f32>(n * layout::NUM_FEATURES)
+            .map_err(|e| Error::Internal(format!("Alloc: {:?}", e)))?;
+        
+        let cfg = LaunchConfig::for_num_elems(n as u32);
+        unsafe {
+            self.kernel.clone().launch(
+                cfg,
+                (&d_input, &mut d_output, n as i32, microprice_levels as i32),
+            ).map_err(|e| Error::Internal(format!("Launch: {:?}", e)))?;
+        }
+        
+        self.device.dtoh_sync_copy(&d_output)
+            .map_err(|e| Error::Internal(format!("Download: {:?}", e)))
+    }
+}
+
+#[cfg(feature = "wgpu")]
+pub(crate) struct WgpuBackend {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::ComputePipeline,
+}
+
+/// Uniforms for `WGSL_SHADER` (padded to 16 bytes)
+#[cfg(feature = "wgpu")]
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct WgpuParams {
+    num_symbols: u32,
+    microprice_levels: u32,
+    _pad: [u32; 2],
+}
+
+#[cfg(feature = "wgpu")]
+impl WgpuBackend {
+    /// Pick a high-performance adapter and build the feature pipeline
+    pub(crate) fn load() -> Result<Self> {
         // WebGPU initialization for AMD/Intel/Apple
         let instance = wgpu::Instance::default();
         
@@ -104,15 +136,138 @@ impl GpuFeatureComputer {
             cache: None,
         });
         
+        tracing::info!("WebGPU device ready: {}", adapter.get_info().name);
+        
+        Ok(Self { device, queue, pipeline })
+    }
+    
+    /// Run the shader over `input` (`n` records of `INPUT_STRIDE`), returning
+    /// `n * NUM_FEATURES` outputs in the same layout as the CUDA kernel
+    pub(crate) fn run(&self, input: &[f32], n: usize, microprice_levels: usize) -> Result<Vec<f32>> {
+        use wgpu::util::DeviceExt;
+        
+        if n == 0 {
+            return Ok(Vec::new());
+        }
+        debug_assert_eq!(input.len(), n * INPUT_STRIDE);
+        
+        let output_bytes = (n * layout::NUM_FEATURES * std::mem::size_of::<f32>()) as u64;
+        let params = WgpuParams {
+            num_symbols: n as u32,
+            microprice_levels: microprice_levels.clamp(1, 10) as u32,
+            _pad: [0; 2],
+        };
+        
+        let input_buf = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("features-input"),
+            contents: bytemuck::cast_slice(input),
+            usage: wgpu::BufferUsages::STORAGE,
+        });
+        let output_buf = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("features-output"),
+            size: output_bytes,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let params_buf = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("features-params"),
+            contents: bytemuck::bytes_of(&params),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let staging_buf = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("features-staging"),
+            size: output_bytes,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("features"),
+            layout: &self.pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: input_buf.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 1, resource: output_buf.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 2, resource: params_buf.as_entire_binding() },
+            ],
+        });
+        
+        let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("features"),
+        });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("features"),
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(n.div_ceil(WGSL_WORKGROUP_SIZE) as u32, 1, 1);
+        }
+        encoder.copy_buffer_to_buffer(&output_buf, 0, &staging_buf, 0, output_bytes);
+        self.queue.submit(Some(encoder.finish()));
+        
+        // Read back
+        let slice = staging_buf.slice(..);
+        let (tx, rx) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |res| {
+            let _ = tx.send(res);
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+        
+        rx.recv()
+            .map_err(|_| Error::Internal("Readback: map callback dropped".to_string()))?
+            .map_err(|e| Error::Internal(format!("Readback: {}", e)))?;
+        
+        let output = bytemuck::cast_slice::<u8, f32>(&slice.get_mapped_range()).to_vec();
+        staging_buf.unmap();
+        
+        Ok(output)
+    }
+}
+
+impl GpuFeatureComputer {
+    pub fn new(device: DeviceType, batch_size: usize) -> Result<Self> {
+        match device {
+            #[cfg(feature = "cuda")]
+            DeviceType::CUDA(id) => Self::new_cuda(id, batch_size),
+            
+            #[cfg(feature = "wgpu")]
+            DeviceType::ROCm(_) => Self::new_wgpu(batch_size),
+            
+            _ => Err(Error::Internal("GPU backend not compiled".to_string())),
+        }
+    }
+    
+    #[cfg(feature = "cuda")]
+    fn new_cuda(device_id: usize, batch_size: usize) -> Result<Self> {
+        Ok(Self {
+            device: DeviceType::CUDA(device_id),
+            batch_size,
+            microprice_levels: indicators::DEFAULT_MICROPRICE_LEVELS,
+            cuda: Some(CudaBackend::load(device_id)?),
+            #[cfg(feature = "wgpu")]
+            wgpu: None,
+        })
+    }
+    
+    #[cfg(feature = "wgpu")]
+    fn new_wgpu(batch_size: usize) -> Result<Self> {
         Ok(Self {
             device: DeviceType::ROCm(0),
             batch_size,
+            microprice_levels: indicators::DEFAULT_MICROPRICE_LEVELS,
             #[cfg(feature = "cuda")]
             cuda: None,
-            wgpu: Some(WgpuBackend { device, queue, pipeline }),
+            wgpu: Some(WgpuBackend::load()?),
         })
     }
     
+    /// Number of book levels (1-10) aggregated into the microprice feature
+    pub fn with_microprice_levels(mut self, levels: usize) -> Self {
+        self.microprice_levels = levels.clamp(1, 10);
+        self
+    }
+    
     /// Compute features for batch
     pub fn compute_batch(&self, snapshots: &[MarketSnapshot]) -> Result<Vec<crate::ComputedFeatures>> {
         #[cfg(feature = "cuda")]
@@ -130,43 +285,17 @@ impl GpuFeatureComputer {
     
     #[cfg(feature = "cuda")]
     fn compute_cuda(&self, backend: &CudaBackend, snapshots: &[MarketSnapshot]) -> Result<Vec<crate::ComputedFeatures>> {
-        use cudarc::driver::*;
-        
         let n = snapshots.len();
-        let features_per_symbol = 100;
+        let features_per_symbol = layout::NUM_FEATURES;
         
         // Prepare input data
-        let mut input = Vec::with_capacity(n * 1024);
+        let mut input = Vec::with_capacity(n * INPUT_STRIDE);
         for snap in snapshots {
             serialize_snapshot(&mut input, snap);
         }
         
-        // Allocate GPU memory
-        let d_input = backend.device.htod_copy(&input)
-            .map_err(|e| Error::Internal(format!("Upload: {:?}", e)))?;
-        
-        let d_output = backend.device.alloc_zeros::<f32>(n * features_per_symbol)
-            .map_err(|e| Error::Internal(format!("Alloc: {:?}", e)))?;
-        
-        // Launch kernel
-        let cfg = LaunchConfig {
-            grid_dim: ((n + 255) / 256, 1, 1),
-            block_dim: (256, 1, 1),
-            shared_mem_bytes: 0,
-        };
-        
-        unsafe {
-            backend.kernel.clone().launch(
-                cfg,
-                (&d_input, &d_output, n as i32),
-            ).map_err(|e| Error::Internal(format!("Launch: {:?}", e)))?;
-        }
-        
-        // Download results
-        let mut output = vec![0.0f32; n * features_per_symbol];
-        backend.device.dtoh_sync_copy_into(&d_output, &mut output)
-            .map_err(|e| Error::Internal(format!("Download: {:?}", e)))?;
-        
+        let output = backend.launch(&input, n, self.microprice_levels)?;
+
         // Convert to features
         let results = snapshots.iter().enumerate().map(|(i, snap)| {
             let start = i * features_per_symbol;
@@ -186,14 +315,41 @@ impl GpuFeatureComputer {
     
     #[cfg(feature = "wgpu")]
     fn compute_wgpu(&self, backend: &WgpuBackend, snapshots: &[MarketSnapshot]) -> Result<Vec<crate::ComputedFeatures>> {
-        // WebGPU implementation
-        todo!("WebGPU compute")
+        let n = snapshots.len();
+        let features_per_symbol = layout::NUM_FEATURES;
+        
+        let mut input = Vec::with_capacity(n * INPUT_STRIDE);
+        for snap in snapshots {
+            serialize_snapshot(&mut input, snap);
+        }
+        
+        let output = backend.run(&input, n, self.microprice_levels)?;
+        
+        let results = snapshots.iter().enumerate().map(|(i, snap)| {
+            let start = i * features_per_symbol;
+            let features = Array1::from_vec(output[start..start + features_per_symbol].to_vec());
+            
+            crate::ComputedFeatures {
+                symbol: snap.symbol.clone(),
+                timestamp_ns: snap.timestamp_ns,
+                features,
+                computed_on: crate::Device::ROCm,
+            }
+        }).collect();
+        
+        Ok(results)
     }
 }
 
+/// f32s per symbol in the kernel input: mid, spread, 10 bids, 10 asks, 100 trades, funding
+pub(crate) const INPUT_STRIDE: usize = 2 + 20 + 20 + 300 + 1;
+
 fn serialize_snapshot(buffer: &mut Vec<f32>, snap: &MarketSnapshot) {
-    let book = &snap.orderbook;
-    
+    serialize_inputs(buffer, &snap.orderbook, &snap.recent_trades, snap.funding_rate_bps.unwrap_or(0.0));
+}
+
+/// Append one `INPUT_STRIDE` kernel input record; trades are the most recent 100
+pub(crate) fn serialize_inputs(buffer: &mut Vec<f32>, book: &OrderBook, trades: &[Trade], funding_bps: f64) {
     // Mid, spread
     buffer.push(book.mid_price().unwrap_or(0.0) as f32);
     buffer.push(book.spread_bps().unwrap_or(0.0) as f32);
@@ -221,8 +377,9 @@ fn serialize_snapshot(buffer: &mut Vec<f32>, snap: &MarketSnapshot) {
     }
     
     // Recent trades
-    for i in 0..100 {
-        if let Some(trade) = snap.recent_trades.get(i) {
+    let mut recent = trades.iter().rev();
+    for _ in 0..100 {
+        if let Some(trade) = recent.next() {
             buffer.push(trade.price as f32);
             buffer.push(trade.quantity as f32);
             buffer.push(if matches!(trade.side, Side::Buy) { 1.0 } else { -1.0 });
@@ -234,17 +391,18 @@ fn serialize_snapshot(buffer: &mut Vec<f32>, snap: &MarketSnapshot) {
     }
     
     // Funding
-    buffer.push(snap.funding_rate_bps.unwrap_or(0.0) as f32);
+    buffer.push(funding_bps as f32);
 }
 
 #[cfg(feature = "cuda")]
-fn compile_cuda_kernel() -> cudarc::nvrtc::Ptx {
+fn compile_cuda_kernel() -> Result<cudarc::nvrtc::Ptx> {
     // Inline CUDA kernel
     const KERNEL: &str = r#"
 extern "C" __global__ void compute_features(
     const float* input,
     float* output,
-    int num_symbols
+    int num_symbols,
+    int microprice_levels
 ) {
     int idx = blockIdx.x * blockDim.x + threadIdx.x;
     if (idx >= num_symbols) return;
@@ -276,6 +434,32 @@ extern "C" __global__ void compute_features(
     float obi = (bid_vol - ask_vol) / (bid_vol + ask_vol + 1e-9f);
     symbol_output[3] = obi;
     
+    // Depth-weighted microprice over the first microprice_levels levels
+    float bid_notional = 0.0f;
+    float bid_size = 0.0f;
+    float ask_notional = 0.0f;
+    float ask_size = 0.0f;
+    
+    for (int i = 0; i < microprice_levels; i++) {
+        float bp = symbol_input[2 + i * 2];
+        float bq = symbol_input[2 + i * 2 + 1];
+        float ap = symbol_input[22 + i * 2];
+        float aq = symbol_input[22 + i * 2 + 1];
+        
+        bid_notional += bp * bq;
+        bid_size += bq;
+        ask_notional += ap * aq;
+        ask_size += aq;
+    }
+    
+    float microprice = mid;
+    if (bid_size > 0.0f && ask_size > 0.0f) {
+        float bid_px = bid_notional / bid_size;
+        float ask_px = ask_notional / ask_size;
+        microprice = (bid_px * ask_size + ask_px * bid_size) / (bid_size + ask_size);
+    }
+    symbol_output[6] = microprice;
+    
     // Trade flow features
     float buy_vol = 0.0f;
     float sell_vol = 0.0f;
@@ -299,29 +483,175 @@ extern "C" __global__ void compute_features(
     float vwap = vwap_sum / (vol_sum + 1e-9f);
     
     symbol_output[4] = ofi;
-    symbol_output[5] = mid / vwap;
+    symbol_output[5] = vol_sum > 0.0f ? mid / vwap : 1.0f;
     
     // Pad remaining
-    for (int i = 6; i < 100; i++) {
+    for (int i = 7; i < 100; i++) {
         symbol_output[i] = 0.0f;
     }
 }
 "#;
     
-    cudarc::nvrtc::compile_ptx(KERNEL).unwrap()
+    cudarc::nvrtc::compile_ptx(KERNEL)
+        .map_err(|e| Error::Internal(format!("Kernel compile: {:?}", e)))
 }
 
+#[cfg(feature = "wgpu")]
+const WGSL_WORKGROUP_SIZE: usize = 256;
+
+/// WGSL port of the CUDA kernel: same input record and output slots
 const WGSL_SHADER: &str = r#"
+struct Params {
+    num_symbols: u32,
+    microprice_levels: u32,
+    _pad0: u32,
+    _pad1: u32,
+}
+
 @group(0) @binding(0) var<storage, read> input: array<f32>;
 @group(0) @binding(1) var<storage, read_write> output: array<f32>;
-@group(0) @binding(2) var<uniform> num_symbols: u32;
+@group(0) @binding(2) var<uniform> params: Params;
+
+// Input layout: mid, spread, 10 bids, 10 asks, 100 trades, funding
+const INPUT_STRIDE: u32 = 343u;
+const NUM_FEATURES: u32 = 100u;
+const BIDS: u32 = 2u;
+const ASKS: u32 = 22u;
+const TRADES: u32 = 42u;
 
 @compute @workgroup_size(256)
 fn main(@builtin(global_invocation_id) gid: vec3<u32>) {
     let idx = gid.x;
-    if (idx >= num_symbols) { return; }
+    if (idx >= params.num_symbols) { return; }
+    
+    let src = idx * INPUT_STRIDE;
+    let dst = idx * NUM_FEATURES;
     
-    // Implement feature computation
-    output[idx * 100] = input[idx * 343]; // Placeholder
+    // Basic features
+    let mid = input[src];
+    output[dst] = mid;
+    output[dst + 1u] = input[src + 1u];
+    output[dst + 2u] = input[src + INPUT_STRIDE - 1u];
+    
+    // Order book imbalance
+    var bid_vol = 0.0;
+    var ask_vol = 0.0;
+    for (var i = 0u; i < 10u; i = i + 1u) {
+        bid_vol = bid_vol + input[src + BIDS + i * 2u + 1u];
+        ask_vol = ask_vol + input[src + ASKS + i * 2u + 1u];
+    }
+    output[dst + 3u] = (bid_vol - ask_vol) / (bid_vol + ask_vol + 1e-9);
+    
+    // Depth-weighted microprice over the first microprice_levels levels
+    var bid_notional = 0.0;
+    var bid_size = 0.0;
+    var ask_notional = 0.0;
+    var ask_size = 0.0;
+    for (var i = 0u; i < min(params.microprice_levels, 10u); i = i + 1u) {
+        let bp = input[src + BIDS + i * 2u];
+        let bq = input[src + BIDS + i * 2u + 1u];
+        let ap = input[src + ASKS + i * 2u];
+        let aq = input[src + ASKS + i * 2u + 1u];
+        
+        bid_notional = bid_notional + bp * bq;
+        bid_size = bid_size + bq;
+        ask_notional = ask_notional + ap * aq;
+        ask_size = ask_size + aq;
+    }
+    
+    var microprice = mid;
+    if (bid_size > 0.0 && ask_size > 0.0) {
+        let bid_px = bid_notional / bid_size;
+        let ask_px = ask_notional / ask_size;
+        microprice = (bid_px * ask_size + ask_px * bid_size) / (bid_size + ask_size);
+    }
+    output[dst + 6u] = microprice;
+    
+    // Trade flow features
+    var buy_vol = 0.0;
+    var sell_vol = 0.0;
+    var vwap_sum = 0.0;
+    var vol_sum = 0.0;
+    for (var i = 0u; i < 100u; i = i + 1u) {
+        let t = src + TRADES + i * 3u;
+        let price = input[t];
+        let qty = input[t + 1u];
+        
+        if (input[t + 2u] > 0.0) {
+            buy_vol = buy_vol + qty;
+        } else {
+            sell_vol = sell_vol + qty;
+        }
+        vwap_sum = vwap_sum + price * qty;
+        vol_sum = vol_sum + qty;
+    }
+    
+    output[dst + 4u] = buy_vol - sell_vol;
+    output[dst + 5u] = select(1.0, mid / (vwap_sum / vol_sum), vol_sum > 0.0);
+    
+    // Pad remaining
+    for (var i = 7u; i < NUM_FEATURES; i = i + 1u) {
+        output[dst + i] = 0.0;
+    }
+}
+"#;
+
+#[cfg(all(test, any(feature = "cuda", feature = "wgpu")))]
+mod tests {
+    use super::*;
+    use crate::CpuFeatureBuilder;
+    use ordered_float::OrderedFloat;
+    
+    /// Slots both the kernels and the CPU builder fill from the book alone
+    const SHARED_SLOTS: [usize; 5] = [layout::MID_PRICE, layout::SPREAD_BPS, layout::FUNDING_BPS, layout::OBI, layout::MICROPRICE];
+    
+    fn snapshots() -> Vec<MarketSnapshot> {
+        let level = |price: f64, quantity: f64| Level { price: OrderedFloat(price), quantity };
+        [(50000.0, 3.0, 0.01), (190.0, 0.5, -0.02)]
+            .into_iter()
+            .map(|(px, bid_qty, funding)| MarketSnapshot {
+                timestamp_ns: 0,
+                symbol: format!("X{}", px),
+                orderbook: OrderBook {
+                    symbol: format!("X{}", px),
+                    timestamp_ns: 0,
+                    bids: vec![level(px, bid_qty), level(px - 1.0, 2.0)],
+                    asks: vec![level(px + 10.0, 1.0), level(px + 11.0, 4.0)],
+                    sequence: 1,
+                },
+                recent_trades: vec![],
+                funding_rate_bps: Some(funding),
+                open_interest: None,
+                volume_24h: 0.0,
+                quality: DataQuality::Live,
+            })
+            .collect()
+    }
+    
+    fn assert_matches_cpu(device: DeviceType) {
+        let snapshots = snapshots();
+        let expected = CpuFeatureBuilder::new().compute_batch(&snapshots).unwrap();
+        let actual = GpuFeatureComputer::new(device, snapshots.len()).unwrap().compute_batch(&snapshots).unwrap();
+        assert_eq!(actual.len(), snapshots.len());
+        
+        for (want, got) in expected.iter().zip(&actual) {
+            for idx in SHARED_SLOTS {
+                let (want, got) = (want.features[idx], got.features[idx]);
+                let tolerance = 1e-4 * want.abs().max(1.0);
+                assert!((want - got).abs() < tolerance, "feature {}: cpu {} {:?} {}", idx, want, device, got);
+            }
+        }
+    }
+    
+    #[cfg(feature = "cuda")]
+    #[test]
+    fn test_cuda_matches_cpu_reference() {
+        assert_matches_cpu(DeviceType::CUDA(0));
+    }
+    
+    #[cfg(feature = "wgpu")]
+    #[test]
+    fn test_wgpu_matches_cpu_reference() {
+        assert_matches_cpu(DeviceType::ROCm(0));
+    }
 }
-"#;
\ No newline at end of file
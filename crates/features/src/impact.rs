@@ -0,0 +1,175 @@
+// crates/features/src/impact.rs - Fit impact curves to realized trade slippage
+use common::*;
+use crate::indicators;
+use std::collections::{HashMap, VecDeque};
+
+/// Trades per symbol the fit runs over
+pub const DEFAULT_WINDOW: usize = 500;
+/// Fewer samples than this and a symbol has no calibrated curve
+pub const MIN_SAMPLES: usize = 30;
+
+/// Per-symbol `ImpactCurve`s from a log-log regression of realized slippage
+/// (distance of a trade's price from mid, as a fraction of mid) on trade
+/// notional, over the last `window` trades
+#[derive(Debug)]
+pub struct ImpactCalibrator {
+    window: usize,
+    symbols: HashMap<String, Samples>,
+}
+
+#[derive(Debug, Default)]
+struct Samples {
+    /// (ln notional, ln slippage), oldest first
+    points: VecDeque<(f64, f64)>,
+    /// Newest trade already sampled, so overlapping snapshots don't count twice
+    last_trade_ns: i64,
+    curve: Option<ImpactCurve>,
+}
+
+impl Default for ImpactCalibrator {
+    fn default() -> Self {
+        Self::new(DEFAULT_WINDOW)
+    }
+}
+
+impl ImpactCalibrator {
+    pub fn new(window: usize) -> Self {
+        Self { window: window.max(MIN_SAMPLES), symbols: HashMap::new() }
+    }
+    
+    /// Sample the snapshot's trades not seen before against its book mid,
+    /// then refit the symbol's curve
+    pub fn observe(&mut self, snap: &MarketSnapshot) {
+        let Some(mid) = snap.orderbook.mid_price().filter(|m| *m > 0.0) else {
+            return;
+        };
+        
+        let samples = self.symbols.entry(snap.symbol.clone()).or_default();
+        let since = samples.last_trade_ns;
+        let mut added = false;
+        
+        for trade in snap.recent_trades.iter().filter(|t| t.timestamp_ns > since) {
+            samples.last_trade_ns = samples.last_trade_ns.max(trade.timestamp_ns);
+            added |= push(samples, self.window, trade.price * trade.quantity, (trade.price / mid - 1.0).abs());
+        }
+        
+        if added {
+            samples.curve = fit(samples);
+        }
+    }
+    
+    /// Record one trade of `notional` that filled `slippage` (fraction of mid)
+    /// away from mid, and refit
+    pub fn record(&mut self, symbol: &str, notional: f64, slippage: f64) {
+        let samples = self.symbols.entry(symbol.to_string()).or_default();
+        if push(samples, self.window, notional, slippage) {
+            samples.curve = fit(samples);
+        }
+    }
+    
+    /// Calibrated curve for `symbol`, once it has `MIN_SAMPLES` trades
+    pub fn curve(&self, symbol: &str) -> Option<ImpactCurve> {
+        self.symbols.get(symbol).and_then(|s| s.curve)
+    }
+}
+
+/// Trades at mid or without size carry nothing to fit in log space
+fn push(samples: &mut Samples, window: usize, notional: f64, slippage: f64) -> bool {
+    if !(notional > 0.0 && slippage > 0.0 && notional.is_finite() && slippage.is_finite()) {
+        return false;
+    }
+    
+    if samples.points.len() == window {
+        samples.points.pop_front();
+    }
+    samples.points.push_back((notional.ln(), slippage.ln()));
+    true
+}
+
+fn fit(samples: &Samples) -> Option<ImpactCurve> {
+    if samples.points.len() < MIN_SAMPLES {
+        return None;
+    }
+    
+    let points: Vec<(f64, f64)> = samples.points.iter().copied().collect();
+    indicators::log_log_fit(&points)
+        .filter(|(a, beta)| a.is_finite() && beta.is_finite())
+        .map(|(a, beta)| ImpactCurve { a, beta })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ordered_float::OrderedFloat;
+    
+    #[test]
+    fn test_recovers_known_curve() {
+        let (a, beta) = (2e-6, 0.6);
+        let mut calibrator = ImpactCalibrator::new(200);
+        
+        // Notionals from $1k to $1M with deterministic multiplicative noise
+        let slippage = |notional: f64, i: usize| a * notional.powf(beta) * (0.1 * (i as f64 * 1.7).sin()).exp();
+        for i in 0..MIN_SAMPLES - 1 {
+            let notional = 1e3 * 10f64.powf(3.0 * i as f64 / MIN_SAMPLES as f64);
+            calibrator.record("BTC", notional, slippage(notional, i));
+        }
+        assert!(calibrator.curve("BTC").is_none());
+        
+        // Zero slippage is skipped rather than poisoning the log fit
+        calibrator.record("BTC", 5e4, 0.0);
+        assert!(calibrator.curve("BTC").is_none());
+        
+        for i in 0..400 {
+            let notional = 1e3 * 10f64.powf(3.0 * (i % 100) as f64 / 100.0);
+            calibrator.record("BTC", notional, slippage(notional, i));
+        }
+        
+        let curve = calibrator.curve("BTC").unwrap();
+        assert!((curve.beta - beta).abs() < 0.05, "beta {}", curve.beta);
+        assert!((curve.a / a).ln().abs() < 0.2, "a {}", curve.a);
+        assert!(calibrator.curve("ETH").is_none());
+    }
+    
+    #[test]
+    fn test_observe_samples_each_trade_once() {
+        let trades: Vec<Trade> = (0..MIN_SAMPLES)
+            .map(|i| {
+                let quantity = 1.0 + i as f64;
+                Trade {
+                    symbol: "BTC".to_string(),
+                    timestamp_ns: i as i64 + 1,
+                    price: 100.0 * (1.0 + 1e-4 * quantity.sqrt()),
+                    quantity,
+                    side: Side::Buy,
+                    trade_id: i.to_string(),
+                }
+            })
+            .collect();
+        
+        let snap = MarketSnapshot {
+            timestamp_ns: MIN_SAMPLES as i64,
+            symbol: "BTC".to_string(),
+            orderbook: OrderBook {
+                symbol: "BTC".to_string(),
+                timestamp_ns: MIN_SAMPLES as i64,
+                bids: vec![Level { price: OrderedFloat(99.0), quantity: 1.0 }],
+                asks: vec![Level { price: OrderedFloat(101.0), quantity: 1.0 }],
+                sequence: 1,
+            },
+            recent_trades: trades,
+            funding_rate_bps: None,
+            open_interest: None,
+            volume_24h: 0.0,
+            quality: DataQuality::Live,
+        };
+        
+        let mut calibrator = ImpactCalibrator::default();
+        calibrator.observe(&snap);
+        calibrator.observe(&snap);
+        assert_eq!(calibrator.symbols["BTC"].points.len(), MIN_SAMPLES);
+        
+        // slippage = 1e-4 * sqrt(q) and notional ~ 100 q, so beta ~ 0.5
+        let curve = calibrator.curve("BTC").unwrap();
+        assert!((curve.beta - 0.5).abs() < 0.01, "beta {}", curve.beta);
+    }
+}
@@ -0,0 +1,451 @@
+// crates/features/src/indicators.rs
+use common::*;
+use crate::layout;
+
+/// Default number of book levels aggregated into the microprice
+pub const DEFAULT_MICROPRICE_LEVELS: usize = 1;
+
+/// Top-of-book microprice: (bid_px * ask_sz + ask_px * bid_sz) / (bid_sz + ask_sz)
+pub fn microprice(book: &OrderBook) -> Option<f64> {
+    book.microprice()
+}
+
+/// Depth-weighted microprice over the first `levels` levels of each side.
+///
+/// The price leans toward the side with more resting liquidity pushing
+/// against it; see `OrderBook::weighted_mid`.
+pub fn depth_weighted_microprice(book: &OrderBook, levels: usize) -> Option<f64> {
+    book.weighted_mid(levels)
+}
+
+/// Write the tick- and spread-normalized feature block into `out`.
+///
+/// Spread-unit features only need the book; tick-unit features are left at
+/// zero when no `SymbolSpec` (or a non-positive tick size) is configured.
+pub fn write_normalized(book: &OrderBook, spec: Option<&SymbolSpec>, out: &mut [f32]) {
+    if out.len() < layout::NUM_FEATURES {
+        return;
+    }
+    
+    let (Some(bid), Some(ask)) = (book.best_bid(), book.best_ask()) else {
+        return;
+    };
+    
+    let mid = (bid.price.0 + ask.price.0) / 2.0;
+    let spread = ask.price.0 - bid.price.0;
+    let micro = microprice(book).unwrap_or(mid);
+    let tick = spec.map(|s| s.tick_size).filter(|t| *t > 0.0);
+    
+    if spread > 0.0 {
+        out[layout::MICRO_OFFSET_SPREADS] = ((micro - mid) / spread) as f32;
+        out[layout::BID_DEPTH_SPREADS] = (depth_distance(&book.bids, mid) / spread) as f32;
+        out[layout::ASK_DEPTH_SPREADS] = (depth_distance(&book.asks, mid) / spread) as f32;
+    }
+    
+    if let Some(tick) = tick {
+        out[layout::SPREAD_TICKS] = (spread / tick) as f32;
+        out[layout::MICRO_OFFSET_TICKS] = ((micro - mid) / tick) as f32;
+        
+        for (i, level) in book.bids.iter().take(layout::NORMALIZED_LEVELS).enumerate() {
+            out[layout::LEVEL_TICKS_START + i] = ((mid - level.price.0) / tick) as f32;
+        }
+        
+        let ask_start = layout::LEVEL_TICKS_START + layout::NORMALIZED_LEVELS;
+        for (i, level) in book.asks.iter().take(layout::NORMALIZED_LEVELS).enumerate() {
+            out[ask_start + i] = ((level.price.0 - mid) / tick) as f32;
+        }
+    }
+}
+
+/// Write the volatility and liquidity block into `out`
+pub fn write_market_block(snap: &MarketSnapshot, out: &mut [f32]) {
+    if out.len() < layout::NUM_FEATURES {
+        return;
+    }
+    
+    let now = snap.timestamp_ns;
+    out[layout::REALIZED_VOL_5S] = realized_vol(&snap.recent_trades, now, layout::REALIZED_VOL_WINDOW_NS) as f32;
+    out[layout::ATR_30S] = atr(&snap.recent_trades, now, layout::ATR_WINDOW_NS, layout::ATR_BAR_NS) as f32;
+    
+    let book = &snap.orderbook;
+    // Near-degenerate ladders can fit an intercept far outside f32 range
+    let (a, beta) = depth_curve(book, layout::DEPTH_CURVE_LEVELS)
+        .filter(|(a, beta)| (*a as f32).is_finite() && (*beta as f32).is_finite())
+        .unwrap_or((ImpactCurve::PRIOR.a, ImpactCurve::PRIOR.beta));
+    out[layout::DEPTH_A] = a as f32;
+    out[layout::DEPTH_BETA] = beta as f32;
+    out[layout::IMPACT_BPS_1PCT] = impact_bps_1pct(book).unwrap_or(0.0) as f32;
+}
+
+/// Trades inside `(now - window, now]`, oldest first
+fn window_trades(trades: &[Trade], now_ns: i64, window_ns: i64) -> Vec<&Trade> {
+    let mut recent: Vec<&Trade> = trades
+        .iter()
+        .filter(|t| t.timestamp_ns > now_ns - window_ns && t.timestamp_ns <= now_ns && t.price > 0.0)
+        .collect();
+    recent.sort_by_key(|t| t.timestamp_ns);
+    recent
+}
+
+/// sqrt of summed squared trade-to-trade log returns over the window
+pub fn realized_vol(trades: &[Trade], now_ns: i64, window_ns: i64) -> f64 {
+    let recent = window_trades(trades, now_ns, window_ns);
+    
+    recent
+        .windows(2)
+        .map(|w| (w[1].price / w[0].price).ln().powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+/// Average true range of fixed-width trade bars over the window (empty bars skipped)
+pub fn atr(trades: &[Trade], now_ns: i64, window_ns: i64, bar_ns: i64) -> f64 {
+    let start = now_ns - window_ns;
+    let mut bars: std::collections::BTreeMap<i64, (f64, f64, f64)> = std::collections::BTreeMap::new();
+    
+    for trade in window_trades(trades, now_ns, window_ns) {
+        let bar = (trade.timestamp_ns - start) / bar_ns.max(1);
+        let entry = bars.entry(bar).or_insert((trade.price, trade.price, trade.price));
+        entry.0 = entry.0.max(trade.price);
+        entry.1 = entry.1.min(trade.price);
+        entry.2 = trade.price;
+    }
+    
+    let mut prev_close: Option<f64> = None;
+    let ranges: Vec<f64> = bars
+        .values()
+        .map(|&(high, low, close)| {
+            let range = match prev_close {
+                Some(pc) => (high - low).max((high - pc).abs()).max((low - pc).abs()),
+                None => high - low,
+            };
+            prev_close = Some(close);
+            range
+        })
+        .collect();
+    
+    if ranges.is_empty() { 0.0 } else { ranges.iter().sum::<f64>() / ranges.len() as f64 }
+}
+
+/// Distance from `mid` in bps of the average fill when sweeping `notional`
+/// through one side; `None` if the displayed depth can't absorb it
+pub fn sweep_impact_bps(side: &[Level], mid: f64, notional: f64) -> Option<f64> {
+    if mid <= 0.0 || notional <= 0.0 {
+        return None;
+    }
+    
+    let mut remaining = notional;
+    let mut quantity = 0.0;
+    
+    for level in side {
+        let price = level.price.0;
+        if price <= 0.0 {
+            continue;
+        }
+        
+        let take = remaining.min(price * level.quantity);
+        quantity += take / price;
+        remaining -= take;
+        
+        if remaining <= notional * 1e-12 {
+            let vwap = notional / quantity;
+            return Some((vwap - mid).abs() / mid * 10000.0);
+        }
+    }
+    
+    None
+}
+
+/// Least-squares fit of `impact = a * notional^beta` to the book, where each
+/// level contributes (cumulative notional, distance of its price from mid / mid)
+pub fn depth_curve(book: &OrderBook, levels: usize) -> Option<(f64, f64)> {
+    let mid = book.mid_price()?;
+    if mid <= 0.0 {
+        return None;
+    }
+    
+    let mut points = Vec::with_capacity(2 * levels);
+    for side in [&book.bids, &book.asks] {
+        let mut cumulative = 0.0;
+        for level in side.iter().take(levels) {
+            cumulative += level.price.0 * level.quantity;
+            let impact = (level.price.0 - mid).abs() / mid;
+            
+            if cumulative > 0.0 && impact > 0.0 {
+                points.push((cumulative.ln(), impact.ln()));
+            }
+        }
+    }
+    
+    log_log_fit(&points)
+}
+
+/// Least-squares line through (ln x, ln y) points, as `(a, beta)` of `y = a * x^beta`
+pub(crate) fn log_log_fit(points: &[(f64, f64)]) -> Option<(f64, f64)> {
+    if points.len() < 2 {
+        return None;
+    }
+    
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let var_x: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+    if var_x <= f64::EPSILON {
+        return None;
+    }
+    
+    let cov: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+    let beta = cov / var_x;
+    let a = (mean_y - beta * mean_x).exp();
+    
+    Some((a, beta))
+}
+
+/// `IMPACT_BPS_1PCT`: see `layout`
+pub fn impact_bps_1pct(book: &OrderBook) -> Option<f64> {
+    let mid = book.mid_price()?;
+    let displayed: f64 = book.bids.iter().take(layout::DEPTH_CURVE_LEVELS)
+        .chain(book.asks.iter().take(layout::DEPTH_CURVE_LEVELS))
+        .map(|l| l.price.0 * l.quantity)
+        .sum();
+    
+    let notional = displayed * 0.01;
+    let sides: Vec<f64> = [&book.asks, &book.bids]
+        .into_iter()
+        .filter_map(|side| sweep_impact_bps(side, mid, notional))
+        .collect();
+    
+    if sides.is_empty() {
+        None
+    } else {
+        Some(sides.iter().sum::<f64>() / sides.len() as f64)
+    }
+}
+
+/// Multi-level order flow imbalance (Cont, Kukanov & Stoikov) between two
+/// consecutive books, summed over the top `levels`. At each level, bid size
+/// added at or above the previous price minus size removed at or below it,
+/// less the same for the asks. Positive means buying pressure.
+pub fn order_flow_imbalance(prev: &OrderBook, curr: &OrderBook, levels: usize) -> f64 {
+    (0..levels)
+        .map(|i| {
+            let bid = level_flow(prev.bids.get(i), curr.bids.get(i), |a, b| a > b);
+            let ask = level_flow(prev.asks.get(i), curr.asks.get(i), |a, b| a < b);
+            bid - ask
+        })
+        .sum()
+}
+
+/// Size arriving at one level minus size leaving it; `better(a, b)` is true
+/// when price `a` is more aggressive than `b` on this side. A level that
+/// appears or vanishes counts as all added or all removed.
+fn level_flow(prev: Option<&Level>, curr: Option<&Level>, better: fn(f64, f64) -> bool) -> f64 {
+    match (prev, curr) {
+        (Some(p), Some(c)) => {
+            let (pp, cp) = (p.price.0, c.price.0);
+            let added = if !better(pp, cp) { c.quantity } else { 0.0 };
+            let removed = if !better(cp, pp) { p.quantity } else { 0.0 };
+            added - removed
+        }
+        (None, Some(c)) => c.quantity,
+        (Some(p), None) => -p.quantity,
+        (None, None) => 0.0,
+    }
+}
+
+/// Size-weighted absolute distance from `mid` over the top 10 levels of one side
+fn depth_distance(side: &[Level], mid: f64) -> f64 {
+    let (weighted, size) = side
+        .iter()
+        .take(10)
+        .fold((0.0, 0.0), |(w, s), l| (w + (l.price.0 - mid).abs() * l.quantity, s + l.quantity));
+    
+    if size > 0.0 { weighted / size } else { 0.0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ordered_float::OrderedFloat;
+    
+    fn book(bids: &[(f64, f64)], asks: &[(f64, f64)]) -> OrderBook {
+        let to_levels = |side: &[(f64, f64)]| {
+            side.iter()
+                .map(|&(p, q)| Level { price: OrderedFloat(p), quantity: q })
+                .collect()
+        };
+        
+        OrderBook {
+            symbol: "BTC".to_string(),
+            timestamp_ns: 0,
+            bids: to_levels(bids),
+            asks: to_levels(asks),
+            sequence: 1,
+        }
+    }
+    
+    #[test]
+    fn test_microprice_leans_toward_heavier_side() {
+        // Heavy bids: pressure is upward, microprice sits above mid
+        let b = book(&[(100.0, 9.0)], &[(101.0, 1.0)]);
+        let mid = b.mid_price().unwrap();
+        let micro = microprice(&b).unwrap();
+        
+        assert!((micro - 100.9).abs() < 1e-9);
+        assert!(micro > mid);
+        assert!(micro < 101.0);
+        
+        // Heavy asks: microprice sits below mid
+        let b = book(&[(100.0, 1.0)], &[(101.0, 9.0)]);
+        assert!(microprice(&b).unwrap() < b.mid_price().unwrap());
+    }
+    
+    #[test]
+    fn test_microprice_balanced_book_equals_mid() {
+        let b = book(&[(100.0, 5.0)], &[(101.0, 5.0)]);
+        assert!((microprice(&b).unwrap() - 100.5).abs() < 1e-9);
+    }
+    
+    #[test]
+    fn test_depth_weighted_microprice_uses_deeper_levels() {
+        // Top of book is balanced but the second bid level is large
+        let b = book(&[(100.0, 1.0), (99.0, 9.0)], &[(101.0, 1.0), (102.0, 1.0)]);
+        
+        let top = depth_weighted_microprice(&b, 1).unwrap();
+        let deep = depth_weighted_microprice(&b, 2).unwrap();
+        
+        assert!((top - 100.5).abs() < 1e-9);
+        assert!(deep > b.mid_price().unwrap());
+    }
+    
+    #[test]
+    fn test_microprice_empty_side() {
+        let b = book(&[(100.0, 1.0)], &[]);
+        assert!(microprice(&b).is_none());
+    }
+    
+    #[test]
+    fn test_normalized_features_scale_invariant() {
+        let bids = [(100.0, 4.0), (99.5, 2.0), (99.0, 7.0)];
+        let asks = [(100.5, 1.0), (101.0, 3.0), (102.0, 5.0)];
+        let spec = SymbolSpec { symbol: "BTC".to_string(), tick_size: 0.5, lot_size: 0.001 };
+        
+        let scale = 37.0;
+        let scaled = |side: &[(f64, f64)]| -> Vec<(f64, f64)> {
+            side.iter().map(|&(p, q)| (p * scale, q)).collect()
+        };
+        let scaled_spec = SymbolSpec { tick_size: spec.tick_size * scale, ..spec.clone() };
+        
+        let mut base = vec![0.0f32; layout::NUM_FEATURES];
+        let mut other = vec![0.0f32; layout::NUM_FEATURES];
+        write_normalized(&book(&bids, &asks), Some(&spec), &mut base);
+        write_normalized(&book(&scaled(&bids), &scaled(&asks)), Some(&scaled_spec), &mut other);
+        
+        assert!((base[layout::SPREAD_TICKS] - 1.0).abs() < 1e-6);
+        
+        let end = layout::LEVEL_TICKS_START + 2 * layout::NORMALIZED_LEVELS;
+        for idx in layout::SPREAD_TICKS..end {
+            assert!(
+                (base[idx] - other[idx]).abs() < 1e-4,
+                "feature {} not scale invariant: {} vs {}", idx, base[idx], other[idx]
+            );
+        }
+    }
+    
+    fn trade(ts_s: f64, price: f64) -> Trade {
+        Trade {
+            symbol: "BTC".to_string(),
+            timestamp_ns: (ts_s * 1e9) as i64,
+            price,
+            quantity: 1.0,
+            side: Side::Buy,
+            trade_id: String::new(),
+        }
+    }
+    
+    #[test]
+    fn test_realized_vol_and_atr() {
+        let now = 10_000_000_000;
+        let trades = vec![trade(9.2, 100.0), trade(9.5, 101.0), trade(9.9, 100.0), trade(1.0, 500.0)];
+        
+        // Out-of-window trade ignored; two equal-magnitude returns
+        let rv = realized_vol(&trades, now, layout::REALIZED_VOL_WINDOW_NS);
+        assert!((rv - 2f64.sqrt() * (1.01f64).ln()).abs() < 1e-3);
+        
+        // Bars: [100, 102] close 102, then [101, 99] close 99 → TR 2 and 3
+        let trades = vec![trade(8.1, 100.0), trade(8.5, 102.0), trade(9.1, 101.0), trade(9.6, 99.0)];
+        let range = atr(&trades, now, 2_000_000_000, 1_000_000_000);
+        assert!((range - 2.5).abs() < 1e-9, "atr {}", range);
+        
+        assert_eq!(atr(&[], now, layout::ATR_WINDOW_NS, layout::ATR_BAR_NS), 0.0);
+    }
+    
+    #[test]
+    fn test_order_flow_imbalance() {
+        let prev = book(&[(100.0, 2.0), (99.0, 4.0)], &[(101.0, 1.0), (102.0, 3.0)]);
+        
+        // Bid size grows at the touch: buying pressure
+        let curr = book(&[(100.0, 5.0), (99.0, 4.0)], &[(101.0, 1.0), (102.0, 3.0)]);
+        assert_eq!(order_flow_imbalance(&prev, &curr, 5), 3.0);
+        
+        // Deeper levels only count when asked for
+        let curr = book(&[(100.0, 2.0), (99.0, 4.0)], &[(101.0, 1.0), (102.0, 7.0)]);
+        assert_eq!(order_flow_imbalance(&prev, &curr, 1), 0.0);
+        assert_eq!(order_flow_imbalance(&prev, &curr, 2), -4.0);
+        
+        // Bid steps up a tick: all of its size is new, none was pulled
+        let curr = book(&[(100.5, 1.0), (100.0, 2.0)], &[(101.0, 1.0), (102.0, 3.0)]);
+        assert_eq!(order_flow_imbalance(&prev, &curr, 1), 1.0);
+        
+        // Best ask lifted away: its resting size left the book
+        let curr = book(&[(100.0, 2.0), (99.0, 4.0)], &[(102.0, 3.0)]);
+        assert_eq!(order_flow_imbalance(&prev, &curr, 1), 1.0);
+        
+        assert_eq!(order_flow_imbalance(&prev, &prev, 10), 0.0);
+    }
+    
+    #[test]
+    fn test_depth_curve_and_impact() {
+        let b = book(&[(100.0, 1.0), (99.0, 2.0)], &[(101.0, 1.0), (102.0, 2.0)]);
+        
+        let (a, beta) = depth_curve(&b, 10).unwrap();
+        assert!(beta > 0.0, "deeper notional should cost more: beta {}", beta);
+        
+        // Fitted curve reproduces the second level within a few percent
+        let fitted = a * 298.0f64.powf(beta);
+        assert!((fitted - 1.5 / 100.5).abs() / (1.5 / 100.5) < 0.05);
+        
+        // 1% of 603 displayed notional fills entirely at the touch: half spread
+        let impact = impact_bps_1pct(&b).unwrap();
+        assert!((impact - 0.5 / 100.5 * 10000.0).abs() < 1e-6);
+        
+        // Sweeping past the displayed depth has no answer
+        assert!(sweep_impact_bps(&b.asks, 100.5, 10_000.0).is_none());
+        
+        // A one-sided book has no fit and carries the prior, not a free curve
+        let snap = MarketSnapshot {
+            timestamp_ns: 0,
+            symbol: "BTC".to_string(),
+            orderbook: book(&[(100.0, 1.0)], &[]),
+            recent_trades: vec![],
+            funding_rate_bps: None,
+            open_interest: None,
+            volume_24h: 0.0,
+            quality: DataQuality::Live,
+        };
+        let mut out = vec![0.0f32; layout::NUM_FEATURES];
+        write_market_block(&snap, &mut out);
+        assert_eq!(out[layout::DEPTH_A], ImpactCurve::PRIOR.a as f32);
+        assert_eq!(out[layout::DEPTH_BETA], ImpactCurve::PRIOR.beta as f32);
+    }
+    
+    #[test]
+    fn test_normalized_features_without_spec() {
+        let b = book(&[(100.0, 9.0)], &[(101.0, 1.0)]);
+        let mut out = vec![0.0f32; layout::NUM_FEATURES];
+        write_normalized(&b, None, &mut out);
+        
+        assert_eq!(out[layout::SPREAD_TICKS], 0.0);
+        assert!((out[layout::MICRO_OFFSET_SPREADS] - 0.4).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,74 @@
+// crates/features/src/layout.rs
+// Feature vector index layout shared by the CPU and GPU paths
+
+/// Mid price
+pub const MID_PRICE: usize = 0;
+/// Top-of-book spread in bps
+pub const SPREAD_BPS: usize = 1;
+/// Funding rate in bps
+pub const FUNDING_BPS: usize = 2;
+/// Order book imbalance over the top OBI_LEVELS levels, in [-1, 1]
+pub const OBI: usize = 3;
+pub const OBI_LEVELS: usize = 10;
+/// Trade flow: buy minus sell volume of recent trades (book OFI is WINDOW_OFI)
+pub const OFI: usize = 4;
+/// Mid / VWAP of recent trades
+pub const VWAP_RATIO: usize = 5;
+/// Depth-weighted microprice
+pub const MICROPRICE: usize = 6;
+
+// Scale-free variants (tick units need a `SymbolSpec`; zero otherwise)
+
+/// Spread in ticks
+pub const SPREAD_TICKS: usize = 7;
+/// (microprice - mid) in ticks
+pub const MICRO_OFFSET_TICKS: usize = 8;
+/// (microprice - mid) in spread units
+pub const MICRO_OFFSET_SPREADS: usize = 9;
+/// Size-weighted distance of the bid ladder from mid, in spread units
+pub const BID_DEPTH_SPREADS: usize = 10;
+/// Size-weighted distance of the ask ladder from mid, in spread units
+pub const ASK_DEPTH_SPREADS: usize = 11;
+/// Per-level distance to mid in ticks: NORMALIZED_LEVELS bids, then NORMALIZED_LEVELS asks
+pub const LEVEL_TICKS_START: usize = 12;
+pub const NORMALIZED_LEVELS: usize = 5;
+
+// Volatility and liquidity (device independent, see `indicators::write_market_block`)
+
+/// Realized volatility of trade log returns over REALIZED_VOL_WINDOW_NS, sqrt(sum r^2)
+pub const REALIZED_VOL_5S: usize = 22;
+pub const REALIZED_VOL_WINDOW_NS: i64 = 5_000_000_000;
+/// Average true range of ATR_BAR_NS trade bars over ATR_WINDOW_NS, in price units
+pub const ATR_30S: usize = 23;
+pub const ATR_WINDOW_NS: i64 = 30_000_000_000;
+pub const ATR_BAR_NS: i64 = 1_000_000_000;
+/// Impact curve: impact fraction = DEPTH_A * notional^DEPTH_BETA. Fit to realized
+/// trade slippage once `impact::MIN_SAMPLES` trades are in, else to the top
+/// DEPTH_CURVE_LEVELS of the book, else `ImpactCurve::PRIOR`
+pub const DEPTH_A: usize = 24;
+pub const DEPTH_BETA: usize = 25;
+pub const DEPTH_CURVE_LEVELS: usize = 10;
+/// Fill distance from mid in bps for a sweep of 1% of displayed notional
+/// (top DEPTH_CURVE_LEVELS, both sides), averaged over buy and sell
+pub const IMPACT_BPS_1PCT: usize = 26;
+
+// Rolling windows over the last `window_size` book updates, kept in CPU state
+// and filled on either device
+
+/// sqrt of summed squared mid log returns
+pub const WINDOW_VOL: usize = 27;
+/// Mean absolute mid move between updates
+pub const WINDOW_ATR: usize = 28;
+/// Summed order flow imbalance over the top OFI_LEVELS (Cont et al.), see
+/// `indicators::order_flow_imbalance`
+pub const WINDOW_OFI: usize = 29;
+pub const OFI_LEVELS: usize = 5;
+/// Mean OBI over OBI_LEVELS
+pub const WINDOW_OBI: usize = 30;
+
+/// Start of the raw depth ladder (10 bid + 10 ask price/qty pairs, CPU path only)
+pub const DEPTH_LADDER_START: usize = 60;
+pub const DEPTH_LADDER_LEVELS: usize = 10;
+
+/// Total feature slots per symbol (unused slots are zero)
+pub const NUM_FEATURES: usize = 100;
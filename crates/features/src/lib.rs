@@ -1,24 +1,43 @@
 // crates/features/src/lib.rs - GPU-First Feature Engineering
 use common::*;
 use ndarray::Array1;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use parking_lot::RwLock;
+use std::time::{Duration, Instant};
+use parking_lot::{Mutex, RwLock};
 
 pub mod gpu;
 pub mod cpu;
+pub mod impact;
 pub mod indicators;
+pub mod layout;
 
-pub use gpu::{GpuFeatureComputer, DeviceType};
+pub use gpu::{GpuBackend, GpuFeatureComputer, DeviceType};
 pub use cpu::CpuFeatureBuilder;
 
 /// Unified feature computer with automatic GPU/CPU fallback
 pub struct FeatureComputer {
-    gpu: Option<Arc<GpuFeatureComputer>>,
+    gpu: Option<Arc<dyn GpuBackend>>,
+    /// Deadline per GPU batch; unset waits on the device indefinitely
+    gpu_timeout: Option<Duration>,
+    /// A GPU call is running on its worker thread (possibly hung)
+    gpu_busy: Arc<AtomicBool>,
+    /// Thread timed GPU batches run on, started with the first one
+    gpu_worker: Mutex<Option<GpuWorker>>,
     cpu: Arc<RwLock<CpuFeatureBuilder>>,
-    mode: ComputeMode,
+    /// Default mode for symbols without a category override
+    mode: RwLock<ComputeMode>,
+    demotion: DemotionPolicy,
+    gpu_health: Mutex<GpuHealth>,
+    specs: RwLock<HashMap<String, SymbolSpec>>,
+    category_modes: RwLock<HashMap<AssetCategory, ComputeMode>>,
+    categories: RwLock<HashMap<String, AssetCategory>>,
+    /// Impact curves fit to recent trades, overriding the book-depth fit
+    impact: Mutex<impact::ImpactCalibrator>,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ComputeMode {
     /// Try GPU first, fallback to CPU on error
     GPUFirst,
@@ -28,13 +47,73 @@ pub enum ComputeMode {
     GPUOnly,
 }
 
+/// When repeated GPU failures demote the default mode from `GPUFirst` to
+/// `CPUOnly`. Category overrides are left alone.
+#[derive(Debug, Clone, Copy)]
+pub struct DemotionPolicy {
+    /// Consecutive failed `GPUFirst` batches before demoting (0 = never)
+    pub max_consecutive_failures: u32,
+    /// How long to stay on CPU before probing the GPU again
+    pub cooldown: Duration,
+}
+
+impl Default for DemotionPolicy {
+    fn default() -> Self {
+        Self {
+            max_consecutive_failures: 3,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct GpuHealth {
+    consecutive_failures: u32,
+    /// Set while the default mode is demoted
+    demoted_at: Option<Instant>,
+    /// Demotion not yet collected by `take_health_alert`
+    alert: Option<String>,
+}
+
+/// Long-lived thread that runs timed GPU batches, so a hung device call can
+/// be abandoned without a thread per batch
+struct GpuWorker {
+    jobs: std::sync::mpsc::Sender<GpuJob>,
+}
+
+struct GpuJob {
+    batch: Vec<MarketSnapshot>,
+    reply: std::sync::mpsc::SyncSender<Result<Vec<ComputedFeatures>>>,
+}
+
+impl GpuWorker {
+    fn spawn(gpu: Arc<dyn GpuBackend>, busy: Arc<AtomicBool>) -> Result<Self> {
+        let (jobs, rx) = std::sync::mpsc::channel::<GpuJob>();
+        std::thread::Builder::new()
+            .name("gpu-features".to_string())
+            .spawn(move || {
+                for job in rx {
+                    // A panicking batch fails alone instead of taking the worker with it
+                    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                        gpu.compute_batch(&job.batch)
+                    }))
+                    .unwrap_or_else(|_| Err(Error::Internal("GPU batch panicked".to_string())));
+                    busy.store(false, Ordering::Release);
+                    let _ = job.reply.send(result);
+                }
+            })
+            .map_err(|e| Error::Internal(format!("Failed to start GPU worker: {}", e)))?;
+        Ok(Self { jobs })
+    }
+}
+
 impl FeatureComputer {
     /// Create new feature computer with GPU support
     pub fn new(device: DeviceType, batch_size: usize) -> Result<Self> {
         let gpu = match GpuFeatureComputer::new(device, batch_size) {
             Ok(computer) => {
                 tracing::info!("GPU feature computer initialized: {:?}", device);
-                Some(Arc::new(computer))
+                Some(Arc::new(computer) as Arc<dyn GpuBackend>)
             }
             Err(e) => {
                 tracing::warn!("GPU initialization failed: {}. Using CPU fallback", e);
@@ -50,8 +129,17 @@ impl FeatureComputer {
         
         Ok(Self {
             gpu,
+            gpu_timeout: None,
+            gpu_busy: Arc::new(AtomicBool::new(false)),
+            gpu_worker: Mutex::new(None),
             cpu: Arc::new(RwLock::new(CpuFeatureBuilder::new())),
-            mode,
+            mode: RwLock::new(mode),
+            demotion: DemotionPolicy::default(),
+            gpu_health: Mutex::new(GpuHealth::default()),
+            specs: RwLock::new(HashMap::new()),
+            category_modes: RwLock::new(HashMap::new()),
+            categories: RwLock::new(HashMap::new()),
+            impact: Mutex::new(impact::ImpactCalibrator::default()),
         })
     }
     
@@ -59,30 +147,155 @@ impl FeatureComputer {
     pub fn cpu_only() -> Self {
         Self {
             gpu: None,
+            gpu_timeout: None,
+            gpu_busy: Arc::new(AtomicBool::new(false)),
+            gpu_worker: Mutex::new(None),
             cpu: Arc::new(RwLock::new(CpuFeatureBuilder::new())),
-            mode: ComputeMode::CPUOnly,
+            mode: RwLock::new(ComputeMode::CPUOnly),
+            demotion: DemotionPolicy::default(),
+            gpu_health: Mutex::new(GpuHealth::default()),
+            specs: RwLock::new(HashMap::new()),
+            category_modes: RwLock::new(HashMap::new()),
+            categories: RwLock::new(HashMap::new()),
+            impact: Mutex::new(impact::ImpactCalibrator::default()),
         }
     }
     
+    /// GPU-first computer on `backend`, e.g. a stand-in device
+    pub fn with_gpu_backend(backend: Arc<dyn GpuBackend>) -> Self {
+        Self {
+            gpu: Some(backend),
+            mode: RwLock::new(ComputeMode::GPUFirst),
+            ..Self::cpu_only()
+        }
+    }
+    
+    /// Give up on a GPU batch after `timeout`: `GPUFirst` falls back to CPU,
+    /// `GPUOnly` fails the batch
+    pub fn with_gpu_timeout(mut self, timeout: Duration) -> Self {
+        self.gpu_timeout = Some(timeout);
+        self
+    }
+    
+    /// Replace the default `DemotionPolicy`
+    pub fn with_demotion(mut self, demotion: DemotionPolicy) -> Self {
+        self.demotion = demotion;
+        self
+    }
+    
+    /// Default compute mode, e.g. to force CPU during a suspected GPU fault.
+    /// Cancels any automatic demotion.
+    pub fn set_mode(&self, mode: ComputeMode) -> Result<()> {
+        if mode != ComputeMode::CPUOnly && self.gpu.is_none() {
+            return Err(Error::Config(format!("{:?} requested but no GPU is available", mode)));
+        }
+        
+        let mut health = self.gpu_health.lock();
+        health.consecutive_failures = 0;
+        health.demoted_at = None;
+        *self.mode.write() = mode;
+        Ok(())
+    }
+    
+    /// Default compute mode in effect, including automatic demotion
+    pub fn current_mode(&self) -> ComputeMode {
+        *self.mode.read()
+    }
+    
+    /// Message for a demotion since the last call, to raise as an alert
+    pub fn take_health_alert(&self) -> Option<String> {
+        self.gpu_health.lock().alert.take()
+    }
+    
     /// Compute features for a batch of market snapshots
+    ///
+    /// Snapshots are grouped by asset category and each group runs on the
+    /// mode configured for it; output order matches `snapshots`.
     pub fn compute_batch(
         &self,
         snapshots: &[MarketSnapshot],
+    ) -> Result<Vec<ComputedFeatures>> {
+        self.reprobe_gpu();
+        let plan = self.dispatch_plan(snapshots);
+        
+        // Single group: no need to split the batch
+        if let [group] = plan.as_slice() {
+            let mut features = self.compute_group(group, snapshots)?;
+            verify_alignment(snapshots, &features)?;
+            self.apply_normalized(snapshots, &mut features);
+            return Ok(features);
+        }
+        
+        let mut slots: Vec<Option<ComputedFeatures>> = vec![None; snapshots.len()];
+        
+        for group in &plan {
+            let batch: Vec<MarketSnapshot> = group.indices
+                .iter()
+                .map(|&i| snapshots[i].clone())
+                .collect();
+            
+            let computed = self.compute_group(group, &batch)?;
+            for (&i, features) in group.indices.iter().zip(computed) {
+                slots[i] = Some(features);
+            }
+        }
+        
+        let mut features: Vec<ComputedFeatures> = slots.into_iter().flatten().collect();
+        verify_alignment(snapshots, &features)?;
+        
+        self.apply_normalized(snapshots, &mut features);
+        Ok(features)
+    }
+    
+    /// Run one dispatch group and record its latency
+    fn compute_group(
+        &self,
+        group: &DispatchGroup,
+        snapshots: &[MarketSnapshot],
     ) -> Result<Vec<ComputedFeatures>> {
         let start = std::time::Instant::now();
         
-        let result = match self.mode {
+        let features = self.compute_with_mode(group.mode, snapshots)?;
+        if features.len() != snapshots.len() {
+            return Err(Error::Internal(format!(
+                "{:?} compute returned {} results for {} snapshots",
+                group.mode, features.len(), snapshots.len()
+            )));
+        }
+        
+        let category = match group.category {
+            Some(c) => format!("{:?}", c),
+            None => "unassigned".to_string(),
+        };
+        
+        let elapsed = start.elapsed();
+        metrics::histogram!("feature_compute_us", elapsed.as_micros() as f64,
+            "mode" => format!("{:?}", group.mode),
+            "category" => category
+        );
+        
+        Ok(features)
+    }
+    
+    fn compute_with_mode(
+        &self,
+        mode: ComputeMode,
+        snapshots: &[MarketSnapshot],
+    ) -> Result<Vec<ComputedFeatures>> {
+        match mode {
             ComputeMode::CPUOnly => self.compute_cpu(snapshots),
             
             ComputeMode::GPUOnly => {
                 let gpu = self.gpu.as_ref()
                     .ok_or_else(|| Error::Internal("GPU not available".to_string()))?;
-                gpu.compute_batch(snapshots)
+                self.compute_on_gpu(gpu, snapshots)
             }
             
             ComputeMode::GPUFirst => {
                 if let Some(gpu) = &self.gpu {
-                    match gpu.compute_batch(snapshots) {
+                    let result = self.compute_on_gpu(gpu, snapshots);
+                    self.record_gpu_outcome(result.is_ok());
+                    match result {
                         Ok(features) => Ok(features),
                         Err(e) => {
                             tracing::warn!("GPU compute failed: {}. Falling back to CPU", e);
@@ -94,19 +307,189 @@ impl FeatureComputer {
                     self.compute_cpu(snapshots)
                 }
             }
-        };
+        }
+    }
+    
+    /// Track a `GPUFirst` batch outcome, demoting the default mode to
+    /// `CPUOnly` after too many failures in a row
+    fn record_gpu_outcome(&self, ok: bool) {
+        let mut health = self.gpu_health.lock();
+        if ok {
+            health.consecutive_failures = 0;
+            return;
+        }
         
-        let elapsed = start.elapsed();
-        metrics::histogram!("feature_compute_us", elapsed.as_micros() as f64,
-            "mode" => format!("{:?}", self.mode)
+        health.consecutive_failures += 1;
+        let max = self.demotion.max_consecutive_failures;
+        if max == 0 || health.consecutive_failures < max || health.demoted_at.is_some() {
+            return;
+        }
+        
+        let mut mode = self.mode.write();
+        if *mode != ComputeMode::GPUFirst {
+            return;
+        }
+        *mode = ComputeMode::CPUOnly;
+        health.demoted_at = Some(Instant::now());
+        
+        let message = format!(
+            "GPU failed {} batches in a row; computing features on CPU, re-probing in {:?}",
+            health.consecutive_failures, self.demotion.cooldown
         );
+        tracing::warn!("{}", message);
+        metrics::increment_counter!("gpu_demotions_total");
+        health.alert = Some(message);
+    }
+    
+    /// Back to `GPUFirst` once a demotion's cooldown is over; the probe
+    /// batch failing demotes again straight away
+    fn reprobe_gpu(&self) {
+        let mut health = self.gpu_health.lock();
+        let Some(demoted_at) = health.demoted_at else {
+            return;
+        };
+        if demoted_at.elapsed() < self.demotion.cooldown {
+            return;
+        }
+        
+        health.demoted_at = None;
+        health.consecutive_failures = self.demotion.max_consecutive_failures.saturating_sub(1);
+        *self.mode.write() = ComputeMode::GPUFirst;
+        tracing::info!("Re-probing GPU after {:?} on CPU", self.demotion.cooldown);
+    }
+    
+    /// GPU batch with the rolling `WINDOW_*` slots filled from CPU state
+    fn compute_on_gpu(
+        &self,
+        gpu: &Arc<dyn GpuBackend>,
+        snapshots: &[MarketSnapshot],
+    ) -> Result<Vec<ComputedFeatures>> {
+        let mut features = self.compute_gpu(gpu, snapshots)?;
+        self.cpu.write().fill_windows(snapshots, &mut features);
+        Ok(features)
+    }
+    
+    /// Run a GPU batch, bounded by `gpu_timeout` when set. Timed batches run
+    /// on one long-lived worker thread; one that times out is left to finish
+    /// there, and the device gets no new batches until it does.
+    fn compute_gpu(
+        &self,
+        gpu: &Arc<dyn GpuBackend>,
+        snapshots: &[MarketSnapshot],
+    ) -> Result<Vec<ComputedFeatures>> {
+        let Some(timeout) = self.gpu_timeout else {
+            return gpu.compute_batch(snapshots);
+        };
+        
+        if self.gpu_busy.swap(true, Ordering::AcqRel) {
+            metrics::increment_counter!("gpu_compute_timeout");
+            return Err(Error::Timeout("GPU still busy with a timed-out batch".to_string()));
+        }
+        
+        let (reply, rx) = std::sync::mpsc::sync_channel(1);
+        // The job owns its copy: a timed-out batch is still read after we return
+        let job = GpuJob { batch: snapshots.to_vec(), reply };
+        if let Err(e) = self.submit_gpu(gpu, job) {
+            self.gpu_busy.store(false, Ordering::Release);
+            return Err(e);
+        }
+        
+        match rx.recv_timeout(timeout) {
+            Ok(result) => result,
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
+                metrics::increment_counter!("gpu_compute_timeout");
+                Err(Error::Timeout(format!(
+                    "GPU batch of {} snapshots exceeded {:?}", snapshots.len(), timeout
+                )))
+            }
+            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
+                self.gpu_busy.store(false, Ordering::Release);
+                Err(Error::Internal("GPU worker dropped the batch".to_string()))
+            }
+        }
+    }
+    
+    /// Hand `job` to the GPU worker, starting it if this is the first
+    /// timed batch or the last worker exited
+    fn submit_gpu(&self, gpu: &Arc<dyn GpuBackend>, job: GpuJob) -> Result<()> {
+        let mut worker = self.gpu_worker.lock();
+        if worker.is_none() {
+            *worker = Some(GpuWorker::spawn(gpu.clone(), self.gpu_busy.clone())?);
+        }
+        
+        let sent = worker.as_ref().map_or(false, |w| w.jobs.send(job).is_ok());
+        if !sent {
+            *worker = None;
+            return Err(Error::Internal("GPU worker thread exited".to_string()));
+        }
+        Ok(())
+    }
+    
+    /// Group snapshot indices by (category, mode), in first-seen order
+    fn dispatch_plan(&self, snapshots: &[MarketSnapshot]) -> Vec<DispatchGroup> {
+        let categories = self.categories.read();
+        let category_modes = self.category_modes.read();
+        let mut plan: Vec<DispatchGroup> = Vec::new();
+        
+        for (i, snap) in snapshots.iter().enumerate() {
+            let category = categories.get(&snap.symbol).copied();
+            let mode = category
+                .and_then(|c| category_modes.get(&c).copied())
+                .unwrap_or(*self.mode.read());
+            
+            match plan.iter_mut().find(|g| g.category == category && g.mode == mode) {
+                Some(group) => group.indices.push(i),
+                None => plan.push(DispatchGroup { category, mode, indices: vec![i] }),
+            }
+        }
+        
+        plan
+    }
+    
+    /// Select the compute mode for every symbol in `category`
+    pub fn set_category_mode(&self, category: AssetCategory, mode: ComputeMode) -> Result<()> {
+        if mode == ComputeMode::GPUOnly && self.gpu.is_none() {
+            return Err(Error::Config(format!(
+                "GPUOnly requested for {:?} but no GPU is available", category
+            )));
+        }
+        
+        self.category_modes.write().insert(category, mode);
+        Ok(())
+    }
+    
+    /// Assign a symbol to an asset category for compute dispatch
+    pub fn set_symbol_category(&self, symbol: impl Into<String>, category: AssetCategory) {
+        self.categories.write().insert(symbol.into(), category);
+    }
+    
+    /// Fill the tick/spread-normalized and volatility/liquidity blocks (device independent)
+    fn apply_normalized(&self, snapshots: &[MarketSnapshot], features: &mut [ComputedFeatures]) {
+        let specs = self.specs.read();
+        let mut impact = self.impact.lock();
         
-        result
+        for (snap, computed) in snapshots.iter().zip(features.iter_mut()) {
+            impact.observe(snap);
+            
+            if let Some(out) = computed.features.as_slice_mut() {
+                indicators::write_normalized(&snap.orderbook, specs.get(&snap.symbol), out);
+                indicators::write_market_block(snap, out);
+                
+                if let Some(curve) = impact.curve(&snap.symbol) {
+                    out[layout::DEPTH_A] = curve.a as f32;
+                    out[layout::DEPTH_BETA] = curve.beta as f32;
+                }
+            }
+        }
+    }
+    
+    /// Set the instrument spec used for tick-normalized features
+    pub fn set_symbol_spec(&self, spec: SymbolSpec) {
+        self.specs.write().insert(spec.symbol.clone(), spec);
     }
     
     fn compute_cpu(&self, snapshots: &[MarketSnapshot]) -> Result<Vec<ComputedFeatures>> {
-        let builder = self.cpu.read();
-        builder.compute_batch(snapshots)
+        self.cpu.write().compute_batch(snapshots)
     }
     
     /// Add symbol to track
@@ -120,6 +503,25 @@ impl FeatureComputer {
     }
 }
 
+/// Check that `features` holds exactly one result per snapshot, in order.
+/// A mismatch means symbols and features would be paired up wrongly.
+pub fn verify_alignment(snapshots: &[MarketSnapshot], features: &[ComputedFeatures]) -> Result<()> {
+    if features.len() != snapshots.len() {
+        return Err(Error::Internal(format!(
+            "Feature batch returned {} results for {} snapshots",
+            features.len(), snapshots.len()
+        )));
+    }
+    
+    match snapshots.iter().zip(features).position(|(s, f)| s.symbol != f.symbol) {
+        Some(i) => Err(Error::Internal(format!(
+            "Feature batch misaligned at {}: snapshot {} got features for {}",
+            i, snapshots[i].symbol, features[i].symbol
+        ))),
+        None => Ok(()),
+    }
+}
+
 /// Computed features with metadata
 #[derive(Debug, Clone)]
 pub struct ComputedFeatures {
@@ -129,6 +531,42 @@ pub struct ComputedFeatures {
     pub computed_on: Device,
 }
 
+impl ComputedFeatures {
+    /// Map the `layout` slots onto the model-facing `FeatureVec`.
+    /// Funding is whatever the snapshot carried; callers zero it for equities.
+    pub fn to_feature_vec(&self) -> FeatureVec {
+        let f = &self.features;
+        let get = |idx: usize, default: f32| f.get(idx).copied().unwrap_or(default) as f64;
+        let mid = get(layout::MID_PRICE, 0.0);
+        
+        FeatureVec {
+            timestamp_ns: self.timestamp_ns,
+            symbol: self.symbol.clone(),
+            mid_price: mid,
+            spread_bps: get(layout::SPREAD_BPS, 0.0),
+            ofi_1s: get(layout::OFI, 0.0),
+            obi_1s: get(layout::OBI, 0.0),
+            depth_imbalance: get(layout::OBI, 0.0),
+            depth_a: get(layout::DEPTH_A, ImpactCurve::PRIOR.a as f32),
+            depth_beta: get(layout::DEPTH_BETA, ImpactCurve::PRIOR.beta as f32),
+            realized_vol_5s: get(layout::REALIZED_VOL_5S, 0.0),
+            atr_30s: get(layout::ATR_30S, 0.0),
+            funding_bps_8h: get(layout::FUNDING_BPS, 0.0),
+            impact_bps_1pct: get(layout::IMPACT_BPS_1PCT, 0.0),
+            microprice: get(layout::MICROPRICE, mid as f32),
+            vwap_ratio: get(layout::VWAP_RATIO, 1.0),
+        }
+    }
+}
+
+/// Snapshots sharing a category and compute mode
+#[derive(Debug, Clone)]
+struct DispatchGroup {
+    category: Option<AssetCategory>,
+    mode: ComputeMode,
+    indices: Vec<usize>,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum Device {
     CPU,
@@ -146,4 +584,386 @@ mod tests {
         let computer = FeatureComputer::cpu_only();
         // Test basic functionality
     }
+    
+    fn snapshot(symbol: &str, mid: f64) -> MarketSnapshot {
+        use ordered_float::OrderedFloat;
+        
+        MarketSnapshot {
+            timestamp_ns: 1,
+            symbol: symbol.to_string(),
+            orderbook: OrderBook {
+                symbol: symbol.to_string(),
+                timestamp_ns: 1,
+                bids: vec![Level { price: OrderedFloat(mid - 0.5), quantity: 2.0 }],
+                asks: vec![Level { price: OrderedFloat(mid + 0.5), quantity: 1.0 }],
+                sequence: 1,
+            },
+            recent_trades: vec![],
+            funding_rate_bps: None,
+            open_interest: None,
+            volume_24h: 0.0,
+            quality: DataQuality::Live,
+        }
+    }
+    
+    #[test]
+    fn test_misaligned_batch_rejected() {
+        let computer = FeatureComputer::cpu_only();
+        let batch = vec![snapshot("BTC", 100.0), snapshot("ETH", 10.0)];
+        let mut features = computer.compute_batch(&batch).unwrap();
+        assert!(verify_alignment(&batch, &features).is_ok());
+        
+        features.swap(0, 1);
+        assert!(verify_alignment(&batch, &features).is_err());
+        
+        features.truncate(1);
+        let err = verify_alignment(&batch, &features).unwrap_err();
+        assert!(err.to_string().contains("1 results for 2 snapshots"));
+    }
+    
+    #[test]
+    fn test_calibrated_impact_feeds_depth_curve() {
+        let computer = FeatureComputer::cpu_only();
+        let mut snap = snapshot("BTC", 100.0);
+        let book_fit = computer.compute_batch(&[snap.clone()]).unwrap()[0].to_feature_vec();
+        
+        // Realized slippage of 1e-5 * notional^0.5 at mid 100
+        snap.recent_trades = (1..=impact::MIN_SAMPLES)
+            .map(|i| {
+                let quantity = i as f64;
+                let price = 100.0 * (1.0 + 1e-5 * (100.0 * quantity).sqrt());
+                Trade {
+                    symbol: "BTC".to_string(),
+                    timestamp_ns: i as i64,
+                    price,
+                    quantity: 100.0 * quantity / price,
+                    side: Side::Buy,
+                    trade_id: i.to_string(),
+                }
+            })
+            .collect();
+        
+        let vec = computer.compute_batch(&[snap]).unwrap()[0].to_feature_vec();
+        assert!((vec.depth_beta - 0.5).abs() < 1e-3, "beta {}", vec.depth_beta);
+        assert!((vec.depth_a - 1e-5).abs() < 1e-7, "a {}", vec.depth_a);
+        assert_ne!(vec.depth_beta, book_fit.depth_beta);
+    }
+    
+    #[test]
+    fn test_category_dispatch() {
+        let computer = FeatureComputer::cpu_only();
+        computer.set_symbol_category("BTC", AssetCategory::CryptoFutures);
+        computer.set_symbol_category("AAPL", AssetCategory::Equity);
+        computer.set_category_mode(AssetCategory::CryptoFutures, ComputeMode::GPUFirst).unwrap();
+        computer.set_category_mode(AssetCategory::Equity, ComputeMode::CPUOnly).unwrap();
+        
+        // GPUOnly cannot be honoured without a GPU
+        assert!(computer.set_category_mode(AssetCategory::Equity, ComputeMode::GPUOnly).is_err());
+        
+        let snapshots = vec![snapshot("BTC", 50000.0), snapshot("AAPL", 190.0), snapshot("BTC", 50001.0)];
+        for snap in &snapshots {
+            computer.add_symbol(snap.symbol.clone(), 100);
+            computer.update_book(&snap.orderbook);
+        }
+        
+        let plan = computer.dispatch_plan(&snapshots);
+        assert_eq!(plan.len(), 2);
+        assert_eq!(plan[0].category, Some(AssetCategory::CryptoFutures));
+        assert_eq!(plan[0].mode, ComputeMode::GPUFirst);
+        assert_eq!(plan[0].indices, vec![0, 2]);
+        assert_eq!(plan[1].category, Some(AssetCategory::Equity));
+        assert_eq!(plan[1].mode, ComputeMode::CPUOnly);
+        assert_eq!(plan[1].indices, vec![1]);
+        
+        let features = computer.compute_batch(&snapshots).unwrap();
+        assert_eq!(features.len(), 3);
+        
+        for (snap, computed) in snapshots.iter().zip(&features) {
+            assert_eq!(computed.symbol, snap.symbol);
+            assert_eq!(computed.features.len(), layout::NUM_FEATURES);
+            assert!(computed.features.iter().all(|v| v.is_finite()));
+        }
+    }
+    
+    /// Device whose kernel fills nothing, so any non-zero slot came from CPU state
+    struct BlankGpu;
+    
+    impl GpuBackend for BlankGpu {
+        fn compute_batch(&self, snapshots: &[MarketSnapshot]) -> Result<Vec<ComputedFeatures>> {
+            Ok(snapshots
+                .iter()
+                .map(|snap| ComputedFeatures {
+                    symbol: snap.symbol.clone(),
+                    timestamp_ns: snap.timestamp_ns,
+                    features: Array1::zeros(layout::NUM_FEATURES),
+                    computed_on: Device::CUDA,
+                })
+                .collect())
+        }
+    }
+    
+    #[test]
+    fn test_gpu_batches_get_rolling_windows() {
+        let gpu = FeatureComputer::with_gpu_backend(Arc::new(BlankGpu));
+        let cpu = FeatureComputer::cpu_only();
+        
+        let snapshots: Vec<_> = [100.0, 101.0, 100.0]
+            .iter()
+            .enumerate()
+            .map(|(i, mid)| {
+                let mut snap = snapshot("BTC", *mid);
+                snap.orderbook.sequence = i as u64 + 1;
+                snap
+            })
+            .collect();
+        
+        for snap in &snapshots {
+            let on_gpu = gpu.compute_batch(std::slice::from_ref(snap)).unwrap().remove(0);
+            let on_cpu = cpu.compute_batch(std::slice::from_ref(snap)).unwrap().remove(0);
+            assert!(matches!(on_gpu.computed_on, Device::CUDA));
+            for slot in [layout::WINDOW_VOL, layout::WINDOW_ATR, layout::WINDOW_OFI, layout::WINDOW_OBI] {
+                assert_eq!(on_gpu.features[slot], on_cpu.features[slot], "slot {}", slot);
+            }
+        }
+        
+        let last = gpu.compute_batch(&snapshots[2..]).unwrap().remove(0);
+        assert!(last.features[layout::WINDOW_VOL] > 0.0);
+        assert!((last.features[layout::WINDOW_ATR] - 1.0).abs() < 1e-6);
+    }
+    
+    /// Device that takes `delay` per batch, then fails
+    struct SlowGpu {
+        delay: Duration,
+    }
+    
+    impl GpuBackend for SlowGpu {
+        fn compute_batch(&self, _snapshots: &[MarketSnapshot]) -> Result<Vec<ComputedFeatures>> {
+            std::thread::sleep(self.delay);
+            Err(Error::Internal("stand-in device".to_string()))
+        }
+    }
+    
+    #[test]
+    fn test_hung_gpu_times_out() {
+        let computer = FeatureComputer::with_gpu_backend(Arc::new(SlowGpu { delay: Duration::from_millis(500) }))
+            .with_gpu_timeout(Duration::from_millis(20));
+        let batch = vec![snapshot("BTC", 100.0)];
+        computer.add_symbol("BTC".to_string(), 100);
+        
+        // GPUFirst: the CPU path answers instead of waiting on the device
+        let start = std::time::Instant::now();
+        let features = computer.compute_batch(&batch).unwrap();
+        assert!(start.elapsed() < Duration::from_millis(400));
+        assert!(matches!(features[0].computed_on, Device::CPU));
+        
+        // GPUOnly: the batch fails, and the still-hung device isn't re-entered
+        computer.set_symbol_category("BTC", AssetCategory::CryptoFutures);
+        computer.set_category_mode(AssetCategory::CryptoFutures, ComputeMode::GPUOnly).unwrap();
+        let start = std::time::Instant::now();
+        assert!(matches!(computer.compute_batch(&batch), Err(Error::Timeout(_))));
+        assert!(start.elapsed() < Duration::from_millis(400));
+    }
+    
+    /// Device that fails every batch, counting calls
+    #[derive(Default)]
+    struct FailingGpu {
+        calls: std::sync::atomic::AtomicU32,
+    }
+    
+    impl GpuBackend for FailingGpu {
+        fn compute_batch(&self, _snapshots: &[MarketSnapshot]) -> Result<Vec<ComputedFeatures>> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            Err(Error::Internal("device lost".to_string()))
+        }
+    }
+    
+    #[test]
+    fn test_repeated_gpu_failures_demote_to_cpu() {
+        let gpu = Arc::new(FailingGpu::default());
+        let computer = FeatureComputer::with_gpu_backend(gpu.clone()).with_demotion(DemotionPolicy {
+            max_consecutive_failures: 3,
+            cooldown: Duration::from_millis(50),
+        });
+        let batch = vec![snapshot("BTC", 100.0)];
+        computer.add_symbol("BTC".to_string(), 100);
+        let calls = || gpu.calls.load(Ordering::Relaxed);
+        
+        for _ in 0..2 {
+            assert!(computer.compute_batch(&batch).is_ok());
+        }
+        assert_eq!(computer.current_mode(), ComputeMode::GPUFirst);
+        assert!(computer.take_health_alert().is_none());
+        
+        assert!(computer.compute_batch(&batch).is_ok());
+        assert_eq!(computer.current_mode(), ComputeMode::CPUOnly);
+        assert!(computer.take_health_alert().unwrap().contains("3 batches"));
+        assert!(computer.take_health_alert().is_none());
+        
+        // Cooling down: the GPU is left alone
+        assert!(computer.compute_batch(&batch).is_ok());
+        assert_eq!(calls(), 3);
+        
+        // After the cooldown one probe batch tries it, and failing demotes again
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(computer.compute_batch(&batch).is_ok());
+        assert_eq!(calls(), 4);
+        assert_eq!(computer.current_mode(), ComputeMode::CPUOnly);
+        assert!(computer.take_health_alert().is_some());
+        
+        // An operator choice overrides the demotion
+        computer.set_mode(ComputeMode::GPUFirst).unwrap();
+        assert_eq!(computer.current_mode(), ComputeMode::GPUFirst);
+        assert!(FeatureComputer::cpu_only().set_mode(ComputeMode::GPUFirst).is_err());
+    }
+    
+    #[test]
+    fn test_feature_vec_mapping() {
+        use ordered_float::OrderedFloat;
+        
+        let level = |price: f64, quantity: f64| Level { price: OrderedFloat(price), quantity };
+        let mut snap = snapshot("BTC", 100.5);
+        snap.orderbook.bids = vec![level(100.0, 1.0), level(99.0, 2.0)];
+        snap.orderbook.asks = vec![level(101.0, 1.0), level(102.0, 2.0)];
+        
+        let computer = FeatureComputer::cpu_only();
+        computer.add_symbol("BTC".to_string(), 100);
+        computer.update_book(&snap.orderbook);
+        
+        let computed = computer.compute_batch(std::slice::from_ref(&snap)).unwrap().remove(0);
+        let vec = computed.to_feature_vec();
+        
+        let (a, beta) = indicators::depth_curve(&snap.orderbook, layout::DEPTH_CURVE_LEVELS).unwrap();
+        assert!((vec.depth_a - a).abs() <= a * 1e-4);
+        assert!((vec.depth_beta - beta).abs() < 1e-4);
+        assert!((vec.impact_bps_1pct - 0.5 / 100.5 * 10000.0).abs() < 1e-3);
+        assert!((vec.mid_price - 100.5).abs() < 1e-3);
+        
+        // No trades in the snapshot: no realized movement
+        assert_eq!(vec.realized_vol_5s, 0.0);
+        assert_eq!(vec.atr_30s, 0.0);
+    }
+    
+    /// Property tests over arbitrary valid books and tapes on the CPU path
+    mod props {
+        use super::*;
+        use ordered_float::OrderedFloat;
+        use proptest::prelude::*;
+        
+        /// (tick gap from the previous level, quantity) per level
+        fn arb_side() -> impl Strategy<Value = Vec<(u32, f64)>> {
+            prop::collection::vec((1u32..50, 1e-6f64..1e6), 1..25)
+        }
+        
+        /// (tick offset from the best bid, quantity, buy, age in ns) per trade
+        fn arb_tape() -> impl Strategy<Value = Vec<(i32, f64, bool, i64)>> {
+            prop::collection::vec((-200i32..200, 1e-6f64..1e4, any::<bool>(), 0i64..60_000_000_000), 0..100)
+        }
+        
+        prop_compose! {
+            fn arb_snapshot(sequence: u64)(
+                best_bid in 1e-3f64..1e5,
+                tick_frac in 1e-6f64..1e-2,
+                spread_ticks in 1u32..500,
+                bids in arb_side(),
+                asks in arb_side(),
+                tape in arb_tape(),
+                funding in prop::option::of(-100f64..100.0),
+            ) -> MarketSnapshot {
+                let tick = best_bid * tick_frac;
+                let now = 100_000_000_000 + sequence as i64 * 1_000_000;
+                
+                let mut bid_levels = Vec::new();
+                let mut price = best_bid + tick;
+                for (gap, quantity) in bids {
+                    price -= gap as f64 * tick;
+                    if price <= 0.0 {
+                        break;
+                    }
+                    bid_levels.push(Level { price: OrderedFloat(price), quantity });
+                }
+                if bid_levels.is_empty() {
+                    bid_levels.push(Level { price: OrderedFloat(best_bid), quantity: 1.0 });
+                }
+                let best_bid = bid_levels[0].price.0;
+                
+                let mut price = best_bid + (spread_ticks as f64 - 1.0) * tick;
+                let ask_levels = asks.into_iter()
+                    .map(|(gap, quantity)| {
+                        price += gap as f64 * tick;
+                        Level { price: OrderedFloat(price), quantity }
+                    })
+                    .collect();
+                
+                let mut recent_trades: Vec<Trade> = tape.into_iter()
+                    .enumerate()
+                    .map(|(i, (offset, quantity, buy, age))| Trade {
+                        symbol: "BTC".to_string(),
+                        timestamp_ns: now - age,
+                        price: (best_bid + offset as f64 * tick).max(tick),
+                        quantity,
+                        side: if buy { Side::Buy } else { Side::Sell },
+                        trade_id: i.to_string(),
+                    })
+                    .collect();
+                recent_trades.sort_by_key(|t| t.timestamp_ns);
+                
+                MarketSnapshot {
+                    timestamp_ns: now,
+                    symbol: "BTC".to_string(),
+                    orderbook: OrderBook {
+                        symbol: "BTC".to_string(),
+                        timestamp_ns: now,
+                        bids: bid_levels,
+                        asks: ask_levels,
+                        sequence,
+                    },
+                    recent_trades,
+                    funding_rate_bps: funding,
+                    open_interest: None,
+                    volume_24h: 0.0,
+                    quality: DataQuality::Live,
+                }
+            }
+        }
+        
+        fn arb_snapshots() -> impl Strategy<Value = Vec<MarketSnapshot>> {
+            (arb_snapshot(1), arb_snapshot(2), arb_snapshot(3)).prop_map(|(a, b, c)| vec![a, b, c])
+        }
+        
+        proptest! {
+            #[test]
+            fn prop_cpu_features_are_sane(snapshots in arb_snapshots(), tick in prop::option::of(1e-6f64..1.0)) {
+                let computer = FeatureComputer::cpu_only();
+                computer.add_symbol("BTC".to_string(), 8);
+                if let Some(tick_size) = tick {
+                    computer.set_symbol_spec(SymbolSpec { symbol: "BTC".to_string(), tick_size, lot_size: 1e-3 });
+                }
+                
+                // One snapshot per batch so the rolling windows advance
+                for snap in &snapshots {
+                    let computed = computer.compute_batch(std::slice::from_ref(snap)).unwrap().remove(0);
+                    let f = &computed.features;
+                    
+                    prop_assert_eq!(f.len(), layout::NUM_FEATURES);
+                    for (i, v) in f.iter().enumerate() {
+                        prop_assert!(v.is_finite(), "slot {} is {}", i, v);
+                    }
+                    
+                    let bid = snap.orderbook.best_bid().unwrap().price.0 as f32;
+                    let ask = snap.orderbook.best_ask().unwrap().price.0 as f32;
+                    let slack = ask * 1e-6;
+                    
+                    prop_assert!(f[layout::SPREAD_BPS] >= 0.0);
+                    prop_assert!((-1.0..=1.0).contains(&f[layout::OBI]), "OBI {}", f[layout::OBI]);
+                    prop_assert!((-1.0..=1.0).contains(&f[layout::WINDOW_OBI]));
+                    prop_assert!(
+                        f[layout::MICROPRICE] >= bid - slack && f[layout::MICROPRICE] <= ask + slack,
+                        "microprice {} outside [{}, {}]", f[layout::MICROPRICE], bid, ask
+                    );
+                    prop_assert!(f[layout::REALIZED_VOL_5S] >= 0.0 && f[layout::ATR_30S] >= 0.0);
+                    prop_assert!(f[layout::WINDOW_VOL] >= 0.0 && f[layout::WINDOW_ATR] >= 0.0);
+                }
+            }
+        }
+    }
 }
\ No newline at end of file
@@ -1,11 +1,480 @@
 use common::*;
+use common::security::DataSourceKeys;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::Duration;
 
+const HYPERLIQUID_INFO_URL: &str = "https://api.hyperliquid.xyz/info";
+const GECKO_TERMINAL_URL: &str = "https://api.geckoterminal.com/api/v2";
+/// CoinGecko's on-chain API serves the GeckoTerminal schema with higher limits
+const GECKO_TERMINAL_PRO_URL: &str = "https://pro-api.coingecko.com/api/v3/onchain";
+const BIRDEYE_URL: &str = "https://public-api.birdeye.so";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const BIRDEYE_TOKEN_LIMIT: usize = 50;
+
+/// Deadline and retry budget for each data-source fetch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FetchPolicy {
+    pub timeout_ms: u64,
+    /// Per-source deadline overrides, keyed by source name (`"Hyperliquid"`)
+    pub source_timeout_ms: HashMap<String, u64>,
+    /// Retries after the first attempt, for retryable errors only
+    pub retries: u32,
+    /// Delay before the first retry, doubled after each further one
+    pub backoff_ms: u64,
+}
+
+impl Default for FetchPolicy {
+    fn default() -> Self {
+        Self {
+            timeout_ms: 15_000,
+            source_timeout_ms: HashMap::new(),
+            retries: 2,
+            backoff_ms: 500,
+        }
+    }
+}
+
+impl FetchPolicy {
+    pub fn timeout(&self, source: &str) -> Duration {
+        Duration::from_millis(self.source_timeout_ms.get(source).copied().unwrap_or(self.timeout_ms))
+    }
+}
+
+/// Run `fetch` under the source's deadline, retrying timeouts and other
+/// retryable errors with exponential backoff
+pub async fn fetch_with_retry<T, F, Fut>(source: &str, policy: &FetchPolicy, mut fetch: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let deadline = policy.timeout(source);
+    let mut backoff = Duration::from_millis(policy.backoff_ms);
+    let mut attempt = 0;
+    
+    loop {
+        let result = match tokio::time::timeout(deadline, fetch()).await {
+            Ok(result) => result,
+            Err(_) => Err(Error::Timeout(format!("{} fetch exceeded {:?}", source, deadline))),
+        };
+        
+        match result {
+            Err(e) if e.is_retryable() && attempt < policy.retries => {
+                attempt += 1;
+                tracing::debug!("{} fetch failed ({}), retry {}/{} in {:?}", source, e, attempt, policy.retries, backoff);
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// One asset listed by a venue
+#[derive(Debug, Clone)]
+pub struct UniverseItem {
+    pub symbol: String,
+    pub metrics: AssetMetrics,
+}
+
+/// Hyperliquid perp listing with venue-side volume, funding and open interest
+pub struct HyperliquidSource {
+    client: reqwest::Client,
+}
+
+impl HyperliquidSource {
+    pub async fn fetch_universe(&self) -> Result<Vec<UniverseItem>> {
+        let response: serde_json::Value = self.client
+            .post(HYPERLIQUID_INFO_URL)
+            .json(&serde_json::json!({ "type": "metaAndAssetCtxs" }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        
+        parse_hyperliquid_universe(&response)
+    }
+}
+
+/// Market data clients used to build the universe
 pub struct DataSources {
-    // Add your data source clients here
+    client: reqwest::Client,
+    keys: Option<DataSourceKeys>,
+    /// GeckoTerminal network ids polled for top pools
+    gecko_networks: Vec<String>,
+    pub hyperliquid: HyperliquidSource,
 }
 
 impl DataSources {
+    /// Sources that need no API key only
     pub fn new() -> Self {
-        Self {}
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        
+        Self {
+            hyperliquid: HyperliquidSource { client: client.clone() },
+            client,
+            keys: None,
+            gecko_networks: ["eth", "solana", "arbitrum", "base"].map(String::from).to_vec(),
+        }
+    }
+    
+    pub fn with_keys(keys: DataSourceKeys) -> Self {
+        Self {
+            keys: Some(keys),
+            ..Self::new()
+        }
+    }
+    
+    fn key(&self, get: fn(&DataSourceKeys) -> &Option<String>) -> Option<&str> {
+        self.keys
+            .as_ref()
+            .and_then(|keys| get(keys).as_deref())
+            .filter(|key| !key.is_empty())
+    }
+    
+    /// Top pools per network, aggregated per base token symbol. Uses the public
+    /// (rate limited) API unless a GeckoTerminal key is configured.
+    pub async fn fetch_gecko_terminal(&self) -> Result<HashMap<String, AssetMetrics>> {
+        let key = self.key(|k| &k.gecko_terminal);
+        let base = if key.is_some() { GECKO_TERMINAL_PRO_URL } else { GECKO_TERMINAL_URL };
+        
+        let mut metrics = HashMap::new();
+        for network in &self.gecko_networks {
+            let mut request = self.client
+                .get(format!("{}/networks/{}/pools", base, network))
+                .header("accept", "application/json");
+            if let Some(key) = key {
+                request = request.header("x-cg-pro-api-key", key);
+            }
+            
+            // One network failing leaves the others' pools
+            let response: GeckoPools = match fetch_json(request).await {
+                Ok(response) => response,
+                Err(e) => {
+                    tracing::warn!("GeckoTerminal {} pools skipped: {}", network, e);
+                    continue;
+                }
+            };
+            for (symbol, pool) in parse_gecko_pools(response) {
+                sum_into(&mut metrics, symbol, pool);
+            }
+        }
+        
+        Ok(metrics)
+    }
+    
+    /// Solana token list by 24h volume; requires a Birdeye key
+    pub async fn fetch_birdeye(&self) -> Result<HashMap<String, AssetMetrics>> {
+        let key = self.key(|k| &k.birdeye)
+            .ok_or_else(|| Error::Config("Birdeye API key not configured".to_string()))?;
+        
+        let response: BirdeyeResponse = self.client
+            .get(format!("{}/defi/tokenlist", BIRDEYE_URL))
+            .query(&[
+                ("sort_by", "v24hUSD".to_string()),
+                ("sort_type", "desc".to_string()),
+                ("offset", "0".to_string()),
+                ("limit", BIRDEYE_TOKEN_LIMIT.to_string()),
+            ])
+            .header("X-API-KEY", key)
+            .header("x-chain", "solana")
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        
+        parse_birdeye_tokens(response)
+    }
+}
+
+impl Default for DataSources {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Merge aggregator metrics into the venue listing. Only listed symbols are
+/// enriched, since an asset the venue doesn't list can't be traded. Venue
+/// values win; aggregator values fill zero volume/liquidity and missing fields.
+pub fn enrich_listed(listed: &mut HashMap<String, AssetMetrics>, aggregated: &HashMap<String, AssetMetrics>) {
+    for (symbol, metrics) in listed.iter_mut() {
+        let Some(extra) = aggregated.get(&symbol.to_uppercase()) else {
+            continue;
+        };
+        if metrics.volume_24h_usd <= 0.0 {
+            metrics.volume_24h_usd = extra.volume_24h_usd;
+        }
+        if metrics.liquidity_usd <= 0.0 {
+            metrics.liquidity_usd = extra.liquidity_usd;
+        }
+        metrics.funding_rate_bps = metrics.funding_rate_bps.or(extra.funding_rate_bps);
+        metrics.open_interest_usd = metrics.open_interest_usd.or(extra.open_interest_usd);
+        metrics.tx_count_1h = metrics.tx_count_1h.or(extra.tx_count_1h);
+        metrics.social_mentions_24h = metrics.social_mentions_24h.or(extra.social_mentions_24h);
+        metrics.market_cap_usd = metrics.market_cap_usd.or(extra.market_cap_usd);
+    }
+}
+
+/// Combine two aggregators' views of the same on-chain market: numbers both
+/// report are averaged, anything only one reports is kept
+pub fn average_into(into: &mut HashMap<String, AssetMetrics>, from: HashMap<String, AssetMetrics>) {
+    fn avg(a: Option<f64>, b: Option<f64>) -> Option<f64> {
+        match (a, b) {
+            (Some(a), Some(b)) => Some((a + b) / 2.0),
+            (a, b) => a.or(b),
+        }
+    }
+    let positive = |v: f64| (v > 0.0).then_some(v);
+    
+    for (symbol, other) in from {
+        let Some(existing) = into.get_mut(&symbol) else {
+            into.insert(symbol, other);
+            continue;
+        };
+        existing.volume_24h_usd = avg(positive(existing.volume_24h_usd), positive(other.volume_24h_usd)).unwrap_or(0.0);
+        existing.liquidity_usd = avg(positive(existing.liquidity_usd), positive(other.liquidity_usd)).unwrap_or(0.0);
+        existing.market_cap_usd = avg(existing.market_cap_usd, other.market_cap_usd);
+        existing.tx_count_1h = match (existing.tx_count_1h, other.tx_count_1h) {
+            (Some(a), Some(b)) => Some((a + b) / 2),
+            (a, b) => a.or(b),
+        };
+    }
+}
+
+/// Send `request` and decode a successful response
+async fn fetch_json<T: serde::de::DeserializeOwned>(request: reqwest::RequestBuilder) -> Result<T> {
+    Ok(request.send().await?.error_for_status()?.json().await?)
+}
+
+/// Add one pool's numbers to the token's running totals
+fn sum_into(totals: &mut HashMap<String, AssetMetrics>, symbol: String, pool: AssetMetrics) {
+    let entry = totals.entry(symbol).or_default();
+    entry.volume_24h_usd += pool.volume_24h_usd;
+    entry.liquidity_usd += pool.liquidity_usd;
+    if let Some(tx) = pool.tx_count_1h {
+        *entry.tx_count_1h.get_or_insert(0) += tx;
+    }
+}
+
+fn parse_num(value: &serde_json::Value) -> Option<f64> {
+    match value {
+        serde_json::Value::String(s) => s.parse().ok(),
+        other => other.as_f64(),
+    }
+    .filter(|v: &f64| v.is_finite())
+}
+
+/// `[meta, assetCtxs]` from `metaAndAssetCtxs`, zipped by index. Hyperliquid
+/// reports no book liquidity here, so open interest notional stands in for it.
+fn parse_hyperliquid_universe(response: &serde_json::Value) -> Result<Vec<UniverseItem>> {
+    let malformed = || Error::Venue("Malformed metaAndAssetCtxs response".to_string());
+    let universe = response.get(0).and_then(|m| m["universe"].as_array()).ok_or_else(malformed)?;
+    let contexts = response.get(1).and_then(|c| c.as_array()).ok_or_else(malformed)?;
+    
+    Ok(universe
+        .iter()
+        .zip(contexts)
+        .filter_map(|(asset, ctx)| {
+            let symbol = asset["name"].as_str()?.to_string();
+            let mark = parse_num(&ctx["markPx"]).unwrap_or(0.0);
+            let open_interest = parse_num(&ctx["openInterest"]).map(|oi| oi * mark);
+            Some(UniverseItem {
+                symbol,
+                metrics: AssetMetrics {
+                    volume_24h_usd: parse_num(&ctx["dayNtlVlm"]).unwrap_or(0.0),
+                    liquidity_usd: open_interest.unwrap_or(0.0),
+                    funding_rate_bps: parse_num(&ctx["funding"]).map(|f| f * 10_000.0),
+                    open_interest_usd: open_interest,
+                    ..Default::default()
+                },
+            })
+        })
+        .collect())
+}
+
+#[derive(Debug, Deserialize)]
+struct GeckoPools {
+    #[serde(default)]
+    data: Vec<GeckoPool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeckoPool {
+    attributes: GeckoPoolAttributes,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeckoPoolAttributes {
+    /// `"WETH / USDC 0.05%"`
+    name: String,
+    #[serde(default)]
+    reserve_in_usd: serde_json::Value,
+    #[serde(default)]
+    volume_usd: serde_json::Value,
+    #[serde(default)]
+    transactions: serde_json::Value,
+}
+
+/// Pools keyed by uppercase base token symbol, wrapped tokens unwrapped (`WETH` -> `ETH`)
+fn parse_gecko_pools(response: GeckoPools) -> Vec<(String, AssetMetrics)> {
+    response
+        .data
+        .into_iter()
+        .filter_map(|pool| {
+            let attrs = pool.attributes;
+            let base = attrs.name.split(" / ").next()?.trim().to_uppercase();
+            if base.is_empty() {
+                return None;
+            }
+            let symbol = match base.as_str() {
+                "WETH" | "WBTC" | "WSOL" => base[1..].to_string(),
+                _ => base,
+            };
+            
+            let h1 = &attrs.transactions["h1"];
+            let tx_count = h1["buys"].as_u64().zip(h1["sells"].as_u64()).map(|(b, s)| b + s);
+            
+            Some((symbol, AssetMetrics {
+                volume_24h_usd: parse_num(&attrs.volume_usd["h24"]).unwrap_or(0.0),
+                liquidity_usd: parse_num(&attrs.reserve_in_usd).unwrap_or(0.0),
+                tx_count_1h: tx_count,
+                ..Default::default()
+            }))
+        })
+        .collect()
+}
+
+#[derive(Debug, Deserialize)]
+struct BirdeyeResponse {
+    #[serde(default)]
+    success: bool,
+    #[serde(default)]
+    data: Option<BirdeyeTokenList>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BirdeyeTokenList {
+    #[serde(default)]
+    tokens: Vec<BirdeyeToken>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BirdeyeToken {
+    symbol: Option<String>,
+    #[serde(rename = "v24hUSD")]
+    volume_24h_usd: Option<f64>,
+    liquidity: Option<f64>,
+    mc: Option<f64>,
+}
+
+fn parse_birdeye_tokens(response: BirdeyeResponse) -> Result<HashMap<String, AssetMetrics>> {
+    let list = response
+        .data
+        .filter(|_| response.success)
+        .ok_or_else(|| Error::Venue("Birdeye returned an unsuccessful token list".to_string()))?;
+    
+    let mut metrics = HashMap::new();
+    for token in list.tokens {
+        let Some(symbol) = token.symbol.map(|s| s.trim().to_uppercase()).filter(|s| !s.is_empty()) else {
+            continue;
+        };
+        // Tokens sharing a ticker are distinct mints; keep the most liquid one
+        let candidate = AssetMetrics {
+            volume_24h_usd: token.volume_24h_usd.unwrap_or(0.0),
+            liquidity_usd: token.liquidity.unwrap_or(0.0),
+            market_cap_usd: token.mc,
+            ..Default::default()
+        };
+        match metrics.get(&symbol) {
+            Some(existing) if existing.liquidity_usd >= candidate.liquidity_usd => {}
+            _ => {
+                metrics.insert(symbol, candidate);
+            }
+        }
+    }
+    
+    Ok(metrics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_and_merge_sources() {
+        let gecko: GeckoPools = serde_json::from_value(serde_json::json!({
+            "data": [
+                { "attributes": {
+                    "name": "WETH / USDC 0.05%",
+                    "reserve_in_usd": "4000000",
+                    "volume_usd": { "h24": "10000000" },
+                    "transactions": { "h1": { "buys": 30, "sells": 20 } }
+                } },
+                { "attributes": {
+                    "name": "WETH / USDT 0.3%",
+                    "reserve_in_usd": "1000000",
+                    "volume_usd": { "h24": "2000000" },
+                    "transactions": { "h1": { "buys": 5, "sells": 5 } }
+                } },
+                { "attributes": { "name": "PEPE / WETH 1%", "reserve_in_usd": "800000", "volume_usd": { "h24": "bad" } } }
+            ]
+        })).unwrap();
+        let mut aggregated = HashMap::new();
+        for (symbol, pool) in parse_gecko_pools(gecko) {
+            sum_into(&mut aggregated, symbol, pool);
+        }
+        assert_eq!(aggregated["ETH"].volume_24h_usd, 12_000_000.0);
+        assert_eq!(aggregated["ETH"].liquidity_usd, 5_000_000.0);
+        assert_eq!(aggregated["ETH"].tx_count_1h, Some(60));
+        assert_eq!(aggregated["PEPE"].volume_24h_usd, 0.0);
+        
+        let birdeye: BirdeyeResponse = serde_json::from_value(serde_json::json!({
+            "success": true,
+            "data": { "tokens": [
+                { "symbol": "eth", "v24hUSD": 8000000.0, "liquidity": 3000000.0, "mc": 1.0e9 },
+                { "symbol": "PEPE", "v24hUSD": 500000.0, "liquidity": 200000.0, "mc": null }
+            ] }
+        })).unwrap();
+        average_into(&mut aggregated, parse_birdeye_tokens(birdeye).unwrap());
+        assert_eq!(aggregated["ETH"].volume_24h_usd, 10_000_000.0);
+        assert_eq!(aggregated["ETH"].liquidity_usd, 4_000_000.0);
+        assert_eq!(aggregated["ETH"].market_cap_usd, Some(1.0e9));
+        // Gecko's unparseable volume doesn't drag Birdeye's down
+        assert_eq!(aggregated["PEPE"].volume_24h_usd, 500_000.0);
+        
+        let failed: BirdeyeResponse = serde_json::from_value(serde_json::json!({ "success": false })).unwrap();
+        assert!(parse_birdeye_tokens(failed).is_err());
+        
+        let hl = serde_json::json!([
+            { "universe": [{ "name": "ETH" }, { "name": "BTC" }] },
+            [
+                { "dayNtlVlm": "0", "funding": "0.0000125", "openInterest": "1000", "markPx": "2500" },
+                { "dayNtlVlm": "90000000", "funding": "0.00001", "openInterest": "10", "markPx": "60000" }
+            ]
+        ]);
+        let mut listed: HashMap<_, _> = parse_hyperliquid_universe(&hl)
+            .unwrap()
+            .into_iter()
+            .map(|item| (item.symbol, item.metrics))
+            .collect();
+        assert_eq!(listed["ETH"].open_interest_usd, Some(2_500_000.0));
+        assert!((listed["ETH"].funding_rate_bps.unwrap() - 0.125).abs() < 1e-9);
+        
+        enrich_listed(&mut listed, &aggregated);
+        // Zero venue volume is filled from aggregators, venue liquidity kept
+        assert_eq!(listed["ETH"].volume_24h_usd, 10_000_000.0);
+        assert_eq!(listed["ETH"].liquidity_usd, 2_500_000.0);
+        assert_eq!(listed["ETH"].tx_count_1h, Some(60));
+        // Aggregator-only symbols aren't tradable on the venue
+        assert!(!listed.contains_key("PEPE"));
+        assert_eq!(listed["BTC"].volume_24h_usd, 90_000_000.0);
     }
 }
\ No newline at end of file
@@ -20,6 +20,21 @@ pub struct UniverseConfig {
     pub refresh_interval_mins: u64,
     pub min_volume_usd: f64,
     pub min_liquidity_usd: f64,
+    /// Quality bar: assets scoring below it are never selected, even if that
+    /// leaves the universe short of `crypto_count`/`equity_count`
+    #[serde(default)]
+    pub min_score: f64,
+    /// Anti-whiplash: a challenger only replaces an incumbent of the top
+    /// selection if it scores this fraction higher
+    #[serde(default = "default_rotation_hysteresis_pct")]
+    pub rotation_hysteresis_pct: f64,
+    /// Per-source deadline and retries for the rebuild fetches
+    #[serde(default)]
+    pub fetch: FetchPolicy,
+}
+
+fn default_rotation_hysteresis_pct() -> f64 {
+    0.10
 }
 
 impl Default for UniverseConfig {
@@ -32,6 +47,45 @@ impl Default for UniverseConfig {
             refresh_interval_mins: 15,
             min_volume_usd: 1_000_000.0,
             min_liquidity_usd: 500_000.0,
+            min_score: 0.0,
+            rotation_hysteresis_pct: default_rotation_hysteresis_pct(),
+            fetch: FetchPolicy::default(),
+        }
+    }
+}
+
+/// A data source left out of a rebuild
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedSource {
+    pub source: String,
+    pub error: String,
+}
+
+/// Outcome of the last master universe rebuild
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RebuildReport {
+    pub duration_ms: u64,
+    pub sources_ok: Vec<String>,
+    pub sources_skipped: Vec<SkippedSource>,
+}
+
+impl RebuildReport {
+    /// Record a source's outcome, returning its data if it arrived
+    pub fn record<T>(&mut self, source: &str, result: Result<T>) -> Option<T> {
+        match result {
+            Ok(data) => {
+                self.sources_ok.push(source.to_string());
+                Some(data)
+            }
+            Err(e) => {
+                tracing::warn!("Skipping {} in universe rebuild: {}", source, e);
+                metrics::increment_counter!("universe_source_skipped_total");
+                self.sources_skipped.push(SkippedSource {
+                    source: source.to_string(),
+                    error: e.to_string(),
+                });
+                None
+            }
         }
     }
 }
@@ -42,6 +96,11 @@ pub struct UniverseManager {
     crypto_scorer: CryptoScorer,
     equity_scorer: EquityScorer,
     current_universe: parking_lot::RwLock<Vec<UniverseAsset>>,
+    /// Current top selection, the incumbents for the next refresh
+    top_selection: parking_lot::RwLock<Vec<UniverseAsset>>,
+    rebuild_report: parking_lot::RwLock<RebuildReport>,
+    /// Receives the ranked universe after each rebuild/refresh
+    publisher: Option<tokio::sync::watch::Sender<Vec<UniverseAsset>>>,
     data_sources: DataSources,
 }
 
@@ -52,10 +111,25 @@ impl UniverseManager {
             crypto_scorer: CryptoScorer::new(),
             equity_scorer: EquityScorer::new(),
             current_universe: parking_lot::RwLock::new(Vec::new()),
+            top_selection: parking_lot::RwLock::new(Vec::new()),
+            rebuild_report: parking_lot::RwLock::new(RebuildReport::default()),
+            publisher: None,
             data_sources,
         }
     }
     
+    /// Publish the ranked universe to `tx` after each rebuild/refresh
+    pub fn with_publisher(mut self, tx: tokio::sync::watch::Sender<Vec<UniverseAsset>>) -> Self {
+        self.publisher = Some(tx);
+        self
+    }
+    
+    fn publish(&self) {
+        if let Some(tx) = &self.publisher {
+            tx.send_replace(self.get_universe());
+        }
+    }
+    
     /// Run the universe management loop
     pub async fn run(&self, mut shutdown: tokio::sync::watch::Receiver<bool>) -> Result<()> {
         let mut rebuild_timer = interval(Duration::from_secs(self.config.rebuild_interval_mins * 60));
@@ -93,9 +167,10 @@ impl UniverseManager {
         tracing::info!("Rebuilding master universe");
         
         let start = std::time::Instant::now();
+        let mut report = RebuildReport::default();
         
         // Collect crypto metrics
-        let crypto_metrics = self.collect_crypto_metrics().await?;
+        let crypto_metrics = self.collect_crypto_metrics(&mut report).await?;
         tracing::debug!("Collected {} crypto assets", crypto_metrics.len());
         
         // Collect equity metrics
@@ -103,14 +178,18 @@ impl UniverseManager {
         tracing::debug!("Collected {} equity assets", equity_metrics.len());
         
         // Score and filter crypto
-        let mut crypto_assets = self.score_crypto(&crypto_metrics)?;
-        crypto_assets.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
-        crypto_assets.truncate(self.config.crypto_count);
+        let crypto_assets = select_top(
+            self.score_crypto(&crypto_metrics)?,
+            self.config.crypto_count,
+            self.config.min_score,
+        );
         
         // Score and filter equity
-        let mut equity_assets = self.score_equity(&equity_metrics)?;
-        equity_assets.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
-        equity_assets.truncate(self.config.equity_count);
+        let equity_assets = select_top(
+            self.score_equity(&equity_metrics)?,
+            self.config.equity_count,
+            self.config.min_score,
+        );
         
         // Combine and store
         let mut universe = Vec::new();
@@ -125,7 +204,14 @@ impl UniverseManager {
         *self.current_universe.write() = universe;
         
         let elapsed = start.elapsed();
-        tracing::info!("Universe rebuilt in {:?}", elapsed);
+        tracing::info!(
+            "Universe rebuilt in {:?} ({} sources skipped)",
+            elapsed, report.sources_skipped.len()
+        );
+        
+        report.duration_ms = elapsed.as_millis() as u64;
+        *self.rebuild_report.write() = report;
+        self.publish();
         
         metrics::histogram!("universe_rebuild_duration_ms", elapsed.as_millis() as f64);
         
@@ -157,19 +243,35 @@ impl UniverseManager {
         let crypto_metrics = self.refresh_crypto_metrics(&crypto_symbols).await?;
         let equity_metrics = self.refresh_equity_metrics(&equity_symbols).await?;
         
-        // Rescore
-        let mut crypto_assets = self.score_crypto(&crypto_metrics)?;
-        crypto_assets.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
-        
-        let mut equity_assets = self.score_equity(&equity_metrics)?;
-        equity_assets.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
-        
-        // Apply anti-whiplash: only rotate if score difference > 10%
+        // Rescore, then apply anti-whiplash: incumbents are only rotated out
+        // for a clearly better challenger
         let (top_crypto_count, top_equity_count) = self.config.top_selection_count;
-        
-        crypto_assets.truncate(top_crypto_count);
-        equity_assets.truncate(top_equity_count);
-        
+        let prior = self.top_selection.read().clone();
+        let incumbents = |category: AssetCategory| -> Vec<UniverseAsset> {
+            prior.iter().filter(|a| a.category == category).cloned().collect()
+        };
+        
+        let crypto_assets = rotate_selection(
+            &incumbents(AssetCategory::CryptoFutures),
+            self.score_crypto(&crypto_metrics)?,
+            top_crypto_count,
+            self.config.min_score,
+            self.config.rotation_hysteresis_pct,
+        );
+        
+        let equity_assets = rotate_selection(
+            &incumbents(AssetCategory::Equity),
+            self.score_equity(&equity_metrics)?,
+            top_equity_count,
+            self.config.min_score,
+            self.config.rotation_hysteresis_pct,
+        );
+        
+        let mut selection = crypto_assets;
+        selection.extend(equity_assets);
+        *self.top_selection.write() = selection;
+        self.publish();
+
         let elapsed = start.elapsed();
         tracing::debug!("Top selection refreshed in {:?}", elapsed);
         
@@ -183,25 +285,48 @@ impl UniverseManager {
         self.current_universe.read().clone()
     }
     
+    /// Get the current top selection (refreshed every 15 minutes)
+    pub fn get_top_selection(&self) -> Vec<UniverseAsset> {
+        self.top_selection.read().clone()
+    }
+    
+    /// Sources used and skipped by the last rebuild
+    pub fn rebuild_report(&self) -> RebuildReport {
+        self.rebuild_report.read().clone()
+    }
+    
     /// Get top N assets
     pub fn get_top(&self, n: usize) -> Vec<UniverseAsset> {
         let universe = self.current_universe.read();
         universe.iter().take(n).cloned().collect()
     }
     
-    async fn collect_crypto_metrics(&self) -> Result<HashMap<String, AssetMetrics>> {
+    async fn collect_crypto_metrics(&self, report: &mut RebuildReport) -> Result<HashMap<String, AssetMetrics>> {
         let mut metrics = HashMap::new();
+        let policy = &self.config.fetch;
+        let sources = &self.data_sources;
+        
+        let (hl_data, gecko, birdeye) = futures::join!(
+            fetch_with_retry("Hyperliquid", policy, || sources.hyperliquid.fetch_universe()),
+            fetch_with_retry("GeckoTerminal", policy, || sources.fetch_gecko_terminal()),
+            fetch_with_retry("Birdeye", policy, || sources.fetch_birdeye()),
+        );
         
         // Hyperliquid data
-        if let Ok(hl_data) = self.data_sources.hyperliquid.fetch_universe().await {
-            for item in hl_data {
-                metrics.insert(item.symbol, item.metrics);
+        for item in report.record("Hyperliquid", hl_data).unwrap_or_default() {
+            metrics.insert(item.symbol, item.metrics);
+        }
+        
+        // GeckoTerminal + Birdeye data, skipped on error
+        let mut aggregated = HashMap::new();
+        for (source, result) in [("GeckoTerminal", gecko), ("Birdeye", birdeye)] {
+            if let Some(source_metrics) = report.record(source, result) {
+                average_into(&mut aggregated, source_metrics);
             }
         }
+        enrich_listed(&mut metrics, &aggregated);
         
-        // DexScreener data (parallel fetch)
-        // GeckoTerminal data
-        // Birdeye data
+        // DexScreener data
         // The Graph data
         // CryptoPanic data
         
@@ -283,4 +408,187 @@ impl UniverseManager {
         
         Ok(assets)
     }
+}
+
+/// Sort by score, best first; NaN scores sink to the bottom instead of panicking
+pub fn sort_by_score_desc(assets: &mut [UniverseAsset]) {
+    let key = |a: &UniverseAsset| if a.score.is_nan() { f64::NEG_INFINITY } else { a.score };
+    assets.sort_by(|a, b| key(b).total_cmp(&key(a)));
+}
+
+/// Best `cap` assets scoring at least `min_score` (NaN never qualifies)
+pub fn select_top(assets: Vec<UniverseAsset>, cap: usize, min_score: f64) -> Vec<UniverseAsset> {
+    let mut assets = qualifying(assets, min_score);
+    assets.truncate(cap);
+    
+    if assets.len() < cap {
+        tracing::info!(
+            "Only {} of {} assets meet the minimum score {:.2}",
+            assets.len(), cap, min_score
+        );
+    }
+    
+    assets
+}
+
+/// Assets scoring at least `min_score`, best first
+fn qualifying(mut assets: Vec<UniverseAsset>, min_score: f64) -> Vec<UniverseAsset> {
+    sort_by_score_desc(&mut assets);
+    assets.retain(|a| a.score >= min_score);
+    assets
+}
+
+/// Next top selection of `cap` assets from freshly scored `candidates`. Incumbents
+/// that still qualify keep their seat unless the best challenger beats the weakest
+/// incumbent by more than `hysteresis_pct`; free seats go to the best challengers.
+pub fn rotate_selection(
+    incumbents: &[UniverseAsset],
+    candidates: Vec<UniverseAsset>,
+    cap: usize,
+    min_score: f64,
+    hysteresis_pct: f64,
+) -> Vec<UniverseAsset> {
+    let candidates = qualifying(candidates, min_score);
+    
+    let is_incumbent = |a: &UniverseAsset| incumbents.iter().any(|i| i.symbol == a.symbol);
+    let (mut selected, mut challengers): (Vec<_>, Vec<_>) = candidates.into_iter().partition(is_incumbent);
+    
+    // Both stay sorted best first; move the lowest incumbents over if the cap shrank
+    while selected.len() > cap {
+        challengers.push(selected.pop().unwrap());
+    }
+    sort_by_score_desc(&mut challengers);
+    
+    let mut challengers = challengers.into_iter().peekable();
+    while selected.len() < cap {
+        match challengers.next() {
+            Some(asset) => selected.push(asset),
+            None => break,
+        }
+    }
+    
+    while let (Some(challenger), Some(weakest)) = (challengers.peek(), selected.last()) {
+        let bar = weakest.score * (1.0 + hysteresis_pct);
+        if challenger.score <= bar {
+            tracing::debug!(
+                "Keeping {} ({:.3}): best challenger {} ({:.3}) is within {:.0}%",
+                weakest.symbol, weakest.score, challenger.symbol, challenger.score, hysteresis_pct * 100.0
+            );
+            break;
+        }
+        
+        let challenger = challengers.next().unwrap();
+        let outgoing = selected.pop().unwrap();
+        tracing::info!(
+            "Rotating {} ({:.3}) out for {} ({:.3})",
+            outgoing.symbol, outgoing.score, challenger.symbol, challenger.score
+        );
+        selected.push(challenger);
+        sort_by_score_desc(&mut selected);
+    }
+    
+    selected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn asset(symbol: &str, score: f64) -> UniverseAsset {
+        UniverseAsset {
+            symbol: symbol.to_string(),
+            venue: Venue::Hyperliquid,
+            category: AssetCategory::CryptoFutures,
+            score,
+            rank: 0,
+            metrics: AssetMetrics::default(),
+        }
+    }
+    
+    #[test]
+    fn test_sort_by_score_nan() {
+        let mut assets = vec![asset("A", 0.2), asset("B", f64::NAN), asset("C", 0.9), asset("D", 0.5)];
+        sort_by_score_desc(&mut assets);
+        
+        let order: Vec<&str> = assets.iter().map(|a| a.symbol.as_str()).collect();
+        assert_eq!(order, vec!["C", "D", "A", "B"]);
+    }
+    
+    #[test]
+    fn test_min_score_leaves_universe_short() {
+        let weak = vec![asset("A", 0.2), asset("B", 0.45), asset("C", 0.3), asset("D", f64::NAN), asset("E", 0.6)];
+        
+        let selected = select_top(weak.clone(), 3, 0.4);
+        let symbols: Vec<&str> = selected.iter().map(|a| a.symbol.as_str()).collect();
+        assert_eq!(symbols, vec!["E", "B"]);
+        
+        // No bar: the cap alone decides
+        assert_eq!(select_top(weak, 3, 0.0).len(), 3);
+    }
+    
+    #[test]
+    fn test_rotation_hysteresis() {
+        let symbols = |assets: &[UniverseAsset]| -> Vec<String> {
+            assets.iter().map(|a| a.symbol.clone()).collect()
+        };
+        let incumbents = vec![asset("A", 0.80), asset("B", 0.50)];
+        
+        // C edges past B by 4%: no churn
+        let near_tie = vec![asset("A", 0.80), asset("B", 0.50), asset("C", 0.52)];
+        assert_eq!(symbols(&rotate_selection(&incumbents, near_tie, 2, 0.0, 0.10)), vec!["A", "B"]);
+        
+        // C beats B by 30%: rotate
+        let clear = vec![asset("A", 0.80), asset("B", 0.50), asset("C", 0.65)];
+        assert_eq!(symbols(&rotate_selection(&incumbents, clear, 2, 0.0, 0.10)), vec!["A", "C"]);
+        
+        // Incumbents that no longer qualify free their seat
+        let weak_b = vec![asset("A", 0.80), asset("B", 0.05), asset("C", 0.30)];
+        assert_eq!(symbols(&rotate_selection(&incumbents, weak_b, 2, 0.1, 0.10)), vec!["A", "C"]);
+    }
+    
+    #[tokio::test]
+    async fn test_slow_source_skipped_within_deadline() {
+        let policy = FetchPolicy {
+            timeout_ms: 1_000,
+            source_timeout_ms: HashMap::from([("Slow".to_string(), 20)]),
+            retries: 2,
+            backoff_ms: 5,
+        };
+        let attempts = std::sync::atomic::AtomicU32::new(0);
+        
+        let start = std::time::Instant::now();
+        let (slow, fast) = futures::join!(
+            fetch_with_retry("Slow", &policy, || async {
+                attempts.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                tokio::time::sleep(Duration::from_secs(30)).await;
+                Ok(vec![1])
+            }),
+            fetch_with_retry("Fast", &policy, || async { Ok(vec![2]) }),
+        );
+        
+        let mut report = RebuildReport::default();
+        let data: Vec<i32> = [("Slow", slow), ("Fast", fast)]
+            .into_iter()
+            .filter_map(|(source, result)| report.record(source, result))
+            .flatten()
+            .collect();
+        
+        // Three 20ms attempts plus 5ms + 10ms backoff, nowhere near the 30s hang
+        assert!(start.elapsed() < Duration::from_secs(1), "{:?}", start.elapsed());
+        assert_eq!(attempts.load(std::sync::atomic::Ordering::Relaxed), 3);
+        assert_eq!(data, vec![2]);
+        assert_eq!(report.sources_ok, vec!["Fast"]);
+        assert_eq!(report.sources_skipped.len(), 1);
+        assert_eq!(report.sources_skipped[0].source, "Slow");
+        
+        // Non-retryable errors are not retried
+        let calls = std::sync::atomic::AtomicU32::new(0);
+        let result: Result<()> = fetch_with_retry("Birdeye", &policy, || async {
+            calls.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            Err(Error::Config("no key".to_string()))
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(std::sync::atomic::Ordering::Relaxed), 1);
+    }
 }
\ No newline at end of file